@@ -1,42 +1,60 @@
-use std::f32::consts::PI;
+use std::{f32::consts::PI, time::Duration};
 
 use crate::{
-    HEIGHT, centerbox,
-    config::{self, AppearanceStyle, Config, Position},
+    centerbox,
+    config::{self, AppearanceStyle, Config, ModuleName, ModuleSection, Position},
+    debug_panel::DebugPanel,
     get_log_spec,
     menu::{MenuSize, MenuType, menu_wrapper},
     modules::{
-        self,
-        app_launcher::AppLauncher,
+        self, LayoutEdit,
+        app_launcher::{AppLauncher, search_input_id},
         clipboard::Clipboard,
         clock::Clock,
+        custom_command::{CustomCommandMessage, CustomCommands},
+        gamepad::Gamepad,
         keyboard_layout::KeyboardLayout,
         keyboard_submap::KeyboardSubmap,
         media_player::MediaPlayer,
+        notifications::{Notifications, NotificationsMessage},
+        pomodoro::Pomodoro,
+        printer::Printer,
         privacy::Privacy,
-        settings::{Settings, brightness::BrightnessMessage},
+        settings::{Settings, audio::AudioMessage, brightness::BrightnessMessage},
         system_info::SystemInfo,
+        systemd_units::SystemdUnits,
         tray::{TrayMessage, TrayModule},
         updates::Updates,
+        uptime::Uptime,
+        usb::Usb,
+        weather::Weather,
         window_title::WindowTitle,
         workspaces::Workspaces,
     },
     outputs::{HasOutput, Outputs},
     position_button::ButtonUIRef,
-    services::{Service, ServiceEvent, brightness::BrightnessCommand, tray::TrayEvent},
+    services::{
+        self, Service, ServiceEvent,
+        brightness::BrightnessCommand,
+        ipc::{self, IpcCommand},
+        tray::TrayEvent,
+    },
     style::{ashell_theme, backdrop_color, darken_color},
     utils,
+    wizard::{self, Wizard},
 };
 use flexi_logger::LoggerHandle;
 use iced::{
-    Alignment, Color, Element, Gradient, Length, Radians, Subscription, Task, Theme,
+    Alignment, Color, Element, Gradient, Length, Padding, Radians, Subscription, Task, Theme,
     daemon::Appearance,
     event::{
         listen_with,
         wayland::{Event as WaylandEvent, OutputEvent},
     },
     gradient::Linear,
-    widget::{Row, container},
+    keyboard, mouse,
+    time::every,
+    widget::{Row, container, text_input},
     window::Id,
 };
 use log::{debug, info, warn};
@@ -57,8 +75,21 @@ pub struct App {
     pub tray: TrayModule,
     pub clock: Clock,
     pub privacy: Privacy,
+    pub notifications: Notifications,
+    pub custom_command: CustomCommands,
+    pub weather: Weather,
+    pub uptime: Uptime,
+    pub pomodoro: Pomodoro,
+    pub systemd_units: SystemdUnits,
+    pub gamepad: Gamepad,
+    pub printer: Printer,
+    pub usb: Usb,
+    pub hidden_modules: Vec<ModuleName>,
+    pub layout_edit: Option<LayoutEdit>,
     pub settings: Settings,
     pub media_player: MediaPlayer,
+    pub wizard: Option<Wizard>,
+    pub debug_panel: Option<DebugPanel>,
 }
 
 #[derive(Debug, Clone)]
@@ -69,6 +100,12 @@ pub enum Message {
     CloseMenu(Id),
     OpenLauncher,
     OpenClipboard,
+    RunCommand(String),
+    DragModule(ModuleSection, usize),
+    DragModuleOver(ModuleSection, usize),
+    DropModule,
+    SetModuleVisible(ModuleName, bool),
+    SaveLayout,
     Updates(modules::updates::Message),
     Workspaces(modules::workspaces::Message),
     WindowTitle(modules::window_title::Message),
@@ -78,23 +115,46 @@ pub enum Message {
     Tray(modules::tray::TrayMessage),
     Clock(modules::clock::Message),
     Privacy(modules::privacy::PrivacyMessage),
+    Notifications(NotificationsMessage),
+    CustomCommand(CustomCommandMessage),
+    Weather(modules::weather::Message),
+    Uptime(modules::uptime::Message),
+    Pomodoro(modules::pomodoro::Message),
+    SystemdUnits(modules::systemd_units::SystemdUnitsMessage),
+    Gamepad(modules::gamepad::GamepadMessage),
+    Printer(modules::printer::PrinterMessage),
+    Usb(modules::usb::UsbMessage),
+    Clipboard(modules::clipboard::Message),
+    AppLauncher(modules::app_launcher::Message),
+    Ipc(IpcCommand),
     Settings(modules::settings::Message),
     MediaPlayer(modules::media_player::Message),
+    Wizard(wizard::Message),
+    ToggleDebugPanel,
+    DebugPanelTick,
     OutputEvent((OutputEvent, WlOutput)),
 }
 
 impl App {
     pub fn new((logger, config): (LoggerHandle, Config)) -> impl FnOnce() -> (Self, Task<Message>) {
         || {
-            let (outputs, task) = Outputs::new(config.appearance.style, config.position);
+            services::set_max_retry_attempts(config.max_retry_attempts);
+            let (outputs, task) =
+                Outputs::new(config.appearance.style, config.bar, config.position);
+            let (wizard, task) = if config::config_file_exists() {
+                (None, task)
+            } else {
+                let (wizard, wizard_task) = Wizard::open();
+                (Some(wizard), Task::batch(vec![task, wizard_task]))
+            };
 
             (
                 App {
                     logger,
                     outputs,
-                    app_launcher: AppLauncher,
+                    app_launcher: AppLauncher::default(),
                     updates: Updates::default(),
-                    clipboard: Clipboard,
+                    clipboard: Clipboard::default(),
                     workspaces: Workspaces::new(&config.workspaces),
                     window_title: WindowTitle::default(),
                     system_info: SystemInfo::default(),
@@ -103,8 +163,21 @@ impl App {
                     tray: TrayModule::default(),
                     clock: Clock::default(),
                     privacy: Privacy::default(),
+                    notifications: Notifications::default(),
+                    custom_command: CustomCommands::default(),
+                    weather: Weather::default(),
+                    uptime: Uptime::default(),
+                    pomodoro: Pomodoro::new(&config.pomodoro),
+                    systemd_units: SystemdUnits::default(),
+                    gamepad: Gamepad::default(),
+                    printer: Printer::default(),
+                    usb: Usb::default(),
+                    hidden_modules: utils::layout_state::load_hidden_modules(),
+                    layout_edit: None,
                     settings: Settings::default(),
                     media_player: MediaPlayer::default(),
+                    wizard,
+                    debug_panel: None,
                     config,
                 },
                 task,
@@ -112,12 +185,23 @@ impl App {
         }
     }
 
+    fn toggle_debug_panel(&mut self) -> Task<Message> {
+        match self.debug_panel.take() {
+            Some(debug_panel) => debug_panel.close(),
+            None => {
+                let (debug_panel, task) = DebugPanel::open();
+                self.debug_panel = Some(debug_panel);
+                task
+            }
+        }
+    }
+
     pub fn title(&self, _id: Id) -> String {
         String::from("ashell")
     }
 
     pub fn theme(&self, _id: Id) -> Theme {
-        ashell_theme(&self.config.appearance)
+        ashell_theme(&self.config.appearance, &self.config.accessibility)
     }
 
     pub fn style(&self, theme: &Theme) -> Appearance {
@@ -128,33 +212,79 @@ impl App {
         }
     }
 
+    // Modules read their configuration from `self.config` on every view and
+    // subscriptions are keyed by their config, so replacing it is enough for
+    // most changes. Only the layer surfaces need to be recreated explicitly.
+    fn apply_config(&mut self, config: Config) -> Task<Message> {
+        info!("New config: {:?}", config);
+        services::set_max_retry_attempts(config.max_retry_attempts);
+        let mut tasks = Vec::new();
+        info!(
+            "Current outputs: {:?}, new outputs: {:?}",
+            self.config.outputs, config.outputs
+        );
+        if self.config.outputs != config.outputs
+            || self.config.position != config.position
+            || self.config.output_overrides != config.output_overrides
+            || self.config.appearance.style != config.appearance.style
+            || self.config.bar != config.bar
+        {
+            warn!("Outputs changed, syncing");
+            tasks.push(self.outputs.sync(
+                config.appearance.style,
+                config.bar,
+                &config.outputs,
+                |name| config.position_for(name),
+            ));
+        }
+        self.config = config;
+
+        // drop the state held by modules removed from the layout, their
+        // subscriptions are already gone
+        if !self.config.is_module_enabled(ModuleName::MediaPlayer) {
+            self.media_player = MediaPlayer::default();
+        }
+        if !self.config.is_module_enabled(ModuleName::Tray) {
+            self.tray = TrayModule::default();
+        }
+        if !self.config.is_module_enabled(ModuleName::Privacy) {
+            self.privacy = Privacy::default();
+        }
+        if !self.config.is_module_enabled(ModuleName::SystemdUnits) {
+            self.systemd_units = SystemdUnits::default();
+        }
+        if !self.config.is_module_enabled(ModuleName::Gamepad) || !self.config.gamepad.show {
+            self.gamepad = Gamepad::default();
+        }
+        if !self.config.is_module_enabled(ModuleName::Printer) {
+            self.printer = Printer::default();
+        }
+        if !self.config.is_module_enabled(ModuleName::Usb) {
+            self.usb = Usb::default();
+        }
+        if !self.config.is_module_enabled(ModuleName::Notifications) {
+            self.notifications = Notifications::default();
+        }
+        if !self.config.is_module_enabled(ModuleName::CustomCommand) {
+            self.custom_command = CustomCommands::default();
+        }
+        if !self.config.is_module_enabled(ModuleName::Weather) {
+            self.weather = Weather::default();
+        }
+        if !self.config.is_module_enabled(ModuleName::Settings) {
+            self.settings = Settings::default();
+        }
+
+        self.logger
+            .set_new_spec(get_log_spec(&self.config.log_level));
+
+        Task::batch(tasks)
+    }
+
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::None => Task::none(),
-            Message::ConfigChanged(config) => {
-                info!("New config: {:?}", config);
-                let mut tasks = Vec::new();
-                info!(
-                    "Current outputs: {:?}, new outputs: {:?}",
-                    self.config.outputs, config.outputs
-                );
-                if self.config.outputs != config.outputs
-                    || self.config.position != config.position
-                    || self.config.appearance.style != config.appearance.style
-                {
-                    warn!("Outputs changed, syncing");
-                    tasks.push(self.outputs.sync(
-                        config.appearance.style,
-                        &config.outputs,
-                        config.position,
-                    ));
-                }
-                self.config = *config;
-                self.logger
-                    .set_new_spec(get_log_spec(&self.config.log_level));
-
-                Task::batch(tasks)
-            }
+            Message::ConfigChanged(config) => self.apply_config(*config),
             Message::ToggleMenu(menu_type, id, button_ui_ref) => {
                 let mut cmd = vec![];
                 match &menu_type {
@@ -173,6 +303,7 @@ impl App {
                     }
                     MenuType::Settings => {
                         self.settings.sub_menu = None;
+                        cmd.push(self.settings.refresh_niri_outputs());
 
                         if let Some(brightness) = self.settings.brightness.as_mut() {
                             cmd.push(brightness.command(BrightnessCommand::Refresh).map(|event| {
@@ -184,10 +315,26 @@ impl App {
                             }));
                         }
                     }
+                    MenuType::Calendar => {
+                        self.clock.reset_calendar();
+                    }
+                    MenuType::AppLauncher => {
+                        cmd.push(self.app_launcher.open().map(Message::AppLauncher));
+                    }
+                    MenuType::Usb => {
+                        cmd.push(self.usb.refresh());
+                    }
                     _ => {}
                 };
+                let launcher = menu_type == MenuType::AppLauncher;
                 cmd.push(self.outputs.toggle_menu(id, menu_type, button_ui_ref));
 
+                // the search field needs the keyboard as soon as the launcher opens
+                if launcher && self.outputs.menu_is_open() {
+                    cmd.push(self.outputs.request_keyboard(id));
+                    cmd.push(text_input::focus(search_input_id()));
+                }
+
                 Task::batch(cmd)
             }
             Message::CloseMenu(id) => self.outputs.close_menu(id),
@@ -211,21 +358,74 @@ impl App {
                 }
                 Task::none()
             }
-            Message::Workspaces(msg) => {
-                self.workspaces.update(msg, &self.config.workspaces);
+            Message::RunCommand(command) => {
+                utils::launcher::execute_command(command);
+                Task::none()
+            }
+            Message::DragModule(section, index) => {
+                if let Some(layout_edit) = self.layout_edit.as_mut() {
+                    layout_edit.dragging = Some((section, index));
+                    layout_edit.target = Some((section, index));
+                }
+                Task::none()
+            }
+            Message::DragModuleOver(section, index) => {
+                if let Some(layout_edit) = self.layout_edit.as_mut() {
+                    layout_edit.target = Some((section, index));
+                }
+                Task::none()
+            }
+            Message::DropModule => {
+                if let Some(layout_edit) = self.layout_edit.as_mut() {
+                    if let Some((from, to)) =
+                        layout_edit.dragging.take().zip(layout_edit.target.take())
+                    {
+                        // the file watcher reloads the config right after, with the same layout
+                        if self.config.modules.move_module(from, to) {
+                            if let Err(err) = config::write_modules(&self.config.modules) {
+                                warn!("Failed to save the modules layout: {}", err);
+                            }
+                        }
+                    }
+                }
+                Task::none()
+            }
+            Message::SetModuleVisible(module, visible) => {
+                // the module keeps its place in the layout, showing it puts it back there
+                self.hidden_modules.retain(|m| *m != module);
+                if !visible {
+                    self.hidden_modules.push(module);
+                }
+                utils::layout_state::save_hidden_modules(&self.hidden_modules);
 
+                if visible {
+                    Task::none()
+                } else {
+                    self.outputs
+                        .close_all_menu_if(MenuType::ModuleContext(module))
+                }
+            }
+            Message::SaveLayout => {
+                // the hidden modules are kept when the layout can't be saved
+                let modules = self.config.modules.without(&self.hidden_modules);
+                match config::write_modules(&modules) {
+                    Ok(()) => {
+                        self.config.modules = modules;
+                        self.hidden_modules.clear();
+                        utils::layout_state::save_hidden_modules(&self.hidden_modules);
+                    }
+                    Err(err) => warn!("Failed to save the modules layout: {}", err),
+                }
                 Task::none()
             }
+            Message::Workspaces(msg) => self.workspaces.update(msg, &self.config.workspaces),
             Message::WindowTitle(message) => {
                 self.window_title
                     .update(message, self.config.truncate_title_after_length);
                 Task::none()
             }
             Message::SystemInfo(message) => self.system_info.update(message),
-            Message::KeyboardLayout(message) => {
-                self.keyboard_layout.update(message);
-                Task::none()
-            }
+            Message::KeyboardLayout(message) => self.keyboard_layout.update(message),
             Message::KeyboardSubmap(message) => {
                 self.keyboard_submap.update(message);
                 Task::none()
@@ -241,10 +441,85 @@ impl App {
                 Task::batch(vec![self.tray.update(msg), close_tray])
             }
             Message::Clock(message) => {
-                self.clock.update(message);
+                self.clock.update(message, &self.config.calendar);
                 Task::none()
             }
-            Message::Privacy(msg) => self.privacy.update(msg),
+            Message::Privacy(msg) => self.privacy.update(msg, &self.config.privacy),
+            Message::Notifications(msg) => self.notifications.update(msg),
+            Message::CustomCommand(msg) => self.custom_command.update(msg, &self.config.custom),
+            Message::Weather(msg) => self.weather.update(msg),
+            Message::Uptime(msg) => {
+                self.uptime.update(msg);
+                Task::none()
+            }
+            Message::Ipc(command) => match command {
+                IpcCommand::ToggleModule(module) => {
+                    if let Some(index) = self.hidden_modules.iter().position(|m| *m == module) {
+                        self.hidden_modules.remove(index);
+                    } else {
+                        self.hidden_modules.push(module);
+                    }
+                    utils::layout_state::save_hidden_modules(&self.hidden_modules);
+                    Task::none()
+                }
+                IpcCommand::ToggleDebugPanel => self.toggle_debug_panel(),
+                IpcCommand::EditLayout => {
+                    self.layout_edit = match self.layout_edit {
+                        Some(_) => None,
+                        None => Some(LayoutEdit::default()),
+                    };
+                    Task::none()
+                }
+                IpcCommand::ReloadConfig => match config::read_config() {
+                    Ok(config) => self.apply_config(config),
+                    Err(err) => {
+                        warn!("Failed to read config file: {}", err);
+                        Task::none()
+                    }
+                },
+                IpcCommand::SetVolume(volume) => self.settings.update(
+                    modules::settings::Message::Audio(AudioMessage::SinkVolumeChanged(
+                        volume.clamp(0, 100),
+                    )),
+                    &self.config.settings,
+                    &mut self.outputs,
+                ),
+            },
+            Message::AppLauncher(msg) => {
+                let close_menu = matches!(
+                    msg,
+                    modules::app_launcher::Message::Launch
+                        | modules::app_launcher::Message::LaunchEntry(_)
+                        | modules::app_launcher::Message::Close
+                );
+
+                self.app_launcher.update(msg);
+
+                if close_menu {
+                    self.outputs.close_all_menu_if(MenuType::AppLauncher)
+                } else {
+                    Task::none()
+                }
+            }
+            Message::Clipboard(msg) => {
+                let close_menu = matches!(msg, modules::clipboard::Message::Copy(_));
+
+                self.clipboard.update(msg, &self.config.clipboard);
+
+                if close_menu {
+                    self.outputs.close_all_menu_if(MenuType::Clipboard)
+                } else {
+                    Task::none()
+                }
+            }
+            Message::Pomodoro(msg) => {
+                self.pomodoro.update(msg, &self.config.pomodoro);
+                Task::none()
+            }
+            Message::SystemdUnits(msg) => self.systemd_units.update(msg),
+            Message::Gamepad(msg) => self.gamepad.update(msg),
+            Message::Printer(msg) => self.printer.update(msg),
+            Message::Usb(msg) => self.usb.update(msg),
             Message::Settings(message) => {
                 self.settings
                     .update(message, &self.config.settings, &mut self.outputs)
@@ -259,8 +534,9 @@ impl App {
 
                     self.outputs.add(
                         self.config.appearance.style,
+                        self.config.bar,
                         &self.config.outputs,
-                        self.config.position,
+                        self.config.position_for(Some(name)),
                         name,
                         wl_output,
                     )
@@ -269,6 +545,7 @@ impl App {
                     info!("Output destroyed");
                     self.outputs.remove(
                         self.config.appearance.style,
+                        self.config.bar,
                         self.config.position,
                         wl_output,
                     )
@@ -276,46 +553,72 @@ impl App {
                 _ => Task::none(),
             },
             Message::MediaPlayer(msg) => self.media_player.update(msg),
+            Message::ToggleDebugPanel => self.toggle_debug_panel(),
+            Message::DebugPanelTick => {
+                if let Some(debug_panel) = self.debug_panel.as_mut() {
+                    debug_panel.refresh();
+                }
+                Task::none()
+            }
+            Message::Wizard(msg) => match self.wizard.as_mut().map(|wizard| wizard.update(msg)) {
+                Some(wizard::Action::Close) => self
+                    .wizard
+                    .take()
+                    .map(|wizard| wizard.close())
+                    .unwrap_or_else(Task::none),
+                _ => Task::none(),
+            },
         }
     }
 
     pub fn view(&self, id: Id) -> Element<Message> {
+        if let Some(wizard) = self.wizard.as_ref().filter(|wizard| wizard.id == id) {
+            return wizard
+                .view(self.config.appearance.menu_opacity())
+                .map(Message::Wizard);
+        }
+        if let Some(debug_panel) = self.debug_panel.as_ref().filter(|panel| panel.id == id) {
+            return debug_panel.view(self.config.appearance.menu_opacity());
+        }
+
+        let output_name = self.outputs.get_monitor_name(id);
+        let position = self.config.position_for(output_name);
+
         match self.outputs.has(id) {
             Some(HasOutput::Main) => {
-                let left = self.modules_section(
-                    &self.config.modules.left,
-                    id,
-                    self.config.appearance.opacity,
-                );
-                let center = self.modules_section(
-                    &self.config.modules.center,
-                    id,
-                    self.config.appearance.opacity,
-                );
-                let right = self.modules_section(
-                    &self.config.modules.right,
-                    id,
-                    self.config.appearance.opacity,
-                );
+                let modules = self.config.modules_for(output_name);
+                // bars with their own layout are not saved back, they can't be edited
+                let editable = std::ptr::eq(modules, &self.config.modules);
+                let [left, center, right] = [
+                    ModuleSection::Left,
+                    ModuleSection::Center,
+                    ModuleSection::Right,
+                ]
+                .map(|section| {
+                    self.modules_section(
+                        modules.section(section),
+                        section,
+                        editable,
+                        id,
+                        self.config.appearance.opacity,
+                    )
+                });
 
                 let centerbox = centerbox::Centerbox::new([left, center, right])
                     .spacing(4)
                     .width(Length::Fill)
                     .align_items(Alignment::Center)
-                    .height(
-                        if self.config.appearance.style == AppearanceStyle::Islands {
-                            HEIGHT
-                        } else {
-                            HEIGHT - 8
-                        } as f32,
-                    )
-                    .padding(
-                        if self.config.appearance.style == AppearanceStyle::Islands {
-                            [4, 4]
-                        } else {
-                            [0, 0]
-                        },
-                    );
+                    .height(self.config.bar.height_for(self.config.appearance.style) as f32)
+                    .padding({
+                        let padding = self.config.bar.padding_for(self.config.appearance.style);
+
+                        Padding {
+                            top: padding.top as f32,
+                            right: padding.right as f32,
+                            bottom: padding.bottom as f32,
+                            left: padding.left as f32,
+                        }
+                    });
 
                 container(centerbox)
                     .style(|t| container::Style {
@@ -342,14 +645,14 @@ impl App {
                                     Linear::new(Radians(PI))
                                         .add_stop(
                                             0.0,
-                                            match self.config.position {
+                                            match position {
                                                 Position::Top => start_color,
                                                 Position::Bottom => end_color,
                                             },
                                         )
                                         .add_stop(
                                             1.0,
-                                            match self.config.position {
+                                            match position {
                                                 Position::Top => end_color,
                                                 Position::Bottom => start_color,
                                             },
@@ -383,84 +686,176 @@ impl App {
                     })
                     .into()
             }
-            Some(HasOutput::Menu(menu_info)) => match menu_info {
-                Some((MenuType::Updates, button_ui_ref)) => menu_wrapper(
-                    id,
-                    self.updates
-                        .menu_view(id, self.config.appearance.menu.opacity)
-                        .map(Message::Updates),
-                    MenuSize::Normal,
-                    *button_ui_ref,
-                    self.config.position,
-                    self.config.appearance.style,
-                    self.config.appearance.menu.opacity,
-                    self.config.appearance.menu.backdrop,
-                ),
-                Some((MenuType::Tray(name), button_ui_ref)) => menu_wrapper(
-                    id,
-                    self.tray
-                        .menu_view(name, self.config.appearance.menu.opacity)
-                        .map(Message::Tray),
-                    MenuSize::Normal,
-                    *button_ui_ref,
-                    self.config.position,
-                    self.config.appearance.style,
-                    self.config.appearance.menu.opacity,
-                    self.config.appearance.menu.backdrop,
-                ),
-                Some((MenuType::Settings, button_ui_ref)) => menu_wrapper(
-                    id,
-                    self.settings
-                        .menu_view(
-                            id,
-                            &self.config.settings,
-                            self.config.appearance.menu.opacity,
-                        )
-                        .map(Message::Settings),
-                    MenuSize::Large,
-                    *button_ui_ref,
-                    self.config.position,
-                    self.config.appearance.style,
-                    self.config.appearance.menu.opacity,
-                    self.config.appearance.menu.backdrop,
-                ),
-                Some((MenuType::MediaPlayer, button_ui_ref)) => menu_wrapper(
-                    id,
-                    self.media_player
-                        .menu_view(
-                            &self.config.media_player,
-                            self.config.appearance.menu.opacity,
-                        )
-                        .map(Message::MediaPlayer),
-                    MenuSize::Large,
-                    *button_ui_ref,
-                    self.config.position,
-                    self.config.appearance.style,
-                    self.config.appearance.menu.opacity,
-                    self.config.appearance.menu.backdrop,
-                ),
-                Some((MenuType::SystemInfo, button_ui_ref)) => menu_wrapper(
-                    id,
-                    self.system_info.menu_view().map(Message::SystemInfo),
-                    MenuSize::Large,
-                    *button_ui_ref,
-                    self.config.position,
-                    self.config.appearance.style,
-                    self.config.appearance.menu.opacity,
-                    self.config.appearance.menu.backdrop,
-                ),
-                None => Row::new().into(),
-            },
+            Some(HasOutput::Menu(menu_info)) => {
+                let menu_opacity = self.config.appearance.menu_opacity();
+
+                match menu_info {
+                    Some((MenuType::Updates, button_ui_ref)) => menu_wrapper(
+                        id,
+                        self.updates
+                            .menu_view(id, menu_opacity)
+                            .map(Message::Updates),
+                        MenuSize::Normal,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        menu_opacity,
+                        self.config.appearance.menu.backdrop,
+                    ),
+                    Some((MenuType::Tray(name), button_ui_ref)) => menu_wrapper(
+                        id,
+                        self.tray.menu_view(name, menu_opacity).map(Message::Tray),
+                        MenuSize::Normal,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        menu_opacity,
+                        self.config.appearance.menu.backdrop,
+                    ),
+                    Some((MenuType::Settings, button_ui_ref)) => menu_wrapper(
+                        id,
+                        self.settings
+                            .menu_view(
+                                id,
+                                &self.config.settings,
+                                self.config.accessibility,
+                                menu_opacity,
+                            )
+                            .map(Message::Settings),
+                        MenuSize::Large,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        menu_opacity,
+                        self.config.appearance.menu.backdrop,
+                    ),
+                    Some((MenuType::MediaPlayer, button_ui_ref)) => menu_wrapper(
+                        id,
+                        self.media_player
+                            .menu_view(&self.config.media_player, menu_opacity)
+                            .map(Message::MediaPlayer),
+                        MenuSize::Large,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        menu_opacity,
+                        self.config.appearance.menu.backdrop,
+                    ),
+                    Some((MenuType::SystemInfo, button_ui_ref)) => menu_wrapper(
+                        id,
+                        self.system_info
+                            .menu_view(&self.config.system)
+                            .map(Message::SystemInfo),
+                        MenuSize::Large,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        menu_opacity,
+                        self.config.appearance.menu.backdrop,
+                    ),
+                    Some((MenuType::AppLauncher, button_ui_ref)) => menu_wrapper(
+                        id,
+                        self.app_launcher
+                            .menu_view(self.config.accessibility, menu_opacity)
+                            .map(Message::AppLauncher),
+                        MenuSize::Normal,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        menu_opacity,
+                        self.config.appearance.menu.backdrop,
+                    ),
+                    Some((MenuType::Clipboard, button_ui_ref)) => menu_wrapper(
+                        id,
+                        self.clipboard
+                            .menu_view(self.config.accessibility, menu_opacity)
+                            .map(Message::Clipboard),
+                        MenuSize::Normal,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        menu_opacity,
+                        self.config.appearance.menu.backdrop,
+                    ),
+                    Some((MenuType::Calendar, button_ui_ref)) => menu_wrapper(
+                        id,
+                        self.clock
+                            .menu_view(&self.config.calendar, menu_opacity)
+                            .map(Message::Clock),
+                        MenuSize::Normal,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        menu_opacity,
+                        self.config.appearance.menu.backdrop,
+                    ),
+                    Some((MenuType::Privacy, button_ui_ref)) => menu_wrapper(
+                        id,
+                        self.privacy.menu_view().map(Message::Privacy),
+                        MenuSize::Large,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        menu_opacity,
+                        self.config.appearance.menu.backdrop,
+                    ),
+                    Some((MenuType::Usb, button_ui_ref)) => menu_wrapper(
+                        id,
+                        self.usb.menu_view(menu_opacity).map(Message::Usb),
+                        MenuSize::Normal,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        menu_opacity,
+                        self.config.appearance.menu.backdrop,
+                    ),
+                    Some((MenuType::ModuleContext(module), button_ui_ref)) => menu_wrapper(
+                        id,
+                        self.module_context_menu(*module, menu_opacity),
+                        MenuSize::Normal,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        menu_opacity,
+                        self.config.appearance.menu.backdrop,
+                    ),
+                    None => Row::new().into(),
+                }
+            }
             None => Row::new().into(),
         }
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
         Subscription::batch(vec![
-            Subscription::batch(self.modules_subscriptions(&self.config.modules.left)),
-            Subscription::batch(self.modules_subscriptions(&self.config.modules.center)),
-            Subscription::batch(self.modules_subscriptions(&self.config.modules.right)),
+            Subscription::batch(self.modules_subscriptions(&self.config.enabled_modules())),
             config::subscription(),
+            ipc::subscription().map(Message::Ipc),
+            self.wizard
+                .as_ref()
+                .map(|wizard| wizard.subscription().map(Message::Wizard))
+                .unwrap_or_else(Subscription::none),
+            if self.debug_panel.is_some() {
+                every(Duration::from_secs(1)).map(|_| Message::DebugPanelTick)
+            } else {
+                Subscription::none()
+            },
+            // a release away from the modules ends the drag too, the surface keeps
+            // getting the pointer events until the button is released
+            if self
+                .layout_edit
+                .as_ref()
+                .is_some_and(|layout_edit| layout_edit.dragging.is_some())
+            {
+                listen_with(|evt, _, _| match evt {
+                    iced::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                        Some(Message::DropModule)
+                    }
+                    _ => None,
+                })
+            } else {
+                Subscription::none()
+            },
             listen_with(|evt, _, _| match evt {
                 iced::Event::PlatformSpecific(iced::event::PlatformSpecific::Wayland(
                     WaylandEvent::Output(event, wl_output),
@@ -468,6 +863,18 @@ impl App {
                     debug!("Wayland event: {:?}", event);
                     Some(Message::OutputEvent((event, wl_output)))
                 }
+                // only seen while one of the surfaces has the keyboard focus,
+                // ashell-msg toggle_debug_panel can be bound in the compositor instead
+                iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                    key: keyboard::Key::Character(c),
+                    modifiers,
+                    ..
+                }) if modifiers.control()
+                    && modifiers.shift()
+                    && c.as_str().eq_ignore_ascii_case("d") =>
+                {
+                    Some(Message::ToggleDebugPanel)
+                }
                 _ => None,
             }),
         ])
@@ -0,0 +1,57 @@
+//! Send a command to a running ashell instance, e.g.
+//! `ashell-msg toggle_module MediaPlayer` or `ashell-msg set_volume 75`.
+
+use std::{
+    env,
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    process::ExitCode,
+};
+
+fn socket_path() -> Option<PathBuf> {
+    env::var_os("XDG_RUNTIME_DIR").map(|dir| PathBuf::from(dir).join("ashell.sock"))
+}
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let Some(command) = args.next() else {
+        eprintln!("Usage: ashell-msg <command> [argument]");
+        return ExitCode::FAILURE;
+    };
+
+    // numbers are sent as numbers, everything else as a string
+    let argument = match args.next() {
+        Some(arg) => arg
+            .parse::<i64>()
+            .map_or_else(|_| serde_json::Value::String(arg), serde_json::Value::from),
+        None => serde_json::Value::Null,
+    };
+    let request = serde_json::Value::Object(serde_json::Map::from_iter([(command, argument)]));
+
+    let Some(socket_path) = socket_path() else {
+        eprintln!("XDG_RUNTIME_DIR is not set");
+        return ExitCode::FAILURE;
+    };
+
+    let result = UnixStream::connect(socket_path).and_then(|mut stream| {
+        writeln!(stream, "{}", request)?;
+
+        let mut response = String::new();
+        BufReader::new(stream).read_line(&mut response)?;
+
+        Ok(response)
+    });
+
+    match result {
+        Ok(response) if response.starts_with("ok") => ExitCode::SUCCESS,
+        Ok(response) => {
+            eprint!("{}", response);
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("Failed to connect to ashell: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
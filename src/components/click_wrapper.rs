@@ -0,0 +1,52 @@
+use crate::config::ClickAction;
+use iced::{Element, widget::mouse_area};
+
+/// Runs the configured commands when the wrapped element is clicked,
+/// a button without a command keeps the element's own behaviour
+pub struct ClickWrapper<'a, Message> {
+    content: Element<'a, Message>,
+    left: Option<Message>,
+    right: Option<Message>,
+    middle: Option<Message>,
+}
+
+impl<'a, Message: Clone + 'a> ClickWrapper<'a, Message> {
+    pub fn new(content: impl Into<Element<'a, Message>>) -> Self {
+        Self {
+            content: content.into(),
+            left: None,
+            right: None,
+            middle: None,
+        }
+    }
+
+    pub fn action(mut self, action: Option<&ClickAction>, on_click: fn(String) -> Message) -> Self {
+        if let Some(action) = action {
+            self.left = action.left.clone().map(on_click);
+            self.right = action.right.clone().map(on_click);
+            self.middle = action.middle.clone().map(on_click);
+        }
+        self
+    }
+}
+
+impl<'a, Message: Clone + 'a> From<ClickWrapper<'a, Message>> for Element<'a, Message> {
+    fn from(wrapper: ClickWrapper<'a, Message>) -> Self {
+        if wrapper.left.is_none() && wrapper.right.is_none() && wrapper.middle.is_none() {
+            return wrapper.content;
+        }
+
+        let mut area = mouse_area(wrapper.content);
+        if let Some(message) = wrapper.left {
+            area = area.on_press(message);
+        }
+        if let Some(message) = wrapper.right {
+            area = area.on_right_press(message);
+        }
+        if let Some(message) = wrapper.middle {
+            area = area.on_middle_press(message);
+        }
+
+        area.into()
+    }
+}
@@ -57,8 +57,12 @@ pub enum Icons {
     Power,
     Reboot,
     Suspend,
+    Hibernate,
+    Uptime,
+    Pomodoro,
     Logout,
     RightArrow,
+    LeftArrow,
     Brightness,
     Point,
     Close,
@@ -69,11 +73,45 @@ pub enum Icons {
     SkipNext,
     MusicNote,
     Drive,
+    HomeDrive,
+    ExternalDrive,
+    Gpu,
+    Bell,
+    BellOff,
+    Screenshot,
+    ScreenshotRegion,
     IpAddress,
     DownloadSpeed,
     UploadSpeed,
     Copy,
     RightChevron,
+    Shuffle,
+    ShuffleDisabled,
+    Repeat,
+    RepeatOff,
+    RepeatOnce,
+    Heart,
+    HeartOutline,
+    Keyboard,
+    Mouse,
+    Phone,
+    NightMode,
+    DayMode,
+    WeatherSunny,
+    WeatherPartlyCloudy,
+    WeatherCloudy,
+    WeatherFog,
+    WeatherRainy,
+    WeatherSnowy,
+    WeatherLightning,
+    Scratchpad,
+    Display,
+    Overview,
+    Record,
+    Cellular,
+    Ups,
+    Gamepad,
+    Printer,
 }
 
 impl From<Icons> for &'static str {
@@ -130,8 +168,12 @@ impl From<Icons> for &'static str {
             Icons::Power => "󰐥",
             Icons::Reboot => "󰑐",
             Icons::Suspend => "󰤄",
+            Icons::Hibernate => "󰜗",
+            Icons::Uptime => "󰅐",
+            Icons::Pomodoro => "󰔛",
             Icons::Logout => "󰗽",
             Icons::RightArrow => "󰁔",
+            Icons::LeftArrow => "󰁍",
             Icons::Brightness => "󰃠",
             Icons::Point => "",
             Icons::Close => "󰅖",
@@ -142,11 +184,45 @@ impl From<Icons> for &'static str {
             Icons::SkipNext => "󰒭",
             Icons::MusicNote => "󰎇",
             Icons::Drive => "󰋊",
+            Icons::HomeDrive => "󰋜",
+            Icons::ExternalDrive => "󰕓",
+            Icons::Gpu => "󰢮",
+            Icons::Bell => "󰂚",
+            Icons::BellOff => "󰂛",
+            Icons::Screenshot => "󰄀",
+            Icons::ScreenshotRegion => "󰆞",
             Icons::IpAddress => "󰩠",
             Icons::DownloadSpeed => "󰛴",
             Icons::UploadSpeed => "󰛶",
             Icons::Copy => "󰆏",
             Icons::RightChevron => "󰅂",
+            Icons::Shuffle => "󰒟",
+            Icons::ShuffleDisabled => "󰒞",
+            Icons::Repeat => "󰑖",
+            Icons::RepeatOff => "󰑗",
+            Icons::RepeatOnce => "󰑘",
+            Icons::Heart => "󰋑",
+            Icons::HeartOutline => "󰋕",
+            Icons::Keyboard => "󰌌",
+            Icons::Mouse => "󰍽",
+            Icons::Phone => "󰄜",
+            Icons::NightMode => "󰖔",
+            Icons::DayMode => "󰖙",
+            Icons::WeatherSunny => "󰖙",
+            Icons::WeatherPartlyCloudy => "󰖕",
+            Icons::WeatherCloudy => "󰖐",
+            Icons::WeatherFog => "󰖑",
+            Icons::WeatherRainy => "󰖗",
+            Icons::WeatherSnowy => "󰖘",
+            Icons::WeatherLightning => "󰖓",
+            Icons::Scratchpad => "󰌨",
+            Icons::Display => "󰍹",
+            Icons::Overview => "󰕰",
+            Icons::Record => "󰑊",
+            Icons::Cellular => "󰒢",
+            Icons::Ups => "󰄌",
+            Icons::Gamepad => "󰊗",
+            Icons::Printer => "󰐪",
         }
     }
 }
@@ -1 +1,4 @@
+pub mod click_wrapper;
 pub mod icons;
+pub mod sparkline;
+pub mod tooltip_wrapper;
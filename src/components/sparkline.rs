@@ -0,0 +1,82 @@
+use iced::{
+    Color, Element, Length, Point, Rectangle, Renderer, Theme, mouse,
+    widget::canvas::{self, Frame, Geometry, Path, Stroke},
+};
+
+type LineColor = fn(&Theme) -> Color;
+
+/// A small line graph, all the lines share the same auto scaled Y axis.
+#[derive(Default)]
+pub struct Sparkline {
+    lines: Vec<(Vec<u64>, LineColor)>,
+}
+
+impl Sparkline {
+    pub fn new() -> Self {
+        Self { lines: Vec::new() }
+    }
+
+    pub fn line(mut self, values: impl IntoIterator<Item = u64>, color: LineColor) -> Self {
+        self.lines.push((values.into_iter().collect(), color));
+        self
+    }
+
+    pub fn view<'a, Message: 'a>(self, width: f32, height: f32) -> Element<'a, Message> {
+        canvas::Canvas::new(self)
+            .width(Length::Fixed(width))
+            .height(Length::Fixed(height))
+            .into()
+    }
+}
+
+impl<Message> canvas::Program<Message> for Sparkline {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        let max = self
+            .lines
+            .iter()
+            .flat_map(|(values, _)| values.iter().copied())
+            .max()
+            .unwrap_or_default()
+            .max(1) as f32;
+
+        for (values, color) in &self.lines {
+            if values.len() < 2 {
+                continue;
+            }
+
+            let step = bounds.width / (values.len() - 1) as f32;
+            // keep the stroke inside the canvas
+            let height = bounds.height - 2.;
+            let line = Path::new(|builder| {
+                for (i, value) in values.iter().enumerate() {
+                    let point =
+                        Point::new(i as f32 * step, 1. + height - *value as f32 / max * height);
+
+                    if i == 0 {
+                        builder.move_to(point);
+                    } else {
+                        builder.line_to(point);
+                    }
+                }
+            });
+
+            frame.stroke(
+                &line,
+                Stroke::default().with_color(color(theme)).with_width(1.5),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
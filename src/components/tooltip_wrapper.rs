@@ -0,0 +1,47 @@
+use iced::{
+    Element,
+    widget::{container, tooltip},
+};
+
+/// Wraps an element with an optional tooltip, without a tooltip the element is returned as is
+pub struct TooltipWrapper<'a, Message> {
+    content: Element<'a, Message>,
+    tooltip: Option<Element<'a, Message>>,
+    position: tooltip::Position,
+}
+
+impl<'a, Message: 'a> TooltipWrapper<'a, Message> {
+    pub fn new(content: impl Into<Element<'a, Message>>) -> Self {
+        Self {
+            content: content.into(),
+            tooltip: None,
+            position: tooltip::Position::Bottom,
+        }
+    }
+
+    pub fn tooltip(mut self, tooltip: Option<impl Into<Element<'a, Message>>>) -> Self {
+        self.tooltip = tooltip.map(Into::into);
+        self
+    }
+
+    pub fn position(mut self, position: tooltip::Position) -> Self {
+        self.position = position;
+        self
+    }
+}
+
+impl<'a, Message: 'a> From<TooltipWrapper<'a, Message>> for Element<'a, Message> {
+    fn from(wrapper: TooltipWrapper<'a, Message>) -> Self {
+        match wrapper.tooltip {
+            Some(content) => tooltip(
+                wrapper.content,
+                container(content)
+                    .padding([4, 8])
+                    .style(container::rounded_box),
+                wrapper.position,
+            )
+            .into(),
+            None => wrapper.content,
+        }
+    }
+}
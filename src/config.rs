@@ -4,15 +4,21 @@ use iced::{
     futures::{SinkExt, StreamExt},
     stream::channel,
     theme::palette,
+    widget::tooltip,
 };
 use inotify::{Event, EventMask, Inotify, WatchMask};
-use serde::{Deserialize, Deserializer, de::Error};
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{Error, IntoDeserializer},
+};
 use std::collections::HashMap;
-use std::{any::TypeId, env, fs::File, io::Read, path::Path};
+use std::{any::TypeId, env, fs::File, io::Read, path::Path, time::Duration};
+use tokio::time::timeout;
 
-use crate::app::Message;
+use crate::{HEIGHT, app::Message};
 
 const CONFIG_PATH: &str = "~/.config/ashell/config.toml";
+const CONFIG_RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct UpdatesModuleConfig {
@@ -20,6 +26,115 @@ pub struct UpdatesModuleConfig {
     pub update_cmd: String,
 }
 
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CustomModuleConfig {
+    pub command: String,
+    #[serde(default = "default_custom_interval_ms")]
+    pub interval_ms: u64,
+    pub icon: Option<String>,
+    #[serde(default = "default_custom_max_chars")]
+    pub max_chars: u32,
+    pub on_click: Option<String>,
+}
+
+impl Default for CustomModuleConfig {
+    fn default() -> Self {
+        Self {
+            command: String::new(),
+            interval_ms: default_custom_interval_ms(),
+            icon: None,
+            max_chars: default_custom_max_chars(),
+            on_click: None,
+        }
+    }
+}
+
+fn default_custom_interval_ms() -> u64 {
+    5000
+}
+
+fn default_custom_max_chars() -> u32 {
+    50
+}
+
+#[derive(Deserialize, Clone, Copy, Default, Debug)]
+pub enum WeatherUnits {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct WeatherModuleConfig {
+    pub latitude: f64,
+    pub longitude: f64,
+    #[serde(default)]
+    pub units: WeatherUnits,
+    #[serde(default = "default_weather_update_interval")]
+    pub update_interval_minutes: u64,
+}
+
+fn default_weather_update_interval() -> u64 {
+    30
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ClipboardModuleConfig {
+    #[serde(default = "default_clipboard_history_size")]
+    pub history_size: usize,
+}
+
+impl Default for ClipboardModuleConfig {
+    fn default() -> Self {
+        Self {
+            history_size: default_clipboard_history_size(),
+        }
+    }
+}
+
+fn default_clipboard_history_size() -> usize {
+    10
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct PomodoroModuleConfig {
+    #[serde(default = "default_pomodoro_work_minutes")]
+    pub work_minutes: u64,
+    #[serde(default = "default_pomodoro_short_break_minutes")]
+    pub short_break_minutes: u64,
+    #[serde(default = "default_pomodoro_long_break_minutes")]
+    pub long_break_minutes: u64,
+    #[serde(default = "default_pomodoro_long_break_after")]
+    pub long_break_after: u8,
+}
+
+impl Default for PomodoroModuleConfig {
+    fn default() -> Self {
+        Self {
+            work_minutes: default_pomodoro_work_minutes(),
+            short_break_minutes: default_pomodoro_short_break_minutes(),
+            long_break_minutes: default_pomodoro_long_break_minutes(),
+            long_break_after: default_pomodoro_long_break_after(),
+        }
+    }
+}
+
+fn default_pomodoro_work_minutes() -> u64 {
+    25
+}
+
+fn default_pomodoro_short_break_minutes() -> u64 {
+    5
+}
+
+fn default_pomodoro_long_break_minutes() -> u64 {
+    15
+}
+
+fn default_pomodoro_long_break_after() -> u8 {
+    4
+}
+
 #[derive(Deserialize, Clone, Default, PartialEq, Eq, Debug)]
 pub enum WorkspaceVisibilityMode {
     #[default]
@@ -34,6 +149,10 @@ pub struct WorkspacesModuleConfig {
     #[serde(default)]
     pub enable_workspace_filling: bool,
     pub max_workspaces: Option<u32>,
+    // niri has no scratchpad, a named workspace takes its place
+    pub niri_scratchpad: Option<String>,
+    #[serde(default)]
+    pub show_overview_button: bool,
 }
 
 #[derive(Deserialize, Clone, Default, Debug)]
@@ -42,12 +161,53 @@ pub struct KeyboardLayoutModuleConfig {
     pub labels: HashMap<String, String>,
 }
 
+// `thresholds = { warn = 60, danger = 80 }` in a section, when missing
+// the section falls back to its `warn_threshold` and `alert_threshold` keys
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct ThresholdConfig {
+    pub warn: f32,
+    pub danger: f32,
+    // lower values are worse, set by the section and not by the user
+    #[serde(skip)]
+    pub inverted: bool,
+}
+
+impl ThresholdConfig {
+    pub fn new(warn: f32, danger: f32) -> Self {
+        Self {
+            warn,
+            danger,
+            inverted: false,
+        }
+    }
+
+    pub fn inverted(self) -> Self {
+        Self {
+            inverted: true,
+            ..self
+        }
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct SystemInfoCpu {
     #[serde(default = "default_cpu_warn_threshold")]
     pub warn_threshold: u32,
     #[serde(default = "default_cpu_alert_threshold")]
     pub alert_threshold: u32,
+    pub thresholds: Option<ThresholdConfig>,
+    #[serde(default = "default_cpu_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    #[serde(default)]
+    pub display_mode: CpuDisplayMode,
+}
+
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum CpuDisplayMode {
+    #[default]
+    Usage,
+    LoadAvg,
 }
 
 impl Default for SystemInfoCpu {
@@ -55,16 +215,34 @@ impl Default for SystemInfoCpu {
         Self {
             warn_threshold: default_cpu_warn_threshold(),
             alert_threshold: default_cpu_alert_threshold(),
+            thresholds: None,
+            poll_interval_ms: default_cpu_poll_interval_ms(),
+            display_mode: CpuDisplayMode::default(),
         }
     }
 }
 
+impl SystemInfoCpu {
+    pub fn thresholds(&self) -> ThresholdConfig {
+        self.thresholds.unwrap_or(ThresholdConfig::new(
+            self.warn_threshold as f32,
+            self.alert_threshold as f32,
+        ))
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct SystemInfoMemory {
     #[serde(default = "default_mem_warn_threshold")]
     pub warn_threshold: u32,
     #[serde(default = "default_mem_alert_threshold")]
     pub alert_threshold: u32,
+    pub thresholds: Option<ThresholdConfig>,
+    #[serde(default = "default_swap_warn_threshold")]
+    pub swap_warn_threshold: u8,
+    #[serde(default = "default_swap_danger_threshold")]
+    pub swap_danger_threshold: u8,
+    pub swap_thresholds: Option<ThresholdConfig>,
 }
 
 impl Default for SystemInfoMemory {
@@ -72,16 +250,39 @@ impl Default for SystemInfoMemory {
         Self {
             warn_threshold: default_mem_warn_threshold(),
             alert_threshold: default_mem_alert_threshold(),
+            thresholds: None,
+            swap_warn_threshold: default_swap_warn_threshold(),
+            swap_danger_threshold: default_swap_danger_threshold(),
+            swap_thresholds: None,
         }
     }
 }
 
+impl SystemInfoMemory {
+    pub fn thresholds(&self) -> ThresholdConfig {
+        self.thresholds.unwrap_or(ThresholdConfig::new(
+            self.warn_threshold as f32,
+            self.alert_threshold as f32,
+        ))
+    }
+
+    pub fn swap_thresholds(&self) -> ThresholdConfig {
+        self.swap_thresholds.unwrap_or(ThresholdConfig::new(
+            self.swap_warn_threshold as f32,
+            self.swap_danger_threshold as f32,
+        ))
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct SystemInfoTemperature {
     #[serde(default = "default_temp_warn_threshold")]
     pub warn_threshold: i32,
     #[serde(default = "default_temp_alert_threshold")]
     pub alert_threshold: i32,
+    pub thresholds: Option<ThresholdConfig>,
+    #[serde(default = "default_temp_sensors")]
+    pub sensors: Vec<String>,
 }
 
 impl Default for SystemInfoTemperature {
@@ -89,16 +290,30 @@ impl Default for SystemInfoTemperature {
         Self {
             warn_threshold: default_temp_warn_threshold(),
             alert_threshold: default_temp_alert_threshold(),
+            thresholds: None,
+            sensors: default_temp_sensors(),
         }
     }
 }
 
+impl SystemInfoTemperature {
+    pub fn thresholds(&self) -> ThresholdConfig {
+        self.thresholds.unwrap_or(ThresholdConfig::new(
+            self.warn_threshold as f32,
+            self.alert_threshold as f32,
+        ))
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct SystemInfoDisk {
     #[serde(default = "default_disk_warn_threshold")]
     pub warn_threshold: u32,
     #[serde(default = "default_disk_alert_threshold")]
     pub alert_threshold: u32,
+    pub thresholds: Option<ThresholdConfig>,
+    #[serde(default)]
+    pub paths: Vec<String>,
 }
 
 impl Default for SystemInfoDisk {
@@ -106,10 +321,21 @@ impl Default for SystemInfoDisk {
         Self {
             warn_threshold: default_disk_warn_threshold(),
             alert_threshold: default_disk_alert_threshold(),
+            thresholds: None,
+            paths: Vec::new(),
         }
     }
 }
 
+impl SystemInfoDisk {
+    pub fn thresholds(&self) -> ThresholdConfig {
+        self.thresholds.unwrap_or(ThresholdConfig::new(
+            self.warn_threshold as f32,
+            self.alert_threshold as f32,
+        ))
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub enum SystemIndicator {
     Cpu,
@@ -120,6 +346,7 @@ pub enum SystemIndicator {
     IpAddress,
     DownloadSpeed,
     UploadSpeed,
+    Gpu,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -152,6 +379,10 @@ fn default_cpu_alert_threshold() -> u32 {
     80
 }
 
+fn default_cpu_poll_interval_ms() -> u64 {
+    1000
+}
+
 fn default_mem_warn_threshold() -> u32 {
     70
 }
@@ -160,6 +391,14 @@ fn default_mem_alert_threshold() -> u32 {
     85
 }
 
+fn default_swap_warn_threshold() -> u8 {
+    50
+}
+
+fn default_swap_danger_threshold() -> u8 {
+    80
+}
+
 fn default_temp_warn_threshold() -> i32 {
     60
 }
@@ -168,6 +407,10 @@ fn default_temp_alert_threshold() -> i32 {
     80
 }
 
+fn default_temp_sensors() -> Vec<String> {
+    vec!["acpitz temp1".to_string()]
+}
+
 fn default_disk_warn_threshold() -> u32 {
     80
 }
@@ -191,16 +434,102 @@ impl Default for SystemModuleConfig {
 #[derive(Deserialize, Clone, Debug)]
 pub struct ClockModuleConfig {
     pub format: String,
+    pub timezone: Option<String>,
+    pub secondary_format: Option<String>,
+    pub secondary_timezone: Option<String>,
 }
 
 impl Default for ClockModuleConfig {
     fn default() -> Self {
         Self {
             format: "%a %d %b %R".to_string(),
+            timezone: None,
+            secondary_format: None,
+            secondary_timezone: None,
         }
     }
 }
 
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub enum NotificationDaemon {
+    #[default]
+    Mako,
+    Dunst,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NotificationsModuleConfig {
+    #[serde(default)]
+    pub daemon: NotificationDaemon,
+    #[serde(default = "default_notifications_warn_threshold")]
+    pub warn_threshold: u32,
+}
+
+impl Default for NotificationsModuleConfig {
+    fn default() -> Self {
+        Self {
+            daemon: NotificationDaemon::default(),
+            warn_threshold: default_notifications_warn_threshold(),
+        }
+    }
+}
+
+fn default_notifications_warn_threshold() -> u32 {
+    5
+}
+
+#[derive(Deserialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PrivacyModuleConfig {
+    #[serde(default)]
+    pub camera_devices: Vec<String>,
+    #[serde(default)]
+    pub audit_log: bool,
+}
+
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct AccessibilityConfig {
+    #[serde(default)]
+    pub large_targets: bool,
+    #[serde(default)]
+    pub high_contrast: bool,
+}
+
+impl AccessibilityConfig {
+    pub fn button_padding(&self) -> [u16; 2] {
+        if self.large_targets { [8, 20] } else { [4, 12] }
+    }
+
+    // the bar height limits how much the module buttons can grow vertically
+    pub fn module_padding(&self) -> [u16; 2] {
+        if self.large_targets { [4, 14] } else { [2, 8] }
+    }
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+pub struct GamepadModuleConfig {
+    #[serde(default)]
+    pub show: bool,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+pub struct PrinterModuleConfig {
+    #[serde(default)]
+    pub show_when_idle: bool,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SystemdUnitConfig {
+    pub name: String,
+    pub label: Option<String>,
+    #[serde(default)]
+    pub user: bool,
+}
+
+#[derive(Deserialize, Default, Clone, Debug)]
+pub struct CalendarConfig {
+    pub on_date_click: Option<String>,
+}
+
 #[derive(Deserialize, Default, Clone, Debug)]
 pub struct SettingsModuleConfig {
     pub lock_cmd: Option<String>,
@@ -209,6 +538,222 @@ pub struct SettingsModuleConfig {
     pub wifi_more_cmd: Option<String>,
     pub vpn_more_cmd: Option<String>,
     pub bluetooth_more_cmd: Option<String>,
+    #[serde(default)]
+    pub night_mode: NightModeConfig,
+    #[serde(default)]
+    pub idle_inhibitor: IdleInhibitorConfig,
+    #[serde(default)]
+    pub screenshot: ScreenshotConfig,
+    #[serde(default)]
+    pub power: PowerConfig,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct PowerConfig {
+    #[serde(default = "default_power_require_confirmation")]
+    pub require_confirmation: bool,
+    // falls back to the lock_cmd of the settings, then to swaylock
+    pub locker: Option<String>,
+    #[serde(default)]
+    pub locker_args: Vec<String>,
+    pub show_hibernate: Option<bool>,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        Self {
+            require_confirmation: default_power_require_confirmation(),
+            locker: None,
+            locker_args: Vec::new(),
+            show_hibernate: None,
+        }
+    }
+}
+
+fn default_power_require_confirmation() -> bool {
+    true
+}
+
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum ScreenshotTool {
+    #[default]
+    Grim,
+    Wayshot,
+}
+
+#[derive(Deserialize, Default, Clone, Debug)]
+pub struct ScreenshotConfig {
+    #[serde(default)]
+    pub tool: ScreenshotTool,
+    #[serde(default)]
+    pub copy_to_clipboard: bool,
+    #[serde(default)]
+    pub prompt_save: bool,
+}
+
+#[derive(Deserialize, Default, Clone, Debug)]
+pub struct IdleInhibitorConfig {
+    /// Minutes after which the idle inhibitor is disabled automatically
+    pub max_duration: Option<u64>,
+}
+
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum NightModeTool {
+    #[default]
+    #[serde(alias = "Wlsunset")]
+    Wlsunset,
+    #[serde(alias = "Gammastep")]
+    Gammastep,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct NightModeConfig {
+    #[serde(default)]
+    pub tool: NightModeTool,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    #[serde(default = "default_night_mode_day_temp")]
+    pub day_temp: u32,
+    #[serde(default = "default_night_mode_night_temp")]
+    pub night_temp: u32,
+}
+
+impl Default for NightModeConfig {
+    fn default() -> Self {
+        Self {
+            tool: NightModeTool::default(),
+            latitude: None,
+            longitude: None,
+            day_temp: default_night_mode_day_temp(),
+            night_temp: default_night_mode_night_temp(),
+        }
+    }
+}
+
+fn default_night_mode_day_temp() -> u32 {
+    6500
+}
+
+fn default_night_mode_night_temp() -> u32 {
+    4000
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct BatteryConfig {
+    #[serde(default = "default_battery_warn_threshold")]
+    pub warn_threshold: u8,
+    #[serde(default = "default_battery_critical_threshold")]
+    pub critical_threshold: u8,
+    pub thresholds: Option<ThresholdConfig>,
+    #[serde(default = "default_battery_health_warn_threshold")]
+    pub health_warn_threshold: u8,
+    #[serde(default = "default_battery_use_display_device")]
+    pub use_display_device: bool,
+    #[serde(default = "default_battery_ups_threshold")]
+    pub ups_threshold: u8,
+}
+
+impl Default for BatteryConfig {
+    fn default() -> Self {
+        Self {
+            warn_threshold: default_battery_warn_threshold(),
+            critical_threshold: default_battery_critical_threshold(),
+            thresholds: None,
+            health_warn_threshold: default_battery_health_warn_threshold(),
+            use_display_device: default_battery_use_display_device(),
+            ups_threshold: default_battery_ups_threshold(),
+        }
+    }
+}
+
+impl BatteryConfig {
+    pub fn thresholds(&self) -> ThresholdConfig {
+        self.thresholds
+            .unwrap_or(ThresholdConfig::new(
+                self.warn_threshold as f32,
+                self.critical_threshold as f32,
+            ))
+            .inverted()
+    }
+}
+
+fn default_battery_warn_threshold() -> u8 {
+    20
+}
+
+fn default_battery_critical_threshold() -> u8 {
+    10
+}
+
+fn default_battery_health_warn_threshold() -> u8 {
+    60
+}
+
+fn default_battery_use_display_device() -> bool {
+    true
+}
+
+fn default_battery_ups_threshold() -> u8 {
+    50
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct AudioConfig {
+    #[serde(default = "default_audio_scroll_step")]
+    pub scroll_step: f64,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            scroll_step: default_audio_scroll_step(),
+        }
+    }
+}
+
+fn default_audio_scroll_step() -> f64 {
+    5.
+}
+
+#[derive(Deserialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BrightnessConfig {
+    #[serde(default)]
+    pub use_ddc: bool,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NetworkConfig {
+    #[serde(default = "default_network_throughput_interface")]
+    pub throughput_interface: String,
+    #[serde(default)]
+    pub show_throughput: bool,
+    #[serde(default = "default_network_show_vpn")]
+    pub show_vpn: bool,
+    #[serde(default)]
+    pub interfaces: Vec<String>,
+    #[serde(default)]
+    pub min_update_interval_ms: u64,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            throughput_interface: default_network_throughput_interface(),
+            show_throughput: false,
+            show_vpn: default_network_show_vpn(),
+            interfaces: Vec::new(),
+            min_update_interval_ms: 0,
+        }
+    }
+}
+
+fn default_network_throughput_interface() -> String {
+    "auto".to_owned()
+}
+
+fn default_network_show_vpn() -> bool {
+    true
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -229,6 +774,13 @@ fn default_media_player_max_title_length() -> u32 {
     100
 }
 
+#[derive(Deserialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MprisConfig {
+    pub preferred_player: Option<String>,
+    #[serde(default)]
+    pub excluded_players: Vec<String>,
+}
+
 #[derive(Deserialize, Clone, Copy, Debug)]
 #[serde(untagged)]
 pub enum AppearanceColor {
@@ -293,10 +845,18 @@ pub enum AppearanceStyle {
     Gradient,
 }
 
+#[derive(Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TooltipPosition {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct MenuAppearance {
-    #[serde(default = "default_opacity")]
-    pub opacity: f32,
+    #[serde(default)]
+    pub opacity: Option<f32>,
     #[serde(default)]
     pub backdrop: f32,
 }
@@ -304,7 +864,7 @@ pub struct MenuAppearance {
 impl Default for MenuAppearance {
     fn default() -> Self {
         Self {
-            opacity: default_opacity(),
+            opacity: None,
             backdrop: f32::default(),
         }
     }
@@ -315,105 +875,382 @@ pub struct Appearance {
     #[serde(default)]
     pub font_name: Option<String>,
     #[serde(default)]
+    pub font_path: Option<String>,
+    #[serde(default)]
+    pub font_size: Option<f32>,
+    #[serde(default)]
     pub style: AppearanceStyle,
     #[serde(default = "default_opacity")]
     pub opacity: f32,
     #[serde(default)]
     pub menu: MenuAppearance,
-    #[serde(default = "default_background_color")]
-    pub background_color: AppearanceColor,
-    #[serde(default = "default_primary_color")]
-    pub primary_color: AppearanceColor,
-    #[serde(default = "default_secondary_color")]
-    pub secondary_color: AppearanceColor,
-    #[serde(default = "default_success_color")]
-    pub success_color: AppearanceColor,
-    #[serde(default = "default_danger_color")]
-    pub danger_color: AppearanceColor,
-    #[serde(default = "default_text_color")]
-    pub text_color: AppearanceColor,
-    #[serde(default = "default_workspace_colors")]
-    pub workspace_colors: Vec<AppearanceColor>,
+    #[serde(default)]
+    pub tooltip_position: Option<TooltipPosition>,
+    #[serde(default)]
+    pub theme: AppearanceTheme,
+    #[serde(default)]
+    background_color: Option<AppearanceColor>,
+    #[serde(default)]
+    primary_color: Option<AppearanceColor>,
+    #[serde(default)]
+    secondary_color: Option<AppearanceColor>,
+    #[serde(default)]
+    success_color: Option<AppearanceColor>,
+    #[serde(default)]
+    danger_color: Option<AppearanceColor>,
+    #[serde(default)]
+    text_color: Option<AppearanceColor>,
+    #[serde(default)]
+    workspace_colors: Option<Vec<AppearanceColor>>,
     pub special_workspace_colors: Option<Vec<AppearanceColor>>,
 }
 
-static PRIMARY: HexColor = HexColor::rgb(250, 179, 135);
-
 fn default_opacity() -> f32 {
     1.0
 }
 
-fn default_background_color() -> AppearanceColor {
-    AppearanceColor::Complete {
-        base: HexColor::rgb(30, 30, 46),
-        strong: Some(HexColor::rgb(69, 71, 90)),
-        weak: Some(HexColor::rgb(49, 50, 68)),
-        text: None,
+impl Appearance {
+    // menus follow the bar opacity unless they have their own
+    pub fn menu_opacity(&self) -> f32 {
+        self.menu.opacity.unwrap_or(self.opacity).clamp(0.0, 1.0)
     }
-}
 
-fn default_primary_color() -> AppearanceColor {
-    AppearanceColor::Complete {
-        base: PRIMARY,
-        strong: None,
-        weak: None,
-        text: Some(HexColor::rgb(30, 30, 46)),
+    // colors set in the config win over the ones of the theme
+    pub fn background_color(&self) -> AppearanceColor {
+        self.background_color
+            .unwrap_or(self.theme.colors().background)
     }
-}
 
-fn default_secondary_color() -> AppearanceColor {
-    AppearanceColor::Complete {
-        base: HexColor::rgb(17, 17, 27),
-        strong: Some(HexColor::rgb(24, 24, 37)),
-        weak: None,
-        text: None,
+    pub fn primary_color(&self) -> AppearanceColor {
+        self.primary_color.unwrap_or(self.theme.colors().primary)
+    }
+
+    pub fn secondary_color(&self) -> AppearanceColor {
+        self.secondary_color
+            .unwrap_or(self.theme.colors().secondary)
+    }
+
+    pub fn success_color(&self) -> AppearanceColor {
+        self.success_color.unwrap_or(self.theme.colors().success)
+    }
+
+    pub fn danger_color(&self) -> AppearanceColor {
+        self.danger_color.unwrap_or(self.theme.colors().danger)
+    }
+
+    pub fn text_color(&self) -> AppearanceColor {
+        self.text_color.unwrap_or(self.theme.colors().text)
+    }
+
+    pub fn workspace_colors(&self) -> &[AppearanceColor] {
+        self.workspace_colors
+            .as_deref()
+            .unwrap_or(self.theme.colors().workspace_colors)
     }
 }
 
-fn default_success_color() -> AppearanceColor {
-    AppearanceColor::Simple(HexColor::rgb(166, 227, 161))
+#[derive(Deserialize, Default, Copy, Clone, Eq, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum AppearanceTheme {
+    Dark,
+    Light,
+    #[default]
+    CatppuccinMocha,
+    GruvboxDark,
 }
 
-fn default_danger_color() -> AppearanceColor {
-    AppearanceColor::Complete {
-        base: HexColor::rgb(243, 139, 168),
-        weak: Some(HexColor::rgb(249, 226, 175)),
-        strong: None,
-        text: None,
-    }
+pub struct ThemeColors {
+    pub background: AppearanceColor,
+    pub primary: AppearanceColor,
+    pub secondary: AppearanceColor,
+    pub success: AppearanceColor,
+    // the weak variant is used for the warning state
+    pub danger: AppearanceColor,
+    pub text: AppearanceColor,
+    pub workspace_colors: &'static [AppearanceColor],
+    pub is_dark: bool,
 }
 
-fn default_text_color() -> AppearanceColor {
-    AppearanceColor::Simple(HexColor::rgb(205, 214, 244))
+const fn complete(
+    base: HexColor,
+    strong: Option<HexColor>,
+    weak: Option<HexColor>,
+    text: Option<HexColor>,
+) -> AppearanceColor {
+    AppearanceColor::Complete {
+        base,
+        strong,
+        weak,
+        text,
+    }
 }
 
-fn default_workspace_colors() -> Vec<AppearanceColor> {
-    vec![
-        AppearanceColor::Simple(PRIMARY),
+const CATPPUCCIN_MOCHA: ThemeColors = ThemeColors {
+    background: complete(
+        HexColor::rgb(30, 30, 46),
+        Some(HexColor::rgb(69, 71, 90)),
+        Some(HexColor::rgb(49, 50, 68)),
+        None,
+    ),
+    primary: complete(
+        HexColor::rgb(250, 179, 135),
+        None,
+        None,
+        Some(HexColor::rgb(30, 30, 46)),
+    ),
+    secondary: complete(
+        HexColor::rgb(17, 17, 27),
+        Some(HexColor::rgb(24, 24, 37)),
+        None,
+        None,
+    ),
+    success: AppearanceColor::Simple(HexColor::rgb(166, 227, 161)),
+    danger: complete(
+        HexColor::rgb(243, 139, 168),
+        None,
+        Some(HexColor::rgb(249, 226, 175)),
+        None,
+    ),
+    text: AppearanceColor::Simple(HexColor::rgb(205, 214, 244)),
+    workspace_colors: &[
+        AppearanceColor::Simple(HexColor::rgb(250, 179, 135)),
         AppearanceColor::Simple(HexColor::rgb(180, 190, 254)),
         AppearanceColor::Simple(HexColor::rgb(203, 166, 247)),
-    ]
+    ],
+    is_dark: true,
+};
+
+const DARK: ThemeColors = ThemeColors {
+    background: complete(
+        HexColor::rgb(30, 30, 30),
+        Some(HexColor::rgb(64, 64, 64)),
+        Some(HexColor::rgb(45, 45, 45)),
+        None,
+    ),
+    primary: complete(
+        HexColor::rgb(122, 162, 247),
+        None,
+        None,
+        Some(HexColor::rgb(30, 30, 30)),
+    ),
+    secondary: complete(
+        HexColor::rgb(18, 18, 18),
+        Some(HexColor::rgb(26, 26, 26)),
+        None,
+        None,
+    ),
+    success: AppearanceColor::Simple(HexColor::rgb(139, 195, 74)),
+    danger: complete(
+        HexColor::rgb(239, 83, 80),
+        None,
+        Some(HexColor::rgb(255, 202, 40)),
+        None,
+    ),
+    text: AppearanceColor::Simple(HexColor::rgb(224, 224, 224)),
+    workspace_colors: &[
+        AppearanceColor::Simple(HexColor::rgb(122, 162, 247)),
+        AppearanceColor::Simple(HexColor::rgb(187, 154, 247)),
+        AppearanceColor::Simple(HexColor::rgb(125, 207, 255)),
+    ],
+    is_dark: true,
+};
+
+const LIGHT: ThemeColors = ThemeColors {
+    background: complete(
+        HexColor::rgb(239, 241, 245),
+        Some(HexColor::rgb(204, 208, 218)),
+        Some(HexColor::rgb(230, 233, 239)),
+        None,
+    ),
+    primary: complete(
+        HexColor::rgb(254, 100, 11),
+        None,
+        None,
+        Some(HexColor::rgb(239, 241, 245)),
+    ),
+    secondary: complete(
+        HexColor::rgb(220, 224, 232),
+        Some(HexColor::rgb(204, 208, 218)),
+        None,
+        None,
+    ),
+    success: AppearanceColor::Simple(HexColor::rgb(64, 160, 43)),
+    danger: complete(
+        HexColor::rgb(210, 15, 57),
+        None,
+        Some(HexColor::rgb(223, 142, 29)),
+        None,
+    ),
+    text: AppearanceColor::Simple(HexColor::rgb(76, 79, 105)),
+    workspace_colors: &[
+        AppearanceColor::Simple(HexColor::rgb(254, 100, 11)),
+        AppearanceColor::Simple(HexColor::rgb(114, 135, 253)),
+        AppearanceColor::Simple(HexColor::rgb(136, 57, 239)),
+    ],
+    is_dark: false,
+};
+
+const GRUVBOX_DARK: ThemeColors = ThemeColors {
+    background: complete(
+        HexColor::rgb(40, 40, 40),
+        Some(HexColor::rgb(80, 73, 69)),
+        Some(HexColor::rgb(60, 56, 54)),
+        None,
+    ),
+    primary: complete(
+        HexColor::rgb(254, 128, 25),
+        None,
+        None,
+        Some(HexColor::rgb(40, 40, 40)),
+    ),
+    secondary: complete(
+        HexColor::rgb(29, 32, 33),
+        Some(HexColor::rgb(50, 48, 47)),
+        None,
+        None,
+    ),
+    success: AppearanceColor::Simple(HexColor::rgb(184, 187, 38)),
+    danger: complete(
+        HexColor::rgb(251, 73, 52),
+        None,
+        Some(HexColor::rgb(250, 189, 47)),
+        None,
+    ),
+    text: AppearanceColor::Simple(HexColor::rgb(235, 219, 178)),
+    workspace_colors: &[
+        AppearanceColor::Simple(HexColor::rgb(254, 128, 25)),
+        AppearanceColor::Simple(HexColor::rgb(131, 165, 152)),
+        AppearanceColor::Simple(HexColor::rgb(211, 134, 155)),
+    ],
+    is_dark: true,
+};
+
+impl AppearanceTheme {
+    pub const ALL: [AppearanceTheme; 4] = [
+        AppearanceTheme::CatppuccinMocha,
+        AppearanceTheme::GruvboxDark,
+        AppearanceTheme::Dark,
+        AppearanceTheme::Light,
+    ];
+
+    // the name used in the config file
+    pub fn name(self) -> &'static str {
+        match self {
+            AppearanceTheme::Dark => "dark",
+            AppearanceTheme::Light => "light",
+            AppearanceTheme::CatppuccinMocha => "catppuccin-mocha",
+            AppearanceTheme::GruvboxDark => "gruvbox-dark",
+        }
+    }
+
+    pub fn colors(self) -> &'static ThemeColors {
+        match self {
+            AppearanceTheme::Dark => &DARK,
+            AppearanceTheme::Light => &LIGHT,
+            AppearanceTheme::CatppuccinMocha => &CATPPUCCIN_MOCHA,
+            AppearanceTheme::GruvboxDark => &GRUVBOX_DARK,
+        }
+    }
 }
 
 impl Default for Appearance {
     fn default() -> Self {
         Self {
             font_name: None,
+            font_path: None,
+            font_size: None,
             style: AppearanceStyle::default(),
             opacity: default_opacity(),
             menu: MenuAppearance::default(),
-            background_color: default_background_color(),
-            primary_color: default_primary_color(),
-            secondary_color: default_secondary_color(),
-            success_color: default_success_color(),
-            danger_color: default_danger_color(),
-            text_color: default_text_color(),
-            workspace_colors: default_workspace_colors(),
+            tooltip_position: None,
+            theme: AppearanceTheme::default(),
+            background_color: None,
+            primary_color: None,
+            secondary_color: None,
+            success_color: None,
+            danger_color: None,
+            text_color: None,
+            workspace_colors: None,
             special_workspace_colors: None,
         }
     }
 }
 
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClickAction {
+    #[serde(default)]
+    pub left: Option<String>,
+    #[serde(default)]
+    pub right: Option<String>,
+    #[serde(default)]
+    pub middle: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ModuleClickActions {
+    pub module: ModuleName,
+    #[serde(flatten)]
+    pub actions: ClickAction,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EdgesConfig {
+    #[serde(default)]
+    pub top: u32,
+    #[serde(default)]
+    pub bottom: u32,
+    #[serde(default)]
+    pub left: u32,
+    #[serde(default)]
+    pub right: u32,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BarConfig {
+    #[serde(default)]
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub padding: Option<EdgesConfig>,
+    #[serde(default)]
+    pub margin: EdgesConfig,
+    #[serde(default)]
+    pub exclusive_zone: Option<i32>,
+}
+
+impl BarConfig {
+    pub fn height_for(&self, style: AppearanceStyle) -> u32 {
+        self.height.unwrap_or(match style {
+            AppearanceStyle::Solid | AppearanceStyle::Gradient => HEIGHT - 8,
+            AppearanceStyle::Islands => HEIGHT,
+        })
+    }
+
+    pub fn padding_for(&self, style: AppearanceStyle) -> EdgesConfig {
+        self.padding.unwrap_or(match style {
+            AppearanceStyle::Islands => EdgesConfig {
+                top: 4,
+                bottom: 4,
+                left: 4,
+                right: 4,
+            },
+            AppearanceStyle::Solid | AppearanceStyle::Gradient => EdgesConfig::default(),
+        })
+    }
+
+    // the compositor adds the margin on the anchored edge on its own,
+    // only the one between the bar and the windows is part of the reserved space
+    pub fn exclusive_zone_for(&self, style: AppearanceStyle, position: Position) -> i32 {
+        self.exclusive_zone.unwrap_or_else(|| {
+            let inner_margin = match position {
+                Position::Top => self.margin.bottom,
+                Position::Bottom => self.margin.top,
+            };
+
+            (self.height_for(style) + inner_margin) as i32
+        })
+    }
+}
+
 #[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum Position {
     #[default]
@@ -421,7 +1258,7 @@ pub enum Position {
     Bottom,
 }
 
-#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ModuleName {
     AppLauncher,
     Updates,
@@ -436,25 +1273,146 @@ pub enum ModuleName {
     Privacy,
     Settings,
     MediaPlayer,
+    Notifications,
+    CustomCommand,
+    Weather,
+    Uptime,
+    Pomodoro,
+    SystemdUnits,
+    Gamepad,
+    Printer,
+    Usb,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum ModuleDef {
     Single(ModuleName),
     Group(Vec<ModuleName>),
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Modules {
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient_modules")]
     pub left: Vec<ModuleDef>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient_modules")]
     pub center: Vec<ModuleDef>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient_modules")]
     pub right: Vec<ModuleDef>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModuleSection {
+    Left,
+    Center,
+    Right,
+}
+
+impl Modules {
+    pub fn section(&self, section: ModuleSection) -> &Vec<ModuleDef> {
+        match section {
+            ModuleSection::Left => &self.left,
+            ModuleSection::Center => &self.center,
+            ModuleSection::Right => &self.right,
+        }
+    }
+
+    // the same layout without the given modules, groups left empty are dropped
+    pub fn without(&self, hidden: &[ModuleName]) -> Modules {
+        let filter = |section: &Vec<ModuleDef>| {
+            section
+                .iter()
+                .filter_map(|module| match module {
+                    ModuleDef::Single(name) => {
+                        (!hidden.contains(name)).then_some(ModuleDef::Single(*name))
+                    }
+                    ModuleDef::Group(group) => {
+                        let group = group
+                            .iter()
+                            .filter(|name| !hidden.contains(name))
+                            .copied()
+                            .collect::<Vec<_>>();
+                        (!group.is_empty()).then_some(ModuleDef::Group(group))
+                    }
+                })
+                .collect()
+        };
+
+        Modules {
+            left: filter(&self.left),
+            center: filter(&self.center),
+            right: filter(&self.right),
+        }
+    }
+
+    fn section_mut(&mut self, section: ModuleSection) -> &mut Vec<ModuleDef> {
+        match section {
+            ModuleSection::Left => &mut self.left,
+            ModuleSection::Center => &mut self.center,
+            ModuleSection::Right => &mut self.right,
+        }
+    }
+
+    // moves a module, or a whole group, in front of the one at `to`,
+    // returns false when nothing changed
+    pub fn move_module(
+        &mut self,
+        (from_section, from): (ModuleSection, usize),
+        (to_section, to): (ModuleSection, usize),
+    ) -> bool {
+        if from >= self.section(from_section).len()
+            || (from_section == to_section && (from == to || from + 1 == to))
+        {
+            return false;
+        }
+
+        let module = self.section_mut(from_section).remove(from);
+        let to = if from_section == to_section && from < to {
+            to - 1
+        } else {
+            to
+        };
+        let target = self.section_mut(to_section);
+        target.insert(to.min(target.len()), module);
+
+        true
+    }
+}
+
+// unknown modules are skipped instead of discarding the whole config
+fn lenient_modules<'de, D>(d: D) -> Result<Vec<ModuleDef>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawModuleDef {
+        Single(String),
+        Group(Vec<String>),
+    }
+
+    fn module_name(name: &str) -> Option<ModuleName> {
+        ModuleName::deserialize(name.into_deserializer())
+            .map_err(|_: serde::de::value::Error| log::warn!("Unknown module: {}", name))
+            .ok()
+    }
+
+    Ok(<Vec<RawModuleDef>>::deserialize(d)?
+        .into_iter()
+        .filter_map(|def| match def {
+            RawModuleDef::Single(name) => module_name(&name).map(ModuleDef::Single),
+            RawModuleDef::Group(names) => {
+                let group = names
+                    .iter()
+                    .filter_map(|name| module_name(name))
+                    .collect::<Vec<_>>();
+
+                (!group.is_empty()).then_some(ModuleDef::Group(group))
+            }
+        })
+        .collect())
+}
+
 impl Default for Modules {
     fn default() -> Self {
         Self {
@@ -491,6 +1449,13 @@ where
     }
 }
 
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OutputConfig {
+    pub name: String,
+    pub position: Option<Position>,
+    pub modules: Option<Modules>,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct Config {
     #[serde(default = "default_log_level")]
@@ -498,13 +1463,23 @@ pub struct Config {
     #[serde(default)]
     pub position: Position,
     #[serde(default)]
+    pub bar: BarConfig,
+    #[serde(default)]
     pub outputs: Outputs,
     #[serde(default)]
+    pub module_click_actions: Vec<ModuleClickActions>,
+    #[serde(default, rename = "output")]
+    pub output_overrides: Vec<OutputConfig>,
+    #[serde(default)]
     pub modules: Modules,
     pub app_launcher_cmd: Option<String>,
     pub clipboard_cmd: Option<String>,
+    #[serde(default)]
+    pub clipboard: ClipboardModuleConfig,
     #[serde(default = "default_truncate_title_after_length")]
     pub truncate_title_after_length: u32,
+    #[serde(default = "default_max_retry_attempts")]
+    pub max_retry_attempts: u32,
     #[serde(default)]
     pub updates: Option<UpdatesModuleConfig>,
     #[serde(default)]
@@ -516,11 +1491,41 @@ pub struct Config {
     #[serde(default)]
     pub settings: SettingsModuleConfig,
     #[serde(default)]
+    pub audio: AudioConfig,
+    #[serde(default)]
+    pub battery: BatteryConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub brightness: BrightnessConfig,
+    #[serde(default)]
     pub appearance: Appearance,
     #[serde(default)]
     pub media_player: MediaPlayerModuleConfig,
     #[serde(default)]
+    pub mpris: MprisConfig,
+    #[serde(default)]
     pub keyboard_layout: KeyboardLayoutModuleConfig,
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+    #[serde(default)]
+    pub notifications: NotificationsModuleConfig,
+    #[serde(default)]
+    pub privacy: PrivacyModuleConfig,
+    #[serde(default)]
+    pub custom: Vec<CustomModuleConfig>,
+    #[serde(default)]
+    pub weather: Option<WeatherModuleConfig>,
+    #[serde(default)]
+    pub pomodoro: PomodoroModuleConfig,
+    #[serde(default)]
+    pub systemd_units: Vec<SystemdUnitConfig>,
+    #[serde(default)]
+    pub gamepad: GamepadModuleConfig,
+    #[serde(default)]
+    pub printer: PrinterModuleConfig,
+    #[serde(default)]
+    pub accessibility: AccessibilityConfig,
 }
 
 fn default_log_level() -> String {
@@ -531,28 +1536,245 @@ fn default_truncate_title_after_length() -> u32 {
     150
 }
 
+fn default_max_retry_attempts() -> u32 {
+    crate::services::DEFAULT_MAX_RETRY_ATTEMPTS
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             log_level: default_log_level(),
             position: Position::Top,
+            bar: BarConfig::default(),
             outputs: Outputs::default(),
+            module_click_actions: Vec::new(),
+            output_overrides: Vec::new(),
             modules: Modules::default(),
             app_launcher_cmd: None,
             clipboard_cmd: None,
+            clipboard: ClipboardModuleConfig::default(),
             truncate_title_after_length: default_truncate_title_after_length(),
+            max_retry_attempts: default_max_retry_attempts(),
             updates: None,
             workspaces: WorkspacesModuleConfig::default(),
             system: SystemModuleConfig::default(),
             clock: ClockModuleConfig::default(),
             settings: SettingsModuleConfig::default(),
+            audio: AudioConfig::default(),
+            battery: BatteryConfig::default(),
+            network: NetworkConfig::default(),
+            brightness: BrightnessConfig::default(),
             appearance: Appearance::default(),
             media_player: MediaPlayerModuleConfig::default(),
+            mpris: MprisConfig::default(),
             keyboard_layout: KeyboardLayoutModuleConfig::default(),
+            calendar: CalendarConfig::default(),
+            notifications: NotificationsModuleConfig::default(),
+            privacy: PrivacyModuleConfig::default(),
+            custom: Vec::new(),
+            weather: None,
+            pomodoro: PomodoroModuleConfig::default(),
+            systemd_units: Vec::new(),
+            gamepad: GamepadModuleConfig::default(),
+            printer: PrinterModuleConfig::default(),
+            accessibility: AccessibilityConfig::default(),
         }
     }
 }
 
+impl Config {
+    fn output_override(&self, name: Option<&str>) -> Option<&OutputConfig> {
+        name.and_then(|name| self.output_overrides.iter().find(|o| o.name == name))
+    }
+
+    pub fn position_for(&self, name: Option<&str>) -> Position {
+        self.output_override(name)
+            .and_then(|o| o.position)
+            .unwrap_or(self.position)
+    }
+
+    pub fn modules_for(&self, name: Option<&str>) -> &Modules {
+        self.output_override(name)
+            .and_then(|o| o.modules.as_ref())
+            .unwrap_or(&self.modules)
+    }
+
+    // modules shown on at least one bar, each listed once
+    pub fn enabled_modules(&self) -> Vec<ModuleName> {
+        let mut enabled = Vec::new();
+        for name in self.all_modules().flat_map(|modules| {
+            [&modules.left, &modules.center, &modules.right]
+                .into_iter()
+                .flatten()
+                .flat_map(|module_def| match module_def {
+                    ModuleDef::Single(module) => std::slice::from_ref(module),
+                    ModuleDef::Group(group) => group.as_slice(),
+                })
+        }) {
+            if !enabled.contains(name) {
+                enabled.push(*name);
+            }
+        }
+
+        enabled
+    }
+
+    pub fn click_action(&self, module: ModuleName) -> Option<&ClickAction> {
+        self.module_click_actions
+            .iter()
+            .find(|a| a.module == module)
+            .map(|a| &a.actions)
+    }
+
+    pub fn is_module_enabled(&self, module: ModuleName) -> bool {
+        self.enabled_modules().contains(&module)
+    }
+
+    // without an explicit position tooltips open away from the screen edge
+    pub fn tooltip_position(&self) -> tooltip::Position {
+        match (self.appearance.tooltip_position, self.position) {
+            (Some(TooltipPosition::Top), _) | (None, Position::Bottom) => tooltip::Position::Top,
+            (Some(TooltipPosition::Bottom), _) | (None, Position::Top) => tooltip::Position::Bottom,
+            (Some(TooltipPosition::Left), _) => tooltip::Position::Left,
+            (Some(TooltipPosition::Right), _) => tooltip::Position::Right,
+        }
+    }
+
+    // every module set, used to keep the subscriptions of all the bars alive
+    pub fn all_modules(&self) -> impl Iterator<Item = &Modules> {
+        std::iter::once(&self.modules).chain(
+            self.output_overrides
+                .iter()
+                .filter_map(|o| o.modules.as_ref()),
+        )
+    }
+}
+
+// only the [modules] table is touched, the rest of the file keeps its formatting and comments
+pub fn write_modules(modules: &Modules) -> anyhow::Result<()> {
+    let home_dir = env::var("HOME")?;
+    let file_path = format!("{}{}", home_dir, CONFIG_PATH.replace('~', ""));
+
+    let content = std::fs::read_to_string(&file_path).unwrap_or_default();
+    let mut document = content.parse::<toml_edit::DocumentMut>()?;
+
+    // the modules skipped while parsing aren't in the layout, writing it back would drop them
+    let unknown = unknown_modules(&document);
+    if !unknown.is_empty() {
+        anyhow::bail!(
+            "the [modules] table has unknown modules ({}), fix them first",
+            unknown.join(", ")
+        );
+    }
+
+    insert_modules(&mut document, modules)?;
+
+    std::fs::write(&file_path, document.to_string())?;
+
+    Ok(())
+}
+
+fn insert_modules(document: &mut toml_edit::DocumentMut, modules: &Modules) -> anyhow::Result<()> {
+    let to_array = |modules: &[ModuleDef]| {
+        modules
+            .iter()
+            .map(|module| match module {
+                ModuleDef::Single(name) => toml_edit::Value::from(format!("{:?}", name)),
+                ModuleDef::Group(group) => toml_edit::Value::Array(
+                    group.iter().map(|name| format!("{:?}", name)).collect(),
+                ),
+            })
+            .collect::<toml_edit::Array>()
+    };
+
+    let table = document
+        .entry("modules")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or_else(|| anyhow::anyhow!("modules is not a table"))?;
+    table.insert("left", toml_edit::value(to_array(&modules.left)));
+    table.insert("center", toml_edit::value(to_array(&modules.center)));
+    table.insert("right", toml_edit::value(to_array(&modules.right)));
+
+    Ok(())
+}
+
+fn unknown_modules(document: &toml_edit::DocumentMut) -> Vec<String> {
+    let Some(table) = document
+        .get("modules")
+        .and_then(|item| item.as_table_like())
+    else {
+        return Vec::new();
+    };
+
+    ["left", "center", "right"]
+        .iter()
+        .filter_map(|section| table.get(section).and_then(|item| item.as_array()))
+        .flatten()
+        .flat_map(|value| match value.as_array() {
+            Some(group) => group.iter().collect::<Vec<_>>(),
+            None => vec![value],
+        })
+        .filter_map(|value| value.as_str())
+        .filter(|name| {
+            let module: Result<ModuleName, serde::de::value::Error> =
+                ModuleName::deserialize(name.into_deserializer());
+            module.is_err()
+        })
+        .map(str::to_owned)
+        .collect()
+}
+
+pub fn config_file_exists() -> bool {
+    env::var("HOME").is_ok_and(|home_dir| {
+        Path::new(&format!("{}{}", home_dir, CONFIG_PATH.replace('~', ""))).exists()
+    })
+}
+
+pub struct FirstRunConfig {
+    pub modules: Modules,
+    pub clock_format: String,
+    pub theme: AppearanceTheme,
+    pub preferred_player: Option<String>,
+}
+
+// written once by the first run wizard, the config watcher then loads it like any other edit
+pub fn write_first_run_config(first_run: &FirstRunConfig) -> anyhow::Result<()> {
+    let home_dir = env::var("HOME")?;
+    let file_path = format!("{}{}", home_dir, CONFIG_PATH.replace('~', ""));
+
+    let mut document = toml_edit::DocumentMut::new();
+    insert_modules(&mut document, &first_run.modules)?;
+    document["clock"]["format"] = toml_edit::value(first_run.clock_format.as_str());
+    document["appearance"]["theme"] = toml_edit::value(first_run.theme.name());
+    if let Some(player) = &first_run.preferred_player {
+        document["mpris"]["preferred_player"] = toml_edit::value(player.as_str());
+    }
+
+    if let Some(dir) = Path::new(&file_path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&file_path, document.to_string())?;
+
+    Ok(())
+}
+
+// written when the first run wizard is skipped, every option keeps its default
+pub fn write_empty_config() -> anyhow::Result<()> {
+    let home_dir = env::var("HOME")?;
+    let file_path = format!("{}{}", home_dir, CONFIG_PATH.replace('~', ""));
+
+    if let Some(dir) = Path::new(&file_path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(
+        &file_path,
+        "# ashell configuration, see the README for the available options\n",
+    )?;
+
+    Ok(())
+}
+
 pub fn read_config() -> Result<Config, toml::de::Error> {
     let home_dir = env::var("HOME").expect("Could not get HOME environment variable");
     let file_path = format!("{}{}", home_dir, CONFIG_PATH.replace('~', ""));
@@ -635,6 +1857,24 @@ pub fn subscription() -> Subscription<Message> {
                                 _ => {
                                     log::info!("ashell config file events: {:?}", name);
                                     if name.is_some_and(|name| name == "config.toml") {
+                                        // editors can write the file several times on save,
+                                        // wait for the events to settle before reloading
+                                        while let Ok(Some(event)) =
+                                            timeout(CONFIG_RELOAD_DEBOUNCE, stream.next()).await
+                                        {
+                                            log::debug!("ashell config folder event: {:?}", event);
+                                        }
+
+                                        if !ashell_config_dir.exists() {
+                                            log::warn!("ashell config directory disappear");
+
+                                            let _ = output
+                                                .send(Message::ConfigChanged(Box::default()))
+                                                .await;
+
+                                            break;
+                                        }
+
                                         let new_config = read_config();
                                         if let Ok(new_config) = new_config {
                                             let _ = output
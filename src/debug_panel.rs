@@ -0,0 +1,110 @@
+use crate::services::inspector::{self, ServiceDebugInfo};
+use iced::{
+    Border, Element, Length, Task, Theme,
+    platform_specific::shell::commands::layer_surface::{
+        Anchor, KeyboardInteractivity, Layer, destroy_layer_surface, get_layer_surface,
+    },
+    runtime::platform_specific::wayland::layer_surface::{IcedOutput, SctkLayerSurfaceSettings},
+    widget::{Column, column, container, horizontal_rule, row, scrollable, text},
+    window::Id,
+};
+use std::collections::BTreeMap;
+
+const WIDTH: u32 = 720;
+const HEIGHT: u32 = 520;
+
+pub struct DebugPanel {
+    pub id: Id,
+    services: BTreeMap<&'static str, ServiceDebugInfo>,
+}
+
+impl DebugPanel {
+    // only the events sent while the panel is open are shown
+    pub fn open<AppMessage: 'static>() -> (Self, Task<AppMessage>) {
+        let id = Id::unique();
+        inspector::set_enabled(true);
+
+        let task = get_layer_surface(SctkLayerSurfaceSettings {
+            id,
+            namespace: "ashell-debug".to_string(),
+            size: Some((Some(WIDTH), Some(HEIGHT))),
+            layer: Layer::Overlay,
+            pointer_interactivity: true,
+            keyboard_interactivity: KeyboardInteractivity::OnDemand,
+            output: IcedOutput::Active,
+            anchor: Anchor::empty(),
+            ..Default::default()
+        });
+
+        (
+            Self {
+                id,
+                services: inspector::snapshot(),
+            },
+            task,
+        )
+    }
+
+    pub fn close<AppMessage: 'static>(&self) -> Task<AppMessage> {
+        inspector::set_enabled(false);
+        destroy_layer_surface(self.id)
+    }
+
+    pub fn refresh(&mut self) {
+        self.services = inspector::snapshot();
+    }
+
+    pub fn view<'a, Message: 'a>(&'a self, opacity: f32) -> Element<'a, Message> {
+        let rows = self.services.iter().map(|(name, info)| {
+            column!(
+                row!(
+                    text(*name).width(Length::Fill),
+                    text(format!(
+                        "{} updates, last {}s ago",
+                        info.event_count,
+                        info.last_event.elapsed().as_secs()
+                    ))
+                    .size(12),
+                )
+                .spacing(8),
+                text(info.data.as_str()).size(11),
+            )
+            .spacing(4)
+            .into()
+        });
+
+        container(
+            column!(
+                text(format!("Services ({})", self.services.len())).size(18),
+                horizontal_rule(1),
+            )
+            .push_maybe(
+                self.services
+                    .is_empty()
+                    .then(|| text("Waiting for the services to send an event").size(12)),
+            )
+            .push(scrollable(
+                Column::with_children(rows).spacing(12).padding([0, 12]),
+            ))
+            .spacing(12),
+        )
+        .padding(16)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(move |theme: &Theme| container::Style {
+            background: Some(theme.palette().background.scale_alpha(opacity).into()),
+            border: Border {
+                color: theme
+                    .extended_palette()
+                    .secondary
+                    .base
+                    .color
+                    .scale_alpha(opacity),
+                width: 1.,
+                radius: 16.0.into(),
+            },
+            ..Default::default()
+        })
+        .into()
+    }
+}
@@ -4,7 +4,7 @@ use flexi_logger::{
     Age, Cleanup, Criterion, FileSpec, LogSpecBuilder, LogSpecification, Logger, Naming,
 };
 use iced::Font;
-use log::error;
+use log::{error, warn};
 use std::panic;
 use std::{backtrace::Backtrace, borrow::Cow};
 
@@ -12,6 +12,7 @@ mod app;
 mod centerbox;
 mod components;
 mod config;
+mod debug_panel;
 mod menu;
 mod modules;
 mod outputs;
@@ -20,10 +21,18 @@ mod position_button;
 mod services;
 mod style;
 mod utils;
+mod wizard;
 
 const ICON_FONT: &[u8] = include_bytes!("../assets/SymbolsNerdFont-Regular.ttf");
 const HEIGHT: u32 = 34;
 
+fn font_settings(config: &Config) -> Font {
+    match config.appearance.font_name {
+        Some(ref font_name) => Font::with_name(Box::leak(font_name.clone().into_boxed_str())),
+        None => Font::DEFAULT,
+    }
+}
+
 fn get_log_spec(log_level: &str) -> LogSpecification {
     LogSpecification::env_or_parse(log_level).unwrap_or_else(|err| {
         panic!("Failed to parse log level: {}", err);
@@ -64,16 +73,34 @@ async fn main() -> iced::Result {
 
     logger.set_new_spec(get_log_spec(&config.log_level));
 
-    let font = match config.appearance.font_name {
-        Some(ref font_name) => Font::with_name(Box::leak(font_name.clone().into_boxed_str())),
-        None => Font::DEFAULT,
+    let font = font_settings(&config);
+    // a font that isn't installed can be loaded from a file, font_name picks the family
+    let font_file = config.appearance.font_path.as_ref().and_then(|path| {
+        std::fs::read(path)
+            .inspect_err(|err| warn!("Failed to read font file {}: {}", path, err))
+            .ok()
+    });
+    let settings = iced::Settings {
+        default_text_size: config
+            .appearance
+            .font_size
+            .map(iced::Pixels)
+            .unwrap_or(iced::Settings::default().default_text_size),
+        ..Default::default()
     };
 
-    iced::daemon(App::title, App::update, App::view)
+    let daemon = iced::daemon(App::title, App::update, App::view)
+        .settings(settings)
         .subscription(App::subscription)
         .theme(App::theme)
         .style(App::style)
-        .font(Cow::from(ICON_FONT))
+        .font(Cow::from(ICON_FONT));
+    let daemon = match font_file {
+        Some(font_file) => daemon.font(Cow::from(font_file)),
+        None => daemon,
+    };
+
+    daemon
         .default_font(font)
         .run_with(App::new((logger, config)))
 }
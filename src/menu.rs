@@ -1,5 +1,5 @@
 use crate::app::{self};
-use crate::config::{AppearanceStyle, Position};
+use crate::config::{AppearanceStyle, ModuleName, Position};
 use crate::position_button::ButtonUIRef;
 use crate::style::backdrop_color;
 use iced::alignment::{Horizontal, Vertical};
@@ -19,6 +19,12 @@ pub enum MenuType {
     Tray(String),
     MediaPlayer,
     SystemInfo,
+    Calendar,
+    Clipboard,
+    AppLauncher,
+    ModuleContext(ModuleName),
+    Privacy,
+    Usb,
 }
 
 #[derive(Clone, Debug)]
@@ -1,17 +1,321 @@
 use crate::{
-    app::{self, Message},
+    app::{self},
     components::icons::{Icons, icon},
+    config::AccessibilityConfig,
+    menu::MenuType,
+    services::tray::{TrayIcon, get_icon_from_name},
+    style::{ghost_button_style, text_input_style},
+    utils::launcher::execute_command,
+};
+use iced::{
+    Element, Length, Subscription, Task, Theme,
+    event::listen_with,
+    keyboard::{self, key::Named},
+    widget::{Column, Image, Svg, button, column, container, row, text, text_input},
+};
+use log::debug;
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs,
+    path::PathBuf,
 };
-use iced::Element;
 
 use super::{Module, OnModulePress};
 
+const MAX_RESULTS: usize = 10;
+
+pub fn search_input_id() -> text_input::Id {
+    text_input::Id::new("app-launcher-search")
+}
+
+#[derive(Debug, Clone)]
+pub struct DesktopEntry {
+    name: String,
+    exec: String,
+    icon: Option<String>,
+}
+
 #[derive(Default, Debug, Clone)]
-pub struct AppLauncher;
+pub struct AppLauncher {
+    entries: Vec<DesktopEntry>,
+    query: String,
+    results: Vec<(usize, Option<TrayIcon>)>,
+    selected: usize,
+    // the icon theme lookup walks the disk, each name is resolved once
+    icons: HashMap<String, Option<TrayIcon>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    EntriesLoaded(Vec<DesktopEntry>),
+    QueryChanged(String),
+    SelectNext,
+    SelectPrevious,
+    Launch,
+    LaunchEntry(usize),
+    Close,
+}
+
+fn applications_dirs() -> Vec<PathBuf> {
+    let data_home = env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .or_else(|| {
+            env::var("HOME")
+                .ok()
+                .map(|home| format!("{}/.local/share", home))
+        });
+    let data_dirs = env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_owned());
+
+    data_home
+        .into_iter()
+        .chain(data_dirs.split(':').map(str::to_owned))
+        .map(|dir| PathBuf::from(dir).join("applications"))
+        .collect()
+}
+
+fn parse_desktop_entry(content: &str) -> Option<DesktopEntry> {
+    let mut in_entry = false;
+    let (mut name, mut exec, mut icon) = (None, None, None);
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_entry {
+            continue;
+        }
+
+        match line.split_once('=').map(|(k, v)| (k.trim(), v.trim())) {
+            Some(("Name", value)) => name = Some(value.to_owned()),
+            Some(("Exec", value)) => exec = Some(value.to_owned()),
+            Some(("Icon", value)) => icon = Some(value.to_owned()),
+            Some(("Type", value)) if value != "Application" => return None,
+            Some(("NoDisplay" | "Hidden", "true")) => return None,
+            _ => {}
+        }
+    }
+
+    Some(DesktopEntry {
+        name: name?,
+        exec: exec?,
+        icon,
+    })
+}
+
+// the first file with a given id wins, as in the XDG lookup order
+fn load_entries() -> Vec<DesktopEntry> {
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+
+    for dir in applications_dirs() {
+        let Ok(files) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for path in files.flatten().map(|file| file.path()) {
+            if path.extension().is_none_or(|ext| ext != "desktop") {
+                continue;
+            }
+            let Some(id) = path.file_name().map(|id| id.to_owned()) else {
+                continue;
+            };
+            if !seen.insert(id) {
+                continue;
+            }
+
+            if let Some(entry) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| parse_desktop_entry(&content))
+            {
+                entries.push(entry);
+            }
+        }
+    }
+
+    debug!("Loaded {} desktop entries", entries.len());
+    entries.sort_by_key(|entry| entry.name.to_lowercase());
+
+    entries
+}
+
+fn trigrams(value: &str) -> HashSet<[char; 3]> {
+    let chars = format!("  {} ", value.to_lowercase())
+        .chars()
+        .collect::<Vec<_>>();
+
+    chars.windows(3).map(|w| [w[0], w[1], w[2]]).collect()
+}
+
+fn similarity(query: &HashSet<[char; 3]>, value: &str) -> f32 {
+    let value = trigrams(value);
+    let common = query.intersection(&value).count();
+    let total = query.union(&value).count();
+
+    if total == 0 {
+        0.
+    } else {
+        common as f32 / total as f32
+    }
+}
+
+// drop the field codes, the launcher never passes files or urls
+fn strip_field_codes(exec: &str) -> String {
+    exec.split_whitespace()
+        .filter(|arg| !(arg.len() == 2 && arg.starts_with('%') && *arg != "%%"))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("%%", "%")
+}
+
+impl AppLauncher {
+    /// Clears the search, the entries are read again in the background since
+    /// an app may have been installed meanwhile.
+    pub fn open(&mut self) -> Task<Message> {
+        self.query.clear();
+        self.search();
+
+        Task::perform(
+            async {
+                tokio::task::spawn_blocking(load_entries)
+                    .await
+                    .unwrap_or_default()
+            },
+            Message::EntriesLoaded,
+        )
+    }
+
+    fn search(&mut self) {
+        let query = self.query.trim().to_lowercase();
+        let mut ranked = if query.is_empty() {
+            (0..self.entries.len()).map(|index| (index, 0.)).collect()
+        } else {
+            let query_trigrams = trigrams(&query);
+
+            self.entries
+                .iter()
+                .enumerate()
+                .filter_map(|(index, entry)| {
+                    let mut score = similarity(&query_trigrams, &entry.name);
+                    if entry.name.to_lowercase().contains(&query) {
+                        score += 1.;
+                    }
+
+                    (score > 0.).then_some((index, score))
+                })
+                .collect::<Vec<_>>()
+        };
+        ranked.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        self.results = ranked
+            .into_iter()
+            .take(MAX_RESULTS)
+            .map(|(index, _)| {
+                let icon = self.entries[index].icon.as_ref().and_then(|name| {
+                    self.icons
+                        .entry(name.clone())
+                        .or_insert_with(|| get_icon_from_name(name))
+                        .clone()
+                });
+
+                (index, icon)
+            })
+            .collect();
+        self.selected = 0;
+    }
+
+    fn launch(&self, index: usize) {
+        if let Some(entry) = self.entries.get(index) {
+            execute_command(strip_field_codes(&entry.exec));
+        }
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::EntriesLoaded(entries) => {
+                self.entries = entries;
+                self.search();
+            }
+            Message::QueryChanged(query) => {
+                self.query = query;
+                self.search();
+            }
+            Message::SelectNext => {
+                if self.selected + 1 < self.results.len() {
+                    self.selected += 1;
+                }
+            }
+            Message::SelectPrevious => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            Message::Launch => {
+                if let Some((index, _)) = self.results.get(self.selected) {
+                    self.launch(*index);
+                }
+            }
+            Message::LaunchEntry(index) => {
+                self.launch(index);
+            }
+            Message::Close => {}
+        }
+    }
+
+    pub fn menu_view(&self, accessibility: AccessibilityConfig, opacity: f32) -> Element<Message> {
+        column!(
+            text_input("Search applications", &self.query)
+                .id(search_input_id())
+                .size(16)
+                .padding([8, 16])
+                .style(text_input_style)
+                .on_input(Message::QueryChanged)
+                .on_submit(Message::Launch),
+            Column::with_children(
+                self.results
+                    .iter()
+                    .enumerate()
+                    .map(|(position, (index, entry_icon))| {
+                        let entry_icon: Element<_> = match entry_icon {
+                            Some(TrayIcon::Image(handle)) => {
+                                Image::new(handle.clone()).height(Length::Fixed(16.)).into()
+                            }
+                            Some(TrayIcon::Svg(handle)) => Svg::new(handle.clone())
+                                .height(Length::Fixed(16.))
+                                .width(Length::Fixed(16.))
+                                .into(),
+                            None => icon(Icons::AppLauncher).into(),
+                        };
+                        let entry =
+                            row!(entry_icon, text(self.entries[*index].name.clone())).spacing(8);
+                        let selected = position == self.selected;
+
+                        button(
+                            container(entry).style(move |theme: &Theme| container::Style {
+                                text_color: selected.then(|| theme.palette().primary),
+                                ..Default::default()
+                            }),
+                        )
+                        .on_press(Message::LaunchEntry(*index))
+                        .padding(accessibility.button_padding())
+                        .width(Length::Fill)
+                        .style(ghost_button_style(opacity))
+                        .into()
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .spacing(4),
+        )
+        .spacing(8)
+        .into()
+    }
+}
 
 impl Module for AppLauncher {
     type ViewData<'a> = &'a Option<String>;
-    type SubscriptionData<'a> = ();
+    type SubscriptionData<'a> = &'a Option<String>;
 
     fn view(
         &self,
@@ -20,10 +324,39 @@ impl Module for AppLauncher {
         if config.is_some() {
             Some((
                 icon(Icons::AppLauncher).into(),
-                Some(OnModulePress::Action(Message::OpenLauncher)),
+                Some(OnModulePress::Action(app::Message::OpenLauncher)),
             ))
         } else {
-            None
+            Some((
+                icon(Icons::AppLauncher).into(),
+                Some(OnModulePress::ToggleMenu(MenuType::AppLauncher)),
+            ))
+        }
+    }
+
+    // key presses only reach the bar while the launcher holds the keyboard
+    fn subscription(
+        &self,
+        config: Self::SubscriptionData<'_>,
+    ) -> Option<Subscription<app::Message>> {
+        if config.is_some() {
+            return None;
         }
+
+        Some(
+            listen_with(|event, _, _| match event {
+                iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                    key: keyboard::Key::Named(key),
+                    ..
+                }) => match key {
+                    Named::ArrowDown => Some(Message::SelectNext),
+                    Named::ArrowUp => Some(Message::SelectPrevious),
+                    Named::Escape => Some(Message::Close),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .map(app::Message::AppLauncher),
+        )
     }
 }
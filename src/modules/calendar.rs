@@ -0,0 +1,134 @@
+use crate::{
+    components::icons::{Icons, icon},
+    config::CalendarConfig,
+    style::ghost_button_style,
+    utils::launcher::execute_command,
+};
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use iced::{
+    Alignment, Element, Length, Theme,
+    widget::{Column, Row, button, column, container, horizontal_space, row, text},
+};
+
+const WEEK_DAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+#[derive(Debug, Clone)]
+pub enum CalendarMessage {
+    PrevMonth,
+    NextMonth,
+    DateClicked(NaiveDate),
+}
+
+pub struct Calendar {
+    current_month: NaiveDate,
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Self {
+            current_month: first_of_month(Local::now().date_naive()),
+        }
+    }
+}
+
+impl Calendar {
+    pub fn update(&mut self, message: CalendarMessage, config: &CalendarConfig) {
+        match message {
+            CalendarMessage::PrevMonth => {
+                if let Some(month) = self.current_month.checked_sub_months(Months::new(1)) {
+                    self.current_month = month;
+                }
+            }
+            CalendarMessage::NextMonth => {
+                if let Some(month) = self.current_month.checked_add_months(Months::new(1)) {
+                    self.current_month = month;
+                }
+            }
+            CalendarMessage::DateClicked(date) => {
+                if let Some(cmd) = &config.on_date_click {
+                    execute_command(format!("{} {}", cmd, date.format("%Y-%m-%d")));
+                }
+            }
+        }
+    }
+
+    pub fn view(&self, config: &CalendarConfig, opacity: f32) -> Element<CalendarMessage> {
+        let today = Local::now().date_naive();
+        let offset = self.current_month.weekday().num_days_from_monday() as usize;
+        let days = self
+            .current_month
+            .checked_add_months(Months::new(1))
+            .map(|next| (next - self.current_month).num_days() as usize)
+            .unwrap_or(31);
+
+        let cells = (0..offset)
+            .map(|_| None)
+            .chain((0..days).map(|day| self.current_month.checked_add_days(Days::new(day as u64))))
+            .collect::<Vec<_>>();
+
+        let weeks = cells.chunks(7).map(|week| {
+            Row::with_children((0..7).map(|i| {
+                let cell: Element<_> = match week.get(i).copied().flatten() {
+                    Some(date) => {
+                        let label = container(text(date.day()).size(12))
+                            .center_x(Length::Fill)
+                            .style(move |theme: &Theme| container::Style {
+                                text_color: if date == today {
+                                    Some(theme.palette().success)
+                                } else {
+                                    None
+                                },
+                                ..Default::default()
+                            });
+
+                        button(label)
+                            .on_press_maybe(
+                                config
+                                    .on_date_click
+                                    .as_ref()
+                                    .map(|_| CalendarMessage::DateClicked(date)),
+                            )
+                            .padding([4, 0])
+                            .width(Length::Fill)
+                            .style(ghost_button_style(opacity))
+                            .into()
+                    }
+                    None => horizontal_space().into(),
+                };
+
+                container(cell).width(Length::Fill).into()
+            }))
+            .spacing(4)
+            .into()
+        });
+
+        column!(
+            row!(
+                button(icon(Icons::LeftArrow))
+                    .on_press(CalendarMessage::PrevMonth)
+                    .padding([4, 8])
+                    .style(ghost_button_style(opacity)),
+                container(text(self.current_month.format("%B %Y").to_string()))
+                    .center_x(Length::Fill),
+                button(icon(Icons::RightArrow))
+                    .on_press(CalendarMessage::NextMonth)
+                    .padding([4, 8])
+                    .style(ghost_button_style(opacity)),
+            )
+            .align_y(Alignment::Center),
+            Row::with_children(
+                WEEK_DAYS
+                    .iter()
+                    .map(|day| { container(text(*day).size(12)).center_x(Length::Fill).into() })
+            )
+            .spacing(4),
+            Column::with_children(weeks).spacing(4),
+        )
+        .spacing(8)
+        .into()
+    }
+}
@@ -1,17 +1,111 @@
 use crate::{
     app::{self},
     components::icons::{Icons, icon},
+    config::{AccessibilityConfig, ClipboardModuleConfig},
+    menu::MenuType,
+    style::ghost_button_style,
+    utils::truncate_text,
+};
+use iced::{
+    Element, Length, Subscription,
+    futures::SinkExt,
+    stream::channel,
+    widget::{Column, button, column, horizontal_rule, text},
+};
+use log::{error, warn};
+use std::{any::TypeId, collections::VecDeque, process::Stdio};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    process::Command,
 };
-use iced::Element;
 
 use super::{Module, OnModulePress};
 
+// larger entries are most likely not something worth keeping around
+const MAX_ENTRY_SIZE: usize = 1024;
+
 #[derive(Default, Debug, Clone)]
-pub struct Clipboard;
+pub struct Clipboard {
+    history: VecDeque<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Entry(String),
+    Copy(String),
+    ClearHistory,
+}
+
+impl Clipboard {
+    pub fn update(&mut self, message: Message, config: &ClipboardModuleConfig) {
+        match message {
+            Message::Entry(entry) => {
+                self.history.retain(|e| e != &entry);
+                self.history.push_front(entry);
+                self.history.truncate(config.history_size);
+            }
+            Message::Copy(entry) => {
+                tokio::spawn(async move {
+                    if let Err(err) = copy(&entry).await {
+                        error!("Failed to copy the clipboard entry: {}", err);
+                    }
+                });
+            }
+            Message::ClearHistory => {
+                self.history.clear();
+            }
+        }
+    }
+
+    pub fn menu_view(&self, accessibility: AccessibilityConfig, opacity: f32) -> Element<Message> {
+        column!(
+            text("Clipboard").size(20),
+            horizontal_rule(1),
+            if self.history.is_empty() {
+                Element::from(text("No entries"))
+            } else {
+                Column::with_children(
+                    self.history
+                        .iter()
+                        .map(|entry| {
+                            button(text(truncate_text(&entry.replace('\n', " "), 60)))
+                                .on_press(Message::Copy(entry.clone()))
+                                .padding(accessibility.button_padding())
+                                .width(Length::Fill)
+                                .style(ghost_button_style(opacity))
+                                .into()
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .spacing(4)
+                .into()
+            },
+            horizontal_rule(1),
+            button("Clear history")
+                .on_press(Message::ClearHistory)
+                .padding(accessibility.button_padding())
+                .width(Length::Fill)
+                .style(ghost_button_style(opacity)),
+        )
+        .spacing(8)
+        .into()
+    }
+}
+
+async fn copy(entry: &str) -> anyhow::Result<()> {
+    let mut child = Command::new("wl-copy").stdin(Stdio::piped()).spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(entry.as_bytes()).await?;
+    }
+    child.wait().await?;
+
+    Ok(())
+}
 
 impl Module for Clipboard {
     type ViewData<'a> = &'a Option<String>;
-    type SubscriptionData<'a> = ();
+    type SubscriptionData<'a> = &'a Option<String>;
 
     fn view(
         &self,
@@ -23,7 +117,69 @@ impl Module for Clipboard {
                 Some(OnModulePress::Action(app::Message::OpenClipboard)),
             ))
         } else {
-            None
+            Some((
+                icon(Icons::Clipboard).into(),
+                Some(OnModulePress::ToggleMenu(MenuType::Clipboard)),
+            ))
+        }
+    }
+
+    // the history is only tracked when no external clipboard manager is configured
+    fn subscription(
+        &self,
+        config: Self::SubscriptionData<'_>,
+    ) -> Option<Subscription<app::Message>> {
+        if config.is_some() {
+            return None;
         }
+
+        let id = TypeId::of::<Self>();
+
+        Some(
+            Subscription::run_with_id(
+                id,
+                channel(10, async |mut output| {
+                    // every entry is terminated by a NUL byte so multiline text stays together
+                    match Command::new("wl-paste")
+                        .args(["--type", "text", "--watch", "sh", "-c", "cat; printf '\\0'"])
+                        .stdout(Stdio::piped())
+                        .kill_on_drop(true)
+                        .spawn()
+                    {
+                        Ok(mut child) => {
+                            if let Some(stdout) = child.stdout.take() {
+                                let mut reader = BufReader::new(stdout);
+                                let mut entry = Vec::new();
+
+                                while reader.read_until(b'\0', &mut entry).await.unwrap_or(0) > 0 {
+                                    if entry.last() == Some(&b'\0') {
+                                        entry.pop();
+                                    }
+
+                                    let value = String::from_utf8(std::mem::take(&mut entry))
+                                        .ok()
+                                        .filter(|value| {
+                                            value.len() <= MAX_ENTRY_SIZE
+                                                && !value.trim().is_empty()
+                                        });
+
+                                    if let Some(value) = value {
+                                        let _ = output.send(Message::Entry(value)).await;
+                                    }
+                                }
+                            }
+
+                            warn!("wl-paste stopped watching the clipboard");
+                        }
+                        Err(err) => {
+                            error!("Failed to run wl-paste: {}", err);
+                        }
+                    }
+
+                    iced::futures::future::pending::<()>().await;
+                }),
+            )
+            .map(app::Message::Clipboard),
+        )
     }
 }
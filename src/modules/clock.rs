@@ -1,50 +1,161 @@
-use crate::app;
+use crate::{
+    app,
+    config::{CalendarConfig, ClockModuleConfig},
+    menu::MenuType,
+};
 
-use super::{Module, OnModulePress};
-use chrono::{DateTime, Local};
-use iced::{Element, Subscription, time::every, widget::text};
-use std::time::Duration;
+use super::{
+    Module, OnModulePress,
+    calendar::{Calendar, CalendarMessage},
+};
+use chrono::{DateTime, Local, Timelike, Utc};
+use chrono_tz::Tz;
+use iced::{
+    Element, Subscription,
+    futures::SinkExt,
+    stream::channel,
+    time::every,
+    widget::{column, text},
+};
+use log::warn;
+use std::{any::TypeId, time::Duration};
+
+fn format_date(date: &DateTime<Utc>, format: &str, timezone: Option<&str>) -> String {
+    match timezone.map(|timezone| timezone.parse::<Tz>()) {
+        Some(Ok(timezone)) => date.with_timezone(&timezone).format(format).to_string(),
+        Some(Err(err)) => {
+            warn!("Invalid clock timezone: {}", err);
+            date.with_timezone(&Local).format(format).to_string()
+        }
+        None => date.with_timezone(&Local).format(format).to_string(),
+    }
+}
+
+fn shows_seconds(format: &str) -> bool {
+    ["%S", "%T", "%X", "%s", "%c", "%+"]
+        .iter()
+        .any(|specifier| format.contains(specifier))
+}
 
 pub struct Clock {
-    date: DateTime<Local>,
+    date: DateTime<Utc>,
+    calendar: Calendar,
 }
 
 impl Default for Clock {
     fn default() -> Self {
-        Self { date: Local::now() }
+        Self {
+            date: Utc::now(),
+            calendar: Calendar::default(),
+        }
     }
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     Update,
+    Calendar(CalendarMessage),
 }
 
 impl Clock {
-    pub fn update(&mut self, message: Message) {
+    pub fn update(&mut self, message: Message, calendar_config: &CalendarConfig) {
         match message {
             Message::Update => {
-                self.date = Local::now();
+                self.date = Utc::now();
+            }
+            Message::Calendar(message) => {
+                self.calendar.update(message, calendar_config);
             }
         }
     }
+
+    pub fn reset_calendar(&mut self) {
+        self.calendar = Calendar::default();
+    }
+
+    pub fn menu_view(&self, calendar_config: &CalendarConfig, opacity: f32) -> Element<Message> {
+        self.calendar
+            .view(calendar_config, opacity)
+            .map(Message::Calendar)
+    }
+
+    pub fn tooltip_view(&self, config: &ClockModuleConfig) -> Option<Element<app::Message>> {
+        Some(
+            text(format_date(
+                &self.date,
+                "%A, %d %B %Y",
+                config.timezone.as_deref(),
+            ))
+            .size(12)
+            .into(),
+        )
+    }
 }
 
 impl Module for Clock {
-    type ViewData<'a> = &'a str;
-    type SubscriptionData<'a> = ();
+    type ViewData<'a> = &'a ClockModuleConfig;
+    type SubscriptionData<'a> = &'a ClockModuleConfig;
     fn view(
         &self,
-        format: Self::ViewData<'_>,
+        config: Self::ViewData<'_>,
     ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
-        Some((text(self.date.format(format).to_string()).into(), None))
+        let primary = format_date(&self.date, &config.format, config.timezone.as_deref());
+
+        if config.secondary_format.is_some() || config.secondary_timezone.is_some() {
+            let secondary = format_date(
+                &self.date,
+                config
+                    .secondary_format
+                    .as_deref()
+                    .unwrap_or(config.format.as_str()),
+                config.secondary_timezone.as_deref(),
+            );
+
+            Some((
+                column!(text(primary).size(12), text(secondary).size(10)).into(),
+                Some(OnModulePress::ToggleMenu(MenuType::Calendar)),
+            ))
+        } else {
+            Some((
+                text(primary).into(),
+                Some(OnModulePress::ToggleMenu(MenuType::Calendar)),
+            ))
+        }
     }
 
-    fn subscription(&self, _: Self::SubscriptionData<'_>) -> Option<Subscription<app::Message>> {
+    fn subscription(
+        &self,
+        config: Self::SubscriptionData<'_>,
+    ) -> Option<Subscription<app::Message>> {
+        let with_seconds = shows_seconds(&config.format)
+            || config
+                .secondary_format
+                .as_deref()
+                .is_some_and(shows_seconds);
+
+        if with_seconds {
+            return Some(
+                every(Duration::from_secs(1))
+                    .map(|_| Message::Update)
+                    .map(app::Message::Clock),
+            );
+        }
+
+        let id = TypeId::of::<Self>();
+
         Some(
-            every(Duration::from_secs(5))
-                .map(|_| Message::Update)
-                .map(app::Message::Clock),
+            Subscription::run_with_id(
+                id,
+                channel(1, async |mut output| {
+                    loop {
+                        let now = Local::now();
+                        tokio::time::sleep(Duration::from_secs(60 - now.second() as u64)).await;
+
+                        let _ = output.send(Message::Update).await;
+                    }
+                }),
+            )
+            .map(app::Message::Clock),
         )
     }
 }
@@ -0,0 +1,129 @@
+use super::{Module, OnModulePress};
+use crate::{
+    app,
+    config::CustomModuleConfig,
+    services::{ReadOnlyService, ServiceEvent, custom_command::CustomCommandService, inspector},
+    utils::{launcher::execute_command, truncate_text},
+};
+use iced::{
+    Alignment, Element, Font, Subscription, Task,
+    widget::{Row, mouse_area, row, text},
+};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub enum CustomCommandMessage {
+    Event(ServiceEvent<CustomCommandService>),
+    Click(usize),
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct CustomCommands {
+    services: HashMap<usize, CustomCommandService>,
+}
+
+impl CustomCommands {
+    pub fn update(
+        &mut self,
+        message: CustomCommandMessage,
+        config: &[CustomModuleConfig],
+    ) -> Task<crate::app::Message> {
+        match message {
+            CustomCommandMessage::Event(event) => match event {
+                ServiceEvent::Init(service) => {
+                    self.services.insert(service.index, service);
+                    Task::none()
+                }
+                ServiceEvent::Update(data) => {
+                    if let Some(service) = self.services.get_mut(&data.index) {
+                        service.update(data);
+                    }
+                    Task::none()
+                }
+                ServiceEvent::Error(_) => Task::none(),
+            },
+            CustomCommandMessage::Click(index) => {
+                if let Some(on_click) = config.get(index).and_then(|c| c.on_click.clone()) {
+                    execute_command(on_click);
+                }
+                Task::none()
+            }
+        }
+    }
+}
+
+impl Module for CustomCommands {
+    type ViewData<'a> = &'a [CustomModuleConfig];
+    type SubscriptionData<'a> = &'a [CustomModuleConfig];
+
+    fn view(
+        &self,
+        config: Self::ViewData<'_>,
+    ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
+        let entries = config
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let service = self.services.get(&index)?;
+
+                if service.output.is_empty() && entry.icon.is_none() {
+                    return None;
+                }
+
+                let content =
+                    row!()
+                        .push_maybe(entry.icon.as_ref().map(|icon| {
+                            text(icon.clone()).font(Font::with_name("Symbols Nerd Font"))
+                        }))
+                        .push_maybe(
+                            Some(&service.output)
+                                .filter(|output| !output.is_empty())
+                                .map(|output| text(truncate_text(output, entry.max_chars))),
+                        )
+                        .align_y(Alignment::Center)
+                        .spacing(4);
+
+                Some(if entry.on_click.is_some() {
+                    mouse_area(content)
+                        .on_press(app::Message::CustomCommand(CustomCommandMessage::Click(
+                            index,
+                        )))
+                        .into()
+                } else {
+                    content.into()
+                })
+            })
+            .collect::<Vec<Element<app::Message>>>();
+
+        if entries.is_empty() {
+            None
+        } else {
+            Some((
+                Row::with_children(entries)
+                    .align_y(Alignment::Center)
+                    .spacing(8)
+                    .into(),
+                None,
+            ))
+        }
+    }
+
+    fn subscription(
+        &self,
+        config: Self::SubscriptionData<'_>,
+    ) -> Option<Subscription<app::Message>> {
+        if config.is_empty() {
+            None
+        } else {
+            Some(Subscription::batch(config.iter().enumerate().map(
+                |(index, entry)| {
+                    CustomCommandService::subscribe_with_config(index, entry)
+                        .map(inspector::record)
+                        .map(|event| {
+                            app::Message::CustomCommand(CustomCommandMessage::Event(event))
+                        })
+                },
+            )))
+        }
+    }
+}
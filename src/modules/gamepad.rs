@@ -0,0 +1,88 @@
+use super::{Module, OnModulePress};
+use crate::{
+    app,
+    components::{
+        icons::{Icons, icon},
+        tooltip_wrapper::TooltipWrapper,
+    },
+    config::GamepadModuleConfig,
+    services::{ReadOnlyService, ServiceEvent, gamepad::GamepadService, inspector},
+};
+use iced::{
+    Element, Subscription, Task,
+    widget::{text, tooltip},
+};
+use itertools::Itertools;
+
+#[derive(Debug, Clone)]
+pub enum GamepadMessage {
+    Event(ServiceEvent<GamepadService>),
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Gamepad {
+    pub service: Option<GamepadService>,
+}
+
+impl Gamepad {
+    pub fn update(&mut self, message: GamepadMessage) -> Task<crate::app::Message> {
+        match message {
+            GamepadMessage::Event(event) => match event {
+                ServiceEvent::Init(service) => {
+                    self.service = Some(service);
+                    Task::none()
+                }
+                ServiceEvent::Update(data) => {
+                    if let Some(service) = self.service.as_mut() {
+                        service.update(data);
+                    }
+                    Task::none()
+                }
+                ServiceEvent::Error(_) => Task::none(),
+            },
+        }
+    }
+}
+
+impl Module for Gamepad {
+    type ViewData<'a> = (&'a GamepadModuleConfig, tooltip::Position);
+    type SubscriptionData<'a> = &'a GamepadModuleConfig;
+
+    fn view(
+        &self,
+        (config, tooltip_position): Self::ViewData<'_>,
+    ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
+        let service = self
+            .service
+            .as_ref()
+            .filter(|service| config.show && service.connected)?;
+
+        Some((
+            TooltipWrapper::new(icon(Icons::Gamepad))
+                .tooltip(Some(
+                    text(
+                        service
+                            .devices
+                            .iter()
+                            .map(|device| device.name.as_str())
+                            .join("\n"),
+                    )
+                    .size(12),
+                ))
+                .position(tooltip_position)
+                .into(),
+            None,
+        ))
+    }
+
+    fn subscription(
+        &self,
+        config: Self::SubscriptionData<'_>,
+    ) -> Option<Subscription<app::Message>> {
+        config.show.then(|| {
+            GamepadService::subscribe()
+                .map(inspector::record)
+                .map(|e| app::Message::Gamepad(GamepadMessage::Event(e)))
+        })
+    }
+}
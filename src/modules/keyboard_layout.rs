@@ -2,14 +2,24 @@ use hyprland::{
     ctl::switch_xkb_layout::SwitchXKBLayoutCmdTypes, event_listener::AsyncEventListener,
     shared::HyprData,
 };
-use iced::{Element, Subscription, stream::channel, widget::text};
+use iced::{Element, Subscription, Task, stream::channel, widget::text};
 use log::{debug, error};
 use std::{
     any::TypeId,
     sync::{Arc, RwLock},
 };
 
-use crate::{app, config::KeyboardLayoutModuleConfig};
+use crate::{
+    app,
+    config::KeyboardLayoutModuleConfig,
+    services::{
+        ReadOnlyService, Service, ServiceEvent, inspector,
+        niri::{
+            NiriService,
+            keyboard::{NiriKeyboardCommand, NiriKeyboardService},
+        },
+    },
+};
 
 use super::{Module, OnModulePress};
 
@@ -33,17 +43,37 @@ fn get_active_layout() -> String {
         .unwrap_or_else(|| "unknown".to_string())
 }
 
+/// Short label for a niri layout name, e.g. "English (US)" becomes "EN"
+fn short_layout_label(layout: &str) -> String {
+    layout
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .take(2)
+        .collect::<String>()
+        .to_uppercase()
+}
+
 #[derive(Debug, Clone)]
 pub struct KeyboardLayout {
     multiple_layout: bool,
     active: String,
+    niri: Option<NiriKeyboardService>,
 }
 
 impl Default for KeyboardLayout {
     fn default() -> Self {
-        Self {
-            multiple_layout: get_multiple_layout_flag(),
-            active: get_active_layout(),
+        if NiriService::is_available() {
+            Self {
+                multiple_layout: false,
+                active: String::new(),
+                niri: None,
+            }
+        } else {
+            Self {
+                multiple_layout: get_multiple_layout_flag(),
+                active: get_active_layout(),
+                niri: None,
+            }
         }
     }
 }
@@ -53,16 +83,39 @@ pub enum Message {
     LayoutConfigChanged(bool),
     ActiveLayoutChanged(String),
     ChangeLayout,
+    Niri(ServiceEvent<NiriKeyboardService>),
 }
 
 impl KeyboardLayout {
-    pub fn update(&mut self, message: Message) {
+    pub fn update(&mut self, message: Message) -> Task<app::Message> {
         match message {
+            Message::Niri(event) => {
+                match event {
+                    ServiceEvent::Init(niri) => self.niri = Some(niri),
+                    ServiceEvent::Update(event) => {
+                        if let Some(niri) = self.niri.as_mut() {
+                            niri.update(event);
+                        }
+                    }
+                    ServiceEvent::Error(_) => {}
+                }
+
+                if let Some(niri) = self.niri.as_ref() {
+                    self.multiple_layout = niri.layouts.len() > 1;
+                    self.active = niri.current().unwrap_or("unknown").to_string();
+                }
+            }
             Message::ActiveLayoutChanged(layout) => {
                 self.active = layout;
             }
             Message::LayoutConfigChanged(layout_flag) => self.multiple_layout = layout_flag,
             Message::ChangeLayout => {
+                if let Some(niri) = self.niri.as_mut() {
+                    return niri
+                        .command(NiriKeyboardCommand::SwitchLayout)
+                        .map(|event| app::Message::KeyboardLayout(Message::Niri(event)));
+                }
+
                 let res =
                     hyprland::ctl::switch_xkb_layout::call("all", SwitchXKBLayoutCmdTypes::Next);
 
@@ -71,6 +124,8 @@ impl KeyboardLayout {
                 }
             }
         }
+
+        Task::none()
     }
 }
 
@@ -87,6 +142,7 @@ impl Module for KeyboardLayout {
         } else {
             let active = match config.labels.get(&self.active) {
                 Some(value) => value.to_string(),
+                None if self.niri.is_some() => short_layout_label(&self.active),
                 None => self.active.clone(),
             };
             Some((
@@ -99,6 +155,14 @@ impl Module for KeyboardLayout {
     }
 
     fn subscription(&self, _: Self::SubscriptionData<'_>) -> Option<Subscription<app::Message>> {
+        if NiriService::is_available() {
+            return Some(
+                NiriKeyboardService::subscribe()
+                    .map(inspector::record)
+                    .map(|event| app::Message::KeyboardLayout(Message::Niri(event))),
+            );
+        }
+
         let id = TypeId::of::<Self>();
 
         Some(
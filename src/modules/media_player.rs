@@ -2,24 +2,35 @@ use super::{Module, OnModulePress};
 use crate::{
     app,
     components::icons::{Icons, icon},
-    config::MediaPlayerModuleConfig,
+    config::{MediaPlayerModuleConfig, MprisConfig},
     menu::MenuType,
     services::{
-        ReadOnlyService, Service, ServiceEvent,
-        mpris::{MprisPlayerCommand, MprisPlayerData, MprisPlayerService, PlayerCommand},
+        ReadOnlyService, Service, ServiceEvent, inspector,
+        mpris::{
+            LoopStatus, MprisPlayerCommand, MprisPlayerData, MprisPlayerEvent, MprisPlayerService,
+            PlaybackStatus, PlayerCommand,
+        },
     },
-    style::settings_button_style,
+    style::{ghost_button_style, settings_button_style},
     utils::truncate_text,
 };
 use iced::{
     Alignment::Center,
-    Element, Subscription, Task,
-    widget::{button, column, container, row, slider, text},
+    Element, Length, Subscription, Task,
+    time::every,
+    widget::{
+        button, column, container, image, mouse_area, progress_bar, responsive, row, slider, text,
+    },
 };
+use std::time::Duration;
+
+const SEEK_BAR_HEIGHT: f32 = 6.;
 
 #[derive(Default)]
 pub struct MediaPlayer {
     service: Option<MprisPlayerService>,
+    // player and bar ratio under the cursor, used when the seek bar is clicked
+    seek_hover: Option<(String, f32)>,
 }
 
 #[derive(Debug, Clone)]
@@ -28,6 +39,13 @@ pub enum Message {
     PlayPause(String),
     Next(String),
     SetVolume(String, f64),
+    SetShuffle(String, bool),
+    SetLoop(String, LoopStatus),
+    SetRating(String, f32),
+    CyclePlayer,
+    SeekHover(String, f32),
+    Seek(String),
+    Tick,
     Event(ServiceEvent<MprisPlayerService>),
 }
 
@@ -38,6 +56,46 @@ impl MediaPlayer {
             Message::PlayPause(s) => self.handle_command(s, PlayerCommand::PlayPause),
             Message::Next(s) => self.handle_command(s, PlayerCommand::Next),
             Message::SetVolume(s, v) => self.handle_command(s, PlayerCommand::Volume(v)),
+            Message::SetShuffle(s, v) => self.handle_command(s, PlayerCommand::SetShuffle(v)),
+            Message::SetLoop(s, v) => self.handle_command(s, PlayerCommand::SetLoop(v)),
+            Message::SetRating(s, v) => self.handle_command(s, PlayerCommand::SetRating(v)),
+            Message::SeekHover(s, ratio) => {
+                self.seek_hover = Some((s, ratio));
+                Task::none()
+            }
+            Message::Seek(s) => {
+                let target = self
+                    .seek_hover
+                    .as_ref()
+                    .filter(|(hovered, _)| hovered == &s);
+                let target = target.and_then(|(hovered, ratio)| {
+                    let length = self
+                        .service
+                        .as_ref()?
+                        .iter()
+                        .find(|d| &d.service == hovered)?
+                        .metadata
+                        .as_ref()?
+                        .length?;
+
+                    Some(length.mul_f32(ratio.clamp(0., 1.)))
+                });
+
+                match target {
+                    Some(target) => self.handle_command(s, PlayerCommand::Seek(target)),
+                    None => Task::none(),
+                }
+            }
+            Message::Tick => {
+                if let Some(service) = self.service.as_mut() {
+                    service.update(MprisPlayerEvent::Elapsed(Duration::from_secs(1)));
+                }
+                Task::none()
+            }
+            Message::CyclePlayer => match self.service.as_ref().and_then(|s| s.active_player()) {
+                Some(d) => self.handle_command(d.service.clone(), PlayerCommand::CyclePlayer),
+                None => Task::none(),
+            },
             Message::Event(event) => match event {
                 ServiceEvent::Init(s) => {
                     self.service = Some(s);
@@ -62,20 +120,97 @@ impl MediaPlayer {
                     .flat_map(|d| {
                         let d = d.clone();
                         let title = text(Self::get_title(&d, config));
-                        let buttons = row![
-                            button(icon(Icons::SkipPrevious))
-                                .on_press(Message::Prev(d.service.clone()))
-                                .padding([5, 12])
-                                .style(settings_button_style(opacity)),
-                            button(icon(Icons::PlayPause))
-                                .on_press(Message::PlayPause(d.service.clone()))
-                                .style(settings_button_style(opacity)),
-                            button(icon(Icons::SkipNext))
-                                .on_press(Message::Next(d.service.clone()))
-                                .padding([5, 12])
-                                .style(settings_button_style(opacity)),
-                        ]
-                        .spacing(8);
+                        let art = d
+                            .art
+                            .clone()
+                            .map(|handle| image(handle).width(128).height(128));
+                        let shuffle_button = d.shuffle.map(|shuffle| {
+                            button(icon(if shuffle {
+                                Icons::Shuffle
+                            } else {
+                                Icons::ShuffleDisabled
+                            }))
+                            .on_press(Message::SetShuffle(d.service.clone(), !shuffle))
+                            .padding([5, 12])
+                            .style(settings_button_style(opacity))
+                        });
+                        let loop_button = d.loop_status.map(|loop_status| {
+                            button(icon(match loop_status {
+                                LoopStatus::None => Icons::RepeatOff,
+                                LoopStatus::Track => Icons::RepeatOnce,
+                                LoopStatus::Playlist => Icons::Repeat,
+                            }))
+                            .on_press(Message::SetLoop(d.service.clone(), loop_status.next()))
+                            .padding([5, 12])
+                            .style(settings_button_style(opacity))
+                        });
+                        let rating_button =
+                            d.metadata
+                                .as_ref()
+                                .and_then(|m| m.user_rating)
+                                .map(|rating| {
+                                    let rated = rating > 0.;
+
+                                    button(icon(if rated {
+                                        Icons::Heart
+                                    } else {
+                                        Icons::HeartOutline
+                                    }))
+                                    .on_press(Message::SetRating(
+                                        d.service.clone(),
+                                        if rated { 0. } else { 1. },
+                                    ))
+                                    .padding([5, 12])
+                                    .style(settings_button_style(opacity))
+                                });
+                        let buttons = row![]
+                            .push_maybe(rating_button)
+                            .push_maybe(shuffle_button)
+                            .push(
+                                button(icon(Icons::SkipPrevious))
+                                    .on_press(Message::Prev(d.service.clone()))
+                                    .padding([5, 12])
+                                    .style(settings_button_style(opacity)),
+                            )
+                            .push(
+                                button(icon(Icons::PlayPause))
+                                    .on_press(Message::PlayPause(d.service.clone()))
+                                    .style(settings_button_style(opacity)),
+                            )
+                            .push(
+                                button(icon(Icons::SkipNext))
+                                    .on_press(Message::Next(d.service.clone()))
+                                    .padding([5, 12])
+                                    .style(settings_button_style(opacity)),
+                            )
+                            .push_maybe(loop_button)
+                            .spacing(8);
+                        let seek_bar = d
+                            .position
+                            .zip(d.metadata.as_ref().and_then(|m| m.length))
+                            .map(|(position, length)| {
+                                let service = d.service.clone();
+                                let progress = position.as_secs_f32() / length.as_secs_f32();
+
+                                container(responsive(move |size| {
+                                    let service = service.clone();
+                                    let width = size.width.max(1.);
+
+                                    mouse_area(
+                                        progress_bar(0.0..=1.0, progress)
+                                            .height(Length::Fixed(SEEK_BAR_HEIGHT)),
+                                    )
+                                    .on_move({
+                                        let service = service.clone();
+                                        move |point| {
+                                            Message::SeekHover(service.clone(), point.x / width)
+                                        }
+                                    })
+                                    .on_press(Message::Seek(service))
+                                    .into()
+                                }))
+                                .height(Length::Fixed(SEEK_BAR_HEIGHT))
+                            });
                         let volume_slider = d.volume.map(|v| {
                             slider(0.0..=100.0, v, move |v| {
                                 Message::SetVolume(d.service.clone(), v)
@@ -85,7 +220,10 @@ impl MediaPlayer {
                         [
                             iced::widget::horizontal_rule(2).into(),
                             container(
-                                column![title]
+                                column![]
+                                    .push_maybe(art)
+                                    .push(title)
+                                    .push_maybe(seek_bar)
                                     .push_maybe(volume_slider)
                                     .push(buttons)
                                     .width(iced::Length::Fill)
@@ -119,6 +257,14 @@ impl MediaPlayer {
         }
     }
 
+    pub fn tooltip_view(&self) -> Option<Element<app::Message>> {
+        self.service
+            .as_ref()
+            .and_then(|s| s.active_player())
+            .and_then(|d| d.metadata.as_ref())
+            .map(|m| text(m.full_display()).size(12).into())
+    }
+
     fn get_title(d: &MprisPlayerData, config: &MediaPlayerModuleConfig) -> String {
         match &d.metadata {
             Some(m) => truncate_text(&m.to_string(), config.max_title_length),
@@ -128,28 +274,50 @@ impl MediaPlayer {
 }
 
 impl Module for MediaPlayer {
-    type ViewData<'a> = &'a MediaPlayerModuleConfig;
-    type SubscriptionData<'a> = ();
+    type ViewData<'a> = (&'a MediaPlayerModuleConfig, f32);
+    type SubscriptionData<'a> = &'a MprisConfig;
 
     fn view(
         &self,
-        config: Self::ViewData<'_>,
+        (config, opacity): Self::ViewData<'_>,
     ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
-        self.service.as_ref().and_then(|s| match s.len() {
-            0 => None,
-            _ => Some((
-                row![icon(Icons::MusicNote), text(Self::get_title(&s[0], config))]
-                    .spacing(8)
-                    .into(),
-                Some(OnModulePress::ToggleMenu(MenuType::MediaPlayer)),
-            )),
+        self.service.as_ref().and_then(|s| {
+            s.active_player().map(|d| {
+                (
+                    row![icon(Icons::MusicNote), text(Self::get_title(d, config))]
+                        .push_maybe((s.len() > 1).then(|| {
+                            button(icon(Icons::RightChevron))
+                                .padding([0, 4])
+                                .style(ghost_button_style(opacity))
+                                .on_press(app::Message::MediaPlayer(Message::CyclePlayer))
+                        }))
+                        .align_y(Center)
+                        .spacing(8)
+                        .into(),
+                    Some(OnModulePress::ToggleMenu(MenuType::MediaPlayer)),
+                )
+            })
         })
     }
 
-    fn subscription(&self, (): Self::SubscriptionData<'_>) -> Option<Subscription<app::Message>> {
-        Some(
-            MprisPlayerService::subscribe()
+    fn subscription(
+        &self,
+        config: Self::SubscriptionData<'_>,
+    ) -> Option<Subscription<app::Message>> {
+        let playing = self.service.as_ref().is_some_and(|s| {
+            s.iter()
+                .any(|d| d.playback_status == Some(PlaybackStatus::Playing))
+        });
+
+        Some(Subscription::batch([
+            MprisPlayerService::subscribe_with_config(config)
+                .map(inspector::record)
                 .map(|event| app::Message::MediaPlayer(Message::Event(event))),
-        )
+            if playing {
+                every(Duration::from_secs(1)).map(|_| app::Message::MediaPlayer(Message::Tick))
+            } else {
+                Subscription::none()
+            },
+        ]))
     }
 }
@@ -1,27 +1,41 @@
 use crate::{
     app::{self, App, Message},
-    config::{AppearanceStyle, ModuleDef, ModuleName},
+    components::{click_wrapper::ClickWrapper, tooltip_wrapper::TooltipWrapper},
+    config::{AppearanceStyle, ModuleDef, ModuleName, ModuleSection},
     menu::MenuType,
     position_button::position_button,
-    style::module_button_style,
+    style::{ghost_button_style, module_button_style},
 };
 use iced::{
-    Alignment, Border, Color, Element, Length, Subscription,
-    widget::{Row, container, row},
+    Alignment, Border, Color, Element, Length, Subscription, Theme,
+    widget::{
+        Column, Row, Space, button, checkbox, column, container, horizontal_rule, mouse_area, row,
+        stack, text,
+    },
     window::Id,
 };
 
 pub mod app_launcher;
+pub mod calendar;
 pub mod clipboard;
 pub mod clock;
+pub mod custom_command;
+pub mod gamepad;
 pub mod keyboard_layout;
 pub mod keyboard_submap;
 pub mod media_player;
+pub mod notifications;
+pub mod pomodoro;
+pub mod printer;
 pub mod privacy;
 pub mod settings;
 pub mod system_info;
+pub mod systemd_units;
 pub mod tray;
 pub mod updates;
+pub mod uptime;
+pub mod usb;
+pub mod weather;
 pub mod window_title;
 pub mod workspaces;
 
@@ -31,6 +45,13 @@ pub enum OnModulePress {
     ToggleMenu(MenuType),
 }
 
+// module being dragged and the place it would be dropped in
+#[derive(Debug, Clone, Default)]
+pub struct LayoutEdit {
+    pub dragging: Option<(ModuleSection, usize)>,
+    pub target: Option<(ModuleSection, usize)>,
+}
+
 pub trait Module {
     type ViewData<'a>;
     type SubscriptionData<'a>;
@@ -48,7 +69,9 @@ pub trait Module {
 impl App {
     pub fn modules_section(
         &self,
-        modules_def: &Vec<ModuleDef>,
+        modules_def: &[ModuleDef],
+        section: ModuleSection,
+        editable: bool,
         id: Id,
         opacity: f32,
     ) -> Element<Message> {
@@ -57,27 +80,71 @@ impl App {
             .align_y(Alignment::Center)
             .spacing(4);
 
-        for module_def in modules_def {
-            row = row.push_maybe(match module_def {
+        for (index, module_def) in modules_def.iter().enumerate() {
+            let module = match module_def {
                 ModuleDef::Single(module) => self.single_module_wrapper(*module, id, opacity),
                 ModuleDef::Group(group) => self.group_module_wrapper(group, id, opacity),
+            };
+
+            row = row.push_maybe(match (&self.layout_edit, editable) {
+                (Some(layout_edit), true) => module
+                    .map(|module| Self::draggable_module(module, layout_edit, (section, index))),
+                _ => module,
             });
         }
 
+        // an empty slot at the end to drop modules after the last one
+        if let (Some(layout_edit), true) = (&self.layout_edit, editable) {
+            row = row.push(Self::draggable_module(
+                Space::new(16, Length::Fill).into(),
+                layout_edit,
+                (section, modules_def.len()),
+            ));
+        }
+
         row.into()
     }
 
-    pub fn modules_subscriptions(&self, modules_def: &[ModuleDef]) -> Vec<Subscription<Message>> {
-        modules_def
+    // while editing the layout a transparent layer on top of the module takes
+    // the mouse, the module itself can't be clicked
+    fn draggable_module<'a>(
+        module: Element<'a, Message>,
+        layout_edit: &LayoutEdit,
+        position: (ModuleSection, usize),
+    ) -> Element<'a, Message> {
+        let is_target = layout_edit.dragging.is_some() && layout_edit.target == Some(position);
+        let is_dragged = layout_edit.dragging == Some(position);
+
+        container(stack![
+            module,
+            mouse_area(Space::new(Length::Fill, Length::Fill))
+                .on_press(Message::DragModule(position.0, position.1))
+                .on_enter(Message::DragModuleOver(position.0, position.1))
+                .on_release(Message::DropModule),
+        ])
+        .height(Length::Fill)
+        .style(move |theme: &Theme| container::Style {
+            border: Border {
+                width: if is_target || is_dragged { 2.0 } else { 1.0 },
+                radius: 12.0.into(),
+                color: if is_target {
+                    theme.palette().primary
+                } else if is_dragged {
+                    theme.palette().primary.scale_alpha(0.5)
+                } else {
+                    theme.extended_palette().background.strong.color
+                },
+            },
+            ..container::Style::default()
+        })
+        .into()
+    }
+
+    // only the modules present in the layout start their services
+    pub fn modules_subscriptions(&self, modules: &[ModuleName]) -> Vec<Subscription<Message>> {
+        modules
             .iter()
-            .flat_map(|module_def| match module_def {
-                ModuleDef::Single(module) => vec![self.get_module_subscription(*module)],
-                ModuleDef::Group(group) => group
-                    .iter()
-                    .map(|module| self.get_module_subscription(*module))
-                    .collect(),
-            })
-            .flatten()
+            .filter_map(|module| self.get_module_subscription(*module))
             .collect()
     }
 
@@ -96,7 +163,7 @@ impl App {
                         .align_y(Alignment::Center)
                         .height(Length::Fill),
                 )
-                .padding([2, 8])
+                .padding(self.config.accessibility.module_padding())
                 .height(Length::Fill)
                 .style(module_button_style(
                     self.config.appearance.style,
@@ -116,7 +183,7 @@ impl App {
             }
             _ => {
                 let container = container(content)
-                    .padding([2, 8])
+                    .padding(self.config.accessibility.module_padding())
                     .height(Length::Fill)
                     .align_y(Alignment::Center);
 
@@ -169,7 +236,7 @@ impl App {
                                         .align_y(Alignment::Center)
                                         .height(Length::Fill),
                                 )
-                                .padding([2, 8])
+                                .padding(self.config.accessibility.module_padding())
                                 .height(Length::Fill)
                                 .style(module_button_style(
                                     self.config.appearance.style,
@@ -191,7 +258,7 @@ impl App {
                                 .into()
                             }
                             _ => container(content)
-                                .padding([2, 8])
+                                .padding(self.config.accessibility.module_padding())
                                 .height(Length::Fill)
                                 .align_y(Alignment::Center)
                                 .into(),
@@ -229,7 +296,11 @@ impl App {
         id: Id,
         opacity: f32,
     ) -> Option<(Element<Message>, Option<OnModulePress>)> {
-        match module_name {
+        if self.hidden_modules.contains(&module_name) {
+            return None;
+        }
+
+        let view = match module_name {
             ModuleName::AppLauncher => self.app_launcher.view(&self.config.app_launcher_cmd),
             ModuleName::Updates => self.updates.view(&self.config.updates),
             ModuleName::Clipboard => self.clipboard.view(&self.config.clipboard_cmd),
@@ -237,40 +308,155 @@ impl App {
                 &self.outputs,
                 id,
                 &self.config.workspaces,
-                &self.config.appearance.workspace_colors,
+                self.config.appearance.workspace_colors(),
                 self.config.appearance.special_workspace_colors.as_deref(),
             )),
             ModuleName::WindowTitle => self.window_title.view(()),
-            ModuleName::SystemInfo => self.system_info.view(&self.config.system),
+            ModuleName::SystemInfo => self
+                .system_info
+                .view((&self.config.system, self.config.tooltip_position())),
             ModuleName::KeyboardLayout => self.keyboard_layout.view(&self.config.keyboard_layout),
             ModuleName::KeyboardSubmap => self.keyboard_submap.view(()),
-            ModuleName::Tray => self.tray.view((id, opacity)),
-            ModuleName::Clock => self.clock.view(&self.config.clock.format),
-            ModuleName::Privacy => self.privacy.view(()),
-            ModuleName::Settings => self.settings.view(()),
-            ModuleName::MediaPlayer => self.media_player.view(&self.config.media_player),
+            ModuleName::Tray => self
+                .tray
+                .view((id, opacity, self.config.tooltip_position())),
+            ModuleName::Clock => self.clock.view(&self.config.clock),
+            ModuleName::Privacy => self
+                .privacy
+                .view((&self.config.privacy, self.config.tooltip_position())),
+            ModuleName::Settings => self.settings.view((
+                &self.config.audio,
+                &self.config.network,
+                self.config.tooltip_position(),
+            )),
+            ModuleName::MediaPlayer => self.media_player.view((&self.config.media_player, opacity)),
+            ModuleName::Notifications => self.notifications.view(&self.config.notifications),
+            ModuleName::CustomCommand => self.custom_command.view(&self.config.custom),
+            ModuleName::Weather => self
+                .weather
+                .view((&self.config.weather, self.config.tooltip_position())),
+            ModuleName::Uptime => self.uptime.view(()),
+            ModuleName::Pomodoro => self.pomodoro.view(opacity),
+            ModuleName::SystemdUnits => self.systemd_units.view(()),
+            ModuleName::Gamepad => self
+                .gamepad
+                .view((&self.config.gamepad, self.config.tooltip_position())),
+            ModuleName::Printer => self
+                .printer
+                .view((&self.config.printer, self.config.tooltip_position())),
+            ModuleName::Usb => self.usb.view(()),
+        };
+
+        view.map(|(content, action)| {
+            // a right click command set in the config takes the place of the context menu
+            let content = position_button(
+                ClickWrapper::new(content)
+                    .action(self.config.click_action(module_name), Message::RunCommand),
+            )
+            .padding(0)
+            .style(|theme: &Theme, _| button::Style {
+                text_color: theme.palette().text,
+                ..button::Style::default()
+            })
+            .on_right_press_with_position(move |button_ui_ref| {
+                Message::ToggleMenu(MenuType::ModuleContext(module_name), id, button_ui_ref)
+            });
+
+            (
+                TooltipWrapper::new(content)
+                    .tooltip(self.get_module_tooltip(module_name))
+                    .position(self.config.tooltip_position())
+                    .into(),
+                action,
+            )
+        })
+    }
+
+    pub fn module_context_menu(&self, module_name: ModuleName, opacity: f32) -> Element<Message> {
+        let modules = Column::with_children(
+            self.config
+                .enabled_modules()
+                .into_iter()
+                .map(|module| {
+                    checkbox(
+                        format!("{:?}", module),
+                        !self.hidden_modules.contains(&module),
+                    )
+                    .on_toggle(move |visible| Message::SetModuleVisible(module, visible))
+                    .into()
+                })
+                .collect::<Vec<_>>(),
+        )
+        .spacing(8)
+        .padding([0, 12]);
+
+        column!(
+            button(text(format!("Hide {:?}", module_name)))
+                .on_press(Message::SetModuleVisible(module_name, false))
+                .padding(self.config.accessibility.button_padding())
+                .width(Length::Fill)
+                .style(ghost_button_style(opacity)),
+            horizontal_rule(1),
+            modules,
+            horizontal_rule(1),
+            button("Save layout")
+                .on_press(Message::SaveLayout)
+                .padding(self.config.accessibility.button_padding())
+                .width(Length::Fill)
+                .style(ghost_button_style(opacity)),
+        )
+        .spacing(8)
+        .into()
+    }
+
+    fn get_module_tooltip(&self, module_name: ModuleName) -> Option<Element<Message>> {
+        match module_name {
+            ModuleName::MediaPlayer => self.media_player.tooltip_view(),
+            ModuleName::Clock => self.clock.tooltip_view(&self.config.clock),
+            _ => None,
         }
     }
 
     fn get_module_subscription(&self, module_name: ModuleName) -> Option<Subscription<Message>> {
         match module_name {
-            ModuleName::AppLauncher => self.app_launcher.subscription(()),
+            ModuleName::AppLauncher => self
+                .app_launcher
+                .subscription(&self.config.app_launcher_cmd),
             ModuleName::Updates => self
                 .config
                 .updates
                 .as_ref()
                 .and_then(|updates_config| self.updates.subscription(updates_config)),
-            ModuleName::Clipboard => self.clipboard.subscription(()),
+            ModuleName::Clipboard => self.clipboard.subscription(&self.config.clipboard_cmd),
             ModuleName::Workspaces => self.workspaces.subscription(&self.config.workspaces),
             ModuleName::WindowTitle => self.window_title.subscription(()),
-            ModuleName::SystemInfo => self.system_info.subscription(()),
+            ModuleName::SystemInfo => self.system_info.subscription(&self.config.system),
             ModuleName::KeyboardLayout => self.keyboard_layout.subscription(()),
             ModuleName::KeyboardSubmap => self.keyboard_submap.subscription(()),
             ModuleName::Tray => self.tray.subscription(()),
-            ModuleName::Clock => self.clock.subscription(()),
-            ModuleName::Privacy => self.privacy.subscription(()),
-            ModuleName::Settings => self.settings.subscription(()),
-            ModuleName::MediaPlayer => self.media_player.subscription(()),
+            ModuleName::Clock => self.clock.subscription(&self.config.clock),
+            ModuleName::Privacy => self.privacy.subscription(&self.config.privacy),
+            ModuleName::Settings => self.settings.subscription((
+                &self.config.battery,
+                &self.config.network,
+                &self.config.brightness,
+            )),
+            ModuleName::MediaPlayer => self.media_player.subscription(&self.config.mpris),
+            ModuleName::Notifications => {
+                self.notifications.subscription(&self.config.notifications)
+            }
+            ModuleName::CustomCommand => self.custom_command.subscription(&self.config.custom),
+            ModuleName::Weather => self
+                .config
+                .weather
+                .as_ref()
+                .and_then(|weather_config| self.weather.subscription(weather_config)),
+            ModuleName::Uptime => self.uptime.subscription(()),
+            ModuleName::Pomodoro => self.pomodoro.subscription(()),
+            ModuleName::SystemdUnits => self.systemd_units.subscription(&self.config.systemd_units),
+            ModuleName::Gamepad => self.gamepad.subscription(&self.config.gamepad),
+            ModuleName::Printer => self.printer.subscription(()),
+            ModuleName::Usb => self.usb.subscription(()),
         }
     }
 }
@@ -0,0 +1,125 @@
+use super::{Module, OnModulePress};
+use crate::{
+    app,
+    components::icons::{Icons, icon},
+    config::NotificationsModuleConfig,
+    services::{
+        ReadOnlyService, Service, ServiceEvent,
+        dnd::{DndCommand, DndService},
+        inspector,
+        notifications::NotificationsService,
+    },
+};
+use iced::{
+    Alignment, Element, Subscription, Task, Theme,
+    widget::{container, row, text},
+};
+
+#[derive(Debug, Clone)]
+pub enum NotificationsMessage {
+    Event(ServiceEvent<NotificationsService>),
+    Dnd(ServiceEvent<DndService>),
+    ToggleDnd,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Notifications {
+    pub service: Option<NotificationsService>,
+    pub dnd: Option<DndService>,
+}
+
+impl Notifications {
+    pub fn update(&mut self, message: NotificationsMessage) -> Task<crate::app::Message> {
+        match message {
+            NotificationsMessage::Event(event) => match event {
+                ServiceEvent::Init(service) => {
+                    self.service = Some(service);
+                    Task::none()
+                }
+                ServiceEvent::Update(data) => {
+                    if let Some(notifications) = self.service.as_mut() {
+                        notifications.update(data);
+                    }
+                    Task::none()
+                }
+                ServiceEvent::Error(_) => Task::none(),
+            },
+            NotificationsMessage::Dnd(event) => match event {
+                ServiceEvent::Init(dnd) => {
+                    self.dnd = Some(dnd);
+                    Task::none()
+                }
+                ServiceEvent::Update(data) => {
+                    if let Some(dnd) = self.dnd.as_mut() {
+                        dnd.update(data);
+                    }
+                    Task::none()
+                }
+                ServiceEvent::Error(_) => Task::none(),
+            },
+            NotificationsMessage::ToggleDnd => match self.dnd.as_mut() {
+                Some(dnd) => dnd.command(DndCommand::Toggle).map(|event| {
+                    crate::app::Message::Notifications(NotificationsMessage::Dnd(event))
+                }),
+                _ => Task::none(),
+            },
+        }
+    }
+}
+
+impl Module for Notifications {
+    type ViewData<'a> = &'a NotificationsModuleConfig;
+    type SubscriptionData<'a> = &'a NotificationsModuleConfig;
+
+    fn view(
+        &self,
+        config: Self::ViewData<'_>,
+    ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
+        self.service.as_ref().map(|service| {
+            let count = service.count;
+            let warning = count > config.warn_threshold;
+            let dnd_active = self.dnd.as_ref().is_some_and(|dnd| dnd.active);
+
+            (
+                container(
+                    row!(icon(if dnd_active {
+                        Icons::BellOff
+                    } else {
+                        Icons::Bell
+                    }))
+                    .push_maybe((count > 0).then(|| text(count).size(12)))
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+                )
+                .style(move |theme: &Theme| container::Style {
+                    text_color: if warning {
+                        Some(theme.extended_palette().danger.weak.color)
+                    } else {
+                        None
+                    },
+                    ..Default::default()
+                })
+                .into(),
+                self.dnd.as_ref().map(|_| {
+                    OnModulePress::Action(app::Message::Notifications(
+                        NotificationsMessage::ToggleDnd,
+                    ))
+                }),
+            )
+        })
+    }
+
+    fn subscription(
+        &self,
+        config: Self::SubscriptionData<'_>,
+    ) -> Option<Subscription<app::Message>> {
+        Some(Subscription::batch(vec![
+            NotificationsService::subscribe_with_config(config)
+                .map(inspector::record)
+                .map(|e| app::Message::Notifications(NotificationsMessage::Event(e))),
+            DndService::subscribe_with_config(config)
+                .map(inspector::record)
+                .map(|e| app::Message::Notifications(NotificationsMessage::Dnd(e))),
+        ]))
+    }
+}
@@ -0,0 +1,171 @@
+use super::{Module, OnModulePress};
+use crate::{
+    app,
+    components::icons::{Icons, icon},
+    config::PomodoroModuleConfig,
+    style::ghost_button_style,
+    utils::{IndicatorState, format_duration_full, launcher},
+};
+use iced::{
+    Alignment, Element, Subscription, Theme,
+    time::every,
+    widget::{button, container, row, text},
+};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PomodoroMode {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl PomodoroMode {
+    fn duration(self, config: &PomodoroModuleConfig) -> Duration {
+        let minutes = match self {
+            PomodoroMode::Work => config.work_minutes,
+            PomodoroMode::ShortBreak => config.short_break_minutes,
+            PomodoroMode::LongBreak => config.long_break_minutes,
+        };
+
+        Duration::from_secs(minutes * 60)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            PomodoroMode::Work => "Work",
+            PomodoroMode::ShortBreak => "Short break",
+            PomodoroMode::LongBreak => "Long break",
+        }
+    }
+
+    fn get_indicator_state(self) -> IndicatorState {
+        match self {
+            PomodoroMode::Work => IndicatorState::Normal,
+            PomodoroMode::ShortBreak | PomodoroMode::LongBreak => IndicatorState::Success,
+        }
+    }
+}
+
+pub struct Pomodoro {
+    mode: PomodoroMode,
+    remaining: Duration,
+    running: bool,
+    completed_cycles: u8,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Tick,
+    ToggleRunning,
+    Reset,
+}
+
+impl Pomodoro {
+    pub fn new(config: &PomodoroModuleConfig) -> Self {
+        Self {
+            mode: PomodoroMode::Work,
+            remaining: PomodoroMode::Work.duration(config),
+            running: false,
+            completed_cycles: 0,
+        }
+    }
+
+    pub fn update(&mut self, message: Message, config: &PomodoroModuleConfig) {
+        match message {
+            Message::Tick => {
+                if self.running {
+                    self.remaining = self.remaining.saturating_sub(Duration::from_secs(1));
+
+                    if self.remaining.is_zero() {
+                        self.next_mode(config);
+                    }
+                }
+            }
+            Message::ToggleRunning => {
+                self.running = !self.running;
+            }
+            Message::Reset => {
+                *self = Self::new(config);
+            }
+        }
+    }
+
+    fn next_mode(&mut self, config: &PomodoroModuleConfig) {
+        let finished = self.mode;
+        self.mode = match finished {
+            PomodoroMode::Work => {
+                self.completed_cycles = self.completed_cycles.saturating_add(1);
+
+                if config.long_break_after > 0
+                    && self.completed_cycles % config.long_break_after == 0
+                {
+                    PomodoroMode::LongBreak
+                } else {
+                    PomodoroMode::ShortBreak
+                }
+            }
+            PomodoroMode::ShortBreak | PomodoroMode::LongBreak => PomodoroMode::Work,
+        };
+        self.remaining = self.mode.duration(config);
+
+        launcher::notify(
+            &format!("{} finished", finished.name()),
+            &format!(
+                "{} for {}",
+                self.mode.name(),
+                format_duration_full(&self.remaining)
+            ),
+            "normal",
+        );
+    }
+}
+
+impl Module for Pomodoro {
+    type ViewData<'a> = f32;
+    type SubscriptionData<'a> = ();
+
+    fn view(
+        &self,
+        opacity: Self::ViewData<'_>,
+    ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
+        let state = self.mode.get_indicator_state();
+
+        Some((
+            row!(
+                container(
+                    row!(
+                        icon(Icons::Pomodoro),
+                        text(format_duration_full(&self.remaining))
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(4)
+                )
+                .style(move |theme: &Theme| container::Style {
+                    text_color: match state {
+                        IndicatorState::Success => Some(theme.palette().success),
+                        _ => None,
+                    },
+                    ..Default::default()
+                }),
+                button(icon(Icons::PlayPause))
+                    .padding([0, 4])
+                    .style(ghost_button_style(opacity))
+                    .on_press(app::Message::Pomodoro(Message::ToggleRunning)),
+                button(icon(Icons::Refresh))
+                    .padding([0, 4])
+                    .style(ghost_button_style(opacity))
+                    .on_press(app::Message::Pomodoro(Message::Reset)),
+            )
+            .align_y(Alignment::Center)
+            .spacing(4)
+            .into(),
+            None,
+        ))
+    }
+
+    fn subscription(&self, _: Self::SubscriptionData<'_>) -> Option<Subscription<app::Message>> {
+        self.running
+            .then(|| every(Duration::from_secs(1)).map(|_| app::Message::Pomodoro(Message::Tick)))
+    }
+}
@@ -0,0 +1,149 @@
+use super::{Module, OnModulePress};
+use crate::{
+    app,
+    components::{
+        icons::{Icons, icon},
+        tooltip_wrapper::TooltipWrapper,
+    },
+    config::PrinterModuleConfig,
+    services::{
+        ReadOnlyService, ServiceEvent, inspector,
+        printer::{PrinterService, PrinterState},
+    },
+    utils::{IndicatorState, launcher::execute_command},
+};
+use iced::{
+    Element, Subscription, Task, Theme,
+    time::every,
+    widget::{container, text, tooltip},
+};
+use itertools::Itertools;
+use std::time::Duration;
+
+const CUPS_WEB_UI: &str = "http://localhost:631";
+const PULSE_INTERVAL: Duration = Duration::from_millis(600);
+
+#[derive(Debug, Clone)]
+pub enum PrinterMessage {
+    Event(ServiceEvent<PrinterService>),
+    Pulse,
+    OpenWebUi,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Printer {
+    pub service: Option<PrinterService>,
+    pulse_on: bool,
+}
+
+impl Printer {
+    pub fn update(&mut self, message: PrinterMessage) -> Task<crate::app::Message> {
+        match message {
+            PrinterMessage::Event(event) => match event {
+                ServiceEvent::Init(service) => {
+                    self.service = Some(service);
+                    Task::none()
+                }
+                ServiceEvent::Update(data) => {
+                    if let Some(service) = self.service.as_mut() {
+                        service.update(data);
+                    }
+                    Task::none()
+                }
+                ServiceEvent::Error(_) => Task::none(),
+            },
+            PrinterMessage::Pulse => {
+                self.pulse_on = !self.pulse_on;
+                Task::none()
+            }
+            PrinterMessage::OpenWebUi => {
+                execute_command(format!("xdg-open {CUPS_WEB_UI}"));
+                Task::none()
+            }
+        }
+    }
+}
+
+impl Module for Printer {
+    type ViewData<'a> = (&'a PrinterModuleConfig, tooltip::Position);
+    type SubscriptionData<'a> = ();
+
+    fn view(
+        &self,
+        (config, tooltip_position): Self::ViewData<'_>,
+    ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
+        let service = self.service.as_ref()?;
+
+        let state = if service.stopped() {
+            IndicatorState::Danger
+        } else if service.processing() {
+            IndicatorState::Success
+        } else if config.show_when_idle && !service.printers.is_empty() {
+            IndicatorState::Normal
+        } else {
+            return None;
+        };
+        let alpha = if matches!(state, IndicatorState::Success) && !self.pulse_on {
+            0.4
+        } else {
+            1.
+        };
+
+        Some((
+            TooltipWrapper::new(container(icon(Icons::Printer)).style(move |theme: &Theme| {
+                container::Style {
+                    text_color: match state {
+                        IndicatorState::Success => Some(theme.palette().success.scale_alpha(alpha)),
+                        IndicatorState::Danger => Some(theme.palette().danger),
+                        _ => None,
+                    },
+                    ..Default::default()
+                }
+            }))
+            .tooltip(Some(
+                text(
+                    service
+                        .printers
+                        .iter()
+                        .map(|printer| {
+                            let state = match printer.state {
+                                PrinterState::Idle => "idle",
+                                PrinterState::Processing => "printing",
+                                PrinterState::Stopped => "stopped",
+                            };
+
+                            format!(
+                                "{}: {}, {} jobs queued",
+                                printer.name, state, printer.queue_count
+                            )
+                        })
+                        .join("\n"),
+                )
+                .size(12),
+            ))
+            .position(tooltip_position)
+            .into(),
+            Some(OnModulePress::Action(app::Message::Printer(
+                PrinterMessage::OpenWebUi,
+            ))),
+        ))
+    }
+
+    fn subscription(&self, _: Self::SubscriptionData<'_>) -> Option<Subscription<app::Message>> {
+        let processing = self
+            .service
+            .as_ref()
+            .is_some_and(|service| service.processing() && !service.stopped());
+
+        Some(Subscription::batch(vec![
+            PrinterService::subscribe()
+                .map(inspector::record)
+                .map(|e| app::Message::Printer(PrinterMessage::Event(e))),
+            if processing {
+                every(PULSE_INTERVAL).map(|_| app::Message::Printer(PrinterMessage::Pulse))
+            } else {
+                Subscription::none()
+            },
+        ]))
+    }
+}
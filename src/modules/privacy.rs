@@ -1,83 +1,275 @@
 use super::{Module, OnModulePress};
 use crate::{
     app,
-    components::icons::{Icons, icon},
-    services::{ReadOnlyService, ServiceEvent, privacy::PrivacyService},
+    components::{
+        icons::{Icons, icon},
+        tooltip_wrapper::TooltipWrapper,
+    },
+    config::PrivacyModuleConfig,
+    menu::MenuType,
+    services::{
+        ReadOnlyService, ServiceEvent, inspector,
+        privacy::{self, AccessAction, PrivacyLog, PrivacyResource, PrivacyService},
+    },
 };
 use iced::{
-    Alignment, Element, Subscription, Task,
-    widget::{Row, container},
+    Alignment, Element, Length, Subscription, Task, Theme,
+    time::every,
+    widget::{Column, Row, column, container, horizontal_rule, row, scrollable, text, tooltip},
 };
+use log::warn;
+use std::time::Duration;
+
+const LOG_ENTRIES: usize = 20;
+const BLINK_INTERVAL: Duration = Duration::from_millis(600);
 
 #[derive(Debug, Clone)]
 pub enum PrivacyMessage {
     Event(ServiceEvent<PrivacyService>),
+    Blink,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct Privacy {
     pub service: Option<PrivacyService>,
+    blink_on: bool,
+    log: Vec<PrivacyLog>,
 }
 
 impl Privacy {
-    pub fn update(&mut self, message: PrivacyMessage) -> Task<crate::app::Message> {
+    pub fn update(
+        &mut self,
+        message: PrivacyMessage,
+        config: &PrivacyModuleConfig,
+    ) -> Task<crate::app::Message> {
         match message {
             PrivacyMessage::Event(event) => match event {
                 ServiceEvent::Init(service) => {
+                    if config.audit_log {
+                        self.log = privacy::read_log(LOG_ENTRIES);
+                    }
                     self.service = Some(service);
                     Task::none()
                 }
                 ServiceEvent::Update(data) => {
                     if let Some(privacy) = self.service.as_mut() {
+                        let before = privacy.accesses();
+                        let was_recording = privacy.screen_record_access();
                         privacy.update(data);
+                        if !was_recording {
+                            self.blink_on = true;
+                        }
+
+                        if config.audit_log {
+                            let entries = PrivacyLog::diff(&before, &privacy.accesses());
+                            if !entries.is_empty() {
+                                self.log.extend(entries.iter().cloned());
+                                let overflow = self.log.len().saturating_sub(LOG_ENTRIES);
+                                self.log.drain(..overflow);
+
+                                tokio::spawn(async move {
+                                    if let Err(err) = privacy::flush_log(&entries).await {
+                                        warn!("Failed to write the privacy log: {}", err);
+                                    }
+                                });
+                            }
+                        }
                     }
                     Task::none()
                 }
                 ServiceEvent::Error(_) => Task::none(),
             },
+            PrivacyMessage::Blink => {
+                self.blink_on = !self.blink_on;
+                Task::none()
+            }
         }
     }
+
+    pub fn menu_view(&self) -> Element<PrivacyMessage> {
+        let entries: Element<_> = if self.log.is_empty() {
+            text("No access recorded yet").size(12).into()
+        } else {
+            scrollable(
+                Column::with_children(
+                    self.log
+                        .iter()
+                        .rev()
+                        .map(|entry| {
+                            row!(
+                                text(entry.timestamp.format("%d/%m %H:%M:%S").to_string()).size(12),
+                                icon(match entry.resource {
+                                    PrivacyResource::Microphone => Icons::Mic1,
+                                    PrivacyResource::Camera => Icons::Webcam,
+                                    PrivacyResource::ScreenShare => Icons::ScreenShare,
+                                }),
+                                text(entry.app.clone()).size(12).width(Length::Fill),
+                                text(match entry.action {
+                                    AccessAction::Started => "started",
+                                    AccessAction::Stopped => "stopped",
+                                })
+                                .size(12),
+                            )
+                            .align_y(Alignment::Center)
+                            .spacing(8)
+                            .into()
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .spacing(4),
+            )
+            .height(Length::Fixed(300.))
+            .into()
+        };
+
+        column!(text("Access log"), horizontal_rule(1), entries)
+            .spacing(8)
+            .into()
+    }
 }
 
 impl Module for Privacy {
-    type ViewData<'a> = ();
-    type SubscriptionData<'a> = ();
+    type ViewData<'a> = (&'a PrivacyModuleConfig, tooltip::Position);
+    type SubscriptionData<'a> = &'a PrivacyModuleConfig;
 
     fn view(
         &self,
-        _: Self::ViewData<'_>,
+        (config, tooltip_position): Self::ViewData<'_>,
     ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
-        if let Some(service) = self.service.as_ref() {
-            if !service.no_access() {
-                Some((
-                    container(
-                        Row::new()
-                            .push_maybe(
-                                service
-                                    .screenshare_access()
-                                    .then(|| icon(Icons::ScreenShare)),
-                            )
-                            .push_maybe(service.webcam_access().then(|| icon(Icons::Webcam)))
-                            .push_maybe(service.microphone_access().then(|| icon(Icons::Mic1)))
-                            .align_y(Alignment::Center)
-                            .spacing(8),
-                    )
-                    .style(|theme| container::Style {
-                        text_color: Some(theme.extended_palette().danger.weak.color),
-                        ..Default::default()
-                    })
-                    .into(),
-                    None,
-                ))
-            } else {
-                None
-            }
-        } else {
-            None
+        let service = self.service.as_ref()?;
+
+        // the access log stays reachable while nothing is in use
+        if service.no_access() {
+            return config.audit_log.then(|| {
+                (
+                    container(icon(Icons::EyeClosed))
+                        .style(|theme: &Theme| container::Style {
+                            text_color: Some(theme.palette().text.scale_alpha(0.5)),
+                            ..Default::default()
+                        })
+                        .into(),
+                    Some(OnModulePress::ToggleMenu(MenuType::Privacy)),
+                )
+            });
         }
+
+        Some((
+            Row::new()
+                .push_maybe(service.screen_record_access().then(|| {
+                    record_indicator(
+                        service.screen_record_apps(),
+                        self.blink_on,
+                        tooltip_position,
+                    )
+                }))
+                .push_maybe(service.screenshare_access().then(|| {
+                    privacy_indicator(
+                        Icons::ScreenShare,
+                        "Screen is being shared",
+                        "Screen shared with",
+                        service.screenshare_apps(),
+                        tooltip_position,
+                    )
+                }))
+                .push_maybe(service.webcam_access().then(|| {
+                    privacy_indicator(
+                        Icons::Webcam,
+                        "Camera in use",
+                        "Camera used by",
+                        service.camera_apps().to_vec(),
+                        tooltip_position,
+                    )
+                }))
+                .push_maybe(service.microphone_access().then(|| {
+                    privacy_indicator(
+                        Icons::Mic1,
+                        "Microphone in use",
+                        "Microphone used by",
+                        service.microphone_apps(),
+                        tooltip_position,
+                    )
+                }))
+                .align_y(Alignment::Center)
+                .spacing(8)
+                .into(),
+            config
+                .audit_log
+                .then_some(OnModulePress::ToggleMenu(MenuType::Privacy)),
+        ))
     }
 
-    fn subscription(&self, _: Self::SubscriptionData<'_>) -> Option<Subscription<app::Message>> {
-        Some(PrivacyService::subscribe().map(|e| app::Message::Privacy(PrivacyMessage::Event(e))))
+    fn subscription(
+        &self,
+        config: Self::SubscriptionData<'_>,
+    ) -> Option<Subscription<app::Message>> {
+        let recording = self
+            .service
+            .as_ref()
+            .is_some_and(|service| service.screen_record_access());
+
+        Some(Subscription::batch(vec![
+            PrivacyService::subscribe_with_config(config)
+                .map(inspector::record)
+                .map(|e| app::Message::Privacy(PrivacyMessage::Event(e))),
+            if recording {
+                every(BLINK_INTERVAL).map(|_| app::Message::Privacy(PrivacyMessage::Blink))
+            } else {
+                Subscription::none()
+            },
+        ]))
     }
 }
+
+// the record icon blinks, it is easy to forget a recording running
+fn record_indicator<'a>(
+    apps: Vec<String>,
+    blink_on: bool,
+    position: tooltip::Position,
+) -> Element<'a, app::Message> {
+    TooltipWrapper::new(container(icon(Icons::Record)).style(move |theme: &Theme| {
+        container::Style {
+            text_color: Some(
+                theme
+                    .palette()
+                    .danger
+                    .scale_alpha(if blink_on { 1. } else { 0.3 }),
+            ),
+            ..Default::default()
+        }
+    }))
+    .tooltip(Some(
+        text(if apps.is_empty() {
+            "Screen is being recorded".to_owned()
+        } else {
+            format!("Screen recorded by {}", apps.join(", "))
+        })
+        .size(12),
+    ))
+    .position(position)
+    .into()
+}
+
+fn privacy_indicator<'a>(
+    icon_type: Icons,
+    fallback: &str,
+    prefix: &str,
+    apps: Vec<String>,
+    position: tooltip::Position,
+) -> Element<'a, app::Message> {
+    TooltipWrapper::new(
+        container(icon(icon_type)).style(|theme: &Theme| container::Style {
+            text_color: Some(theme.palette().danger),
+            ..Default::default()
+        }),
+    )
+    .tooltip(Some(
+        text(if apps.is_empty() {
+            fallback.to_owned()
+        } else {
+            format!("{} {}", prefix, apps.join(", "))
+        })
+        .size(12),
+    ))
+    .position(position)
+    .into()
+}
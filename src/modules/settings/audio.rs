@@ -1,11 +1,12 @@
 use super::{Message, SubMenu};
 use crate::{
     components::icons::{Icons, icon},
+    config::AccessibilityConfig,
     services::{
         ServiceEvent,
         audio::{AudioData, AudioService, DeviceType, Sinks},
     },
-    style::{ghost_button_style, settings_button_style},
+    style::{focus_ring_button_style, ghost_button_style, settings_button_style},
 };
 use iced::{
     Alignment, Element, Length, Theme,
@@ -22,6 +23,9 @@ pub enum AudioMessage {
     SinkVolumeChanged(i32),
     ToggleSourceMute,
     SourceVolumeChanged(i32),
+    ScrollVolume(f64),
+    ToggleStreamMute(u32, bool),
+    StreamVolumeChanged(u32, f32),
     SinksMore(Id),
     SourcesMore(Id),
 }
@@ -37,6 +41,21 @@ impl AudioData {
         }
     }
 
+    pub fn source_indicator<Message: 'static>(&self) -> Option<Element<Message>> {
+        if !self.sources.is_empty() && self.source_muted {
+            Some(
+                container(icon(Icons::Mic0))
+                    .style(|theme: &Theme| container::Style {
+                        text_color: Some(theme.palette().danger),
+                        ..Default::default()
+                    })
+                    .into(),
+            )
+        } else {
+            None
+        }
+    }
+
     pub fn audio_sliders(
         &self,
         sub_menu: Option<SubMenu>,
@@ -54,7 +73,14 @@ impl AudioData {
                 Message::Audio(AudioMessage::ToggleSinkMute),
                 self.cur_sink_volume,
                 |v| Message::Audio(AudioMessage::SinkVolumeChanged(v)),
-                if self.sinks.iter().map(|s| s.ports.len()).sum::<usize>() > 1 {
+                if self
+                    .sinks
+                    .iter()
+                    .map(|s| s.ports.len().max(1))
+                    .sum::<usize>()
+                    > 1
+                    || !self.streams.is_empty()
+                {
                     Some((sub_menu, Message::ToggleSubMenu(SubMenu::Sinks)))
                 } else {
                     None
@@ -76,7 +102,13 @@ impl AudioData {
                     Message::Audio(AudioMessage::ToggleSourceMute),
                     self.cur_source_volume,
                     |v| Message::Audio(AudioMessage::SourceVolumeChanged(v)),
-                    if self.sources.iter().map(|s| s.ports.len()).sum::<usize>() > 1 {
+                    if self
+                        .sources
+                        .iter()
+                        .map(|s| s.ports.len().max(1))
+                        .sum::<usize>()
+                        > 1
+                    {
                         Some((sub_menu, Message::ToggleSubMenu(SubMenu::Sources)))
                     } else {
                         None
@@ -91,52 +123,166 @@ impl AudioData {
         }
     }
 
-    pub fn sinks_submenu(&self, id: Id, show_more: bool, opacity: f32) -> Element<Message> {
-        audio_submenu(
-            self.sinks
-                .iter()
-                .flat_map(|s| {
-                    s.ports.iter().map(|p| SubmenuEntry {
-                        name: format!("{}: {}", p.description, s.description),
-                        device: p.device_type,
-                        active: p.active && s.name == self.server_info.default_sink,
+    fn sink_entries(&self) -> Vec<SubmenuEntry<Message>> {
+        self.sinks
+            .iter()
+            .flat_map(|s| {
+                if s.ports.is_empty() {
+                    vec![SubmenuEntry {
+                        name: s.description.clone(),
+                        device: DeviceType::Speaker,
+                        active: s.name == self.server_info.default_sink,
                         msg: Message::Audio(AudioMessage::DefaultSinkChanged(
                             s.name.clone(),
-                            p.name.clone(),
+                            String::new(),
                         )),
-                    })
-                })
-                .collect(),
+                    }]
+                } else {
+                    s.ports
+                        .iter()
+                        .map(|p| SubmenuEntry {
+                            name: format!("{}: {}", p.description, s.description),
+                            device: p.device_type,
+                            active: p.active && s.name == self.server_info.default_sink,
+                            msg: Message::Audio(AudioMessage::DefaultSinkChanged(
+                                s.name.clone(),
+                                p.name.clone(),
+                            )),
+                        })
+                        .collect()
+                }
+            })
+            .collect()
+    }
+
+    fn source_entries(&self) -> Vec<SubmenuEntry<Message>> {
+        self.sources
+            .iter()
+            .flat_map(|s| {
+                if s.ports.is_empty() {
+                    vec![SubmenuEntry {
+                        name: s.description.clone(),
+                        device: DeviceType::Speaker,
+                        active: s.name == self.server_info.default_source,
+                        msg: Message::Audio(AudioMessage::DefaultSourceChanged(
+                            s.name.clone(),
+                            String::new(),
+                        )),
+                    }]
+                } else {
+                    s.ports
+                        .iter()
+                        .map(|p| SubmenuEntry {
+                            name: format!("{}: {}", p.description, s.description),
+                            device: p.device_type,
+                            active: p.active && s.name == self.server_info.default_source,
+                            msg: Message::Audio(AudioMessage::DefaultSourceChanged(
+                                s.name.clone(),
+                                p.name.clone(),
+                            )),
+                        })
+                        .collect()
+                }
+            })
+            .collect()
+    }
+
+    // the entries reachable with the keyboard, the active one can't be selected again
+    pub fn sink_actions(&self) -> Vec<Option<Message>> {
+        Self::entry_actions(self.sink_entries())
+    }
+
+    pub fn source_actions(&self) -> Vec<Option<Message>> {
+        Self::entry_actions(self.source_entries())
+    }
+
+    fn entry_actions(entries: Vec<SubmenuEntry<Message>>) -> Vec<Option<Message>> {
+        entries
+            .into_iter()
+            .map(|entry| (!entry.active).then_some(entry.msg))
+            .collect()
+    }
+
+    pub fn sinks_submenu(
+        &self,
+        id: Id,
+        show_more: bool,
+        focused_index: usize,
+        accessibility: AccessibilityConfig,
+        opacity: f32,
+    ) -> Element<Message> {
+        let devices = audio_submenu(
+            self.sink_entries(),
             if show_more {
                 Some(Message::Audio(AudioMessage::SinksMore(id)))
             } else {
                 None
             },
+            focused_index,
+            accessibility,
             opacity,
-        )
+        );
+
+        if self.streams.is_empty() {
+            devices
+        } else {
+            column!(
+                devices,
+                horizontal_rule(1),
+                Column::with_children(
+                    self.streams
+                        .iter()
+                        .map(|stream| {
+                            let stream_id = stream.id;
+
+                            column!(
+                                text(stream.name.clone()).size(12),
+                                audio_slider(
+                                    SliderType::Sink,
+                                    stream.muted,
+                                    Message::Audio(AudioMessage::ToggleStreamMute(
+                                        stream_id,
+                                        !stream.muted,
+                                    )),
+                                    stream.volume as i32,
+                                    move |v| {
+                                        Message::Audio(AudioMessage::StreamVolumeChanged(
+                                            stream_id, v as f32,
+                                        ))
+                                    },
+                                    None,
+                                    opacity,
+                                ),
+                            )
+                            .spacing(4)
+                            .into()
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .spacing(8),
+            )
+            .spacing(12)
+            .into()
+        }
     }
 
-    pub fn sources_submenu(&self, id: Id, show_more: bool, opacity: f32) -> Element<Message> {
+    pub fn sources_submenu(
+        &self,
+        id: Id,
+        show_more: bool,
+        focused_index: usize,
+        accessibility: AccessibilityConfig,
+        opacity: f32,
+    ) -> Element<Message> {
         audio_submenu(
-            self.sources
-                .iter()
-                .flat_map(|s| {
-                    s.ports.iter().map(|p| SubmenuEntry {
-                        name: format!("{}: {}", p.description, s.description),
-                        device: p.device_type,
-                        active: p.active && s.name == self.server_info.default_source,
-                        msg: Message::Audio(AudioMessage::DefaultSourceChanged(
-                            s.name.clone(),
-                            p.name.clone(),
-                        )),
-                    })
-                })
-                .collect(),
+            self.source_entries(),
             if show_more {
                 Some(Message::Audio(AudioMessage::SourcesMore(id)))
             } else {
                 None
             },
+            focused_index,
+            accessibility,
             opacity,
         )
     }
@@ -209,18 +355,21 @@ pub struct SubmenuEntry<Message> {
 pub fn audio_submenu<'a, Message: 'a + Clone>(
     entries: Vec<SubmenuEntry<Message>>,
     more_msg: Option<Message>,
+    focused_index: usize,
+    accessibility: AccessibilityConfig,
     opacity: f32,
 ) -> Element<'a, Message> {
     let entries = Column::with_children(
         entries
             .into_iter()
-            .map(|e| {
+            .enumerate()
+            .map(|(index, e)| {
                 if e.active {
                     container(
                         row!(icon(e.device.get_icon()), text(e.name))
                             .align_y(Alignment::Center)
                             .spacing(16)
-                            .padding([4, 12]),
+                            .padding(accessibility.button_padding()),
                     )
                     .style(|theme: &Theme| container::Style {
                         text_color: Some(theme.palette().success),
@@ -234,9 +383,9 @@ pub fn audio_submenu<'a, Message: 'a + Clone>(
                             .align_y(Alignment::Center),
                     )
                     .on_press(e.msg)
-                    .padding([4, 12])
+                    .padding(accessibility.button_padding())
                     .width(Length::Fill)
-                    .style(ghost_button_style(opacity))
+                    .style(focus_ring_button_style(index == focused_index, opacity))
                     .into()
                 }
             })
@@ -251,7 +400,7 @@ pub fn audio_submenu<'a, Message: 'a + Clone>(
             horizontal_rule(1),
             button("More")
                 .on_press(more_msg)
-                .padding([4, 12])
+                .padding(accessibility.button_padding())
                 .width(Length::Fill)
                 .style(ghost_button_style(opacity)),
         )
@@ -1,38 +1,67 @@
 use super::{Message, SubMenu, quick_setting_button};
 use crate::{
     components::icons::{Icons, icon},
+    config::AccessibilityConfig,
     services::{
         ServiceEvent,
-        bluetooth::{BluetoothData, BluetoothService, BluetoothState},
+        bluetooth::{BluetoothData, BluetoothDevice, BluetoothService, BluetoothState},
     },
-    style::ghost_button_style,
+    style::{ghost_button_style, settings_button_style, with_focus_ring},
 };
 use iced::{
-    Element, Length, Theme,
-    widget::{Column, Row, button, column, container, horizontal_rule, row, text},
+    Alignment, Element, Length, Theme,
+    widget::{
+        Column, Row, button, column, container, horizontal_rule, pick_list, row, scrollable, text,
+    },
     window::Id,
 };
+use zbus::zvariant::OwnedObjectPath;
 
 #[derive(Debug, Clone)]
 pub enum BluetoothMessage {
     Event(ServiceEvent<BluetoothService>),
     Toggle,
+    ToggleDiscovery,
+    Pair(OwnedObjectPath),
+    Connect(OwnedObjectPath),
+    Disconnect(OwnedObjectPath),
+    SetProfile(String, String),
     More(Id),
 }
 
 impl BluetoothData {
+    fn connected_devices_summary(&self) -> Option<String> {
+        let connected = self
+            .devices
+            .iter()
+            .filter(|d| d.connected)
+            .collect::<Vec<_>>();
+
+        match connected.as_slice() {
+            [] => None,
+            [device] => Some(match device.battery {
+                Some(battery) => format!("{}: {}%", device.name, battery),
+                None => device.name.clone(),
+            }),
+            devices => Some(format!("{} devices", devices.len())),
+        }
+    }
+
     pub fn get_quick_setting_button(
         &self,
         id: Id,
         sub_menu: Option<SubMenu>,
         show_more_button: bool,
+        focused_index: usize,
+        accessibility: AccessibilityConfig,
         opacity: f32,
     ) -> Option<(Element<Message>, Option<Element<Message>>)> {
         Some((
             quick_setting_button(
                 Icons::Bluetooth,
                 "Bluetooth".to_owned(),
-                None,
+                self.connected_devices_summary()
+                    .filter(|_| self.state == BluetoothState::Active),
                 self.state == BluetoothState::Active,
                 Message::Bluetooth(BluetoothMessage::Toggle),
                 Some((
@@ -45,21 +74,75 @@ impl BluetoothData {
             ),
             sub_menu
                 .filter(|menu_type| *menu_type == SubMenu::Bluetooth)
-                .map(|_| self.bluetooth_menu(id, show_more_button, opacity)),
+                .map(|_| {
+                    self.bluetooth_menu(id, show_more_button, focused_index, accessibility, opacity)
+                }),
         ))
     }
 
-    pub fn bluetooth_menu(&self, id: Id, show_more_button: bool, opacity: f32) -> Element<Message> {
-        let main = if self.devices.is_empty() {
+    // connected devices first, the same order used for the keyboard navigation
+    fn menu_devices(&self) -> impl Iterator<Item = &BluetoothDevice> {
+        self.devices
+            .iter()
+            .filter(|d| d.connected)
+            .chain(self.devices.iter().filter(|d| !d.connected))
+    }
+
+    pub fn focusable_actions(&self) -> Vec<Option<Message>> {
+        self.menu_devices()
+            .map(|d| Self::connection_message(d).map(Message::Bluetooth))
+            .collect()
+    }
+
+    pub fn bluetooth_menu(
+        &self,
+        id: Id,
+        show_more_button: bool,
+        focused_index: usize,
+        accessibility: AccessibilityConfig,
+        opacity: f32,
+    ) -> Element<Message> {
+        let focused = self.menu_devices().nth(focused_index).map(|d| &d.path);
+
+        let connected_devices = if self.devices.iter().all(|d| !d.connected) {
             text("No devices connected").into()
         } else {
             Column::with_children(
                 self.devices
                     .iter()
+                    .filter(|d| d.connected)
                     .map(|d| {
-                        Row::new()
-                            .push(text(d.name.to_string()).width(Length::Fill))
-                            .push_maybe(d.battery.map(Self::battery_level))
+                        let address = d.address.clone();
+
+                        Column::new()
+                            .push(
+                                Row::new()
+                                    .push(icon(d.class.get_icon()))
+                                    .push(text(d.name.to_string()).width(Length::Fill))
+                                    .push_maybe(d.battery.map(Self::battery_level))
+                                    .push(Self::connection_button(
+                                        d,
+                                        focused == Some(&d.path),
+                                        accessibility,
+                                        opacity,
+                                    ))
+                                    .align_y(Alignment::Center)
+                                    .spacing(8),
+                            )
+                            .push_maybe(Some(&d.profiles).filter(|p| p.len() > 1).map(|profiles| {
+                                pick_list(
+                                    profiles.clone(),
+                                    d.active_profile.clone(),
+                                    move |profile| {
+                                        Message::Bluetooth(BluetoothMessage::SetProfile(
+                                            address.clone(),
+                                            profile,
+                                        ))
+                                    },
+                                )
+                                .width(Length::Fill)
+                            }))
+                            .spacing(4)
                             .into()
                     })
                     .collect::<Vec<Element<Message>>>(),
@@ -68,13 +151,62 @@ impl BluetoothData {
             .into()
         };
 
+        let available_devices = container(scrollable(
+            Column::with_children(
+                self.devices
+                    .iter()
+                    .filter(|d| !d.connected)
+                    .map(|d| {
+                        row!(
+                            icon(d.class.get_icon()),
+                            text(d.name.to_string()).width(Length::Fill),
+                            Self::connection_button(
+                                d,
+                                focused == Some(&d.path),
+                                accessibility,
+                                opacity,
+                            ),
+                        )
+                        .align_y(Alignment::Center)
+                        .spacing(8)
+                        .into()
+                    })
+                    .collect::<Vec<Element<Message>>>(),
+            )
+            .spacing(8),
+        ))
+        .max_height(200);
+
+        let main = column!(
+            connected_devices,
+            horizontal_rule(1),
+            row!(
+                text("Available devices").width(Length::Fill),
+                text(if self.discovering { "Scanning..." } else { "" }).size(12),
+                button(icon(if self.discovering {
+                    Icons::Close
+                } else {
+                    Icons::Refresh
+                }))
+                .padding([4, 10])
+                .style(settings_button_style(opacity))
+                .on_press(Message::Bluetooth(BluetoothMessage::ToggleDiscovery)),
+            )
+            .spacing(8)
+            .width(Length::Fill)
+            .align_y(Alignment::Center),
+            available_devices,
+        )
+        .spacing(8)
+        .into();
+
         if show_more_button {
             column!(
                 main,
                 horizontal_rule(1),
                 button("More")
                     .on_press(Message::Bluetooth(BluetoothMessage::More(id)))
-                    .padding([4, 12])
+                    .padding(accessibility.button_padding())
                     .width(Length::Fill)
                     .style(ghost_button_style(opacity))
             )
@@ -85,6 +217,42 @@ impl BluetoothData {
         }
     }
 
+    fn connection_message(device: &BluetoothDevice) -> Option<BluetoothMessage> {
+        if device.connecting {
+            None
+        } else if device.connected {
+            Some(BluetoothMessage::Disconnect(device.path.clone()))
+        } else if device.paired {
+            Some(BluetoothMessage::Connect(device.path.clone()))
+        } else {
+            Some(BluetoothMessage::Pair(device.path.clone()))
+        }
+    }
+
+    fn connection_button<'a>(
+        device: &BluetoothDevice,
+        focused: bool,
+        accessibility: AccessibilityConfig,
+        opacity: f32,
+    ) -> Element<'a, Message> {
+        match Self::connection_message(device) {
+            None => text("Connecting...").size(12).into(),
+            Some(msg) => {
+                let label = match msg {
+                    BluetoothMessage::Disconnect(_) => "Disconnect",
+                    BluetoothMessage::Connect(_) => "Connect",
+                    _ => "Pair",
+                };
+
+                button(label)
+                    .padding(accessibility.button_padding())
+                    .style(with_focus_ring(focused, settings_button_style(opacity)))
+                    .on_press(Message::Bluetooth(msg))
+                    .into()
+            }
+        }
+    }
+
     fn battery_level<'a>(battery: u8) -> Element<'a, Message> {
         container(
             row!(
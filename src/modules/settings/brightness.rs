@@ -7,7 +7,7 @@ use crate::{
 };
 use iced::{
     Alignment, Element, Length,
-    widget::{container, row, slider},
+    widget::{Column, container, row, slider, text},
 };
 
 use super::Message;
@@ -16,9 +16,50 @@ use super::Message;
 pub enum BrightnessMessage {
     Event(ServiceEvent<BrightnessService>),
     Change(u32),
+    ChangeOutput(String, u8),
+    ReleaseOutput(String),
 }
 
 impl BrightnessData {
+    pub fn output_sliders(&self) -> Option<Element<Message>> {
+        if self.displays.is_empty() {
+            None
+        } else {
+            Some(
+                Column::with_children(
+                    self.displays
+                        .iter()
+                        .map(|display| {
+                            let change_output = display.output_name.clone();
+                            let release_output = display.output_name.clone();
+
+                            row!(
+                                container(icon(Icons::Brightness)).padding([8, 11]),
+                                text(display.output_name.clone()).size(12),
+                                slider(0..=100, display.level, move |v| {
+                                    Message::Brightness(BrightnessMessage::ChangeOutput(
+                                        change_output.clone(),
+                                        v,
+                                    ))
+                                })
+                                .on_release(Message::Brightness(BrightnessMessage::ReleaseOutput(
+                                    release_output
+                                )))
+                                .step(1_u8)
+                                .width(Length::Fill),
+                            )
+                            .align_y(Alignment::Center)
+                            .spacing(8)
+                            .into()
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .spacing(8)
+                .into(),
+            )
+        }
+    }
+
     pub fn brightness_slider(&self) -> Element<Message> {
         row!(
             container(icon(Icons::Brightness)).padding([8, 11]),
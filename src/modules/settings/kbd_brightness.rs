@@ -0,0 +1,35 @@
+use crate::{
+    components::icons::{Icons, icon},
+    services::{
+        ServiceEvent,
+        kbd_brightness::{KbdBrightnessData, KbdBrightnessService},
+    },
+};
+use iced::{
+    Alignment, Element, Length,
+    widget::{container, row, slider},
+};
+
+use super::Message;
+
+#[derive(Debug, Clone)]
+pub enum KbdBrightnessMessage {
+    Event(ServiceEvent<KbdBrightnessService>),
+    Change(u32),
+}
+
+impl KbdBrightnessData {
+    pub fn kbd_brightness_slider(&self) -> Element<Message> {
+        row!(
+            container(icon(Icons::Keyboard)).padding([8, 11]),
+            slider(0..=self.max, self.current, |v| {
+                Message::KbdBrightness(KbdBrightnessMessage::Change(v))
+            })
+            .step(1_u32)
+            .width(Length::Fill),
+        )
+        .align_y(Alignment::Center)
+        .spacing(8)
+        .into()
+    }
+}
@@ -1,13 +1,20 @@
 use self::{
-    audio::AudioMessage, bluetooth::BluetoothMessage, network::NetworkMessage, power::PowerMessage,
+    audio::AudioMessage, bluetooth::BluetoothMessage, network::NetworkMessage,
+    niri_outputs::NiriOutputsMessage, power::PowerMessage,
 };
 use super::{Module, OnModulePress};
 use crate::{
     app,
-    components::icons::{Icons, icon},
-    config::SettingsModuleConfig,
+    components::{
+        icons::{Icons, icon},
+        tooltip_wrapper::TooltipWrapper,
+    },
+    config::{
+        AccessibilityConfig, AudioConfig, BatteryConfig, BrightnessConfig, NetworkConfig,
+        SettingsModuleConfig,
+    },
     menu::MenuType,
-    modules::settings::power::power_menu,
+    modules::settings::power::{PowerAction, PowerState, power_actions, power_menu},
     outputs::Outputs,
     password_dialog,
     position_button::ButtonUIRef,
@@ -15,40 +22,64 @@ use crate::{
         ReadOnlyService, Service, ServiceEvent,
         audio::{AudioCommand, AudioService},
         bluetooth::{BluetoothCommand, BluetoothService, BluetoothState},
-        brightness::{BrightnessCommand, BrightnessService},
+        brightness::{BrightnessCommand, BrightnessEvent, BrightnessService},
         idle_inhibitor::IdleInhibitorManager,
+        inspector,
+        kbd_brightness::{KbdBrightnessCommand, KbdBrightnessService},
         network::{NetworkCommand, NetworkEvent, NetworkService},
+        night_mode::{NightModeCommand, NightModeEvent, NightModeManager},
+        niri::{NiriCommand, NiriService},
         upower::{PowerProfileCommand, UPowerService},
     },
     style::{
         quick_settings_button_style, quick_settings_submenu_button_style, settings_button_style,
     },
+    utils::format_duration_full,
 };
 use brightness::BrightnessMessage;
 use iced::{
     Alignment, Background, Border, Element, Length, Padding, Subscription, Task, Theme,
     alignment::{Horizontal, Vertical},
-    widget::{Column, Row, Space, button, column, container, horizontal_space, row, text},
+    event::listen_with,
+    keyboard::{self, key::Named},
+    mouse::ScrollDelta,
+    time::every,
+    widget::{
+        Column, Row, Space, button, column, container, horizontal_space, mouse_area, row, slider,
+        text, tooltip,
+    },
     window::Id,
 };
+use kbd_brightness::KbdBrightnessMessage;
 use log::info;
+use std::time::Duration;
 use upower::UPowerMessage;
 
 pub mod audio;
 pub mod bluetooth;
 pub mod brightness;
+pub mod kbd_brightness;
 pub mod network;
+mod niri_outputs;
 mod power;
 mod upower;
 
 pub struct Settings {
     audio: Option<AudioService>,
     pub brightness: Option<BrightnessService>,
+    kbd_brightness: Option<KbdBrightnessService>,
     network: Option<NetworkService>,
     bluetooth: Option<BluetoothService>,
     idle_inhibitor: Option<IdleInhibitorManager>,
+    night_mode: NightModeManager,
     pub sub_menu: Option<SubMenu>,
+    power_state: PowerState,
+    focused_index: usize,
+    locker_available: bool,
+    hibernate_available: bool,
     upower: Option<UPowerService>,
+    // only used for the outputs, the workspaces module follows the event stream
+    niri: Option<NiriService>,
     pub password_dialog: Option<(String, String)>,
 }
 
@@ -57,11 +88,18 @@ impl Default for Settings {
         Settings {
             audio: None,
             brightness: None,
+            kbd_brightness: None,
             network: None,
             bluetooth: None,
             idle_inhibitor: IdleInhibitorManager::new(),
+            night_mode: NightModeManager::default(),
             sub_menu: None,
+            power_state: PowerState::Idle,
+            focused_index: 0,
+            locker_available: false,
+            hibernate_available: false,
             upower: None,
+            niri: None,
             password_dialog: None,
         }
     }
@@ -75,10 +113,21 @@ pub enum Message {
     Bluetooth(BluetoothMessage),
     Audio(AudioMessage),
     Brightness(BrightnessMessage),
+    KbdBrightness(KbdBrightnessMessage),
     ToggleInhibitIdle,
+    IdleInhibitorTick,
+    ToggleNightMode,
+    NightModeTemperatureChanged(u32),
+    ApplyNightModeTemperature,
+    NightModeSettled(NightModeEvent),
     Lock,
     Power(PowerMessage),
+    NiriOutputs(NiriOutputsMessage),
     ToggleSubMenu(SubMenu),
+    FocusNext,
+    FocusPrev,
+    ActivateFocused,
+    CloseMenu,
     PasswordDialog(password_dialog::Message),
 }
 
@@ -90,9 +139,69 @@ pub enum SubMenu {
     Wifi,
     Vpn,
     Bluetooth,
+    PowerProfiles,
+    Outputs,
+}
+
+impl SubMenu {
+    // the submenus navigated with tab, they take the keyboard while open
+    fn keyboard_navigation(self) -> bool {
+        matches!(
+            self,
+            SubMenu::Power | SubMenu::Sinks | SubMenu::Sources | SubMenu::Bluetooth
+        )
+    }
 }
 
 impl Settings {
+    // niri sends no event when the outputs change, they are read each time the menu opens
+    pub fn refresh_niri_outputs(&mut self) -> Task<crate::app::Message> {
+        if !NiriService::is_available() {
+            return Task::none();
+        }
+
+        self.niri
+            .get_or_insert_with(NiriService::default)
+            .command(NiriCommand::RefreshOutputs)
+            .map(|event| {
+                crate::app::Message::Settings(Message::NiriOutputs(NiriOutputsMessage::Event(
+                    event,
+                )))
+            })
+    }
+
+    // the actions of the open submenu in the order they are rendered,
+    // None for the entries that can be focused but not activated
+    fn focusable_actions(&self, config: &SettingsModuleConfig) -> Vec<Option<Message>> {
+        match self.sub_menu {
+            Some(SubMenu::Power) => power_actions(
+                self.power_state,
+                &config.power,
+                self.locker_available,
+                self.hibernate_available,
+            )
+            .into_iter()
+            .map(|action| action.map(Message::Power))
+            .collect(),
+            Some(SubMenu::Sinks) => self
+                .audio
+                .as_ref()
+                .map(|audio| audio.sink_actions())
+                .unwrap_or_default(),
+            Some(SubMenu::Sources) => self
+                .audio
+                .as_ref()
+                .map(|audio| audio.source_actions())
+                .unwrap_or_default(),
+            Some(SubMenu::Bluetooth) => self
+                .bluetooth
+                .as_ref()
+                .map(|bluetooth| bluetooth.focusable_actions())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
     pub fn update(
         &mut self,
         message: Message,
@@ -105,6 +214,32 @@ impl Settings {
                 self.password_dialog = None;
                 outputs.toggle_menu(id, MenuType::Settings, button_ui_ref)
             }
+            Message::NiriOutputs(msg) => {
+                let Some(niri) = self.niri.as_mut() else {
+                    return Task::none();
+                };
+
+                let command = match msg {
+                    NiriOutputsMessage::Event(event) => {
+                        if let ServiceEvent::Update(event) = event {
+                            niri.update(event);
+                        }
+                        return Task::none();
+                    }
+                    NiriOutputsMessage::SetScale(output, scale) => {
+                        NiriCommand::SetOutputScale { output, scale }
+                    }
+                    NiriOutputsMessage::SetPower(output, on) => {
+                        NiriCommand::SetOutputPower { output, on }
+                    }
+                };
+
+                niri.command(command).map(|event| {
+                    crate::app::Message::Settings(Message::NiriOutputs(NiriOutputsMessage::Event(
+                        event,
+                    )))
+                })
+            }
             Message::Audio(msg) => match msg {
                 AudioMessage::Event(event) => match event {
                     ServiceEvent::Init(service) => {
@@ -155,6 +290,29 @@ impl Settings {
                     }
                     Task::none()
                 }
+                AudioMessage::ScrollVolume(delta) => {
+                    if delta == 0. {
+                        return Task::none();
+                    }
+
+                    if let Some(audio) = self.audio.as_mut() {
+                        let volume = (audio.cur_sink_volume as f64 + delta).clamp(0., 100.);
+                        let _ = audio.command(AudioCommand::SinkVolume(volume as i32));
+                    }
+                    Task::none()
+                }
+                AudioMessage::ToggleStreamMute(id, muted) => {
+                    if let Some(audio) = self.audio.as_mut() {
+                        let _ = audio.command(AudioCommand::MuteStream { id, muted });
+                    }
+                    Task::none()
+                }
+                AudioMessage::StreamVolumeChanged(id, volume) => {
+                    if let Some(audio) = self.audio.as_mut() {
+                        let _ = audio.command(AudioCommand::SetStreamVolume { id, volume });
+                    }
+                    Task::none()
+                }
                 AudioMessage::SinksMore(id) => {
                     if let Some(cmd) = &config.audio_sinks_more_cmd {
                         crate::utils::launcher::execute_command(cmd.to_string());
@@ -192,6 +350,16 @@ impl Settings {
                     }),
                     _ => Task::none(),
                 },
+                UPowerMessage::SetPowerProfile(profile) => match self.upower.as_mut() {
+                    Some(upower) => upower
+                        .command(PowerProfileCommand::SetProfile(profile))
+                        .map(|event| {
+                            crate::app::Message::Settings(Message::UPower(UPowerMessage::Event(
+                                event,
+                            )))
+                        }),
+                    _ => Task::none(),
+                },
             },
             Message::Network(msg) => match msg {
                 NetworkMessage::Event(event) => match event {
@@ -320,6 +488,68 @@ impl Settings {
                     }
                     _ => Task::none(),
                 },
+                BluetoothMessage::ToggleDiscovery => match self.bluetooth.as_mut() {
+                    Some(bluetooth) => {
+                        let command = if bluetooth.discovering {
+                            BluetoothCommand::StopDiscovery
+                        } else {
+                            BluetoothCommand::StartDiscovery
+                        };
+
+                        bluetooth.command(command).map(|event| {
+                            crate::app::Message::Settings(Message::Bluetooth(
+                                BluetoothMessage::Event(event),
+                            ))
+                        })
+                    }
+                    _ => Task::none(),
+                },
+                BluetoothMessage::Pair(path) => match self.bluetooth.as_mut() {
+                    Some(bluetooth) => {
+                        bluetooth
+                            .command(BluetoothCommand::Pair(path))
+                            .map(|event| {
+                                crate::app::Message::Settings(Message::Bluetooth(
+                                    BluetoothMessage::Event(event),
+                                ))
+                            })
+                    }
+                    _ => Task::none(),
+                },
+                BluetoothMessage::Connect(path) => match self.bluetooth.as_mut() {
+                    Some(bluetooth) => {
+                        bluetooth
+                            .command(BluetoothCommand::Connect(path))
+                            .map(|event| {
+                                crate::app::Message::Settings(Message::Bluetooth(
+                                    BluetoothMessage::Event(event),
+                                ))
+                            })
+                    }
+                    _ => Task::none(),
+                },
+                BluetoothMessage::Disconnect(path) => {
+                    match self.bluetooth.as_mut() {
+                        Some(bluetooth) => bluetooth
+                            .command(BluetoothCommand::Disconnect(path))
+                            .map(|event| {
+                                crate::app::Message::Settings(Message::Bluetooth(
+                                    BluetoothMessage::Event(event),
+                                ))
+                            }),
+                        _ => Task::none(),
+                    }
+                }
+                BluetoothMessage::SetProfile(address, profile) => match self.bluetooth.as_mut() {
+                    Some(bluetooth) => bluetooth
+                        .command(BluetoothCommand::SetProfile { address, profile })
+                        .map(|event| {
+                            crate::app::Message::Settings(Message::Bluetooth(
+                                BluetoothMessage::Event(event),
+                            ))
+                        }),
+                    _ => Task::none(),
+                },
                 BluetoothMessage::More(id) => {
                     if let Some(cmd) = &config.bluetooth_more_cmd {
                         crate::utils::launcher::execute_command(cmd.to_string());
@@ -343,6 +573,31 @@ impl Settings {
                     }
                     _ => Task::none(),
                 },
+                BrightnessMessage::ChangeOutput(output, level) => {
+                    if let Some(brightness) = self.brightness.as_mut() {
+                        brightness.update(BrightnessEvent::Output { output, level });
+                    }
+                    Task::none()
+                }
+                BrightnessMessage::ReleaseOutput(output) => match self.brightness.as_mut() {
+                    Some(brightness) => {
+                        let level = brightness
+                            .displays
+                            .iter()
+                            .find(|d| d.output_name == output)
+                            .map(|d| d.level)
+                            .unwrap_or_default();
+
+                        brightness
+                            .command(BrightnessCommand::SetOutput { output, level })
+                            .map(|event| {
+                                crate::app::Message::Settings(Message::Brightness(
+                                    BrightnessMessage::Event(event),
+                                ))
+                            })
+                    }
+                    _ => Task::none(),
+                },
                 BrightnessMessage::Change(value) => match self.brightness.as_mut() {
                     Some(brightness) => {
                         brightness
@@ -356,43 +611,175 @@ impl Settings {
                     _ => Task::none(),
                 },
             },
+            Message::KbdBrightness(msg) => match msg {
+                KbdBrightnessMessage::Event(event) => match event {
+                    ServiceEvent::Init(service) => {
+                        self.kbd_brightness = Some(service);
+                        Task::none()
+                    }
+                    ServiceEvent::Update(data) => {
+                        if let Some(kbd_brightness) = self.kbd_brightness.as_mut() {
+                            kbd_brightness.update(data);
+                        }
+                        Task::none()
+                    }
+                    _ => Task::none(),
+                },
+                KbdBrightnessMessage::Change(value) => match self.kbd_brightness.as_mut() {
+                    Some(kbd_brightness) => kbd_brightness
+                        .command(KbdBrightnessCommand::Set(value))
+                        .map(|event| {
+                            crate::app::Message::Settings(Message::KbdBrightness(
+                                KbdBrightnessMessage::Event(event),
+                            ))
+                        }),
+                    _ => Task::none(),
+                },
+            },
             Message::ToggleSubMenu(menu_type) => {
+                self.power_state = PowerState::Idle;
+                self.focused_index = 0;
+
                 if self.sub_menu == Some(menu_type) {
                     self.sub_menu.take();
+
+                    if menu_type.keyboard_navigation() {
+                        return outputs.release_keyboard_if(MenuType::Settings);
+                    }
                 } else {
-                    self.sub_menu.replace(menu_type);
+                    let had_keyboard = self
+                        .sub_menu
+                        .replace(menu_type)
+                        .is_some_and(SubMenu::keyboard_navigation);
+
+                    if menu_type == SubMenu::Power {
+                        self.locker_available = crate::utils::launcher::can_lock(config);
+                        self.hibernate_available = config
+                            .power
+                            .show_hibernate
+                            .unwrap_or_else(crate::utils::launcher::can_hibernate);
+                    }
+
+                    // the keyboard is needed for tab navigation inside the submenu
+                    let keyboard = match (had_keyboard, menu_type.keyboard_navigation()) {
+                        (false, true) => outputs.request_keyboard_if(MenuType::Settings),
+                        (true, false) => outputs.release_keyboard_if(MenuType::Settings),
+                        _ => Task::none(),
+                    };
 
                     if menu_type == SubMenu::Wifi {
                         if let Some(network) = self.network.as_mut() {
-                            return network
-                                .command(NetworkCommand::ScanNearByWiFi)
-                                .map(|event| {
-                                    crate::app::Message::Settings(Message::Network(
-                                        NetworkMessage::Event(event),
-                                    ))
-                                });
+                            return Task::batch(vec![
+                                keyboard,
+                                network
+                                    .command(NetworkCommand::ScanNearByWiFi)
+                                    .map(|event| {
+                                        crate::app::Message::Settings(Message::Network(
+                                            NetworkMessage::Event(event),
+                                        ))
+                                    }),
+                            ]);
                         }
                     }
+
+                    return keyboard;
                 }
 
                 Task::none()
             }
+            Message::ToggleNightMode => self
+                .night_mode
+                .command(NightModeCommand::Toggle, &config.night_mode)
+                .map(|event| app::Message::Settings(Message::NightModeSettled(event))),
+            Message::NightModeSettled(event) => self
+                .night_mode
+                .settled(event, &config.night_mode)
+                .map(|event| app::Message::Settings(Message::NightModeSettled(event))),
+            Message::NightModeTemperatureChanged(temperature) => {
+                self.night_mode.preview_temperature(temperature);
+                Task::none()
+            }
+            Message::ApplyNightModeTemperature => {
+                let temperature = self.night_mode.temperature(&config.night_mode);
+                self.night_mode
+                    .command(
+                        NightModeCommand::SetTemperature(temperature),
+                        &config.night_mode,
+                    )
+                    .map(|event| app::Message::Settings(Message::NightModeSettled(event)))
+            }
             Message::ToggleInhibitIdle => {
                 if let Some(idle_inhibitor) = &mut self.idle_inhibitor {
                     idle_inhibitor.toggle();
                 }
                 Task::none()
             }
+            Message::IdleInhibitorTick => {
+                if let (Some(idle_inhibitor), Some(max_duration)) =
+                    (&mut self.idle_inhibitor, config.idle_inhibitor.max_duration)
+                {
+                    if idle_inhibitor
+                        .elapsed()
+                        .is_some_and(|elapsed| elapsed.as_secs() >= max_duration * 60)
+                    {
+                        info!("Idle inhibitor max duration reached, deactivating");
+                        idle_inhibitor.deactivate();
+                    }
+                }
+                Task::none()
+            }
             Message::Lock => {
                 if let Some(lock_cmd) = &config.lock_cmd {
                     crate::utils::launcher::execute_command(lock_cmd.to_string());
                 }
                 Task::none()
             }
-            Message::Power(msg) => {
-                msg.update();
+            Message::FocusNext => {
+                let count = self.focusable_actions(config).len().max(1);
+                self.focused_index = (self.focused_index + 1) % count;
+                Task::none()
+            }
+            Message::FocusPrev => {
+                let count = self.focusable_actions(config).len().max(1);
+                self.focused_index = (self.focused_index + count - 1) % count;
                 Task::none()
             }
+            Message::ActivateFocused => {
+                match self
+                    .focusable_actions(config)
+                    .into_iter()
+                    .nth(self.focused_index)
+                    .flatten()
+                {
+                    Some(action) => self.update(action, config, outputs),
+                    None => Task::none(),
+                }
+            }
+            Message::CloseMenu => {
+                self.sub_menu = None;
+                outputs.close_all_menu_if(MenuType::Settings)
+            }
+            Message::Power(msg) => {
+                let close_menu = matches!(
+                    msg,
+                    PowerMessage::Lock | PowerMessage::Screenshot | PowerMessage::ScreenshotRegion
+                );
+
+                self.power_state = match msg {
+                    PowerMessage::ConfirmReboot => PowerState::Confirming(PowerAction::Reboot),
+                    PowerMessage::ConfirmShutdown => PowerState::Confirming(PowerAction::Shutdown),
+                    _ => PowerState::Idle,
+                };
+                self.focused_index = 0;
+
+                msg.update(config);
+
+                if close_menu {
+                    outputs.close_all_menu_if(MenuType::Settings)
+                } else {
+                    Task::none()
+                }
+            }
             Message::PasswordDialog(msg) => match msg {
                 password_dialog::Message::PasswordChanged(password) => {
                     if let Some((_, current_password)) = &mut self.password_dialog {
@@ -445,16 +832,17 @@ impl Settings {
         &self,
         id: Id,
         config: &SettingsModuleConfig,
+        accessibility: AccessibilityConfig,
         opacity: f32,
     ) -> Element<Message> {
         if let Some((ssid, current_password)) = &self.password_dialog {
             password_dialog::view(id, ssid, current_password, opacity).map(Message::PasswordDialog)
         } else {
-            let battery_data = self
-                .upower
-                .as_ref()
-                .and_then(|upower| upower.battery)
-                .map(|battery| battery.settings_indicator(opacity));
+            let battery_data = self.upower.as_ref().and_then(|upower| {
+                upower
+                    .battery
+                    .map(|battery| battery.settings_indicator(&upower.config, opacity))
+            });
             let right_buttons = Row::new()
                 .push_maybe(config.lock_cmd.as_ref().map(|_| {
                     button(icon(Icons::Lock))
@@ -492,6 +880,7 @@ impl Settings {
                     id,
                     self.sub_menu,
                     config.wifi_more_cmd.is_some(),
+                    accessibility,
                     opacity,
                 )
             });
@@ -506,6 +895,8 @@ impl Settings {
                                 id,
                                 self.sub_menu,
                                 config.bluetooth_more_cmd.is_some(),
+                                self.focused_index,
+                                accessibility,
                                 opacity,
                             )
                         }),
@@ -514,6 +905,7 @@ impl Settings {
                             id,
                             self.sub_menu,
                             config.vpn_more_cmd.is_some(),
+                            accessibility,
                             opacity,
                         )
                     }),
@@ -529,7 +921,9 @@ impl Settings {
                                     Icons::EyeClosed
                                 },
                                 "Idle Inhibitor".to_string(),
-                                None,
+                                idle_inhibitor.elapsed().map(|elapsed| {
+                                    format!("Active: {}", format_duration_full(&elapsed))
+                                }),
                                 idle_inhibitor.is_inhibited(),
                                 Message::ToggleInhibitIdle,
                                 None,
@@ -538,9 +932,32 @@ impl Settings {
                             None,
                         )
                     }),
-                    self.upower
+                    Some((
+                        quick_setting_button(
+                            if self.night_mode.is_active() {
+                                Icons::NightMode
+                            } else {
+                                Icons::DayMode
+                            },
+                            "Night Mode".to_string(),
+                            None,
+                            self.night_mode.is_active(),
+                            Message::ToggleNightMode,
+                            None,
+                            opacity,
+                        ),
+                        None,
+                    )),
+                    self.upower.as_ref().and_then(|u| {
+                        u.get_power_profile_quick_setting_button(
+                            self.sub_menu,
+                            accessibility,
+                            opacity,
+                        )
+                    }),
+                    self.niri
                         .as_ref()
-                        .and_then(|u| u.power_profile.get_quick_setting_button(opacity)),
+                        .and_then(|n| n.get_outputs_quick_setting_button(self.sub_menu, opacity)),
                 ]
                 .into_iter()
                 .flatten()
@@ -554,7 +971,19 @@ impl Settings {
                     self.sub_menu
                         .filter(|menu_type| *menu_type == SubMenu::Power)
                         .map(|_| {
-                            sub_menu_wrapper(power_menu(opacity).map(Message::Power), opacity)
+                            sub_menu_wrapper(
+                                power_menu(
+                                    self.power_state,
+                                    &config.power,
+                                    self.locker_available,
+                                    self.hibernate_available,
+                                    self.focused_index,
+                                    accessibility,
+                                    opacity,
+                                )
+                                .map(Message::Power),
+                                opacity,
+                            )
                         }),
                 )
                 .push_maybe(sink_slider)
@@ -567,6 +996,8 @@ impl Settings {
                                     a.sinks_submenu(
                                         id,
                                         config.audio_sinks_more_cmd.is_some(),
+                                        self.focused_index,
+                                        accessibility,
                                         opacity,
                                     ),
                                     opacity,
@@ -584,6 +1015,8 @@ impl Settings {
                                     a.sources_submenu(
                                         id,
                                         config.audio_sources_more_cmd.is_some(),
+                                        self.focused_index,
+                                        accessibility,
                                         opacity,
                                     ),
                                     opacity,
@@ -591,7 +1024,33 @@ impl Settings {
                             })
                         }),
                 )
-                .push_maybe(self.brightness.as_ref().map(|b| b.brightness_slider()))
+                .push_maybe(
+                    self.brightness
+                        .as_ref()
+                        .filter(|b| b.max > 0)
+                        .map(|b| b.brightness_slider()),
+                )
+                .push_maybe(self.brightness.as_ref().and_then(|b| b.output_sliders()))
+                .push_maybe(self.night_mode.is_active().then(|| {
+                    row!(
+                        container(icon(Icons::NightMode)).padding([8, 11]),
+                        slider(
+                            1000..=config.night_mode.day_temp.max(1000),
+                            self.night_mode.temperature(&config.night_mode),
+                            Message::NightModeTemperatureChanged,
+                        )
+                        .on_release(Message::ApplyNightModeTemperature)
+                        .step(100_u32)
+                        .width(Length::Fill),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(8)
+                }))
+                .push_maybe(
+                    self.kbd_brightness
+                        .as_ref()
+                        .map(|b| b.kbd_brightness_slider()),
+                )
                 .push(quick_settings)
                 .spacing(16)
                 .into()
@@ -600,12 +1059,12 @@ impl Settings {
 }
 
 impl Module for Settings {
-    type ViewData<'a> = ();
-    type SubscriptionData<'a> = ();
+    type ViewData<'a> = (&'a AudioConfig, &'a NetworkConfig, tooltip::Position);
+    type SubscriptionData<'a> = (&'a BatteryConfig, &'a NetworkConfig, &'a BrightnessConfig);
 
     fn view(
         &self,
-        _: Self::ViewData<'_>,
+        (audio_config, network_config, tooltip_position): Self::ViewData<'_>,
     ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
         Some((
             Row::new()
@@ -622,27 +1081,101 @@ impl Module for Settings {
                             })
                         }),
                 )
+                .push_maybe(self.night_mode.is_active().then(|| {
+                    mouse_area(icon(Icons::NightMode))
+                        .on_press(app::Message::Settings(Message::ToggleNightMode))
+                }))
                 .push_maybe(
                     self.upower
                         .as_ref()
                         .and_then(|p| p.power_profile.indicator()),
                 )
-                .push_maybe(self.audio.as_ref().and_then(|a| a.sink_indicator()))
+                .push_maybe(self.audio.as_ref().and_then(|a| a.source_indicator()))
+                .push_maybe(
+                    self.audio
+                        .as_ref()
+                        .and_then(|a| a.sink_indicator())
+                        .map(|indicator| {
+                            let scroll_step = audio_config.scroll_step.clamp(1., 20.);
+
+                            mouse_area(indicator).on_scroll(move |delta| {
+                                let delta = match delta {
+                                    ScrollDelta::Lines { y, .. }
+                                    | ScrollDelta::Pixels { y, .. } => y,
+                                };
+
+                                // signum is 1 for a zero delta, horizontal scrolls would raise the volume
+                                let step = if delta == 0. {
+                                    0.
+                                } else {
+                                    delta.signum() as f64 * scroll_step
+                                };
+
+                                app::Message::Settings(Message::Audio(AudioMessage::ScrollVolume(
+                                    step,
+                                )))
+                            })
+                        }),
+                )
                 .push(
                     Row::new()
+                        .push_maybe(self.network.as_ref().and_then(|n| {
+                            n.get_connection_indicator(network_config.show_vpn, tooltip_position)
+                        }))
                         .push_maybe(
                             self.network
                                 .as_ref()
-                                .and_then(|n| n.get_connection_indicator()),
+                                .filter(|_| network_config.show_vpn)
+                                .and_then(|n| n.get_vpn_indicator()),
+                        )
+                        .push_maybe(
+                            self.network
+                                .as_ref()
+                                .filter(|_| network_config.show_throughput)
+                                .and_then(|n| n.get_throughput_indicator(tooltip_position)),
                         )
-                        .push_maybe(self.network.as_ref().and_then(|n| n.get_vpn_indicator()))
                         .spacing(4),
                 )
+                .push_maybe(self.upower.as_ref().and_then(|upower| {
+                    upower.battery.map(|battery| {
+                        let indicator = if upower.batteries.len() > 1 {
+                            Row::with_children(
+                                upower
+                                    .batteries
+                                    .iter()
+                                    .map(|b| b.data.compact_indicator(&upower.config))
+                                    .collect::<Vec<_>>(),
+                            )
+                            .spacing(4)
+                            .into()
+                        } else {
+                            battery.indicator(&upower.config)
+                        };
+
+                        TooltipWrapper::new(indicator)
+                            .tooltip(Some(battery.battery_tooltip_view()))
+                            .position(tooltip_position)
+                    })
+                }))
                 .push_maybe(
                     self.upower
                         .as_ref()
-                        .and_then(|upower| upower.battery)
-                        .map(|battery| battery.indicator()),
+                        .filter(|upower| !upower.ups.is_empty())
+                        .map(|upower| {
+                            Row::with_children(
+                                upower
+                                    .ups
+                                    .iter()
+                                    .map(|ups| {
+                                        TooltipWrapper::new(ups.indicator(&upower.config))
+                                            .tooltip(Some(ups.tooltip_view()))
+                                            .position(tooltip_position)
+                                            .into()
+                                    })
+                                    .collect::<Vec<_>>(),
+                            )
+                            .spacing(4)
+                        }),
                 )
                 .spacing(8)
                 .into(),
@@ -650,18 +1183,57 @@ impl Module for Settings {
         ))
     }
 
-    fn subscription(&self, _: Self::SubscriptionData<'_>) -> Option<Subscription<app::Message>> {
+    fn subscription(
+        &self,
+        (battery_config, network_config, brightness_config): Self::SubscriptionData<'_>,
+    ) -> Option<Subscription<app::Message>> {
         Some(
             Subscription::batch(vec![
-                UPowerService::subscribe()
+                UPowerService::subscribe_with_config(battery_config)
+                    .map(inspector::record)
                     .map(|event| Message::UPower(UPowerMessage::Event(event))),
-                AudioService::subscribe().map(|evenet| Message::Audio(AudioMessage::Event(evenet))),
-                BrightnessService::subscribe()
+                AudioService::subscribe()
+                    .map(inspector::record)
+                    .map(|evenet| Message::Audio(AudioMessage::Event(evenet))),
+                BrightnessService::subscribe_with_config(brightness_config)
+                    .map(inspector::record)
                     .map(|event| Message::Brightness(BrightnessMessage::Event(event))),
-                NetworkService::subscribe()
+                KbdBrightnessService::subscribe()
+                    .map(inspector::record)
+                    .map(|event| Message::KbdBrightness(KbdBrightnessMessage::Event(event))),
+                NetworkService::subscribe_with_config(network_config)
+                    .map(inspector::record)
                     .map(|event| Message::Network(NetworkMessage::Event(event))),
                 BluetoothService::subscribe()
+                    .map(inspector::record)
                     .map(|event| Message::Bluetooth(BluetoothMessage::Event(event))),
+                if self
+                    .idle_inhibitor
+                    .as_ref()
+                    .is_some_and(|idle_inhibitor| idle_inhibitor.is_inhibited())
+                {
+                    every(Duration::from_secs(1)).map(|_| Message::IdleInhibitorTick)
+                } else {
+                    Subscription::none()
+                },
+                if self.sub_menu.is_some_and(SubMenu::keyboard_navigation) {
+                    listen_with(|event, _, _| match event {
+                        iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                            key: keyboard::Key::Named(key),
+                            modifiers,
+                            ..
+                        }) => match key {
+                            Named::Tab if modifiers.shift() => Some(Message::FocusPrev),
+                            Named::Tab => Some(Message::FocusNext),
+                            Named::Enter => Some(Message::ActivateFocused),
+                            Named::Escape => Some(Message::CloseMenu),
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+                } else {
+                    Subscription::none()
+                },
             ])
             .map(app::Message::Settings),
         )
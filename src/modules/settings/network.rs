@@ -1,19 +1,27 @@
 use super::{Message, SubMenu, quick_setting_button};
 use crate::{
-    components::icons::{Icons, icon},
+    components::{
+        icons::{Icons, icon},
+        sparkline::Sparkline,
+        tooltip_wrapper::TooltipWrapper,
+    },
+    config::AccessibilityConfig,
     services::{
         ServiceEvent,
         network::{
-            AccessPoint, ActiveConnectionInfo, KnownConnection, NetworkData, NetworkService, Vpn,
-            dbus::ConnectivityState,
+            AccessPoint, ActiveConnectionInfo, InterfaceKind, KnownConnection, NetworkData,
+            NetworkInterface, NetworkService, Vpn, dbus::ConnectivityState,
         },
     },
     style::{ghost_button_style, settings_button_style},
-    utils::IndicatorState,
+    utils::{IndicatorState, format_bytes_rate},
 };
 use iced::{
     Alignment, Element, Length, Theme,
-    widget::{Column, button, column, container, horizontal_rule, row, scrollable, text, toggler},
+    widget::{
+        Column, Row, button, column, container, horizontal_rule, row, scrollable, text, toggler,
+        tooltip,
+    },
     window::Id,
 };
 
@@ -64,19 +72,139 @@ impl ActiveConnectionInfo {
         }
     }
 
+    pub fn details(&self) -> String {
+        match self {
+            Self::Wired { name, speed } if *speed > 0 => {
+                format!("{name} (Ethernet, {speed} Mb/s)")
+            }
+            Self::Wired { name, .. } => format!("{name} (Ethernet)"),
+            Self::WiFi { name, strength, .. } => format!("{name} (Wi-Fi, {strength}%)"),
+            Self::Vpn { name, .. } => format!("{name} (VPN)"),
+        }
+    }
+
     pub fn get_indicator_state(&self) -> IndicatorState {
         match self {
-            Self::WiFi {
-                strength: 0 | 1, ..
-            } => IndicatorState::Warning,
+            Self::WiFi { strength, .. } if *strength < 40 => IndicatorState::Danger,
+            Self::WiFi { strength, .. } if *strength < 70 => IndicatorState::Warning,
+            Self::Vpn { .. } => IndicatorState::Success,
             _ => IndicatorState::Normal,
         }
     }
 }
 
+// e.g. 1000 -> "1G", 2500 -> "2.5G", 100 -> "100M"
+fn format_link_speed(speed_mbps: u32) -> String {
+    if speed_mbps >= 1000 {
+        format!("{}G", speed_mbps as f32 / 1000.)
+    } else {
+        format!("{speed_mbps}M")
+    }
+}
+
+impl InterfaceKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Ethernet { .. } => "Ethernet",
+            Self::WiFi { .. } => "Wi-Fi",
+            Self::Cellular { .. } => "Cellular",
+            Self::Vpn => "VPN",
+            Self::Other => "Other",
+        }
+    }
+
+    pub fn details(&self) -> String {
+        match self {
+            Self::Ethernet {
+                speed_mbps: Some(speed),
+            } => format!("Ethernet, {speed} Mb/s"),
+            Self::WiFi { ssid, signal } => format!("Wi-Fi, {ssid} {signal}%"),
+            Self::Cellular {
+                operator: Some(operator),
+            } => format!("Cellular, {operator}"),
+            _ => self.label().to_string(),
+        }
+    }
+
+    fn short_label(&self) -> Option<String> {
+        match self {
+            Self::Ethernet {
+                speed_mbps: Some(speed),
+            } => Some(format_link_speed(*speed)),
+            _ => None,
+        }
+    }
+}
+
+impl NetworkInterface {
+    pub fn get_indicator_state(&self) -> IndicatorState {
+        if self.connected {
+            IndicatorState::Normal
+        } else {
+            IndicatorState::Danger
+        }
+    }
+}
+
 impl NetworkData {
-    pub fn get_connection_indicator<Message: 'static>(&self) -> Option<Element<Message>> {
-        if self.airplane_mode || !self.wifi_present {
+    fn get_interface_icon(&self, interface: &NetworkInterface) -> Icons {
+        match &interface.kind {
+            InterfaceKind::WiFi { .. } if !interface.connected => Icons::Wifi0,
+            InterfaceKind::WiFi { signal, .. } => ActiveConnectionInfo::get_wifi_icon(*signal),
+            InterfaceKind::Cellular { .. } => Icons::Cellular,
+            InterfaceKind::Vpn => Icons::Vpn,
+            InterfaceKind::Ethernet { .. } | InterfaceKind::Other => Icons::Ethernet,
+        }
+    }
+
+    fn get_interfaces_indicator<Message: 'static>(
+        &self,
+        tooltip_position: tooltip::Position,
+    ) -> Element<Message> {
+        Row::with_children(
+            self.interfaces
+                .iter()
+                .map(|interface| {
+                    let state = interface.get_indicator_state();
+
+                    TooltipWrapper::new(
+                        container(
+                            row!(icon(self.get_interface_icon(interface)))
+                                .push_maybe(interface.kind.short_label().map(text))
+                                .spacing(2)
+                                .align_y(Alignment::Center),
+                        )
+                        .style(move |theme: &Theme| container::Style {
+                            text_color: match state {
+                                IndicatorState::Danger => Some(theme.palette().danger),
+                                _ => None,
+                            },
+                            ..Default::default()
+                        }),
+                    )
+                    .tooltip(Some(text(format!(
+                        "{} ({}): {}",
+                        interface.name,
+                        interface.kind.details(),
+                        interface.ip4.as_deref().unwrap_or("no address")
+                    ))))
+                    .position(tooltip_position)
+                    .into()
+                })
+                .collect::<Vec<_>>(),
+        )
+        .spacing(4)
+        .into()
+    }
+
+    pub fn get_connection_indicator<Message: 'static>(
+        &self,
+        show_vpn: bool,
+        tooltip_position: tooltip::Position,
+    ) -> Option<Element<Message>> {
+        if self.interfaces.len() > 1 {
+            Some(self.get_interfaces_indicator(tooltip_position))
+        } else if self.airplane_mode || !self.wifi_present {
             None
         } else {
             Some(
@@ -89,20 +217,45 @@ impl NetworkData {
                     .map_or_else(
                         || icon(Icons::Wifi0).into(),
                         |a| {
-                            let icon_type = a.get_icon();
+                            let icon_type = match a {
+                                ActiveConnectionInfo::WiFi { strength, .. }
+                                    if show_vpn && self.vpn_active() =>
+                                {
+                                    ActiveConnectionInfo::get_wifi_lock_icon(*strength)
+                                }
+                                _ => a.get_icon(),
+                            };
                             let state = (self.connectivity, a.get_indicator_state());
+                            let speed = match a {
+                                ActiveConnectionInfo::Wired { speed, .. } if *speed > 0 => {
+                                    Some(format_link_speed(*speed))
+                                }
+                                _ => None,
+                            };
 
-                            container(icon(icon_type))
-                                .style(move |theme: &Theme| container::Style {
-                                    text_color: match state {
-                                        (ConnectivityState::Full, IndicatorState::Warning) => {
-                                            Some(theme.extended_palette().danger.weak.color)
-                                        }
-                                        (ConnectivityState::Full, _) => None,
-                                        _ => Some(theme.palette().danger),
-                                    },
-                                    ..Default::default()
-                                })
+                            let indicator = container(
+                                row!(icon(icon_type))
+                                    .push_maybe(speed.map(text))
+                                    .spacing(2)
+                                    .align_y(Alignment::Center),
+                            )
+                            .style(move |theme: &Theme| container::Style {
+                                text_color: match state {
+                                    (ConnectivityState::Full, IndicatorState::Warning) => {
+                                        Some(theme.extended_palette().danger.weak.color)
+                                    }
+                                    (ConnectivityState::Full, IndicatorState::Danger) => {
+                                        Some(theme.palette().danger)
+                                    }
+                                    (ConnectivityState::Full, _) => None,
+                                    _ => Some(theme.palette().danger),
+                                },
+                                ..Default::default()
+                            });
+
+                            TooltipWrapper::new(indicator)
+                                .tooltip(Some(text(a.details())))
+                                .position(tooltip_position)
                                 .into()
                         },
                     ),
@@ -111,26 +264,65 @@ impl NetworkData {
     }
 
     pub fn get_vpn_indicator<Message: 'static>(&self) -> Option<Element<Message>> {
+        if self.interfaces.len() > 1 {
+            return None;
+        }
+
         self.active_connections
             .iter()
             .find(|c| matches!(c, ActiveConnectionInfo::Vpn { .. }))
             .map(|a| {
                 let icon_type = a.get_icon();
+                let state = a.get_indicator_state();
 
                 container(icon(icon_type))
-                    .style(|theme: &Theme| container::Style {
-                        text_color: Some(theme.extended_palette().danger.weak.color),
+                    .style(move |theme: &Theme| container::Style {
+                        text_color: match state {
+                            IndicatorState::Success => Some(theme.palette().success),
+                            _ => None,
+                        },
                         ..Default::default()
                     })
                     .into()
             })
     }
 
+    pub fn get_throughput_indicator<Message: 'static>(
+        &self,
+        tooltip_position: tooltip::Position,
+    ) -> Option<Element<Message>> {
+        self.throughput.map(|throughput| {
+            TooltipWrapper::new(
+                row!(
+                    icon(Icons::DownloadSpeed),
+                    text(format_bytes_rate(throughput.rx_bps)),
+                    icon(Icons::UploadSpeed),
+                    text(format_bytes_rate(throughput.tx_bps)),
+                )
+                .align_y(Alignment::Center)
+                .spacing(4),
+            )
+            .tooltip(Some(
+                Sparkline::new()
+                    .line(self.throughput_history.iter().map(|t| t.rx_bps), |theme| {
+                        theme.palette().success
+                    })
+                    .line(self.throughput_history.iter().map(|t| t.tx_bps), |theme| {
+                        theme.palette().primary
+                    })
+                    .view(120., 20.),
+            ))
+            .position(tooltip_position)
+            .into()
+        })
+    }
+
     pub fn get_wifi_quick_setting_button(
         &self,
         id: Id,
         sub_menu: Option<SubMenu>,
         show_more_button: bool,
+        accessibility: AccessibilityConfig,
         opacity: f32,
     ) -> Option<(Element<Message>, Option<Element<Message>>)> {
         if self.wifi_present {
@@ -163,6 +355,7 @@ impl NetworkData {
                             id,
                             active_connection.map(|(name, strengh, _)| (name.as_str(), *strengh)),
                             show_more_button,
+                            accessibility,
                             opacity,
                         )
                         .map(Message::Network)
@@ -178,16 +371,15 @@ impl NetworkData {
         id: Id,
         sub_menu: Option<SubMenu>,
         show_more_button: bool,
+        accessibility: AccessibilityConfig,
         opacity: f32,
     ) -> (Element<Message>, Option<Element<Message>>) {
         (
             quick_setting_button(
                 Icons::Vpn,
                 "Vpn".to_string(),
-                None,
-                self.active_connections
-                    .iter()
-                    .any(|c| matches!(c, ActiveConnectionInfo::Vpn { .. })),
+                self.vpn_name().map(str::to_owned),
+                self.vpn_active(),
                 Message::ToggleSubMenu(SubMenu::Vpn),
                 None,
                 opacity,
@@ -195,7 +387,7 @@ impl NetworkData {
             sub_menu
                 .filter(|menu_type| *menu_type == SubMenu::Vpn)
                 .map(|_| {
-                    self.vpn_menu(id, show_more_button, opacity)
+                    self.vpn_menu(id, show_more_button, accessibility, opacity)
                         .map(Message::Network)
                 }),
         )
@@ -206,6 +398,7 @@ impl NetworkData {
         id: Id,
         active_connection: Option<(&str, u8)>,
         show_more_button: bool,
+        accessibility: AccessibilityConfig,
         opacity: f32,
     ) -> Element<NetworkMessage> {
         let main = column!(
@@ -254,6 +447,13 @@ impl NetworkData {
                                         .width(Length::Shrink),
                                         text(ac.ssid.clone()).width(Length::Fill),
                                     )
+                                    .push_maybe(
+                                        active_connection
+                                            .filter(|_| is_active)
+                                            .map(|(_, strength)| {
+                                                text(format!("Signal: {}%", strength)).size(12)
+                                            }),
+                                    )
                                     .align_y(Alignment::Center)
                                     .spacing(8),
                                 )
@@ -296,7 +496,7 @@ impl NetworkData {
                 horizontal_rule(1),
                 button("More")
                     .on_press(NetworkMessage::WiFiMore(id))
-                    .padding([4, 12])
+                    .padding(accessibility.button_padding())
                     .width(Length::Fill)
                     .style(ghost_button_style(opacity))
             )
@@ -311,6 +511,7 @@ impl NetworkData {
         &self,
         id: Id,
         show_more_button: bool,
+        accessibility: AccessibilityConfig,
         opacity: f32,
     ) -> Element<NetworkMessage> {
         let main = Column::with_children(
@@ -343,7 +544,7 @@ impl NetworkData {
                 horizontal_rule(1),
                 button("More")
                     .on_press(NetworkMessage::VpnMore(id))
-                    .padding([4, 12])
+                    .padding(accessibility.button_padding())
                     .width(Length::Fill)
                     .style(ghost_button_style(opacity))
             )
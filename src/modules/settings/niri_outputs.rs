@@ -0,0 +1,117 @@
+use crate::{
+    components::icons::{Icons, icon},
+    services::{ServiceEvent, niri::NiriService},
+    style::{ghost_button_style, settings_button_style},
+};
+use iced::{
+    Alignment, Element, Length, Theme,
+    widget::{Column, button, container, horizontal_space, row, text},
+};
+
+use super::{Message, SubMenu, quick_setting_button};
+
+const SCALE_STEP: f64 = 0.25;
+const MIN_SCALE: f64 = 0.5;
+const MAX_SCALE: f64 = 4.;
+
+#[derive(Clone, Debug)]
+pub enum NiriOutputsMessage {
+    Event(ServiceEvent<NiriService>),
+    SetScale(String, f64),
+    SetPower(String, bool),
+}
+
+impl NiriService {
+    pub fn get_outputs_quick_setting_button(
+        &self,
+        sub_menu: Option<SubMenu>,
+        opacity: f32,
+    ) -> Option<(Element<Message>, Option<Element<Message>>)> {
+        if self.outputs.is_empty() {
+            return None;
+        }
+
+        let active = self.outputs.iter().filter(|output| output.on).count();
+
+        Some((
+            quick_setting_button(
+                Icons::Display,
+                "Displays".to_string(),
+                Some(format!("{} of {} on", active, self.outputs.len())),
+                true,
+                Message::ToggleSubMenu(SubMenu::Outputs),
+                Some((
+                    SubMenu::Outputs,
+                    sub_menu,
+                    Message::ToggleSubMenu(SubMenu::Outputs),
+                )),
+                opacity,
+            ),
+            sub_menu
+                .filter(|menu_type| *menu_type == SubMenu::Outputs)
+                .map(|_| self.outputs_menu(opacity)),
+        ))
+    }
+
+    pub fn outputs_menu(&self, opacity: f32) -> Element<Message> {
+        Column::with_children(
+            self.outputs
+                .iter()
+                .map(|output| {
+                    let scale_button = |label: &'static str, scale: Option<f64>| {
+                        button(text(label).width(Length::Fixed(12.)).center())
+                            .padding([4, 8])
+                            .on_press_maybe(scale.map(|scale| {
+                                Message::NiriOutputs(NiriOutputsMessage::SetScale(
+                                    output.name.clone(),
+                                    scale,
+                                ))
+                            }))
+                            .style(ghost_button_style(opacity))
+                    };
+                    let scale = output.scale.filter(|_| output.on);
+                    let on = output.on;
+
+                    row!(
+                        text(output.name.clone()),
+                        horizontal_space(),
+                        scale_button(
+                            "-",
+                            scale
+                                .map(|scale| scale - SCALE_STEP)
+                                .filter(|scale| *scale >= MIN_SCALE),
+                        ),
+                        text(scale.map_or("-".to_owned(), |scale| format!("{:.2}x", scale))),
+                        scale_button(
+                            "+",
+                            scale
+                                .map(|scale| scale + SCALE_STEP)
+                                .filter(|scale| *scale <= MAX_SCALE),
+                        ),
+                        button(container(icon(Icons::Power)).style(move |theme: &Theme| {
+                            container::Style {
+                                text_color: Some(if on {
+                                    theme.palette().success
+                                } else {
+                                    theme.palette().text
+                                }),
+                                ..Default::default()
+                            }
+                        }))
+                        .padding([8, 13])
+                        .on_press(Message::NiriOutputs(NiriOutputsMessage::SetPower(
+                            output.name.clone(),
+                            !on,
+                        )))
+                        .style(settings_button_style(opacity)),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(8)
+                    .into()
+                })
+                .collect::<Vec<_>>(),
+        )
+        .spacing(8)
+        .into()
+    }
+}
@@ -1,27 +1,54 @@
 use crate::{
     components::icons::{Icons, icon},
-    style::ghost_button_style,
+    config::{AccessibilityConfig, PowerConfig, SettingsModuleConfig},
+    style::focus_ring_button_style,
     utils,
 };
 use iced::{
-    Element, Length,
-    widget::{button, column, horizontal_rule, row, text},
+    Alignment, Element, Length, Theme,
+    widget::{Column, button, column, container, horizontal_rule, row, text},
 };
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerAction {
+    Reboot,
+    Shutdown,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PowerState {
+    #[default]
+    Idle,
+    Confirming(PowerAction),
+}
+
 #[derive(Debug, Clone)]
 pub enum PowerMessage {
+    Lock,
     Suspend,
+    Hibernate,
     Reboot,
     Shutdown,
+    ConfirmReboot,
+    ConfirmShutdown,
+    CancelConfirmation,
     Logout,
+    Screenshot,
+    ScreenshotRegion,
 }
 
 impl PowerMessage {
-    pub fn update(self) {
+    pub fn update(self, config: &SettingsModuleConfig) {
         match self {
+            PowerMessage::Lock => {
+                utils::launcher::lock_screen(config);
+            }
             PowerMessage::Suspend => {
                 utils::launcher::suspend();
             }
+            PowerMessage::Hibernate => {
+                utils::launcher::hibernate();
+            }
             PowerMessage::Reboot => {
                 utils::launcher::reboot();
             }
@@ -31,36 +58,173 @@ impl PowerMessage {
             PowerMessage::Logout => {
                 utils::launcher::logout();
             }
+            PowerMessage::Screenshot => {
+                utils::launcher::screenshot(&config.screenshot);
+            }
+            PowerMessage::ScreenshotRegion => {
+                utils::launcher::screenshot_region(&config.screenshot);
+            }
+            PowerMessage::ConfirmReboot
+            | PowerMessage::ConfirmShutdown
+            | PowerMessage::CancelConfirmation => {}
         }
     }
 }
 
-pub fn power_menu<'a>(opacity: f32) -> Element<'a, PowerMessage> {
+struct PowerEntry {
+    icon: Icons,
+    label: &'static str,
+    message: Option<PowerMessage>,
+    separated: bool,
+}
+
+fn power_entries(
+    config: &PowerConfig,
+    locker_available: bool,
+    hibernate_available: bool,
+) -> Vec<PowerEntry> {
+    let (reboot, shutdown) = if config.require_confirmation {
+        (PowerMessage::ConfirmReboot, PowerMessage::ConfirmShutdown)
+    } else {
+        (PowerMessage::Reboot, PowerMessage::Shutdown)
+    };
+    let entry = |icon, label, message| PowerEntry {
+        icon,
+        label,
+        message: Some(message),
+        separated: false,
+    };
+
+    let mut entries = vec![
+        PowerEntry {
+            icon: Icons::Lock,
+            label: "Lock",
+            message: locker_available.then_some(PowerMessage::Lock),
+            separated: false,
+        },
+        entry(Icons::Suspend, "Suspend", PowerMessage::Suspend),
+    ];
+    if hibernate_available {
+        entries.push(entry(
+            Icons::Hibernate,
+            "Hibernate",
+            PowerMessage::Hibernate,
+        ));
+    }
+    entries.extend([
+        entry(Icons::Reboot, "Reboot", reboot),
+        entry(Icons::Power, "Shutdown", shutdown),
+        entry(Icons::Screenshot, "Screenshot", PowerMessage::Screenshot),
+        entry(
+            Icons::ScreenshotRegion,
+            "Screenshot region",
+            PowerMessage::ScreenshotRegion,
+        ),
+        PowerEntry {
+            separated: true,
+            ..entry(Icons::Logout, "Logout", PowerMessage::Logout)
+        },
+    ]);
+
+    entries
+}
+
+// the actions reachable with the keyboard, in the same order they are rendered
+pub fn power_actions(
+    state: PowerState,
+    config: &PowerConfig,
+    locker_available: bool,
+    hibernate_available: bool,
+) -> Vec<Option<PowerMessage>> {
+    match state {
+        PowerState::Confirming(action) => vec![
+            Some(match action {
+                PowerAction::Reboot => PowerMessage::Reboot,
+                PowerAction::Shutdown => PowerMessage::Shutdown,
+            }),
+            Some(PowerMessage::CancelConfirmation),
+        ],
+        PowerState::Idle => power_entries(config, locker_available, hibernate_available)
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect(),
+    }
+}
+
+fn confirmation<'a>(
+    action: PowerAction,
+    focused_index: usize,
+    accessibility: AccessibilityConfig,
+    opacity: f32,
+) -> Element<'a, PowerMessage> {
     column!(
-        button(row!(icon(Icons::Suspend), text("Suspend")).spacing(16))
-            .padding([4, 12])
-            .on_press(PowerMessage::Suspend)
-            .width(Length::Fill)
-            .style(ghost_button_style(opacity)),
-        button(row!(icon(Icons::Reboot), text("Reboot")).spacing(16))
-            .padding([4, 12])
-            .on_press(PowerMessage::Reboot)
-            .width(Length::Fill)
-            .style(ghost_button_style(opacity)),
-        button(row!(icon(Icons::Power), text("Shutdown")).spacing(16))
-            .padding([4, 12])
-            .on_press(PowerMessage::Shutdown)
-            .width(Length::Fill)
-            .style(ghost_button_style(opacity)),
-        horizontal_rule(1),
-        button(row!(icon(Icons::Logout), text("Logout")).spacing(16))
-            .padding([4, 12])
-            .on_press(PowerMessage::Logout)
-            .width(Length::Fill)
-            .style(ghost_button_style(opacity)),
+        text(match action {
+            PowerAction::Reboot => "Are you sure you want to reboot?",
+            PowerAction::Shutdown => "Are you sure you want to shutdown?",
+        }),
+        row!(
+            button(text("Yes").width(Length::Fill).center())
+                .padding(accessibility.button_padding())
+                .on_press(match action {
+                    PowerAction::Reboot => PowerMessage::Reboot,
+                    PowerAction::Shutdown => PowerMessage::Shutdown,
+                })
+                .width(Length::Fill)
+                .style(focus_ring_button_style(focused_index == 0, opacity)),
+            button(text("Cancel").width(Length::Fill).center())
+                .padding(accessibility.button_padding())
+                .on_press(PowerMessage::CancelConfirmation)
+                .width(Length::Fill)
+                .style(focus_ring_button_style(focused_index == 1, opacity)),
+        )
+        .spacing(8),
     )
+    .align_x(Alignment::Center)
     .padding(8)
     .width(Length::Fill)
-    .spacing(8)
+    .spacing(12)
     .into()
 }
+
+pub fn power_menu<'a>(
+    state: PowerState,
+    config: &PowerConfig,
+    locker_available: bool,
+    hibernate_available: bool,
+    focused_index: usize,
+    accessibility: AccessibilityConfig,
+    opacity: f32,
+) -> Element<'a, PowerMessage> {
+    if let PowerState::Confirming(action) = state {
+        return confirmation(action, focused_index, accessibility, opacity);
+    }
+
+    let mut menu = Column::new().padding(8).width(Length::Fill).spacing(8);
+
+    for (index, entry) in power_entries(config, locker_available, hibernate_available)
+        .into_iter()
+        .enumerate()
+    {
+        if entry.separated {
+            menu = menu.push(horizontal_rule(1));
+        }
+
+        let enabled = entry.message.is_some();
+        menu = menu.push(
+            button(
+                container(row!(icon(entry.icon), text(entry.label)).spacing(16)).style(
+                    move |theme: &Theme| container::Style {
+                        text_color: (!enabled).then(|| theme.palette().text.scale_alpha(0.5)),
+                        ..Default::default()
+                    },
+                ),
+            )
+            .padding(accessibility.button_padding())
+            .on_press_maybe(entry.message)
+            .width(Length::Fill)
+            .style(focus_ring_button_style(index == focused_index, opacity)),
+        );
+    }
+
+    menu.into()
+}
@@ -1,28 +1,66 @@
 use crate::{
     components::icons::{Icons, icon},
+    config::{AccessibilityConfig, BatteryConfig},
     services::{
         ServiceEvent,
-        upower::{BatteryData, BatteryStatus, PowerProfile, UPowerService},
+        upower::{BatteryData, BatteryStatus, PowerProfile, UPowerService, UpsData, UpsStatus},
     },
+    style::ghost_button_style,
     utils::{IndicatorState, format_duration},
 };
 use iced::{
-    Alignment, Background, Border, Element, Theme,
-    widget::{Container, container, row, text},
+    Alignment, Background, Border, Element, Length, Theme,
+    widget::{Column, Container, button, container, row, text},
 };
 
-use super::{Message, quick_setting_button};
+use super::{Message, SubMenu, quick_setting_button};
 
 #[derive(Clone, Debug)]
 pub enum UPowerMessage {
     Event(ServiceEvent<UPowerService>),
     TogglePowerProfile,
+    SetPowerProfile(PowerProfile),
+}
+
+impl UpsData {
+    pub fn indicator<'a, Message: 'static>(&self, config: &BatteryConfig) -> Element<'a, Message> {
+        let state = self.get_indicator_state(config);
+
+        container(
+            row!(icon(Icons::Ups), text(format!("{}%", self.charge)))
+                .spacing(4)
+                .align_y(Alignment::Center),
+        )
+        .style(move |theme: &Theme| container::Style {
+            text_color: Some(match state {
+                IndicatorState::Danger => theme.palette().danger,
+                _ => theme.palette().text,
+            }),
+            ..Default::default()
+        })
+        .into()
+    }
+
+    pub fn tooltip_view<'a, Message: 'static>(&self) -> Element<'a, Message> {
+        let status = match (self.status, self.time_remaining) {
+            (UpsStatus::Discharging, Some(remaining)) => {
+                format!("On battery, empty in {}", format_duration(&remaining))
+            }
+            (UpsStatus::Discharging, None) => "On battery".to_string(),
+            (UpsStatus::Charging, _) => "Online, charging".to_string(),
+            (UpsStatus::Full | UpsStatus::Unknown, _) => "Online".to_string(),
+        };
+
+        text(format!("{}: {}% - {}", self.label, self.charge, status))
+            .size(12)
+            .into()
+    }
 }
 
 impl BatteryData {
-    pub fn indicator<'a, Message: 'static>(&self) -> Element<'a, Message> {
+    pub fn indicator<'a, Message: 'static>(&self, config: &BatteryConfig) -> Element<'a, Message> {
         let icon_type = self.get_icon();
-        let state = self.get_indicator_state();
+        let state = self.get_indicator_state(config);
 
         container(
             row!(icon(icon_type), text(format!("{}%", self.capacity)))
@@ -32,6 +70,7 @@ impl BatteryData {
         .style(move |theme: &Theme| container::Style {
             text_color: Some(match state {
                 IndicatorState::Success => theme.palette().success,
+                IndicatorState::Warning => theme.extended_palette().danger.weak.color,
                 IndicatorState::Danger => theme.palette().danger,
                 _ => theme.palette().text,
             }),
@@ -40,8 +79,53 @@ impl BatteryData {
         .into()
     }
 
-    pub fn settings_indicator<'a, Message: 'static>(&self, opacity: f32) -> Container<'a, Message> {
-        let state = self.get_indicator_state();
+    pub fn battery_tooltip_view<'a, Message: 'static>(&self) -> Element<'a, Message> {
+        let status = match self.status {
+            BatteryStatus::Charging(remaining) if !remaining.is_zero() => {
+                format!("Charging, full in {}", format_duration(&remaining))
+            }
+            BatteryStatus::Charging(_) => "Charging".to_string(),
+            BatteryStatus::Discharging(remaining) if !remaining.is_zero() => {
+                format!("Empty in {}", format_duration(&remaining))
+            }
+            BatteryStatus::Discharging(_) => "Discharging".to_string(),
+            BatteryStatus::Full => "Full".to_string(),
+        };
+
+        text(match self.health_percent {
+            Some(health) => format!("{}% - {} - Health {}%", self.capacity, status, health),
+            None => format!("{}% - {}", self.capacity, status),
+        })
+        .size(12)
+        .into()
+    }
+
+    pub fn compact_indicator<'a, Message: 'static>(
+        &self,
+        config: &BatteryConfig,
+    ) -> Element<'a, Message> {
+        let state = self.get_indicator_state(config);
+
+        container(icon(self.get_icon()))
+            .style(move |theme: &Theme| container::Style {
+                text_color: Some(match state {
+                    IndicatorState::Success => theme.palette().success,
+                    IndicatorState::Warning => theme.extended_palette().danger.weak.color,
+                    IndicatorState::Danger => theme.palette().danger,
+                    _ => theme.palette().text,
+                }),
+                ..Default::default()
+            })
+            .into()
+    }
+
+    pub fn settings_indicator<'a, Message: 'static>(
+        &self,
+        config: &BatteryConfig,
+        opacity: f32,
+    ) -> Container<'a, Message> {
+        let state = self.get_indicator_state(config);
+        let health_state = self.get_health_indicator_state(config);
 
         container({
             let battery_info = container(
@@ -50,24 +134,46 @@ impl BatteryData {
             .style(move |theme: &Theme| container::Style {
                 text_color: Some(match state {
                     IndicatorState::Success => theme.palette().success,
+                    IndicatorState::Warning => theme.extended_palette().danger.weak.color,
                     IndicatorState::Danger => theme.palette().danger,
                     _ => theme.palette().text,
                 }),
                 ..Default::default()
             });
-            match self.status {
-                BatteryStatus::Charging(remaining) if self.capacity < 95 => row!(
-                    battery_info,
-                    text(format!("Full in {}", format_duration(&remaining)))
-                )
-                .spacing(16),
-                BatteryStatus::Discharging(remaining) if self.capacity < 95 => row!(
-                    battery_info,
-                    text(format!("Empty in {}", format_duration(&remaining)))
+            let health_info = self.health_percent.map(|health| {
+                container(text(format!("Battery health: {}%", health))).style(
+                    move |theme: &Theme| container::Style {
+                        text_color: Some(match health_state {
+                            IndicatorState::Warning => theme.extended_palette().danger.weak.color,
+                            _ => theme.palette().text,
+                        }),
+                        ..Default::default()
+                    },
                 )
-                .spacing(16),
+            });
+            match self.status {
+                BatteryStatus::Charging(remaining)
+                    if self.capacity < 95 && !remaining.is_zero() =>
+                {
+                    row!(
+                        battery_info,
+                        text(format!("Full in {}", format_duration(&remaining)))
+                    )
+                    .spacing(16)
+                }
+                BatteryStatus::Discharging(remaining)
+                    if self.capacity < 95 && !remaining.is_zero() =>
+                {
+                    row!(
+                        battery_info,
+                        text(format!("Empty in {}", format_duration(&remaining)))
+                    )
+                    .spacing(16)
+                }
                 _ => row!(battery_info),
             }
+            .push_maybe(health_info)
+            .spacing(16)
         })
         .padding([8, 12])
         .style(move |theme: &Theme| container::Style {
@@ -88,53 +194,91 @@ impl BatteryData {
 
 impl PowerProfile {
     pub fn indicator<Message: 'static>(&self) -> Option<Element<Message>> {
-        match self {
-            PowerProfile::Balanced => None,
-            PowerProfile::Performance => Some(
-                container(icon(Icons::Performance))
-                    .style(|theme: &Theme| container::Style {
-                        text_color: Some(theme.palette().danger),
-                        ..Default::default()
-                    })
-                    .into(),
-            ),
-            PowerProfile::PowerSaver => Some(
-                container(icon(Icons::PowerSaver))
-                    .style(|theme: &Theme| container::Style {
-                        text_color: Some(theme.palette().success),
-                        ..Default::default()
-                    })
-                    .into(),
-            ),
-            PowerProfile::Unknown => None,
+        if *self == PowerProfile::Unknown {
+            return None;
         }
+
+        let state = self.get_indicator_state();
+
+        Some(
+            container(icon((*self).into()))
+                .style(move |theme: &Theme| container::Style {
+                    text_color: Some(match state {
+                        IndicatorState::Warning => theme.extended_palette().danger.weak.color,
+                        IndicatorState::Danger => theme.palette().danger,
+                        _ => theme.palette().text,
+                    }),
+                    ..Default::default()
+                })
+                .into(),
+        )
     }
+}
 
-    pub fn get_quick_setting_button(
+impl UPowerService {
+    pub fn get_power_profile_quick_setting_button(
         &self,
+        sub_menu: Option<SubMenu>,
+        accessibility: AccessibilityConfig,
         opacity: f32,
     ) -> Option<(Element<Message>, Option<Element<Message>>)> {
-        if !matches!(self, PowerProfile::Unknown) {
+        if !matches!(self.power_profile, PowerProfile::Unknown) {
             Some((
                 quick_setting_button(
-                    (*self).into(),
-                    match self {
-                        PowerProfile::Balanced => "Balanced",
-                        PowerProfile::Performance => "Performance",
-                        PowerProfile::PowerSaver => "Power Saver",
-                        PowerProfile::Unknown => "",
-                    }
-                    .to_string(),
+                    self.power_profile.into(),
+                    self.power_profile.name().to_string(),
                     None,
                     true,
                     Message::UPower(UPowerMessage::TogglePowerProfile),
-                    None,
+                    Some((
+                        SubMenu::PowerProfiles,
+                        sub_menu,
+                        Message::ToggleSubMenu(SubMenu::PowerProfiles),
+                    ))
+                    .filter(|_| self.power_profiles.len() > 1),
                     opacity,
                 ),
-                None,
+                sub_menu
+                    .filter(|menu_type| *menu_type == SubMenu::PowerProfiles)
+                    .map(|_| self.power_profiles_menu(accessibility, opacity)),
             ))
         } else {
             None
         }
     }
+
+    pub fn power_profiles_menu(
+        &self,
+        accessibility: AccessibilityConfig,
+        opacity: f32,
+    ) -> Element<Message> {
+        Column::with_children(
+            self.power_profiles
+                .iter()
+                .map(|profile| {
+                    let content = row!(icon((*profile).into()), text(profile.name()))
+                        .align_y(Alignment::Center)
+                        .spacing(16);
+
+                    if *profile == self.power_profile {
+                        container(content.padding(accessibility.button_padding()))
+                            .style(|theme: &Theme| container::Style {
+                                text_color: Some(theme.palette().success),
+                                ..Default::default()
+                            })
+                            .into()
+                    } else {
+                        button(content)
+                            .on_press(Message::UPower(UPowerMessage::SetPowerProfile(*profile)))
+                            .padding(accessibility.button_padding())
+                            .width(Length::Fill)
+                            .style(ghost_button_style(opacity))
+                            .into()
+                    }
+                })
+                .collect::<Vec<_>>(),
+        )
+        .spacing(4)
+        .into()
+    }
 }
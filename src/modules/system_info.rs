@@ -1,73 +1,260 @@
 use crate::{
     app,
-    components::icons::{Icons, icon},
-    config::{SystemIndicator, SystemModuleConfig},
+    components::{
+        icons::{Icons, icon},
+        tooltip_wrapper::TooltipWrapper,
+    },
+    config::{
+        CpuDisplayMode, SystemIndicator, SystemInfoDisk, SystemInfoTemperature, SystemModuleConfig,
+    },
     menu::MenuType,
+    utils::{IndicatorState, classify, format_bytes, format_bytes_rate},
 };
 use iced::{
     Alignment, Element, Length, Subscription, Task, Theme,
     time::every,
-    widget::{Column, Row, column, container, horizontal_rule, row, text},
+    widget::{Column, Row, column, container, horizontal_rule, row, text, tooltip},
 };
 use itertools::Itertools;
-use std::time::{Duration, Instant};
+use log::{debug, info};
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
 use sysinfo::{Components, Disks, Networks, System};
 
 use super::{Module, OnModulePress};
 
+// the gpu driver can come up after ashell, keep looking for it at a slower pace
+const GPU_RETRIES: u32 = 3;
+const GPU_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const GPU_MISSING_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
 struct NetworkData {
     ip: String,
-    download_speed: u32,
-    upload_speed: u32,
+    download_speed: u64,
+    upload_speed: u64,
     last_check: Instant,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuVendor {
+    Nvidia,
+    Amd,
+}
+
+#[derive(Debug, Clone)]
+pub struct GpuData {
+    pub vendor: GpuVendor,
+    pub usage_percent: u32,
+    pub vram_used_mb: u64,
+    pub vram_total_mb: u64,
+}
+
+async fn get_nvidia_gpu_info() -> Option<GpuData> {
+    let output = tokio::process::Command::new("nvidia-smi")
+        .args([
+            "--query-gpu=utilization.gpu,memory.used,memory.total",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut values = stdout.lines().next()?.split(',').map(|v| v.trim());
+
+    Some(GpuData {
+        vendor: GpuVendor::Nvidia,
+        usage_percent: values.next()?.parse().ok()?,
+        vram_used_mb: values.next()?.parse().ok()?,
+        vram_total_mb: values.next()?.parse().ok()?,
+    })
+}
+
+fn get_amd_gpu_info() -> Option<GpuData> {
+    let read_value = |path: std::path::PathBuf| -> Option<u64> {
+        fs::read_to_string(path).ok()?.trim().parse().ok()
+    };
+
+    fs::read_dir("/sys/class/drm")
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("card") && !name.contains('-')
+        })
+        .sorted_by_key(|entry| entry.file_name())
+        .find_map(|entry| {
+            let device = entry.path().join("device");
+
+            Some(GpuData {
+                vendor: GpuVendor::Amd,
+                usage_percent: read_value(device.join("gpu_busy_percent"))? as u32,
+                vram_used_mb: read_value(device.join("mem_info_vram_used")).unwrap_or_default()
+                    / 1024
+                    / 1024,
+                vram_total_mb: read_value(device.join("mem_info_vram_total")).unwrap_or_default()
+                    / 1024
+                    / 1024,
+            })
+        })
+}
+
+async fn get_gpu_info() -> Option<GpuData> {
+    match get_nvidia_gpu_info().await {
+        Some(data) => Some(data),
+        None => get_amd_gpu_info(),
+    }
+}
+
+struct DiskData {
+    path: String,
+    used_bytes: u64,
+    total_bytes: u64,
+    used_percent: u32,
+}
+
+impl DiskData {
+    fn icon(&self) -> Icons {
+        if self.path == "/home" || self.path.starts_with("/home/") {
+            Icons::HomeDrive
+        } else if ["/media/", "/mnt/", "/run/media/"]
+            .iter()
+            .any(|prefix| self.path.starts_with(prefix))
+        {
+            Icons::ExternalDrive
+        } else {
+            Icons::Drive
+        }
+    }
+
+    fn usage(&self) -> String {
+        format!(
+            "{} / {}",
+            format_bytes(self.used_bytes),
+            format_bytes(self.total_bytes)
+        )
+    }
+}
+
 struct SystemInfoData {
     pub cpu_usage: u32,
+    pub cpu_per_core: Vec<u32>,
+    pub load_avg_1m: f32,
+    pub load_avg_5m: f32,
+    pub load_avg_15m: f32,
     pub memory_usage: u32,
     pub memory_swap_usage: u32,
-    pub temperature: Option<i32>,
-    pub disks: Vec<(String, u32)>,
+    pub memory_used_kb: u64,
+    pub memory_total_kb: u64,
+    pub swap_used_kb: u64,
+    pub swap_total_kb: u64,
+    pub temperatures: Vec<(String, i32)>,
+    pub disks: Vec<DiskData>,
     pub network: Option<NetworkData>,
 }
 
+fn get_cpu_usage(system: &mut System) -> (u32, Vec<u32>) {
+    system.refresh_cpu_specifics(sysinfo::CpuRefreshKind::everything());
+
+    (
+        system.global_cpu_usage().floor() as u32,
+        system
+            .cpus()
+            .iter()
+            .map(|cpu| cpu.cpu_usage().floor() as u32)
+            .collect(),
+    )
+}
+
+fn get_load_avg() -> (f32, f32, f32) {
+    let content = fs::read_to_string("/proc/loadavg").unwrap_or_default();
+    let mut values = content
+        .split_whitespace()
+        .map(|v| v.parse::<f32>().unwrap_or_default());
+
+    (
+        values.next().unwrap_or_default(),
+        values.next().unwrap_or_default(),
+        values.next().unwrap_or_default(),
+    )
+}
+
+fn get_core_count() -> usize {
+    fs::read_to_string("/proc/cpuinfo")
+        .map(|content| {
+            content
+                .lines()
+                .filter(|line| line.starts_with("processor"))
+                .count()
+        })
+        .unwrap_or_default()
+        .max(1)
+}
+
+// under one runnable task per core is fine, up to twice the cores is a warning
+fn load_avg_state(load: f32, core_count: usize) -> IndicatorState {
+    let cores = core_count as f32;
+
+    if load > cores * 2. {
+        IndicatorState::Danger
+    } else if load >= cores {
+        IndicatorState::Warning
+    } else {
+        IndicatorState::Normal
+    }
+}
+
+fn get_disks(disks: &mut Disks) -> Vec<DiskData> {
+    disks.refresh(true);
+
+    disks
+        .into_iter()
+        .filter(|d| !d.is_removable() && d.total_space() != 0)
+        .map(|d| {
+            let used_bytes = d.total_space() - d.available_space();
+
+            DiskData {
+                path: d.mount_point().to_string_lossy().to_string(),
+                used_bytes,
+                total_bytes: d.total_space(),
+                used_percent: ((used_bytes as f32) / d.total_space() as f32 * 100.) as u32,
+            }
+        })
+        .sorted_by(|a, b| a.path.cmp(&b.path))
+        .collect::<Vec<_>>()
+}
+
 fn get_system_info(
     system: &mut System,
     components: &mut Components,
-    disks: &mut Disks,
+    disks: Vec<DiskData>,
     (networks, last_check): (&mut Networks, Option<Instant>),
 ) -> SystemInfoData {
     system.refresh_memory();
-    system.refresh_cpu_specifics(sysinfo::CpuRefreshKind::everything());
+    let (cpu_usage, cpu_per_core) = get_cpu_usage(system);
+    let (load_avg_1m, load_avg_5m, load_avg_15m) = get_load_avg();
 
     components.refresh(true);
-    disks.refresh(true);
     networks.refresh(true);
 
-    let cpu_usage = system.global_cpu_usage().floor() as u32;
     let memory_usage = ((system.total_memory() - system.available_memory()) as f32
         / system.total_memory() as f32
         * 100.) as u32;
 
-    let memory_swap_usage = ((system.total_swap() - system.free_swap()) as f32
-        / system.total_swap() as f32
-        * 100.) as u32;
+    let memory_swap_usage = if system.total_swap() > 0 {
+        ((system.total_swap() - system.free_swap()) as f32 / system.total_swap() as f32 * 100.)
+            as u32
+    } else {
+        0
+    };
 
-    let temperature = components
+    let temperatures = components
         .iter()
-        .find(|c| c.label() == "acpitz temp1")
-        .and_then(|c| c.temperature().map(|t| t as i32));
-
-    let disks = disks
-        .into_iter()
-        .filter(|d| !d.is_removable() && d.total_space() != 0)
-        .map(|d| {
-            (
-                d.mount_point().to_string_lossy().to_string(),
-                (((d.total_space() - d.available_space()) as f32) / d.total_space() as f32 * 100.)
-                    as u32,
-            )
-        })
+        .filter_map(|c| c.temperature().map(|t| (c.label().to_string(), t as i32)))
         .sorted_by(|a, b| a.0.cmp(&b.0))
         .collect::<Vec<_>>();
 
@@ -98,15 +285,23 @@ fn get_system_info(
     let network_speed = |value: u64| {
         match elapsed {
             None | Some(0) => 0, // avoid division by zero
-            Some(elapsed) => (value / 1000) as u32 / elapsed as u32,
+            Some(elapsed) => value / elapsed,
         }
     };
 
     SystemInfoData {
         cpu_usage,
+        cpu_per_core,
+        load_avg_1m,
+        load_avg_5m,
+        load_avg_15m,
         memory_usage,
         memory_swap_usage,
-        temperature,
+        memory_used_kb: (system.total_memory() - system.available_memory()) / 1024,
+        memory_total_kb: system.total_memory() / 1024,
+        swap_used_kb: (system.total_swap() - system.free_swap()) / 1024,
+        swap_total_kb: system.total_swap() / 1024,
+        temperatures,
         disks,
         network: network.0.map(|ip| NetworkData {
             ip: ip.to_string(),
@@ -117,12 +312,36 @@ fn get_system_info(
     }
 }
 
+impl SystemInfoData {
+    fn temperatures<'a>(
+        &'a self,
+        config: &'a SystemInfoTemperature,
+    ) -> impl Iterator<Item = &'a (String, i32)> {
+        self.temperatures
+            .iter()
+            .filter(|(label, _)| config.sensors.is_empty() || config.sensors.contains(label))
+    }
+
+    fn max_temperature(&self, config: &SystemInfoTemperature) -> Option<i32> {
+        self.temperatures(config).map(|(_, t)| *t).max()
+    }
+
+    fn disks<'a>(&'a self, config: &'a SystemInfoDisk) -> impl Iterator<Item = &'a DiskData> {
+        self.disks
+            .iter()
+            .filter(|disk| config.paths.is_empty() || config.paths.contains(&disk.path))
+    }
+}
+
 pub struct SystemInfo {
     system: System,
     components: Components,
     disks: Disks,
     networks: Networks,
     data: SystemInfoData,
+    core_count: usize,
+    gpu: Option<GpuData>,
+    gpu_misses: u32,
 }
 
 impl Default for SystemInfo {
@@ -134,7 +353,7 @@ impl Default for SystemInfo {
         let data = get_system_info(
             &mut system,
             &mut components,
-            &mut disks,
+            get_disks(&mut disks),
             (&mut networks, None),
         );
 
@@ -144,6 +363,9 @@ impl Default for SystemInfo {
             disks,
             data,
             networks,
+            core_count: get_core_count(),
+            gpu: None,
+            gpu_misses: 0,
         }
     }
 }
@@ -151,6 +373,10 @@ impl Default for SystemInfo {
 #[derive(Debug, Clone)]
 pub enum Message {
     Update,
+    UpdateCpu,
+    UpdateDisks,
+    UpdateGpu,
+    GpuUpdated(Option<GpuData>),
 }
 
 impl SystemInfo {
@@ -160,18 +386,65 @@ impl SystemInfo {
                 self.data = get_system_info(
                     &mut self.system,
                     &mut self.components,
-                    &mut self.disks,
+                    std::mem::take(&mut self.data.disks),
                     (
                         &mut self.networks,
                         self.data.network.as_ref().map(|n| n.last_check),
                     ),
                 );
 
+                Task::none()
+            }
+            Message::UpdateGpu => Task::perform(get_gpu_info(), |gpu| {
+                app::Message::SystemInfo(Message::GpuUpdated(gpu))
+            }),
+            Message::GpuUpdated(gpu) => {
+                if gpu.is_some() {
+                    self.gpu_misses = 0;
+                } else {
+                    self.gpu_misses = self.gpu_misses.saturating_add(1);
+                    if self.gpu_misses == GPU_RETRIES {
+                        info!(
+                            "No supported GPU found, checking again every {}s",
+                            GPU_MISSING_POLL_INTERVAL.as_secs()
+                        );
+                    }
+                }
+
+                debug!("GPU info: {:?}", gpu);
+                self.gpu = gpu;
+
+                Task::none()
+            }
+            Message::UpdateCpu => {
+                (self.data.cpu_usage, self.data.cpu_per_core) = get_cpu_usage(&mut self.system);
+                (
+                    self.data.load_avg_1m,
+                    self.data.load_avg_5m,
+                    self.data.load_avg_15m,
+                ) = get_load_avg();
+
+                Task::none()
+            }
+            Message::UpdateDisks => {
+                self.data.disks = get_disks(&mut self.disks);
+
                 Task::none()
             }
         }
     }
 
+    fn with_tooltip<'a>(
+        element: Element<'a, app::Message>,
+        content: String,
+        position: tooltip::Position,
+    ) -> Element<'a, app::Message> {
+        TooltipWrapper::new(element)
+            .tooltip(Some(text(content).size(12)))
+            .position(position)
+            .into()
+    }
+
     fn info_element<'a>(info_icon: Icons, label: String, value: String) -> Element<'a, Message> {
         row!(
             container(icon(info_icon).size(22)).center_x(Length::Fixed(32.)),
@@ -183,11 +456,11 @@ impl SystemInfo {
         .into()
     }
 
-    fn indicator_info_element<'a, V: std::fmt::Display + PartialOrd + 'a>(
+    fn indicator_info_element<'a, V: std::fmt::Display + 'a>(
         info_icon: Icons,
         value: V,
         unit: &str,
-        threshold: Option<(V, V)>,
+        state: IndicatorState,
         prefix: Option<&str>,
     ) -> Element<'a, app::Message> {
         let element = container(
@@ -202,25 +475,35 @@ impl SystemInfo {
             .spacing(4),
         );
 
-        if let Some((warn_threshold, alert_threshold)) = threshold {
-            element
-                .style(move |theme: &Theme| container::Style {
-                    text_color: if value > warn_threshold && value < alert_threshold {
-                        Some(theme.extended_palette().danger.weak.color)
-                    } else if value >= alert_threshold {
-                        Some(theme.palette().danger)
-                    } else {
-                        None
-                    },
-                    ..Default::default()
-                })
-                .into()
-        } else {
-            element.into()
-        }
+        element
+            .style(move |theme: &Theme| container::Style {
+                text_color: match state {
+                    IndicatorState::Warning => Some(theme.extended_palette().danger.weak.color),
+                    IndicatorState::Danger => Some(theme.palette().danger),
+                    _ => None,
+                },
+                ..Default::default()
+            })
+            .into()
     }
 
-    pub fn menu_view(&self) -> Element<Message> {
+    // hidden entirely when the system has no swap configured
+    fn swap_indicator(&self, config: &SystemModuleConfig) -> Option<Element<app::Message>> {
+        (self.data.swap_total_kb > 0).then(|| {
+            Self::indicator_info_element(
+                Icons::Mem,
+                self.data.memory_swap_usage,
+                "%",
+                classify(
+                    self.data.memory_swap_usage as f32,
+                    &config.memory.swap_thresholds(),
+                ),
+                Some("swap"),
+            )
+        })
+    }
+
+    pub fn menu_view(&self, config: &SystemModuleConfig) -> Element<Message> {
         column!(
             column!(text("System Info").size(20), horizontal_rule(1)).spacing(4),
             Column::new()
@@ -230,38 +513,78 @@ impl SystemInfo {
                     format!("{}%", self.data.cpu_usage),
                 ))
                 .push(Self::info_element(
-                    Icons::Mem,
-                    "Memory Usage".to_string(),
-                    format!("{}%", self.data.memory_usage),
+                    Icons::Cpu,
+                    "Load Average".to_string(),
+                    format!(
+                        "{:.2} {:.2} {:.2}",
+                        self.data.load_avg_1m, self.data.load_avg_5m, self.data.load_avg_15m
+                    ),
                 ))
                 .push(Self::info_element(
                     Icons::Mem,
-                    "Swap memory Usage".to_string(),
-                    format!("{}%", self.data.memory_swap_usage),
+                    "Memory Usage".to_string(),
+                    format!(
+                        "{}% ({} / {})",
+                        self.data.memory_usage,
+                        format_bytes(self.data.memory_used_kb * 1024),
+                        format_bytes(self.data.memory_total_kb * 1024)
+                    ),
                 ))
-                .push_maybe(self.data.temperature.map(|temp| {
+                .push_maybe((self.data.swap_total_kb > 0).then(|| {
                     Self::info_element(
-                        Icons::Temp,
-                        "Temperature".to_string(),
-                        format!("{}°C", temp),
+                        Icons::Mem,
+                        "Swap memory Usage".to_string(),
+                        format!(
+                            "{}% ({} / {})",
+                            self.data.memory_swap_usage,
+                            format_bytes(self.data.swap_used_kb * 1024),
+                            format_bytes(self.data.swap_total_kb * 1024)
+                        ),
                     )
                 }))
                 .push(
                     Column::with_children(
                         self.data
-                            .disks
-                            .iter()
-                            .map(|(mount_point, usage)| {
+                            .temperatures(&config.temperature)
+                            .map(|(label, temp)| {
+                                Self::info_element(
+                                    Icons::Temp,
+                                    format!("Temperature {}", label),
+                                    format!("{}°C", temp),
+                                )
+                            })
+                            .collect::<Vec<Element<_>>>(),
+                    )
+                    .spacing(4),
+                )
+                .push(
+                    Column::with_children(
+                        self.data
+                            .disks(&config.disk)
+                            .map(|disk| {
                                 Self::info_element(
-                                    Icons::Drive,
-                                    format!("Disk Usage {}", mount_point),
-                                    format!("{}%", usage),
+                                    disk.icon(),
+                                    format!("Disk Usage {}", disk.path),
+                                    format!("{}% ({})", disk.used_percent, disk.usage()),
                                 )
                             })
                             .collect::<Vec<Element<_>>>(),
                     )
                     .spacing(4),
                 )
+                .push_maybe(self.gpu.as_ref().map(|gpu| {
+                    Self::info_element(
+                        Icons::Gpu,
+                        match gpu.vendor {
+                            GpuVendor::Nvidia => "GPU Usage (NVIDIA)".to_string(),
+                            GpuVendor::Amd => "GPU Usage (AMD)".to_string(),
+                        },
+                        format!(
+                            "{}% ({} / {} MB)",
+                            gpu.usage_percent, gpu.vram_used_mb, gpu.vram_total_mb
+                        ),
+                    )
+                }))
                 .push_maybe(self.data.network.as_ref().map(|network| {
                     Column::with_children(vec![
                         Self::info_element(
@@ -272,20 +595,12 @@ impl SystemInfo {
                         Self::info_element(
                             Icons::DownloadSpeed,
                             "Download Speed".to_string(),
-                            if network.download_speed > 1000 {
-                                format!("{} MB/s", network.download_speed / 1000)
-                            } else {
-                                format!("{} KB/s", network.download_speed)
-                            },
+                            format_bytes_rate(network.download_speed),
                         ),
                         Self::info_element(
                             Icons::UploadSpeed,
                             "Upload Speed".to_string(),
-                            if network.upload_speed > 1000 {
-                                format!("{} MB/s", network.upload_speed / 1000)
-                            } else {
-                                format!("{} KB/s", network.upload_speed)
-                            },
+                            format_bytes_rate(network.upload_speed),
                         ),
                     ])
                 }))
@@ -298,102 +613,155 @@ impl SystemInfo {
 }
 
 impl Module for SystemInfo {
-    type ViewData<'a> = &'a SystemModuleConfig;
-    type SubscriptionData<'a> = ();
+    type ViewData<'a> = (&'a SystemModuleConfig, tooltip::Position);
+    type SubscriptionData<'a> = &'a SystemModuleConfig;
 
     fn view(
         &self,
-        config: Self::ViewData<'_>,
+        (config, tooltip_position): Self::ViewData<'_>,
     ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
         let indicators = config.indicators.iter().filter_map(|i| match i {
-            SystemIndicator::Cpu => Some(Self::indicator_info_element(
-                Icons::Cpu,
-                self.data.cpu_usage,
-                "%",
-                Some((config.cpu.warn_threshold, config.cpu.alert_threshold)),
-                None,
-            )),
-            SystemIndicator::Memory => Some(Self::indicator_info_element(
-                Icons::Mem,
-                self.data.memory_usage,
-                "%",
-                Some((config.memory.warn_threshold, config.memory.alert_threshold)),
-                None,
-            )),
-            SystemIndicator::MemorySwap => Some(Self::indicator_info_element(
-                Icons::Mem,
-                self.data.memory_swap_usage,
-                "%",
-                Some((config.memory.warn_threshold, config.memory.alert_threshold)),
-                Some("swap"),
+            SystemIndicator::Cpu => Some(Self::with_tooltip(
+                match config.cpu.display_mode {
+                    CpuDisplayMode::Usage => Self::indicator_info_element(
+                        Icons::Cpu,
+                        self.data.cpu_usage.to_string(),
+                        "%",
+                        classify(self.data.cpu_usage as f32, &config.cpu.thresholds()),
+                        None,
+                    ),
+                    CpuDisplayMode::LoadAvg => Self::indicator_info_element(
+                        Icons::Cpu,
+                        format!("{:.1}", self.data.load_avg_1m),
+                        "",
+                        load_avg_state(self.data.load_avg_1m, self.core_count),
+                        None,
+                    ),
+                },
+                format!(
+                    "Load: {:.2} {:.2} {:.2} ({} cores)\n{}",
+                    self.data.load_avg_1m,
+                    self.data.load_avg_5m,
+                    self.data.load_avg_15m,
+                    self.core_count,
+                    self.data
+                        .cpu_per_core
+                        .iter()
+                        .enumerate()
+                        .map(|(core, usage)| format!("Core {}: {}%", core, usage))
+                        .join("\n")
+                ),
+                tooltip_position,
             )),
-            SystemIndicator::Temperature => self.data.temperature.map(|temperature| {
-                Self::indicator_info_element(
-                    Icons::Temp,
-                    temperature,
-                    "°C",
-                    Some((
-                        config.temperature.warn_threshold,
-                        config.temperature.alert_threshold,
-                    )),
+            SystemIndicator::Memory => Some(Self::with_tooltip(
+                row!(Self::indicator_info_element(
+                    Icons::Mem,
+                    self.data.memory_usage,
+                    "%",
+                    classify(self.data.memory_usage as f32, &config.memory.thresholds()),
                     None,
-                )
-            }),
-            SystemIndicator::Disk(mount) => {
-                self.data.disks.iter().find_map(|(disk_mount, disk)| {
-                    if disk_mount == mount {
-                        Some(Self::indicator_info_element(
-                            Icons::Drive,
-                            *disk,
-                            "%",
-                            Some((config.disk.warn_threshold, config.disk.alert_threshold)),
-                            Some(disk_mount),
-                        ))
+                ))
+                // don't show swap twice when it has its own indicator
+                .push_maybe(self.swap_indicator(config).filter(|_| {
+                    !config
+                        .indicators
+                        .iter()
+                        .any(|i| matches!(i, SystemIndicator::MemorySwap))
+                }))
+                .spacing(4)
+                .into(),
+                format!(
+                    "Memory: {} / {}\nSwap: {}",
+                    format_bytes(self.data.memory_used_kb * 1024),
+                    format_bytes(self.data.memory_total_kb * 1024),
+                    if self.data.swap_total_kb > 0 {
+                        format!(
+                            "{} / {} ({}%)",
+                            format_bytes(self.data.swap_used_kb * 1024),
+                            format_bytes(self.data.swap_total_kb * 1024),
+                            self.data.memory_swap_usage
+                        )
                     } else {
-                        None
+                        "not configured".to_string()
                     }
-                })
+                ),
+                tooltip_position,
+            )),
+            SystemIndicator::MemorySwap => self.swap_indicator(config),
+            SystemIndicator::Temperature => {
+                self.data
+                    .max_temperature(&config.temperature)
+                    .map(|temperature| {
+                        Self::with_tooltip(
+                            Self::indicator_info_element(
+                                Icons::Temp,
+                                temperature,
+                                "°C",
+                                classify(temperature as f32, &config.temperature.thresholds()),
+                                None,
+                            ),
+                            self.data
+                                .temperatures(&config.temperature)
+                                .map(|(label, temp)| format!("{}: {}°C", label, temp))
+                                .join("\n"),
+                            tooltip_position,
+                        )
+                    })
             }
+            SystemIndicator::Disk(mount) => self.data.disks.iter().find_map(|disk| {
+                if &disk.path == mount {
+                    Some(Self::with_tooltip(
+                        Self::indicator_info_element(
+                            disk.icon(),
+                            disk.used_percent,
+                            "%",
+                            classify(disk.used_percent as f32, &config.disk.thresholds()),
+                            Some(&disk.path),
+                        ),
+                        disk.usage(),
+                        tooltip_position,
+                    ))
+                } else {
+                    None
+                }
+            }),
+            SystemIndicator::Gpu => self.gpu.as_ref().map(|gpu| {
+                Self::with_tooltip(
+                    Self::indicator_info_element(
+                        Icons::Gpu,
+                        gpu.usage_percent,
+                        "%",
+                        IndicatorState::Normal,
+                        None,
+                    ),
+                    format!("VRAM: {} / {} MB", gpu.vram_used_mb, gpu.vram_total_mb),
+                    tooltip_position,
+                )
+            }),
             SystemIndicator::IpAddress => self.data.network.as_ref().map(|network| {
                 Self::indicator_info_element(
                     Icons::IpAddress,
                     network.ip.to_string(),
                     "",
-                    None,
+                    IndicatorState::Normal,
                     None,
                 )
             }),
             SystemIndicator::DownloadSpeed => self.data.network.as_ref().map(|network| {
                 Self::indicator_info_element(
                     Icons::DownloadSpeed,
-                    if network.download_speed > 1000 {
-                        network.download_speed / 1000
-                    } else {
-                        network.download_speed
-                    },
-                    if network.download_speed > 1000 {
-                        "MB/s"
-                    } else {
-                        "KB/s"
-                    },
-                    None,
+                    format_bytes_rate(network.download_speed),
+                    "",
+                    IndicatorState::Normal,
                     None,
                 )
             }),
             SystemIndicator::UploadSpeed => self.data.network.as_ref().map(|network| {
                 Self::indicator_info_element(
                     Icons::UploadSpeed,
-                    if network.upload_speed > 1000 {
-                        network.upload_speed / 1000
-                    } else {
-                        network.upload_speed
-                    },
-                    if network.upload_speed > 1000 {
-                        "MB/s"
-                    } else {
-                        "KB/s"
-                    },
-                    None,
+                    format_bytes_rate(network.upload_speed),
+                    "",
+                    IndicatorState::Normal,
                     None,
                 )
             }),
@@ -408,7 +776,32 @@ impl Module for SystemInfo {
         ))
     }
 
-    fn subscription(&self, _: Self::SubscriptionData<'_>) -> Option<Subscription<app::Message>> {
-        Some(every(Duration::from_secs(5)).map(|_| app::Message::SystemInfo(Message::Update)))
+    fn subscription(
+        &self,
+        config: Self::SubscriptionData<'_>,
+    ) -> Option<Subscription<app::Message>> {
+        let mut subscriptions = vec![
+            every(Duration::from_secs(5)).map(|_| app::Message::SystemInfo(Message::Update)),
+            every(Duration::from_millis(config.cpu.poll_interval_ms.max(200)))
+                .map(|_| app::Message::SystemInfo(Message::UpdateCpu)),
+            every(Duration::from_secs(30)).map(|_| app::Message::SystemInfo(Message::UpdateDisks)),
+        ];
+
+        // nvidia-smi is slow to start, only poll it when the indicator is shown
+        if config
+            .indicators
+            .iter()
+            .any(|i| matches!(i, SystemIndicator::Gpu))
+        {
+            let interval = if self.gpu_misses >= GPU_RETRIES {
+                GPU_MISSING_POLL_INTERVAL
+            } else {
+                GPU_POLL_INTERVAL
+            };
+            subscriptions
+                .push(every(interval).map(|_| app::Message::SystemInfo(Message::UpdateGpu)));
+        }
+
+        Some(Subscription::batch(subscriptions))
     }
 }
@@ -0,0 +1,98 @@
+use super::{Module, OnModulePress};
+use crate::{
+    app,
+    config::SystemdUnitConfig,
+    services::{ReadOnlyService, ServiceEvent, inspector, systemd::SystemdService},
+    utils::IndicatorState,
+};
+use iced::{
+    Alignment, Element, Subscription, Task, Theme,
+    widget::{Row, container, text},
+};
+
+#[derive(Debug, Clone)]
+pub enum SystemdUnitsMessage {
+    Event(ServiceEvent<SystemdService>),
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SystemdUnits {
+    pub service: Option<SystemdService>,
+}
+
+impl SystemdUnits {
+    pub fn update(&mut self, message: SystemdUnitsMessage) -> Task<crate::app::Message> {
+        match message {
+            SystemdUnitsMessage::Event(event) => match event {
+                ServiceEvent::Init(service) => {
+                    self.service = Some(service);
+                    Task::none()
+                }
+                ServiceEvent::Update(data) => {
+                    if let Some(service) = self.service.as_mut() {
+                        service.update(data);
+                    }
+                    Task::none()
+                }
+                ServiceEvent::Error(_) => Task::none(),
+            },
+        }
+    }
+}
+
+impl Module for SystemdUnits {
+    type ViewData<'a> = ();
+    type SubscriptionData<'a> = &'a [SystemdUnitConfig];
+
+    fn view(
+        &self,
+        _: Self::ViewData<'_>,
+    ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
+        let service = self.service.as_ref().filter(|s| !s.units.is_empty())?;
+
+        Some((
+            Row::with_children(
+                service
+                    .units
+                    .iter()
+                    .map(|unit| {
+                        let state = unit.indicator_state();
+
+                        container(text(unit.label.clone()))
+                            .style(move |theme: &Theme| container::Style {
+                                text_color: match state {
+                                    IndicatorState::Success => Some(theme.palette().success),
+                                    IndicatorState::Warning => {
+                                        Some(theme.extended_palette().danger.weak.color)
+                                    }
+                                    IndicatorState::Danger => Some(theme.palette().danger),
+                                    _ => None,
+                                },
+                                ..Default::default()
+                            })
+                            .into()
+                    })
+                    .collect::<Vec<Element<app::Message>>>(),
+            )
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .into(),
+            None,
+        ))
+    }
+
+    fn subscription(
+        &self,
+        config: Self::SubscriptionData<'_>,
+    ) -> Option<Subscription<app::Message>> {
+        if config.is_empty() {
+            None
+        } else {
+            Some(
+                SystemdService::subscribe_with_config(config)
+                    .map(inspector::record)
+                    .map(|e| app::Message::SystemdUnits(SystemdUnitsMessage::Event(e))),
+            )
+        }
+    }
+}
@@ -1,11 +1,14 @@
 use super::{Module, OnModulePress};
 use crate::{
     app,
-    components::icons::{Icons, icon},
+    components::{
+        icons::{Icons, icon},
+        tooltip_wrapper::TooltipWrapper,
+    },
     menu::MenuType,
     position_button::position_button,
     services::{
-        ReadOnlyService, Service, ServiceEvent,
+        ReadOnlyService, Service, ServiceEvent, inspector,
         tray::{
             TrayCommand, TrayIcon, TrayService,
             dbus::{Layout, LayoutProps},
@@ -15,7 +18,7 @@ use crate::{
 };
 use iced::{
     Alignment, Element, Length, Subscription, Task,
-    widget::{Column, Image, Row, Svg, button, horizontal_rule, row, text, toggler},
+    widget::{Column, Image, Row, Svg, button, horizontal_rule, row, text, toggler, tooltip},
     window::Id,
 };
 use log::debug;
@@ -25,6 +28,7 @@ pub enum TrayMessage {
     Event(ServiceEvent<TrayService>),
     ToggleSubmenu(i32),
     MenuSelected(String, i32),
+    Activate(String, i32, i32),
 }
 
 #[derive(Debug, Default, Clone)]
@@ -66,6 +70,12 @@ impl TrayModule {
                 }
                 _ => Task::none(),
             },
+            TrayMessage::Activate(name, x, y) => match self.service.as_mut() {
+                Some(service) => service
+                    .command(TrayCommand::Activate(name, x, y))
+                    .map(|event| crate::app::Message::Tray(TrayMessage::Event(event))),
+                _ => Task::none(),
+            },
         }
     }
 
@@ -157,12 +167,12 @@ impl TrayModule {
 }
 
 impl Module for TrayModule {
-    type ViewData<'a> = (Id, f32);
+    type ViewData<'a> = (Id, f32, tooltip::Position);
     type SubscriptionData<'a> = ();
 
     fn view(
         &self,
-        (id, opacity): Self::ViewData<'_>,
+        (id, opacity, tooltip_position): Self::ViewData<'_>,
     ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
         self.service
             .as_ref()
@@ -174,7 +184,7 @@ impl Module for TrayModule {
                             .data
                             .iter()
                             .map(|item| {
-                                position_button(match &item.icon {
+                                let button = position_button(match &item.icon {
                                     Some(TrayIcon::Image(handle)) => Into::<Element<_>>::into(
                                         Image::new(handle.clone()).height(Length::Fixed(14.)),
                                     ),
@@ -185,7 +195,16 @@ impl Module for TrayModule {
                                     ),
                                     _ => icon(Icons::Point).into(),
                                 })
+                                // activated at the icon position, relative to the bar surface
+                                // since wayland doesn't expose global coordinates
                                 .on_press_with_position(move |button_ui_ref| {
+                                    app::Message::Tray(TrayMessage::Activate(
+                                        item.name.to_owned(),
+                                        button_ui_ref.position.x as i32,
+                                        button_ui_ref.position.y as i32,
+                                    ))
+                                })
+                                .on_right_press_with_position(move |button_ui_ref| {
                                     app::Message::ToggleMenu(
                                         MenuType::Tray(item.name.to_owned()),
                                         id,
@@ -193,8 +212,14 @@ impl Module for TrayModule {
                                     )
                                 })
                                 .padding([2, 2])
-                                .style(ghost_button_style(opacity))
-                                .into()
+                                .style(ghost_button_style(opacity));
+
+                                TooltipWrapper::new(button)
+                                    .tooltip(
+                                        item.title.as_deref().map(|title| text(title).size(12)),
+                                    )
+                                    .position(tooltip_position)
+                                    .into()
                             })
                             .collect::<Vec<_>>(),
                     )
@@ -207,6 +232,10 @@ impl Module for TrayModule {
     }
 
     fn subscription(&self, _: Self::SubscriptionData<'_>) -> Option<Subscription<app::Message>> {
-        Some(TrayService::subscribe().map(|e| app::Message::Tray(TrayMessage::Event(e))))
+        Some(
+            TrayService::subscribe()
+                .map(inspector::record)
+                .map(|e| app::Message::Tray(TrayMessage::Event(e))),
+        )
     }
 }
@@ -0,0 +1,79 @@
+use super::{Module, OnModulePress};
+use crate::{
+    app,
+    components::icons::{Icons, icon},
+    utils::format_duration,
+};
+use iced::{
+    Alignment, Element, Subscription,
+    time::every,
+    widget::{row, text},
+};
+use log::warn;
+use std::{fs, time::Duration};
+
+fn read_uptime() -> Option<Duration> {
+    let uptime = fs::read_to_string("/proc/uptime")
+        .inspect_err(|err| warn!("Failed to read /proc/uptime: {}", err))
+        .ok()?;
+
+    uptime
+        .split_whitespace()
+        .next()
+        .and_then(|seconds| seconds.parse::<f64>().ok())
+        .map(Duration::from_secs_f64)
+}
+
+pub struct Uptime {
+    uptime: Option<Duration>,
+}
+
+impl Default for Uptime {
+    fn default() -> Self {
+        Self {
+            uptime: read_uptime(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Update,
+}
+
+impl Uptime {
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Update => {
+                self.uptime = read_uptime();
+            }
+        }
+    }
+}
+
+impl Module for Uptime {
+    type ViewData<'a> = ();
+    type SubscriptionData<'a> = ();
+
+    fn view(
+        &self,
+        _: Self::ViewData<'_>,
+    ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
+        self.uptime.map(|uptime| {
+            (
+                row!(
+                    icon(Icons::Uptime),
+                    text(format_duration(&uptime).trim().to_owned())
+                )
+                .align_y(Alignment::Center)
+                .spacing(4)
+                .into(),
+                None,
+            )
+        })
+    }
+
+    fn subscription(&self, _: Self::SubscriptionData<'_>) -> Option<Subscription<app::Message>> {
+        Some(every(Duration::from_secs(60)).map(|_| app::Message::Uptime(Message::Update)))
+    }
+}
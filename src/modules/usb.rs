@@ -0,0 +1,142 @@
+use super::{Module, OnModulePress};
+use crate::{
+    app,
+    components::icons::{Icons, icon},
+    menu::MenuType,
+    services::{
+        ReadOnlyService, Service, ServiceEvent, inspector,
+        usb::{UsbCommand, UsbEvent, UsbService},
+    },
+    style::ghost_button_style,
+    utils::launcher::notify,
+};
+use iced::{
+    Alignment, Element, Length, Subscription, Task,
+    widget::{Column, button, column, horizontal_rule, row, text},
+};
+
+#[derive(Debug, Clone)]
+pub enum UsbMessage {
+    Event(ServiceEvent<UsbService>),
+    Eject(String),
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Usb {
+    pub service: Option<UsbService>,
+}
+
+impl Usb {
+    pub fn update(&mut self, message: UsbMessage) -> Task<crate::app::Message> {
+        match message {
+            UsbMessage::Event(event) => match event {
+                ServiceEvent::Init(service) => {
+                    self.service = Some(service);
+                    Task::none()
+                }
+                ServiceEvent::Update(data) => {
+                    if let UsbEvent::Connected(device) = &data {
+                        notify("USB device connected", &device.name, "normal");
+                    }
+
+                    if let Some(service) = self.service.as_mut() {
+                        service.update(data);
+                    }
+                    Task::none()
+                }
+                ServiceEvent::Error(_) => Task::none(),
+            },
+            UsbMessage::Eject(dev_node) => self.command(UsbCommand::Eject(dev_node)),
+        }
+    }
+
+    pub fn refresh(&mut self) -> Task<crate::app::Message> {
+        self.command(UsbCommand::Refresh)
+    }
+
+    fn command(&mut self, command: UsbCommand) -> Task<crate::app::Message> {
+        match self.service.as_mut() {
+            Some(service) => service
+                .command(command)
+                .map(|event| app::Message::Usb(UsbMessage::Event(event))),
+            None => Task::none(),
+        }
+    }
+
+    pub fn menu_view(&self, opacity: f32) -> Element<UsbMessage> {
+        let devices = self
+            .service
+            .as_ref()
+            .map(|service| service.devices.as_slice())
+            .unwrap_or_default();
+
+        column!(
+            text("USB devices"),
+            horizontal_rule(1),
+            Column::with_children(
+                devices
+                    .iter()
+                    .map(|device| {
+                        row!(
+                            column!(
+                                text(device.name.clone()),
+                                text(
+                                    device
+                                        .mount_point
+                                        .clone()
+                                        .unwrap_or_else(|| "Not mounted".to_owned())
+                                )
+                                .size(12),
+                            )
+                            .width(Length::Fill),
+                            button(text("Eject"))
+                                .padding([4, 8])
+                                .style(ghost_button_style(opacity))
+                                .on_press(UsbMessage::Eject(device.dev_node.clone())),
+                        )
+                        .align_y(Alignment::Center)
+                        .spacing(8)
+                        .into()
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .spacing(8),
+        )
+        .spacing(8)
+        .into()
+    }
+}
+
+impl Module for Usb {
+    type ViewData<'a> = ();
+    type SubscriptionData<'a> = ();
+
+    fn view(
+        &self,
+        _: Self::ViewData<'_>,
+    ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
+        let service = self
+            .service
+            .as_ref()
+            .filter(|service| !service.devices.is_empty())?;
+
+        Some((
+            row!(
+                icon(Icons::ExternalDrive),
+                text(service.devices.len().to_string()).size(10)
+            )
+            .align_y(Alignment::Center)
+            .spacing(2)
+            .into(),
+            Some(OnModulePress::ToggleMenu(MenuType::Usb)),
+        ))
+    }
+
+    fn subscription(&self, _: Self::SubscriptionData<'_>) -> Option<Subscription<app::Message>> {
+        Some(
+            UsbService::subscribe()
+                .map(inspector::record)
+                .map(|e| app::Message::Usb(UsbMessage::Event(e))),
+        )
+    }
+}
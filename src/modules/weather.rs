@@ -0,0 +1,173 @@
+use super::{Module, OnModulePress};
+use crate::{
+    app,
+    components::{
+        icons::{Icons, icon},
+        tooltip_wrapper::TooltipWrapper,
+    },
+    config::{WeatherModuleConfig, WeatherUnits},
+};
+use iced::{
+    Alignment, Element, Subscription, Task,
+    stream::channel,
+    widget::{row, text, tooltip},
+};
+use log::warn;
+use serde::Deserialize;
+use std::{any::TypeId, time::Duration};
+use tokio::time::sleep;
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct WeatherData {
+    pub temperature: f32,
+    pub windspeed: f32,
+    pub weathercode: u16,
+}
+
+#[derive(Deserialize)]
+struct ForecastResponse {
+    current_weather: WeatherData,
+}
+
+impl WeatherData {
+    // WMO weather interpretation codes
+    fn icon(&self) -> Icons {
+        match self.weathercode {
+            0 | 1 => Icons::WeatherSunny,
+            2 => Icons::WeatherPartlyCloudy,
+            3 => Icons::WeatherCloudy,
+            45 | 48 => Icons::WeatherFog,
+            51..=67 | 80..=82 => Icons::WeatherRainy,
+            71..=77 | 85 | 86 => Icons::WeatherSnowy,
+            95..=99 => Icons::WeatherLightning,
+            _ => Icons::WeatherCloudy,
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self.weathercode {
+            0 => "Clear sky",
+            1 => "Mainly clear",
+            2 => "Partly cloudy",
+            3 => "Overcast",
+            45 | 48 => "Fog",
+            51..=57 => "Drizzle",
+            61..=67 => "Rain",
+            71..=77 => "Snow",
+            80..=82 => "Rain showers",
+            85 | 86 => "Snow showers",
+            95..=99 => "Thunderstorm",
+            _ => "Unknown",
+        }
+    }
+}
+
+async fn fetch_weather(config: &WeatherModuleConfig) -> anyhow::Result<WeatherData> {
+    let (temperature_unit, windspeed_unit) = match config.units {
+        WeatherUnits::Celsius => ("celsius", "kmh"),
+        WeatherUnits::Fahrenheit => ("fahrenheit", "mph"),
+    };
+    let url = format!(
+        "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&current_weather=true&temperature_unit={}&windspeed_unit={}",
+        config.latitude, config.longitude, temperature_unit, windspeed_unit
+    );
+
+    let response = reqwest::get(url).await?.error_for_status()?.text().await?;
+    let forecast: ForecastResponse = serde_json::from_str(&response)?;
+
+    Ok(forecast.current_weather)
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Updated(WeatherData),
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Weather {
+    data: Option<WeatherData>,
+}
+
+impl Weather {
+    pub fn update(&mut self, message: Message) -> Task<crate::app::Message> {
+        match message {
+            Message::Updated(data) => {
+                self.data = Some(data);
+                Task::none()
+            }
+        }
+    }
+}
+
+impl Module for Weather {
+    type ViewData<'a> = (&'a Option<WeatherModuleConfig>, tooltip::Position);
+    type SubscriptionData<'a> = &'a WeatherModuleConfig;
+
+    fn view(
+        &self,
+        (config, tooltip_position): Self::ViewData<'_>,
+    ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
+        let config = config.as_ref()?;
+
+        self.data.map(|data| {
+            let (unit, speed_unit) = match config.units {
+                WeatherUnits::Celsius => ("°C", "km/h"),
+                WeatherUnits::Fahrenheit => ("°F", "mph"),
+            };
+
+            (
+                TooltipWrapper::new(
+                    row!(
+                        icon(data.icon()),
+                        text(format!("{:.0}{}", data.temperature, unit))
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(4),
+                )
+                .tooltip(Some(
+                    text(format!(
+                        "{}, wind {:.0} {}",
+                        data.description(),
+                        data.windspeed,
+                        speed_unit
+                    ))
+                    .size(12),
+                ))
+                .position(tooltip_position)
+                .into(),
+                None,
+            )
+        })
+    }
+
+    fn subscription(
+        &self,
+        config: Self::SubscriptionData<'_>,
+    ) -> Option<Subscription<app::Message>> {
+        let id = format!("{:?}-{:?}", TypeId::of::<Self>(), config);
+        let config = config.clone();
+
+        Some(
+            Subscription::run_with_id(
+                id,
+                channel(10, async move |mut output| {
+                    loop {
+                        // on failure keep the last known data and retry on the next interval
+                        match fetch_weather(&config).await {
+                            Ok(data) => {
+                                let _ = output.try_send(Message::Updated(data));
+                            }
+                            Err(err) => warn!("Failed to fetch weather data: {}", err),
+                        }
+
+                        sleep(Duration::from_secs(
+                            config.update_interval_minutes.max(1) * 60,
+                        ))
+                        .await;
+                    }
+                }),
+            )
+            .map(app::Message::Weather),
+        )
+    }
+}
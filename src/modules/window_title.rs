@@ -1,6 +1,18 @@
-use crate::{app, utils::truncate_text};
+use crate::{
+    app,
+    services::{
+        ReadOnlyService, ServiceEvent, inspector,
+        niri::NiriService,
+        tray::{TrayIcon, get_icon_from_name},
+    },
+    utils::truncate_text,
+};
 use hyprland::{data::Client, event_listener::AsyncEventListener, shared::HyprDataActiveOptional};
-use iced::{Element, Subscription, stream::channel, widget::text};
+use iced::{
+    Alignment, Element, Length, Subscription,
+    stream::channel,
+    widget::{Image, Row, Svg, text},
+};
 use log::{debug, error};
 use std::{
     any::TypeId,
@@ -11,18 +23,31 @@ use super::{Module, OnModulePress};
 
 pub struct WindowTitle {
     value: Option<String>,
+    niri: Option<NiriService>,
+    app_id: Option<String>,
+    icon: Option<TrayIcon>,
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     TitleChanged(Option<String>),
+    Niri(ServiceEvent<NiriService>),
 }
 
 impl Default for WindowTitle {
     fn default() -> Self {
-        let init = Client::get_active().ok().and_then(|w| w.map(|w| w.title));
-
-        Self { value: init }
+        let init = if NiriService::is_available() {
+            None
+        } else {
+            Client::get_active().ok().and_then(|w| w.map(|w| w.title))
+        };
+
+        Self {
+            value: init,
+            niri: None,
+            app_id: None,
+            icon: None,
+        }
     }
 }
 
@@ -36,6 +61,29 @@ impl WindowTitle {
                     self.value = None;
                 }
             }
+            Message::Niri(event) => {
+                match event {
+                    ServiceEvent::Init(niri) => self.niri = Some(niri),
+                    ServiceEvent::Update(event) => {
+                        if let Some(niri) = self.niri.as_mut() {
+                            niri.update(event);
+                        }
+                    }
+                    ServiceEvent::Error(_) => {}
+                }
+
+                let focused = self.niri.as_ref().and_then(|niri| niri.focused_window());
+
+                self.value = focused
+                    .and_then(|w| w.title.as_ref())
+                    .map(|title| truncate_text(title, truncate_title_after_length));
+
+                let app_id = focused.and_then(|w| w.app_id.clone());
+                if app_id != self.app_id {
+                    self.icon = app_id.as_deref().and_then(get_icon_from_name);
+                    self.app_id = app_id;
+                }
+            }
         }
     }
 }
@@ -48,12 +96,39 @@ impl Module for WindowTitle {
         &self,
         _: Self::ViewData<'_>,
     ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
-        self.value
-            .as_ref()
-            .map(|value| (text(value).size(12).into(), None))
+        self.value.as_ref().map(|value| {
+            (
+                Row::new()
+                    .push_maybe(self.icon.as_ref().map(|icon| {
+                        match icon {
+                            TrayIcon::Image(handle) => Into::<Element<_>>::into(
+                                Image::new(handle.clone()).height(Length::Fixed(14.)),
+                            ),
+                            TrayIcon::Svg(handle) => Into::<Element<_>>::into(
+                                Svg::new(handle.clone())
+                                    .height(Length::Fixed(16.))
+                                    .width(Length::Shrink),
+                            ),
+                        }
+                    }))
+                    .push(text(value).size(12))
+                    .align_y(Alignment::Center)
+                    .spacing(8)
+                    .into(),
+                None,
+            )
+        })
     }
 
     fn subscription(&self, _: Self::SubscriptionData<'_>) -> Option<Subscription<app::Message>> {
+        if NiriService::is_available() {
+            return Some(
+                NiriService::subscribe()
+                    .map(inspector::record)
+                    .map(|event| app::Message::WindowTitle(Message::Niri(event))),
+            );
+        }
+
         let id = TypeId::of::<Self>();
 
         Some(
@@ -1,9 +1,15 @@
 use super::{Module, OnModulePress};
 use crate::{
     app,
+    components::icons::{Icons, icon},
     config::{AppearanceColor, WorkspaceVisibilityMode, WorkspacesModuleConfig},
     outputs::Outputs,
-    style::workspace_button_style,
+    services::{
+        ReadOnlyService, Service, ServiceEvent, inspector,
+        niri::{NiriCommand, NiriService},
+    },
+    style::{ghost_button_style, workspace_button_style},
+    utils::IndicatorState,
 };
 use hyprland::{
     dispatch::MonitorIdentifier,
@@ -11,9 +17,9 @@ use hyprland::{
     shared::{HyprData, HyprDataActive, HyprDataVec},
 };
 use iced::{
-    Element, Length, Subscription, alignment,
+    Element, Length, Subscription, Task, Theme, alignment,
     stream::channel,
-    widget::{Row, button, container, text},
+    widget::{Row, button, column, container, text},
     window::Id,
 };
 use itertools::Itertools;
@@ -31,6 +37,9 @@ pub struct Workspace {
     pub monitor: String,
     pub active: bool,
     pub windows: u16,
+    pub niri_id: Option<u64>,
+    // always true on Hyprland, the active workspace already shows the focus there
+    pub focused_output: bool,
 }
 
 fn get_workspaces(config: &WorkspacesModuleConfig) -> Vec<Workspace> {
@@ -60,6 +69,8 @@ fn get_workspaces(config: &WorkspacesModuleConfig) -> Vec<Workspace> {
             monitor: w.monitor.clone(),
             active: monitors.iter().any(|m| m.special_workspace.id == w.id),
             windows: w.windows,
+            niri_id: None,
+            focused_output: true,
         });
     }
 
@@ -72,6 +83,8 @@ fn get_workspaces(config: &WorkspacesModuleConfig) -> Vec<Workspace> {
             monitor: w.monitor.clone(),
             active: Some(w.id) == active.as_ref().map(|a| a.id),
             windows: w.windows,
+            niri_id: None,
+            focused_output: true,
         });
     }
 
@@ -104,6 +117,8 @@ fn get_workspaces(config: &WorkspacesModuleConfig) -> Vec<Workspace> {
             monitor: "".to_string(),
             active: false,
             windows: 0,
+            niri_id: None,
+            focused_output: true,
         });
     }
 
@@ -112,14 +127,50 @@ fn get_workspaces(config: &WorkspacesModuleConfig) -> Vec<Workspace> {
     result
 }
 
+fn get_niri_workspaces(niri: &NiriService, config: &WorkspacesModuleConfig) -> Vec<Workspace> {
+    let outputs = niri
+        .workspaces
+        .iter()
+        .filter_map(|w| w.output.clone())
+        .sorted()
+        .dedup()
+        .collect_vec();
+    let focused_output = niri.focused_output();
+
+    niri.workspaces
+        .iter()
+        .filter(|w| w.name.is_none() || w.name != config.niri_scratchpad)
+        .map(|w| Workspace {
+            id: w.idx as i32,
+            name: w.name.clone().unwrap_or_else(|| w.idx.to_string()),
+            monitor_id: w
+                .output
+                .as_ref()
+                .and_then(|output| outputs.iter().position(|o| o == output)),
+            monitor: w.output.clone().unwrap_or_default(),
+            active: w.is_active,
+            windows: w.active_window_id.is_some() as u16,
+            niri_id: Some(w.id),
+            focused_output: focused_output.is_some() && w.output == focused_output,
+        })
+        .sorted_by_key(|w| (w.monitor_id, w.id))
+        .collect()
+}
+
 pub struct Workspaces {
     workspaces: Vec<Workspace>,
+    niri: Option<NiriService>,
 }
 
 impl Workspaces {
     pub fn new(config: &WorkspacesModuleConfig) -> Self {
         Self {
-            workspaces: get_workspaces(config),
+            workspaces: if NiriService::is_available() {
+                Vec::new()
+            } else {
+                get_workspaces(config)
+            },
+            niri: None,
         }
     }
 }
@@ -129,14 +180,56 @@ pub enum Message {
     WorkspacesChanged,
     ChangeWorkspace(i32),
     ToggleSpecialWorkspace(i32),
+    Niri(ServiceEvent<NiriService>),
+    FocusNiriWorkspace(u64),
+    ToggleNiriScratchpad,
+    ToggleNiriOverview,
 }
 
 impl Workspaces {
-    pub fn update(&mut self, message: Message, config: &WorkspacesModuleConfig) {
+    pub fn update(
+        &mut self,
+        message: Message,
+        config: &WorkspacesModuleConfig,
+    ) -> Task<app::Message> {
         match message {
             Message::WorkspacesChanged => {
                 self.workspaces = get_workspaces(config);
             }
+            Message::Niri(event) => match event {
+                ServiceEvent::Init(niri) => {
+                    self.workspaces = get_niri_workspaces(&niri, config);
+                    self.niri = Some(niri);
+                }
+                ServiceEvent::Update(event) => {
+                    if let Some(niri) = self.niri.as_mut() {
+                        niri.update(event);
+                        self.workspaces = get_niri_workspaces(niri, config);
+                    }
+                }
+                ServiceEvent::Error(_) => {}
+            },
+            Message::FocusNiriWorkspace(id) => {
+                if let Some(niri) = self.niri.as_mut() {
+                    return niri
+                        .command(NiriCommand::FocusWorkspace(id))
+                        .map(|event| app::Message::Workspaces(Message::Niri(event)));
+                }
+            }
+            Message::ToggleNiriOverview => {
+                if let Some(niri) = self.niri.as_mut() {
+                    return niri
+                        .command(NiriCommand::ToggleOverview)
+                        .map(|event| app::Message::Workspaces(Message::Niri(event)));
+                }
+            }
+            Message::ToggleNiriScratchpad => {
+                if let (Some(niri), Some(name)) = (self.niri.as_mut(), &config.niri_scratchpad) {
+                    return niri
+                        .command(NiriCommand::ToggleScratchpad(name.clone()))
+                        .map(|event| app::Message::Workspaces(Message::Niri(event)));
+                }
+            }
             Message::ChangeWorkspace(id) => {
                 if id > 0 {
                     let already_active = self.workspaces.iter().any(|w| w.active && w.id == id);
@@ -177,9 +270,75 @@ impl Workspaces {
                 }
             }
         }
+
+        Task::none()
     }
 }
 
+impl Workspaces {
+    fn niri_overview_button(&self, config: &WorkspacesModuleConfig) -> Option<Element<Message>> {
+        let niri = self.niri.as_ref().filter(|_| config.show_overview_button)?;
+
+        Some(niri_indicator_button(
+            Icons::Overview,
+            if niri.overview_open {
+                IndicatorState::Success
+            } else {
+                IndicatorState::Normal
+            },
+            Message::ToggleNiriOverview,
+        ))
+    }
+
+    fn niri_scratchpad_indicator(
+        &self,
+        config: &WorkspacesModuleConfig,
+    ) -> Option<Element<Message>> {
+        let name = config.niri_scratchpad.as_ref()?;
+        let niri = self.niri.as_ref()?;
+        // the named workspace appears once niri's config declares it
+        if !niri
+            .workspaces
+            .iter()
+            .any(|w| w.name.as_ref() == Some(name))
+        {
+            return None;
+        }
+
+        let state = if niri.scratchpad_visible(name) {
+            IndicatorState::Success
+        } else {
+            IndicatorState::Normal
+        };
+
+        Some(niri_indicator_button(
+            Icons::Scratchpad,
+            state,
+            Message::ToggleNiriScratchpad,
+        ))
+    }
+}
+
+fn niri_indicator_button<'a>(
+    icon_type: Icons,
+    state: IndicatorState,
+    on_press: Message,
+) -> Element<'a, Message> {
+    button(
+        container(icon(icon_type)).style(move |theme: &Theme| container::Style {
+            text_color: match state {
+                IndicatorState::Success => Some(theme.palette().success),
+                _ => None,
+            },
+            ..Default::default()
+        }),
+    )
+    .padding([0, 4])
+    .style(ghost_button_style(1.))
+    .on_press(on_press)
+    .into()
+}
+
 impl Module for Workspaces {
     type ViewData<'a> = (
         &'a Outputs,
@@ -196,72 +355,97 @@ impl Module for Workspaces {
     ) -> Option<(Element<app::Message>, Option<OnModulePress>)> {
         let monitor_name = outputs.get_monitor_name(id);
 
+        let visible = self
+            .workspaces
+            .iter()
+            .filter(|w| {
+                config.visibility_mode == WorkspaceVisibilityMode::All
+                    || w.monitor == monitor_name.unwrap_or_else(|| &w.monitor)
+                    || !outputs.has_name(&w.monitor)
+            })
+            .collect_vec();
+
+        let workspace_button = |w: &Workspace| -> Element<Message> {
+            let empty = w.windows == 0;
+            let monitor = w.monitor_id;
+
+            let color = monitor.map(|m| {
+                if w.id > 0 {
+                    workspace_colors.get(m).copied()
+                } else {
+                    special_workspace_colors
+                        .unwrap_or(workspace_colors)
+                        .get(m)
+                        .copied()
+                }
+            });
+
+            button(
+                container(
+                    if w.id < 0 {
+                        text(w.name.as_str())
+                    } else {
+                        text(w.id)
+                    }
+                    .size(10),
+                )
+                .align_x(alignment::Horizontal::Center)
+                .align_y(alignment::Vertical::Center),
+            )
+            .style(workspace_button_style(empty, color, !w.focused_output))
+            .padding(if w.id < 0 {
+                if w.active { [0, 16] } else { [0, 8] }
+            } else {
+                [0, 0]
+            })
+            .on_press(match w.niri_id {
+                Some(niri_id) => Message::FocusNiriWorkspace(niri_id),
+                None if w.id > 0 => Message::ChangeWorkspace(w.id),
+                None => Message::ToggleSpecialWorkspace(w.id),
+            })
+            .width(if w.id < 0 {
+                Length::Shrink
+            } else if w.active {
+                Length::Fixed(32.)
+            } else {
+                Length::Fixed(16.)
+            })
+            .height(16)
+            .into()
+        };
+
+        // on niri the workspaces of several outputs are grouped under the output name
+        let grouped =
+            self.niri.is_some() && visible.iter().map(|w| &w.monitor).unique().count() > 1;
+        let workspaces = if grouped {
+            let groups = visible.iter().chunk_by(|w| w.monitor.as_str());
+
+            groups
+                .into_iter()
+                .map(|(monitor, group)| {
+                    column!(
+                        text(monitor.to_owned()).size(8),
+                        Row::with_children(group.map(|w| workspace_button(w))).spacing(4),
+                    )
+                    .align_x(alignment::Horizontal::Center)
+                    .spacing(1)
+                    .into()
+                })
+                .collect::<Vec<_>>()
+        } else {
+            visible
+                .iter()
+                .map(|w| workspace_button(w))
+                .collect::<Vec<_>>()
+        };
+
         Some((
             Into::<Element<Message>>::into(
-                Row::with_children(
-                    self.workspaces
-                        .iter()
-                        .filter_map(|w| {
-                            if config.visibility_mode == WorkspaceVisibilityMode::All
-                                || w.monitor == monitor_name.unwrap_or_else(|| &w.monitor)
-                                || !outputs.has_name(&w.monitor)
-                            {
-                                let empty = w.windows == 0;
-                                let monitor = w.monitor_id;
-
-                                let color = monitor.map(|m| {
-                                    if w.id > 0 {
-                                        workspace_colors.get(m).copied()
-                                    } else {
-                                        special_workspace_colors
-                                            .unwrap_or(workspace_colors)
-                                            .get(m)
-                                            .copied()
-                                    }
-                                });
-
-                                Some(
-                                    button(
-                                        container(
-                                            if w.id < 0 {
-                                                text(w.name.as_str())
-                                            } else {
-                                                text(w.id)
-                                            }
-                                            .size(10),
-                                        )
-                                        .align_x(alignment::Horizontal::Center)
-                                        .align_y(alignment::Vertical::Center),
-                                    )
-                                    .style(workspace_button_style(empty, color))
-                                    .padding(if w.id < 0 {
-                                        if w.active { [0, 16] } else { [0, 8] }
-                                    } else {
-                                        [0, 0]
-                                    })
-                                    .on_press(if w.id > 0 {
-                                        Message::ChangeWorkspace(w.id)
-                                    } else {
-                                        Message::ToggleSpecialWorkspace(w.id)
-                                    })
-                                    .width(if w.id < 0 {
-                                        Length::Shrink
-                                    } else if w.active {
-                                        Length::Fixed(32.)
-                                    } else {
-                                        Length::Fixed(16.)
-                                    })
-                                    .height(16)
-                                    .into(),
-                                )
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<Element<'_, _, _>>>(),
-                )
-                .padding([2, 0])
-                .spacing(4),
+                Row::with_children(workspaces)
+                    .push_maybe(self.niri_scratchpad_indicator(config))
+                    .push_maybe(self.niri_overview_button(config))
+                    .padding([2, 0])
+                    .spacing(4),
             )
             .map(app::Message::Workspaces),
             None,
@@ -272,6 +456,14 @@ impl Module for Workspaces {
         &self,
         config: Self::SubscriptionData<'_>,
     ) -> Option<Subscription<app::Message>> {
+        if NiriService::is_available() {
+            return Some(
+                NiriService::subscribe()
+                    .map(inspector::record)
+                    .map(|event| app::Message::Workspaces(Message::Niri(event))),
+            );
+        }
+
         let id = TypeId::of::<Self>();
         let enable_workspace_filling = config.enable_workspace_filling;
 
@@ -2,17 +2,18 @@ use iced::{
     Task,
     platform_specific::shell::commands::layer_surface::{
         Anchor, KeyboardInteractivity, Layer, destroy_layer_surface, get_layer_surface, set_anchor,
-        set_exclusive_zone, set_size,
+        set_exclusive_zone, set_margin, set_size,
+    },
+    runtime::platform_specific::wayland::layer_surface::{
+        IcedMargin, IcedOutput, SctkLayerSurfaceSettings,
     },
-    runtime::platform_specific::wayland::layer_surface::{IcedOutput, SctkLayerSurfaceSettings},
     window::Id,
 };
 use log::debug;
 use wayland_client::protocol::wl_output::WlOutput;
 
 use crate::{
-    HEIGHT,
-    config::{self, AppearanceStyle, Position},
+    config::{self, AppearanceStyle, BarConfig, Position},
     menu::{Menu, MenuType},
     position_button::ButtonUIRef,
 };
@@ -22,6 +23,7 @@ struct ShellInfo {
     id: Id,
     position: Position,
     style: AppearanceStyle,
+    bar: BarConfig,
     menu: Menu,
 }
 
@@ -36,9 +38,10 @@ pub enum HasOutput<'a> {
 impl Outputs {
     pub fn new<Message: 'static>(
         style: AppearanceStyle,
+        bar: BarConfig,
         position: Position,
     ) -> (Self, Task<Message>) {
-        let (id, menu_id, task) = Self::create_output_layers(style, None, position);
+        let (id, menu_id, task) = Self::create_output_layers(style, bar, None, position);
 
         (
             Self(vec![(
@@ -48,6 +51,7 @@ impl Outputs {
                     menu: Menu::new(menu_id),
                     position,
                     style,
+                    bar,
                 }),
                 None,
             )]),
@@ -55,21 +59,23 @@ impl Outputs {
         )
     }
 
-    fn get_height(style: AppearanceStyle) -> u32 {
-        HEIGHT
-            - match style {
-                AppearanceStyle::Solid | AppearanceStyle::Gradient => 8,
-                AppearanceStyle::Islands => 0,
-            }
+    fn get_margin(bar: &BarConfig) -> IcedMargin {
+        IcedMargin {
+            top: bar.margin.top as i32,
+            right: bar.margin.right as i32,
+            bottom: bar.margin.bottom as i32,
+            left: bar.margin.left as i32,
+        }
     }
 
     fn create_output_layers<Message: 'static>(
         style: AppearanceStyle,
+        bar: BarConfig,
         wl_output: Option<WlOutput>,
         position: Position,
     ) -> (Id, Id, Task<Message>) {
         let id = Id::unique();
-        let height = Self::get_height(style);
+        let height = bar.height_for(style);
 
         let task = get_layer_surface(SctkLayerSurfaceSettings {
             id,
@@ -78,7 +84,8 @@ impl Outputs {
             layer: Layer::Bottom,
             pointer_interactivity: true,
             keyboard_interactivity: KeyboardInteractivity::None,
-            exclusive_zone: height as i32,
+            exclusive_zone: bar.exclusive_zone_for(style, position),
+            margin: Self::get_margin(&bar),
             output: wl_output.clone().map_or(IcedOutput::Active, |wl_output| {
                 IcedOutput::Output(wl_output)
             }),
@@ -137,7 +144,7 @@ impl Outputs {
     pub fn get_monitor_name(&self, id: Id) -> Option<&str> {
         self.0.iter().find_map(|(name, info, _)| {
             if let Some(info) = info {
-                if info.id == id {
+                if info.id == id || info.menu.id == id {
                     name.as_ref().map(|n| n.as_str())
                 } else {
                     None
@@ -157,6 +164,7 @@ impl Outputs {
     pub fn add<Message: 'static>(
         &mut self,
         style: AppearanceStyle,
+        bar: BarConfig,
         request_outputs: &config::Outputs,
         position: Position,
         name: &str,
@@ -168,7 +176,7 @@ impl Outputs {
             debug!("Found target output, creating a new layer surface");
 
             let (id, menu_id, task) =
-                Self::create_output_layers(style, Some(wl_output.clone()), position);
+                Self::create_output_layers(style, bar, Some(wl_output.clone()), position);
 
             let destroy_task = match self
                 .0
@@ -198,6 +206,7 @@ impl Outputs {
                     menu: Menu::new(menu_id),
                     position,
                     style,
+                    bar,
                 }),
                 Some(wl_output),
             ));
@@ -235,6 +244,7 @@ impl Outputs {
     pub fn remove<Message: 'static>(
         &mut self,
         style: AppearanceStyle,
+        bar: BarConfig,
         position: Position,
         wl_output: WlOutput,
     ) -> Task<Message> {
@@ -263,7 +273,8 @@ impl Outputs {
                 if !self.0.iter().any(|(_, shell_info, _)| shell_info.is_some()) {
                     debug!("No outputs left, creating a fallback layer surface");
 
-                    let (id, menu_id, task) = Self::create_output_layers(style, None, position);
+                    let (id, menu_id, task) =
+                        Self::create_output_layers(style, bar, None, position);
 
                     self.0.push((
                         None,
@@ -272,6 +283,7 @@ impl Outputs {
                             menu: Menu::new(menu_id),
                             position,
                             style,
+                            bar,
                         }),
                         None,
                     ));
@@ -288,8 +300,9 @@ impl Outputs {
     pub fn sync<Message: 'static>(
         &mut self,
         style: AppearanceStyle,
+        bar: BarConfig,
         request_outputs: &config::Outputs,
-        position: Position,
+        position_for: impl Fn(Option<&str>) -> Position,
     ) -> Task<Message> {
         debug!(
             "Syncing outputs: {:?}, request_outputs: {:?}",
@@ -334,8 +347,9 @@ impl Outputs {
                 if let Some(name) = name {
                     tasks.push(self.add(
                         style,
+                        bar,
                         request_outputs,
-                        position,
+                        position_for(Some(name.as_str())),
                         name.as_str(),
                         wl_output,
                     ));
@@ -344,13 +358,14 @@ impl Outputs {
         }
 
         for wl_output in to_remove {
-            tasks.push(self.remove(style, position, wl_output));
+            tasks.push(self.remove(style, bar, position_for(None), wl_output));
         }
 
-        for shell_info in self.0.iter_mut().filter_map(|(_, shell_info, _)| {
+        for (shell_info, position) in self.0.iter_mut().filter_map(|(name, shell_info, _)| {
+            let position = position_for(name.as_deref());
             if let Some(shell_info) = shell_info {
                 if shell_info.position != position {
-                    Some(shell_info)
+                    Some((shell_info, position))
                 } else {
                     None
                 }
@@ -371,11 +386,17 @@ impl Outputs {
                 } | Anchor::LEFT
                     | Anchor::RIGHT,
             ));
+            tasks.push(set_exclusive_zone(
+                shell_info.id,
+                shell_info
+                    .bar
+                    .exclusive_zone_for(shell_info.style, position),
+            ));
         }
 
         for shell_info in self.0.iter_mut().filter_map(|(_, shell_info, _)| {
             if let Some(shell_info) = shell_info {
-                if shell_info.style != style {
+                if shell_info.style != style || shell_info.bar != bar {
                     Some(shell_info)
                 } else {
                     None
@@ -385,14 +406,25 @@ impl Outputs {
             }
         }) {
             debug!(
-                "Change style for output: {:?}, new style {:?}",
-                shell_info.id, style
+                "Change style for output: {:?}, new style {:?}, bar {:?}",
+                shell_info.id, style, bar
             );
             shell_info.style = style;
-            let height = Self::get_height(style);
+            shell_info.bar = bar;
+            let margin = Self::get_margin(&bar);
             tasks.push(Task::batch(vec![
-                set_size(shell_info.id, None, Some(height)),
-                set_exclusive_zone(shell_info.id, height as i32),
+                set_size(shell_info.id, None, Some(bar.height_for(style))),
+                set_exclusive_zone(
+                    shell_info.id,
+                    bar.exclusive_zone_for(style, shell_info.position),
+                ),
+                set_margin(
+                    shell_info.id,
+                    margin.top,
+                    margin.right,
+                    margin.bottom,
+                    margin.left,
+                ),
             ]));
         }
 
@@ -491,6 +523,35 @@ impl Outputs {
         }
     }
 
+    // the menus of the given type open on any output
+    fn open_menus(&self, menu_type: MenuType) -> impl Iterator<Item = &Menu> {
+        self.0
+            .iter()
+            .filter_map(|(_, shell_info, _)| shell_info.as_ref())
+            .map(|shell_info| &shell_info.menu)
+            .filter(move |menu| {
+                menu.menu_info
+                    .as_ref()
+                    .is_some_and(|(current_type, _)| *current_type == menu_type)
+            })
+    }
+
+    pub fn request_keyboard_if<Message: 'static>(&self, menu_type: MenuType) -> Task<Message> {
+        Task::batch(
+            self.open_menus(menu_type)
+                .map(|menu| menu.request_keyboard())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn release_keyboard_if<Message: 'static>(&self, menu_type: MenuType) -> Task<Message> {
+        Task::batch(
+            self.open_menus(menu_type)
+                .map(|menu| menu.release_keyboard())
+                .collect::<Vec<_>>(),
+        )
+    }
+
     pub fn release_keyboard<Message: 'static>(&self, id: Id) -> Task<Message> {
         match self.0.iter().find(|(_, shell_info, _)| {
             shell_info.as_ref().map(|shell_info| shell_info.id) == Some(id)
@@ -28,6 +28,7 @@ where
 {
     content: Element<'a, Message, Theme, Renderer>,
     on_press: Option<OnPress<'a, Message>>,
+    on_right_press: Option<Box<dyn Fn(ButtonUIRef) -> Message + 'a>>,
     id: Id,
     width: Length,
     height: Length,
@@ -49,6 +50,7 @@ where
             content,
             id: Id::unique(),
             on_press: None,
+            on_right_press: None,
             width: size.width.fluid(),
             height: size.height.fluid(),
             padding: DEFAULT_PADDING,
@@ -91,6 +93,16 @@ where
         self
     }
 
+    /// Sets the message produced when the [`Button`] is right clicked,
+    /// it doesn't enable the left click.
+    pub fn on_right_press_with_position(
+        mut self,
+        on_right_press: impl Fn(ButtonUIRef) -> Message + 'a,
+    ) -> Self {
+        self.on_right_press = Some(Box::new(on_right_press));
+        self
+    }
+
     /// Sets whether the contents of the [`Button`] should be clipped on
     /// overflow.
     pub fn clip(mut self, clip: bool) -> Self {
@@ -221,6 +233,22 @@ where
                     }
                 }
             }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                if let Some(on_right_press) = self.on_right_press.as_ref() {
+                    if cursor.is_over(layout.bounds()) {
+                        let ui_data = ButtonUIRef {
+                            position: Point::new(
+                                layout.bounds().width / 2. + layout.position().x,
+                                layout.bounds().height / 2. + layout.position().y,
+                            ),
+                            viewport: (viewport.width, viewport.height),
+                        };
+                        shell.publish(on_right_press(ui_data));
+
+                        return event::Status::Captured;
+                    }
+                }
+            }
             Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
             | Event::Touch(touch::Event::FingerLifted { .. }) => {
                 if let Some(on_press) = self.on_press.as_ref() {
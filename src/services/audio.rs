@@ -1,4 +1,4 @@
-use super::{ReadOnlyService, Service, ServiceEvent};
+use super::{ListenerState, ReadOnlyService, Retry, Service, ServiceEvent};
 use crate::components::icons::Icons;
 use iced::{
     Subscription, Task,
@@ -9,7 +9,7 @@ use libpulse_binding::{
     callbacks::ListResult,
     context::{
         self, Context, FlagSet,
-        introspect::{Introspector, SinkInfo, SourceInfo},
+        introspect::{Introspector, SinkInfo, SinkInputInfo, SourceInfo},
         subscribe::InterestMaskSet,
     },
     def::{DevicePortType, PortAvailable, SinkState, SourceState},
@@ -38,6 +38,12 @@ pub struct Device {
     pub ports: Vec<Port>,
 }
 
+impl Device {
+    pub fn has_active_port(&self) -> bool {
+        self.ports.is_empty() || self.ports.iter().any(|p| p.active)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Port {
     pub name: String,
@@ -65,6 +71,15 @@ impl DeviceType {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct AppStream {
+    pub id: u32,
+    pub name: String,
+    pub volume: f32,
+    pub muted: bool,
+    channels: u8,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct ServerInfo {
     pub default_sink: String,
@@ -97,7 +112,7 @@ pub trait Sinks {
 impl Sinks for Vec<Device> {
     fn get_icon(&self, default_sink: &str) -> Icons {
         match self.iter().find_map(|s| {
-            if s.ports.iter().any(|p| p.active) && s.name == default_sink {
+            if s.has_active_port() && s.name == default_sink {
                 Some((s.is_mute, s.volume.get_volume()))
             } else {
                 None
@@ -125,8 +140,23 @@ pub struct AudioData {
     pub server_info: ServerInfo,
     pub sinks: Vec<Device>,
     pub sources: Vec<Device>,
+    pub streams: Vec<AppStream>,
     pub cur_sink_volume: i32,
     pub cur_source_volume: i32,
+    pub source_muted: bool,
+    pub source_volume: Option<f64>,
+}
+
+impl AudioData {
+    fn update_default_source_state(&mut self) {
+        let source = self
+            .sources
+            .iter()
+            .find(|source| source.name == self.server_info.default_source);
+
+        self.source_muted = source.is_some_and(|source| source.is_mute);
+        self.source_volume = source.map(|source| source.volume.get_volume() * 100.);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -157,6 +187,15 @@ struct PulseAudioServerHandle {
 }
 
 impl AudioService {
+    pub fn set_default_source_mute(&self, muted: bool) -> anyhow::Result<()> {
+        self.commander
+            .send(PulseAudioCommand::SourceMute(
+                self.data.server_info.default_source.clone(),
+                muted,
+            ))
+            .map_err(|_| anyhow::anyhow!("PulseAudio commander is not running"))
+    }
+
     async fn init_service() -> anyhow::Result<PulseAudioServerHandle> {
         PulseAudioServer::start().await
     }
@@ -171,8 +210,11 @@ impl AudioService {
                                 server_info: ServerInfo::default(),
                                 sinks: Vec::new(),
                                 sources: Vec::new(),
+                                streams: Vec::new(),
                                 cur_sink_volume: 0,
                                 cur_source_volume: 0,
+                                source_muted: false,
+                                source_volume: None,
                             },
                             commander: handle.sender.clone(),
                         }))
@@ -210,6 +252,13 @@ impl AudioService {
 
                     State::Active(handle)
                 }
+                Some(PulseAudioServerEvent::Streams(streams)) => {
+                    let _ = output
+                        .send(ServiceEvent::Update(AudioEvent::StreamUpdate(streams)))
+                        .await;
+
+                    State::Active(handle)
+                }
                 None => State::Active(handle),
             },
             State::Error => {
@@ -227,13 +276,10 @@ pub enum AudioEvent {
     Sinks(Vec<Device>),
     Sources(Vec<Device>),
     ServerInfo(ServerInfo),
+    StreamUpdate(Vec<AppStream>),
 }
 
-enum State {
-    Init,
-    Active(PulseAudioServerHandle),
-    Error,
-}
+type State = ListenerState<PulseAudioServerHandle>;
 
 impl ReadOnlyService for AudioService {
     type UpdateEvent = AudioEvent;
@@ -247,11 +293,7 @@ impl ReadOnlyService for AudioService {
                     .sinks
                     .iter()
                     .find_map(|sink| {
-                        if sink
-                            .ports
-                            .iter()
-                            .any(|p| p.active && sink.name == self.server_info.default_sink)
-                        {
+                        if sink.has_active_port() && sink.name == self.server_info.default_sink {
                             Some(if sink.is_mute {
                                 0.
                             } else {
@@ -270,10 +312,8 @@ impl ReadOnlyService for AudioService {
                     .sources
                     .iter()
                     .find_map(|source| {
-                        if source
-                            .ports
-                            .iter()
-                            .any(|p| p.active && source.name == self.server_info.default_source)
+                        if source.has_active_port()
+                            && source.name == self.server_info.default_source
                         {
                             Some(if source.is_mute {
                                 0.
@@ -286,6 +326,7 @@ impl ReadOnlyService for AudioService {
                     })
                     .unwrap_or_default()
                     * 100.) as i32;
+                self.data.update_default_source_state();
             }
             AudioEvent::ServerInfo(info) => {
                 self.data.server_info = info;
@@ -293,11 +334,7 @@ impl ReadOnlyService for AudioService {
                     .sinks
                     .iter()
                     .find_map(|sink| {
-                        if sink
-                            .ports
-                            .iter()
-                            .any(|p| p.active && sink.name == self.server_info.default_sink)
-                        {
+                        if sink.has_active_port() && sink.name == self.server_info.default_sink {
                             Some(if sink.is_mute {
                                 0.
                             } else {
@@ -313,10 +350,8 @@ impl ReadOnlyService for AudioService {
                     .sources
                     .iter()
                     .find_map(|source| {
-                        if source
-                            .ports
-                            .iter()
-                            .any(|p| p.active && source.name == self.server_info.default_source)
+                        if source.has_active_port()
+                            && source.name == self.server_info.default_source
                         {
                             Some(if source.is_mute {
                                 0.
@@ -329,6 +364,10 @@ impl ReadOnlyService for AudioService {
                     })
                     .unwrap_or_default()
                     * 100.) as i32;
+                self.data.update_default_source_state();
+            }
+            AudioEvent::StreamUpdate(streams) => {
+                self.data.streams = streams;
             }
         }
     }
@@ -339,11 +378,10 @@ impl ReadOnlyService for AudioService {
         Subscription::run_with_id(
             id,
             channel(100, async |mut output| {
-                let mut state = State::Init;
-
-                loop {
-                    state = AudioService::start_listening(state, &mut output).await;
-                }
+                Retry::run(std::any::type_name::<AudioService>(), async |state| {
+                    AudioService::start_listening(state, &mut output).await
+                })
+                .await;
             }),
         )
     }
@@ -356,6 +394,8 @@ pub enum AudioCommand {
     SourceVolume(i32),
     DefaultSink(String, String),
     DefaultSource(String, String),
+    SetStreamVolume { id: u32, volume: f32 },
+    MuteStream { id: u32, muted: bool },
 }
 
 impl Service for AudioService {
@@ -377,16 +417,8 @@ impl Service for AudioService {
                 }
             }
             AudioCommand::ToggleSourceMute => {
-                if let Some(source) = self
-                    .data
-                    .sources
-                    .iter()
-                    .find(|source| source.name == self.data.server_info.default_source)
-                {
-                    let _ = self.commander.send(PulseAudioCommand::SourceMute(
-                        source.name.clone(),
-                        !source.is_mute,
-                    ));
+                if let Err(e) = self.set_default_source_mute(!self.data.source_muted) {
+                    error!("Failed to toggle source mute: {}", e);
                 }
             }
             AudioCommand::SinkVolume(volume) => {
@@ -428,6 +460,26 @@ impl Service for AudioService {
                     .commander
                     .send(PulseAudioCommand::DefaultSource(name, port));
             }
+            AudioCommand::SetStreamVolume { id, volume } => {
+                if let Some(stream) = self.data.streams.iter_mut().find(|s| s.id == id) {
+                    let mut channel_volumes = ChannelVolumes::default();
+                    channel_volumes.set(stream.channels, libpulse_binding::volume::Volume::NORMAL);
+
+                    if let Some(channel_volumes) =
+                        channel_volumes.scale_volume(volume as f64 / 100.)
+                    {
+                        stream.volume = volume;
+                        let _ = self
+                            .commander
+                            .send(PulseAudioCommand::StreamVolume(id, *channel_volumes));
+                    }
+                }
+            }
+            AudioCommand::MuteStream { id, muted } => {
+                let _ = self
+                    .commander
+                    .send(PulseAudioCommand::StreamMute(id, muted));
+            }
         }
 
         iced::Task::none()
@@ -439,6 +491,7 @@ enum PulseAudioServerEvent {
     Sinks(Vec<Device>),
     Sources(Vec<Device>),
     ServerInfo(ServerInfo),
+    Streams(Vec<AppStream>),
 }
 
 enum PulseAudioCommand {
@@ -448,6 +501,8 @@ enum PulseAudioCommand {
     SourceVolume(String, ChannelVolumes),
     DefaultSink(String, String),
     DefaultSource(String, String),
+    StreamVolume(u32, ChannelVolumes),
+    StreamMute(u32, bool),
 }
 
 struct PulseAudioServer {
@@ -529,7 +584,8 @@ impl PulseAudioServer {
                     server.context.subscribe(
                         InterestMaskSet::SERVER
                             .union(InterestMaskSet::SINK)
-                            .union(InterestMaskSet::SOURCE),
+                            .union(InterestMaskSet::SOURCE)
+                            .union(InterestMaskSet::SINK_INPUT),
                         |res| {
                             if !res {
                                 error!("Audio subscription failed!");
@@ -580,6 +636,24 @@ impl PulseAudioServer {
                         }
                     };
 
+                    let streams = Rc::new(RefCell::new(Vec::new()));
+                    if let Err(e) =
+                        server.wait_for_response(server.introspector.get_sink_input_info_list({
+                            let tx = from_server_tx.clone();
+                            let streams = streams.clone();
+                            move |info| {
+                                Self::populate_and_send_streams(
+                                    info,
+                                    &tx,
+                                    &mut streams.borrow_mut(),
+                                );
+                            }
+                        }))
+                    {
+                        error!("Failed to get sink input info: {}", e);
+                        let _ = from_server_tx.send(PulseAudioServerEvent::Error);
+                    };
+
                     let introspector = server.context.introspect();
                     server.context.set_subscribe_callback(Some(Box::new(
                         move |_facility, _operation, _idx| {
@@ -614,6 +688,18 @@ impl PulseAudioServer {
                                     );
                                 }
                             });
+                            introspector.get_sink_input_info_list({
+                                let tx = from_server_tx.clone();
+                                let streams = streams.clone();
+
+                                move |info| {
+                                    Self::populate_and_send_streams(
+                                        info,
+                                        &tx,
+                                        &mut streams.borrow_mut(),
+                                    );
+                                }
+                            });
                         },
                     )));
 
@@ -670,6 +756,12 @@ impl PulseAudioServer {
                                 Some(PulseAudioCommand::DefaultSource(name, port)) => {
                                     let _ = server.set_default_source(&name, &port);
                                 }
+                                Some(PulseAudioCommand::StreamVolume(id, volume)) => {
+                                    let _ = server.set_stream_volume(id, &volume);
+                                }
+                                Some(PulseAudioCommand::StreamMute(id, mute)) => {
+                                    let _ = server.set_stream_mute(id, mute);
+                                }
                                 None => {}
                             }
                         }
@@ -722,10 +814,11 @@ impl PulseAudioServer {
     ) {
         match info {
             ListResult::Item(data) => {
-                if data
-                    .ports
-                    .iter()
-                    .any(|port| port.available != PortAvailable::No)
+                if data.ports.is_empty()
+                    || data
+                        .ports
+                        .iter()
+                        .any(|port| port.available != PortAvailable::No)
                 {
                     debug!("Adding sink data: {:?}", data);
                     sinks.push(data.into());
@@ -768,6 +861,27 @@ impl PulseAudioServer {
         }
     }
 
+    fn populate_and_send_streams(
+        info: ListResult<&SinkInputInfo<'_>>,
+        tx: &UnboundedSender<PulseAudioServerEvent>,
+        streams: &mut Vec<AppStream>,
+    ) {
+        match info {
+            ListResult::Item(data) => {
+                if data.has_volume {
+                    debug!("Adding stream data: {:?}", data);
+                    streams.push(data.into());
+                }
+            }
+            ListResult::End => {
+                debug!("New streams list {:?}", streams);
+                let _ = tx.send(PulseAudioServerEvent::Streams(streams.clone()));
+                streams.clear();
+            }
+            ListResult::Error => error!("Error during streams list population"),
+        }
+    }
+
     fn set_sink_mute(&mut self, name: &str, mute: bool) -> anyhow::Result<()> {
         let op = self.introspector.set_sink_mute_by_name(name, mute, None);
 
@@ -796,10 +910,26 @@ impl PulseAudioServer {
         self.wait_for_response(op)
     }
 
+    fn set_stream_volume(&mut self, id: u32, volume: &ChannelVolumes) -> anyhow::Result<()> {
+        let op = self.introspector.set_sink_input_volume(id, volume, None);
+
+        self.wait_for_response(op)
+    }
+
+    fn set_stream_mute(&mut self, id: u32, mute: bool) -> anyhow::Result<()> {
+        let op = self.introspector.set_sink_input_mute(id, mute, None);
+
+        self.wait_for_response(op)
+    }
+
     fn set_default_sink(&mut self, name: &str, port: &str) -> anyhow::Result<()> {
         let op = self.context.set_default_sink(name, |_| {});
         self.wait_for_response(op)?;
 
+        if port.is_empty() {
+            return Ok(());
+        }
+
         let op = self.introspector.set_sink_port_by_name(name, port, None);
         self.wait_for_response(op)
     }
@@ -808,6 +938,10 @@ impl PulseAudioServer {
         let op = self.context.set_default_source(name, |_| {});
         self.wait_for_response(op)?;
 
+        if port.is_empty() {
+            return Ok(());
+        }
+
         let op = self.introspector.set_source_port_by_name(name, port, None);
         self.wait_for_response(op)
     }
@@ -915,3 +1049,19 @@ impl From<&SourceInfo<'_>> for Device {
         }
     }
 }
+
+impl From<&SinkInputInfo<'_>> for AppStream {
+    fn from(value: &SinkInputInfo<'_>) -> Self {
+        Self {
+            id: value.index,
+            name: value
+                .proplist
+                .get_str(APPLICATION_NAME)
+                .or_else(|| value.name.as_ref().map(|n| n.to_string()))
+                .unwrap_or_default(),
+            volume: (value.volume.get_volume() * 100.) as f32,
+            muted: value.mute,
+            channels: value.volume.len(),
+        }
+    }
+}
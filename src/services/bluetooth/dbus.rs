@@ -5,7 +5,8 @@ use zbus::{
     zvariant::{OwnedObjectPath, OwnedValue},
 };
 
-use super::{BluetoothDevice, BluetoothState};
+use super::{BluetoothClass, BluetoothDevice, BluetoothState};
+use crate::services::upower::dbus::{DeviceProxy as UPowerDeviceProxy, UPowerDbus};
 
 type ManagedObjects = HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>;
 
@@ -47,6 +48,59 @@ impl BluetoothDbus<'_> {
         Ok(())
     }
 
+    pub async fn discovering(&self) -> zbus::Result<bool> {
+        match &self.adapter {
+            Some(adapter) => adapter.discovering().await,
+            _ => Ok(false),
+        }
+    }
+
+    pub async fn set_discovery(&self, discovery: bool) -> zbus::Result<()> {
+        if let Some(adapter) = &self.adapter {
+            if discovery {
+                adapter.start_discovery().await?;
+            } else {
+                adapter.stop_discovery().await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn pair(&self, device_path: &OwnedObjectPath) -> anyhow::Result<()> {
+        let device = DeviceProxy::builder(self.bluez.inner().connection())
+            .path(device_path)?
+            .build()
+            .await?;
+
+        device.pair().await?;
+        device.set_trusted(true).await?;
+
+        Ok(())
+    }
+
+    pub async fn connect(&self, device_path: &OwnedObjectPath) -> anyhow::Result<()> {
+        let device = DeviceProxy::builder(self.bluez.inner().connection())
+            .path(device_path)?
+            .build()
+            .await?;
+
+        device.connect().await?;
+
+        Ok(())
+    }
+
+    pub async fn disconnect(&self, device_path: &OwnedObjectPath) -> anyhow::Result<()> {
+        let device = DeviceProxy::builder(self.bluez.inner().connection())
+            .path(device_path)?
+            .build()
+            .await?;
+
+        device.disconnect().await?;
+
+        Ok(())
+    }
+
     pub async fn state(&self) -> zbus::Result<BluetoothState> {
         match &self.adapter {
             Some(adapter) => {
@@ -82,11 +136,24 @@ impl BluetoothDbus<'_> {
                 .build()
                 .await?;
 
-            let name = device.name().await?;
+            // discovered devices without a name are not useful to show
+            let Ok(name) = device.name().await else {
+                continue;
+            };
             let connected = device.connected().await?;
+            let paired = device.paired().await.unwrap_or_default();
+            let address = device.address().await.unwrap_or_default();
 
-            if connected {
-                let battery = if has_battery {
+            let battery = if connected {
+                let upower_battery = self.upower_battery(&device_path, &device).await;
+
+                if let Some(upower_battery) = upower_battery {
+                    upower_battery
+                        .percentage()
+                        .await
+                        .ok()
+                        .map(|percentage| percentage as u8)
+                } else if has_battery {
                     let battery_proxy = BatteryProxy::builder(self.bluez.inner().connection())
                         .path(&device_path)?
                         .build()
@@ -95,18 +162,68 @@ impl BluetoothDbus<'_> {
                     Some(battery_proxy.percentage().await?)
                 } else {
                     None
-                };
+                }
+            } else {
+                None
+            };
 
-                devices.push(BluetoothDevice {
-                    name,
-                    battery,
-                    path: device_path,
-                });
-            }
+            let class = device
+                .icon()
+                .await
+                .map(|icon| BluetoothClass::from(icon.as_str()))
+                .unwrap_or(BluetoothClass::Unknown);
+
+            devices.push(BluetoothDevice {
+                name,
+                battery,
+                path: device_path,
+                class,
+                address,
+                paired,
+                connected,
+                profiles: Vec::new(),
+                active_profile: None,
+                connecting: false,
+            });
         }
 
         Ok(devices)
     }
+
+    pub async fn upower_batteries(&self) -> anyhow::Result<Vec<UPowerDeviceProxy<'static>>> {
+        let devices = self.devices().await?;
+
+        let mut batteries = Vec::with_capacity(devices.len());
+        for d in devices.into_iter().filter(|d| d.connected) {
+            let device = DeviceProxy::builder(self.bluez.inner().connection())
+                .path(d.path.clone())?
+                .build()
+                .await?;
+
+            if let Some(battery) = self.upower_battery(&d.path, &device).await {
+                batteries.push(battery);
+            }
+        }
+
+        Ok(batteries)
+    }
+
+    async fn upower_battery(
+        &self,
+        device_path: &OwnedObjectPath,
+        device: &DeviceProxy<'_>,
+    ) -> Option<UPowerDeviceProxy<'static>> {
+        let address = device.address().await.ok()?;
+        let upower = UPowerDbus::new(self.bluez.inner().connection())
+            .await
+            .ok()?;
+
+        upower
+            .get_bluetooth_device(device_path.as_str(), &address)
+            .await
+            .ok()
+            .flatten()
+    }
 }
 
 #[proxy(
@@ -135,6 +252,13 @@ pub trait Adapter {
 
     #[zbus(property)]
     fn set_powered(&self, value: bool) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn discovering(&self) -> zbus::Result<bool>;
+
+    fn start_discovery(&self) -> zbus::Result<()>;
+
+    fn stop_discovery(&self) -> zbus::Result<()>;
 }
 
 #[proxy(default_service = "org.bluez", interface = "org.bluez.Device1")]
@@ -144,6 +268,24 @@ trait Device {
 
     #[zbus(property)]
     fn connected(&self) -> zbus::Result<bool>;
+
+    #[zbus(property)]
+    fn address(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn icon(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn paired(&self) -> zbus::Result<bool>;
+
+    #[zbus(property)]
+    fn set_trusted(&self, value: bool) -> zbus::Result<()>;
+
+    fn pair(&self) -> zbus::Result<()>;
+
+    fn connect(&self) -> zbus::Result<()>;
+
+    fn disconnect(&self) -> zbus::Result<()>;
 }
 
 #[proxy(default_service = "org.bluez", interface = "org.bluez.Battery1")]
@@ -1,4 +1,5 @@
-use super::{ReadOnlyService, Service, ServiceEvent};
+use super::{ListenerState, ReadOnlyService, Retry, Service, ServiceEvent};
+use crate::components::icons::Icons;
 use dbus::{BatteryProxy, BluetoothDbus};
 use iced::{
     Subscription, Task,
@@ -12,7 +13,7 @@ use iced::{
 };
 use inotify::{Inotify, WatchMask};
 use log::{debug, error, info};
-use std::{any::TypeId, ops::Deref};
+use std::{any::TypeId, collections::HashMap, ops::Deref};
 use tokio::process::Command;
 use zbus::zvariant::OwnedObjectPath;
 
@@ -25,17 +26,64 @@ pub enum BluetoothState {
     Inactive,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BluetoothClass {
+    Headphones,
+    Headset,
+    Keyboard,
+    Mouse,
+    Phone,
+    Speaker,
+    Unknown,
+}
+
+impl From<&str> for BluetoothClass {
+    fn from(icon: &str) -> Self {
+        match icon {
+            "audio-headphones" => BluetoothClass::Headphones,
+            "audio-headset" => BluetoothClass::Headset,
+            "input-keyboard" => BluetoothClass::Keyboard,
+            "input-mouse" | "input-tablet" => BluetoothClass::Mouse,
+            "phone" => BluetoothClass::Phone,
+            "audio-card" | "audio-speakers" => BluetoothClass::Speaker,
+            _ => BluetoothClass::Unknown,
+        }
+    }
+}
+
+impl BluetoothClass {
+    pub fn get_icon(&self) -> Icons {
+        match self {
+            BluetoothClass::Headphones => Icons::Headphones1,
+            BluetoothClass::Headset => Icons::Headset,
+            BluetoothClass::Keyboard => Icons::Keyboard,
+            BluetoothClass::Mouse => Icons::Mouse,
+            BluetoothClass::Phone => Icons::Phone,
+            BluetoothClass::Speaker => Icons::Speaker3,
+            BluetoothClass::Unknown => Icons::Bluetooth,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BluetoothDevice {
     pub name: String,
     pub battery: Option<u8>,
     pub path: OwnedObjectPath,
+    pub class: BluetoothClass,
+    pub address: String,
+    pub paired: bool,
+    pub connected: bool,
+    pub profiles: Vec<String>,
+    pub active_profile: Option<String>,
+    pub connecting: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct BluetoothData {
     pub state: BluetoothState,
     pub devices: Vec<BluetoothDevice>,
+    pub discovering: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -55,13 +103,15 @@ impl Deref for BluetoothService {
 #[derive(Debug, Clone)]
 pub enum BluetoothCommand {
     Toggle,
+    StartDiscovery,
+    StopDiscovery,
+    Pair(OwnedObjectPath),
+    Connect(OwnedObjectPath),
+    Disconnect(OwnedObjectPath),
+    SetProfile { address: String, profile: String },
 }
 
-enum State {
-    Init,
-    Active(zbus::Connection),
-    Error,
-}
+type State = ListenerState<zbus::Connection>;
 
 impl BluetoothService {
     async fn initialize_data(conn: &zbus::Connection) -> anyhow::Result<BluetoothData> {
@@ -75,9 +125,25 @@ impl BluetoothService {
             BluetoothState::Active if rfkill_soft_block => BluetoothState::Inactive,
             state => state,
         };
-        let devices = bluetooth.devices().await?;
+        let mut devices = bluetooth.devices().await?;
+        let discovering = bluetooth.discovering().await.unwrap_or_default();
+
+        if devices.iter().any(|d| d.connected) {
+            let cards = BluetoothService::card_profiles().await.unwrap_or_default();
 
-        Ok(BluetoothData { state, devices })
+            for device in devices.iter_mut().filter(|d| d.connected) {
+                if let Some((profiles, active_profile)) = cards.get(&card_name(&device.address)) {
+                    device.profiles = profiles.clone();
+                    device.active_profile = active_profile.clone();
+                }
+            }
+        }
+
+        Ok(BluetoothData {
+            state,
+            devices,
+            discovering,
+        })
     }
 
     async fn events(conn: &zbus::Connection) -> anyhow::Result<impl Stream<Item = ()> + use<>> {
@@ -100,19 +166,42 @@ impl BluetoothService {
         let combined = match bluetooth.adapter.as_ref() {
             Some(adapter) => {
                 let powered = adapter.receive_powered_changed().await.map(|_| {});
+                let discovering = adapter.receive_discovering_changed().await.map(|_| {});
                 let rfkill = BluetoothService::listen_rfkill_soft_block_changes().await?;
                 let devices = bluetooth.devices().await?;
 
                 let mut batteries = Vec::with_capacity(devices.len());
+                for battery in bluetooth.upower_batteries().await? {
+                    batteries.push(
+                        battery
+                            .receive_percentage_changed()
+                            .await
+                            .map(|_| {})
+                            .boxed(),
+                    );
+                }
                 for device in devices {
                     let battery = BatteryProxy::builder(bluetooth.bluez.inner().connection())
                         .path(device.path)?
                         .build()
                         .await?;
-                    batteries.push(battery.receive_percentage_changed().await.map(|_| {}));
+                    batteries.push(
+                        battery
+                            .receive_percentage_changed()
+                            .await
+                            .map(|_| {})
+                            .boxed(),
+                    );
                 }
 
-                stream_select!(interface_changed, powered, rfkill, select_all(batteries)).boxed()
+                stream_select!(
+                    interface_changed,
+                    powered,
+                    discovering,
+                    rfkill,
+                    select_all(batteries)
+                )
+                .boxed()
             }
             _ => interface_changed,
         };
@@ -201,6 +290,77 @@ impl BluetoothService {
         Ok(inotify.into_event_stream(buffer)?.map(|_| {}))
     }
 
+    async fn set_discovery(conn: &zbus::Connection, discovery: bool) -> anyhow::Result<()> {
+        let bluetooth = BluetoothDbus::new(conn).await?;
+
+        bluetooth.set_discovery(discovery).await?;
+
+        Ok(())
+    }
+
+    async fn pair(conn: &zbus::Connection, device_path: &OwnedObjectPath) -> anyhow::Result<()> {
+        let bluetooth = BluetoothDbus::new(conn).await?;
+
+        bluetooth.pair(device_path).await?;
+        bluetooth.connect(device_path).await?;
+
+        Ok(())
+    }
+
+    async fn connect(conn: &zbus::Connection, device_path: &OwnedObjectPath) -> anyhow::Result<()> {
+        let bluetooth = BluetoothDbus::new(conn).await?;
+
+        bluetooth.connect(device_path).await?;
+
+        Ok(())
+    }
+
+    async fn disconnect(
+        conn: &zbus::Connection,
+        device_path: &OwnedObjectPath,
+    ) -> anyhow::Result<()> {
+        let bluetooth = BluetoothDbus::new(conn).await?;
+
+        bluetooth.disconnect(device_path).await?;
+
+        Ok(())
+    }
+
+    fn set_connecting(&mut self, device_path: &OwnedObjectPath) {
+        if let Some(device) = self
+            .data
+            .devices
+            .iter_mut()
+            .find(|d| &d.path == device_path)
+        {
+            device.connecting = true;
+        }
+    }
+
+    async fn card_profiles() -> anyhow::Result<HashMap<String, (Vec<String>, Option<String>)>> {
+        // the labels parsed below are translated with the user locale
+        let output = Command::new("pactl")
+            .env("LC_ALL", "C")
+            .arg("list")
+            .arg("cards")
+            .output()
+            .await?;
+        let output = String::from_utf8(output.stdout)?;
+
+        Ok(parse_card_profiles(&output))
+    }
+
+    async fn set_card_profile(address: &str, profile: &str) -> anyhow::Result<()> {
+        Command::new("pactl")
+            .arg("set-card-profile")
+            .arg(card_name(address))
+            .arg(profile)
+            .output()
+            .await?;
+
+        Ok(())
+    }
+
     async fn toggle_power(conn: &zbus::Connection, power: bool) -> anyhow::Result<()> {
         let bluetooth = BluetoothDbus::new(conn).await?;
 
@@ -224,11 +384,10 @@ impl ReadOnlyService for BluetoothService {
         Subscription::run_with_id(
             id,
             channel(100, async |mut output| {
-                let mut state = State::Init;
-
-                loop {
-                    state = BluetoothService::start_listening(state, &mut output).await;
-                }
+                Retry::run(std::any::type_name::<BluetoothService>(), async |state| {
+                    BluetoothService::start_listening(state, &mut output).await
+                })
+                .await;
             }),
         )
     }
@@ -267,6 +426,212 @@ impl Service for BluetoothService {
                     )
                 }
             }
+            BluetoothCommand::StartDiscovery | BluetoothCommand::StopDiscovery => {
+                let conn = self.conn.clone();
+                let mut data = self.data.clone();
+                let discovery = matches!(command, BluetoothCommand::StartDiscovery);
+
+                Task::perform(
+                    async move {
+                        debug!("Setting bluetooth discovery to: {}", discovery);
+                        match BluetoothService::set_discovery(&conn, discovery).await {
+                            Ok(()) => data.discovering = discovery,
+                            Err(err) => error!("Failed to set bluetooth discovery: {}", err),
+                        }
+
+                        data
+                    },
+                    ServiceEvent::Update,
+                )
+            }
+            BluetoothCommand::Pair(device_path) => {
+                let conn = self.conn.clone();
+                let data = self.data.clone();
+                self.set_connecting(&device_path);
+
+                Task::perform(
+                    async move {
+                        debug!("Pairing bluetooth device: {:?}", device_path);
+                        if let Err(err) = BluetoothService::pair(&conn, &device_path).await {
+                            error!("Failed to pair bluetooth device: {}", err);
+                        }
+
+                        BluetoothService::initialize_data(&conn)
+                            .await
+                            .unwrap_or(data)
+                    },
+                    ServiceEvent::Update,
+                )
+            }
+            BluetoothCommand::SetProfile { address, profile } => {
+                let conn = self.conn.clone();
+                let data = self.data.clone();
+
+                Task::perform(
+                    async move {
+                        debug!("Setting bluetooth card profile: {} {}", address, profile);
+                        if let Err(err) =
+                            BluetoothService::set_card_profile(&address, &profile).await
+                        {
+                            error!("Failed to set bluetooth card profile: {}", err);
+                        }
+
+                        BluetoothService::initialize_data(&conn)
+                            .await
+                            .unwrap_or(data)
+                    },
+                    ServiceEvent::Update,
+                )
+            }
+            BluetoothCommand::Connect(device_path) => {
+                let conn = self.conn.clone();
+                let data = self.data.clone();
+                self.set_connecting(&device_path);
+
+                Task::perform(
+                    async move {
+                        debug!("Connecting bluetooth device: {:?}", device_path);
+                        if let Err(err) = BluetoothService::connect(&conn, &device_path).await {
+                            error!("Failed to connect bluetooth device: {}", err);
+                        }
+
+                        BluetoothService::initialize_data(&conn)
+                            .await
+                            .unwrap_or(data)
+                    },
+                    ServiceEvent::Update,
+                )
+            }
+            BluetoothCommand::Disconnect(device_path) => {
+                let conn = self.conn.clone();
+                let data = self.data.clone();
+                self.set_connecting(&device_path);
+
+                Task::perform(
+                    async move {
+                        debug!("Disconnecting bluetooth device: {:?}", device_path);
+                        if let Err(err) = BluetoothService::disconnect(&conn, &device_path).await {
+                            error!("Failed to disconnect bluetooth device: {}", err);
+                        }
+
+                        BluetoothService::initialize_data(&conn)
+                            .await
+                            .unwrap_or(data)
+                    },
+                    ServiceEvent::Update,
+                )
+            }
         }
     }
 }
+
+fn card_name(address: &str) -> String {
+    format!("bluez_card.{}", address.replace(':', "_"))
+}
+
+// available profiles and active profile of each card in the `pactl list cards` output
+fn parse_card_profiles(output: &str) -> HashMap<String, (Vec<String>, Option<String>)> {
+    let mut cards = HashMap::new();
+    let mut current: Option<String> = None;
+    let mut in_profiles = false;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+
+        if let Some(name) = trimmed.strip_prefix("Name: ") {
+            current = Some(name.to_owned());
+            in_profiles = false;
+        } else if trimmed == "Profiles:" {
+            in_profiles = true;
+        } else if let Some(active) = trimmed.strip_prefix("Active Profile: ") {
+            in_profiles = false;
+            if let Some(card) = current.as_ref() {
+                cards
+                    .entry(card.clone())
+                    .or_insert_with(|| (Vec::new(), None))
+                    .1 = Some(active.to_owned());
+            }
+        } else if in_profiles && line.starts_with("\t\t") {
+            if let (Some(card), Some((profile, details))) =
+                (current.as_ref(), trimmed.split_once(": "))
+            {
+                if profile != "off" && !details.contains("available: no") {
+                    cards
+                        .entry(card.clone())
+                        .or_insert_with(|| (Vec::new(), None))
+                        .0
+                        .push(profile.to_owned());
+                }
+            }
+        } else {
+            in_profiles = false;
+        }
+    }
+
+    cards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACTL_LIST_CARDS: &str = "\
+Card #52
+\tName: alsa_card.pci-0000_00_1f.3
+\tDriver: alsa
+\tOwner Module: n/a
+\tProperties:
+\t\tdevice.description = \"Built-in Audio\"
+\t\tdevice.name = \"alsa_card.pci-0000_00_1f.3\"
+\tProfiles:
+\t\toff: Off (sinks: 0, sources: 0, priority: 0, available: yes)
+\t\toutput:analog-stereo+input:analog-stereo: Analog Stereo Duplex (sinks: 1, sources: 1, priority: 6565, available: yes)
+\t\toutput:hdmi-stereo: Digital Stereo (HDMI) Output (sinks: 1, sources: 0, priority: 5900, available: no)
+\tActive Profile: output:analog-stereo+input:analog-stereo
+\tPorts:
+\t\tanalog-output-speaker: Speakers (type: Speaker, priority: 10000, latency offset: 0 usec, availability unknown)
+\t\t\tPart of profile(s): output:analog-stereo+input:analog-stereo
+
+Card #97
+\tName: bluez_card.AC_80_0A_2F_10_4B
+\tDriver: module-bluez5-device.c
+\tOwner Module: n/a
+\tProperties:
+\t\tdevice.description = \"WH-1000XM4\"
+\t\tdevice.name = \"bluez_card.AC_80_0A_2F_10_4B\"
+\tProfiles:
+\t\toff: Off (sinks: 0, sources: 0, priority: 0, available: yes)
+\t\ta2dp-sink-sbc: High Fidelity Playback (A2DP Sink, codec SBC) (sinks: 1, sources: 0, priority: 18, available: yes)
+\t\ta2dp-sink: High Fidelity Playback (A2DP Sink, codec LDAC) (sinks: 1, sources: 0, priority: 34, available: yes)
+\t\theadset-head-unit-cvsd: Headset Head Unit (HSP/HFP, codec CVSD) (sinks: 1, sources: 1, priority: 1, available: no)
+\t\theadset-head-unit: Headset Head Unit (HSP/HFP, codec mSBC) (sinks: 1, sources: 1, priority: 2, available: yes)
+\tActive Profile: a2dp-sink
+\tPorts:
+\t\theadphone-output: Headphone (type: Headphones, priority: 0, latency offset: 0 usec, availability unknown)
+\t\t\tPart of profile(s): a2dp-sink-sbc, a2dp-sink, headset-head-unit
+";
+
+    #[test]
+    fn parse_card_profiles_available_and_active() {
+        let cards = parse_card_profiles(PACTL_LIST_CARDS);
+
+        let (profiles, active) = &cards[&card_name("AC:80:0A:2F:10:4B")];
+        assert_eq!(
+            profiles,
+            &["a2dp-sink-sbc", "a2dp-sink", "headset-head-unit"]
+        );
+        assert_eq!(active.as_deref(), Some("a2dp-sink"));
+
+        let (profiles, active) = &cards["alsa_card.pci-0000_00_1f.3"];
+        assert_eq!(profiles, &["output:analog-stereo+input:analog-stereo"]);
+        assert_eq!(
+            active.as_deref(),
+            Some("output:analog-stereo+input:analog-stereo")
+        );
+    }
+
+    #[test]
+    fn parse_card_profiles_empty_output() {
+        assert!(parse_card_profiles("").is_empty());
+    }
+}
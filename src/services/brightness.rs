@@ -1,30 +1,45 @@
-use super::{ReadOnlyService, Service, ServiceEvent};
+use super::{ListenerState, ReadOnlyService, Retry, Service, ServiceEvent, niri::ipc};
+use crate::config::BrightnessConfig;
 use iced::{
     Subscription, Task,
     futures::{SinkExt, StreamExt, channel::mpsc::Sender, stream::pending},
     stream::channel,
 };
 use log::{debug, error, info, warn};
+use serde_json::json;
 use std::{
     any::TypeId,
     fs,
     ops::Deref,
     path::{Path, PathBuf},
 };
-use tokio::io::{Interest, unix::AsyncFd};
+use tokio::{
+    io::{Interest, unix::AsyncFd},
+    process::Command,
+};
 use zbus::proxy;
 
+#[derive(Debug, Clone)]
+pub struct DisplayBrightness {
+    pub output_name: String,
+    pub level: u8,
+    display: u32,
+    max: u32,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct BrightnessData {
     pub current: u32,
     pub max: u32,
+    pub displays: Vec<DisplayBrightness>,
 }
 
 #[derive(Debug, Clone)]
 pub struct BrightnessService {
     data: BrightnessData,
-    device_path: PathBuf,
+    device_path: Option<PathBuf>,
     conn: zbus::Connection,
+    config: BrightnessConfig,
 }
 
 impl Deref for BrightnessService {
@@ -35,6 +50,127 @@ impl Deref for BrightnessService {
     }
 }
 
+// a display found by `ddcutil detect`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DdcDetected {
+    display: u32,
+    connector: Option<String>,
+    model: Option<String>,
+    serial: Option<String>,
+}
+
+// the model and serial numbers reported by niri for an output
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct OutputInfo {
+    name: String,
+    model: String,
+    serial: Option<String>,
+}
+
+impl DdcDetected {
+    // niri knows the outputs even when ddcutil reports no drm connector, as
+    // on nvidia or with i2c only buses
+    fn output_name(&self, outputs: &[OutputInfo]) -> Option<String> {
+        let model = self.model.as_deref().filter(|model| !model.is_empty());
+        let serial = self.serial.as_deref().filter(|serial| !serial.is_empty());
+
+        outputs
+            .iter()
+            .find(|output| {
+                Some(output.model.as_str()) == model
+                    && (serial.is_none() || output.serial.as_deref() == serial)
+            })
+            .map(|output| output.name.clone())
+            .or_else(|| {
+                // the connector is reported as `card1-DP-1`, the output name is `DP-1`
+                self.connector
+                    .as_deref()
+                    .and_then(|connector| connector.split_once('-'))
+                    .map(|(_, name)| name.to_owned())
+            })
+    }
+}
+
+fn parse_ddc_detect(output: &str) -> Vec<DdcDetected> {
+    let mut displays: Vec<DdcDetected> = Vec::new();
+
+    for line in output.lines().map(str::trim) {
+        if let Some(n) = line.strip_prefix("Display ") {
+            if let Ok(display) = n.trim().parse() {
+                displays.push(DdcDetected {
+                    display,
+                    connector: None,
+                    model: None,
+                    serial: None,
+                });
+            }
+            continue;
+        }
+
+        // the invalid displays have no number, their fields are skipped
+        if line.starts_with("Invalid display") {
+            displays.push(DdcDetected {
+                display: 0,
+                connector: None,
+                model: None,
+                serial: None,
+            });
+            continue;
+        }
+
+        let Some(current) = displays.last_mut() else {
+            continue;
+        };
+        if let Some(connector) = line.strip_prefix("DRM connector:") {
+            current.connector = Some(connector.trim().to_owned());
+        } else if let Some(monitor) = line.strip_prefix("Monitor:") {
+            // `<manufacturer>:<model>:<serial>`
+            let mut fields = monitor.trim().splitn(3, ':');
+            fields.next();
+            current.model = fields.next().map(str::to_owned);
+            current.serial = fields.next().map(str::to_owned);
+        }
+    }
+
+    displays.retain(|display| display.display > 0);
+
+    displays
+}
+
+// brief output format: `VCP 10 C <current> <max>`, as a percentage and the max
+fn parse_getvcp_brightness(output: &str) -> Option<(u8, u32)> {
+    let mut values = output.split_whitespace();
+    if values.next()? != "VCP" {
+        return None;
+    }
+    let mut values = values.skip(2);
+    let current = values.next()?.parse::<u32>().ok()?;
+    let max = values.next()?.parse::<u32>().ok()?.max(1);
+
+    // some monitors report a current value above the max
+    Some(((current * 100 / max).min(100) as u8, max))
+}
+
+async fn niri_outputs() -> anyhow::Result<Vec<OutputInfo>> {
+    let response = ipc::request(json!({ "Outputs": null })).await?;
+
+    Ok(response["Outputs"]
+        .as_object()
+        .map(|outputs| {
+            outputs
+                .values()
+                .filter_map(|output| {
+                    Some(OutputInfo {
+                        name: output["name"].as_str()?.to_owned(),
+                        model: output["model"].as_str()?.to_owned(),
+                        serial: output["serial"].as_str().map(str::to_owned),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
 impl BrightnessService {
     async fn get_max_brightness(device_path: &Path) -> anyhow::Result<u32> {
         let max_brightness = fs::read_to_string(device_path.join("max_brightness"))?;
@@ -50,39 +186,160 @@ impl BrightnessService {
         Ok(actual_brightness)
     }
 
-    async fn initialize_data(device_path: &Path) -> anyhow::Result<BrightnessData> {
-        let max_brightness = Self::get_max_brightness(device_path).await?;
-        let actual_brightness = Self::get_actual_brightness(device_path).await?;
+    async fn initialize_data(
+        device_path: Option<&Path>,
+        config: &BrightnessConfig,
+    ) -> anyhow::Result<BrightnessData> {
+        let (max_brightness, actual_brightness) = match device_path {
+            Some(device_path) => (
+                Self::get_max_brightness(device_path).await?,
+                Self::get_actual_brightness(device_path).await?,
+            ),
+            None => (0, 0),
+        };
 
         debug!(
             "Max brightness: {}, current brightness: {}",
             max_brightness, actual_brightness
         );
 
+        let displays = if config.use_ddc {
+            Self::get_ddc_displays().await.unwrap_or_else(|err| {
+                warn!("Failed to get ddc displays: {}", err);
+
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+
+        debug!("DDC displays: {:?}", displays);
+
         Ok(BrightnessData {
             current: actual_brightness,
             max: max_brightness,
+            displays,
         })
     }
 
-    async fn init_service() -> anyhow::Result<(zbus::Connection, PathBuf)> {
+    async fn get_ddc_displays() -> anyhow::Result<Vec<DisplayBrightness>> {
+        let output = Command::new("ddcutil")
+            .arg("detect")
+            .arg("--terse")
+            .output()
+            .await?;
+        let output = String::from_utf8(output.stdout)?;
+
+        let outputs = niri_outputs().await.unwrap_or_else(|err| {
+            debug!("Failed to get the niri outputs: {}", err);
+
+            Vec::new()
+        });
+
+        let mut displays = Vec::new();
+        for detected in parse_ddc_detect(&output) {
+            let Some(output_name) = detected.output_name(&outputs) else {
+                warn!("No output found for the ddc display {}", detected.display);
+                continue;
+            };
+
+            // a monitor with ddc disabled in its osd shouldn't hide the others
+            match Self::get_ddc_brightness(detected.display).await {
+                Ok((level, max)) => displays.push(DisplayBrightness {
+                    output_name,
+                    level,
+                    display: detected.display,
+                    max,
+                }),
+                Err(err) => {
+                    warn!("Failed to get the {} brightness: {}", output_name, err);
+                }
+            }
+        }
+
+        Ok(displays)
+    }
+
+    // `ddcutil detect` takes a few seconds, the displays found at startup are
+    // only read again
+    async fn refresh_ddc_displays(displays: Vec<DisplayBrightness>) -> Vec<DisplayBrightness> {
+        let mut refreshed = Vec::with_capacity(displays.len());
+        for mut display in displays {
+            match Self::get_ddc_brightness(display.display).await {
+                Ok((level, max)) => {
+                    display.level = level;
+                    display.max = max;
+                }
+                Err(err) => {
+                    warn!(
+                        "Failed to refresh the {} brightness: {}",
+                        display.output_name, err
+                    );
+                }
+            }
+            refreshed.push(display);
+        }
+
+        refreshed
+    }
+
+    async fn get_ddc_brightness(display: u32) -> anyhow::Result<(u8, u32)> {
+        let output = Command::new("ddcutil")
+            .arg("getvcp")
+            .arg("10")
+            .arg("--brief")
+            .arg("--display")
+            .arg(display.to_string())
+            .output()
+            .await?;
+        let output = String::from_utf8(output.stdout)?;
+
+        parse_getvcp_brightness(&output)
+            .ok_or_else(|| anyhow::anyhow!("unexpected getvcp output: {}", output.trim()))
+    }
+
+    async fn set_ddc_brightness(display: &DisplayBrightness, level: u8) -> anyhow::Result<()> {
+        let value = level as u32 * display.max / 100;
+
+        let output = Command::new("ddcutil")
+            .arg("setvcp")
+            .arg("10")
+            .arg(value.to_string())
+            .arg("--display")
+            .arg(display.display.to_string())
+            .output()
+            .await?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "ddcutil setvcp failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+
+    async fn init_service(
+        config: &BrightnessConfig,
+    ) -> anyhow::Result<(zbus::Connection, Option<PathBuf>)> {
         let backlight_devices = Self::backlight_enumerate()?;
 
-        match backlight_devices
+        let device_path = backlight_devices
             .iter()
             .find(|d| d.subsystem().and_then(|s| s.to_str()) == Some("backlight"))
-        {
-            Some(device) => {
-                let device_path = device.syspath().to_path_buf();
+            .map(|device| device.syspath().to_path_buf());
 
+        match device_path {
+            None if !config.use_ddc => {
+                warn!("No backlight devices found");
+                Err(anyhow::anyhow!("No backlight devices found"))
+            }
+            device_path => {
                 let conn = zbus::Connection::system().await?;
 
                 Ok((conn, device_path))
             }
-            _ => {
-                warn!("No backlight devices found");
-                Err(anyhow::anyhow!("No backlight devices found"))
-            }
         }
     }
 
@@ -104,23 +361,37 @@ impl BrightnessService {
         Ok(enumerator.scan_devices()?.collect())
     }
 
-    async fn start_listening(state: State, output: &mut Sender<ServiceEvent<Self>>) -> State {
+    async fn start_listening(
+        state: State,
+        config: &BrightnessConfig,
+        output: &mut Sender<ServiceEvent<Self>>,
+    ) -> State {
         match state {
-            State::Init => match Self::init_service().await {
+            State::Init => match Self::init_service(config).await {
                 Ok((conn, device_path)) => {
-                    let data = BrightnessService::initialize_data(&device_path).await;
+                    let data =
+                        BrightnessService::initialize_data(device_path.as_deref(), config).await;
 
                     match data {
                         Ok(data) => {
                             let _ = output
                                 .send(ServiceEvent::Init(BrightnessService {
                                     data,
-                                    device_path: device_path.to_path_buf(),
+                                    device_path: device_path.clone(),
                                     conn,
+                                    config: config.clone(),
                                 }))
                                 .await;
 
-                            State::Active(device_path)
+                            match device_path {
+                                Some(device_path) => State::Active(device_path),
+                                None => {
+                                    info!("No backlight device, only ddc displays are available");
+
+                                    let _ = pending::<u8>().next().await;
+                                    State::Init
+                                }
+                            }
                         }
                         Err(err) => {
                             error!("Failed to initialize brightness data: {}", err);
@@ -168,7 +439,9 @@ impl BrightnessService {
                                                     if new_value != current_value {
                                                         let _ = output
                                                             .send(ServiceEvent::Update(
-                                                                BrightnessEvent(new_value),
+                                                                BrightnessEvent::Backlight(
+                                                                    new_value,
+                                                                ),
                                                             ))
                                                             .await;
                                                     }
@@ -230,34 +503,57 @@ impl BrightnessService {
     }
 }
 
-enum State {
-    Init,
-    Active(PathBuf),
-    Error,
-}
+type State = ListenerState<PathBuf>;
 
 #[derive(Debug, Clone)]
-pub struct BrightnessEvent(u32);
+pub enum BrightnessEvent {
+    Backlight(u32),
+    Output { output: String, level: u8 },
+    Displays(Vec<DisplayBrightness>),
+}
 
 impl ReadOnlyService for BrightnessService {
     type UpdateEvent = BrightnessEvent;
     type Error = ();
 
     fn update(&mut self, event: Self::UpdateEvent) {
-        self.data.current = event.0;
+        match event {
+            BrightnessEvent::Backlight(value) => {
+                self.data.current = value;
+            }
+            BrightnessEvent::Output { output, level } => {
+                if let Some(display) = self
+                    .data
+                    .displays
+                    .iter_mut()
+                    .find(|d| d.output_name == output)
+                {
+                    display.level = level;
+                }
+            }
+            BrightnessEvent::Displays(displays) => {
+                self.data.displays = displays;
+            }
+        }
     }
 
     fn subscribe() -> Subscription<ServiceEvent<Self>> {
-        let id = TypeId::of::<Self>();
+        Self::subscribe_with_config(&BrightnessConfig::default())
+    }
+}
+
+impl BrightnessService {
+    pub fn subscribe_with_config(config: &BrightnessConfig) -> Subscription<ServiceEvent<Self>> {
+        let id = format!("{:?}-{:?}", TypeId::of::<Self>(), config);
+        let config = config.clone();
 
         Subscription::run_with_id(
             id,
-            channel(100, async |mut output| {
-                let mut state = State::Init;
-
-                loop {
-                    state = BrightnessService::start_listening(state, &mut output).await;
-                }
+            channel(100, async move |mut output| {
+                Retry::run(std::any::type_name::<BrightnessService>(), async |state| {
+                    BrightnessService::start_listening(state, &config, &mut output).await
+                })
+                .await;
             }),
         )
     }
@@ -266,6 +562,7 @@ impl ReadOnlyService for BrightnessService {
 #[derive(Debug, Clone)]
 pub enum BrightnessCommand {
     Set(u32),
+    SetOutput { output: String, level: u8 },
     Refresh,
 }
 
@@ -273,30 +570,73 @@ impl Service for BrightnessService {
     type Command = BrightnessCommand;
 
     fn command(&mut self, command: Self::Command) -> Task<ServiceEvent<Self>> {
-        Task::perform(
-            {
-                let conn = self.conn.clone();
-                let device_path = self.device_path.clone();
-
-                async move {
-                    match command {
-                        BrightnessCommand::Set(v) => {
+        match command {
+            BrightnessCommand::Set(v) => match self.device_path.clone() {
+                Some(device_path) => {
+                    let conn = self.conn.clone();
+
+                    Task::perform(
+                        async move {
                             debug!("Setting brightness to {}", v);
                             let _ = BrightnessService::set_brightness(&conn, &device_path, v).await;
 
                             v
-                        }
-                        BrightnessCommand::Refresh => {
+                        },
+                        |v| ServiceEvent::Update(BrightnessEvent::Backlight(v)),
+                    )
+                }
+                None => Task::none(),
+            },
+            BrightnessCommand::SetOutput { output, level } => {
+                match self
+                    .data
+                    .displays
+                    .iter()
+                    .find(|d| d.output_name == output)
+                    .cloned()
+                {
+                    Some(display) => Task::perform(
+                        async move {
+                            debug!("Setting {} brightness to {}", output, level);
+                            if let Err(err) =
+                                BrightnessService::set_ddc_brightness(&display, level).await
+                            {
+                                error!("Failed to set {} brightness: {}", output, err);
+                            }
+
+                            BrightnessEvent::Output { output, level }
+                        },
+                        ServiceEvent::Update,
+                    ),
+                    None => Task::none(),
+                }
+            }
+            BrightnessCommand::Refresh => {
+                let backlight = match self.device_path.clone() {
+                    Some(device_path) => Task::perform(
+                        async move {
                             debug!("Refreshing brightness data");
                             BrightnessService::get_actual_brightness(&device_path)
                                 .await
                                 .unwrap_or_default()
-                        }
-                    }
-                }
-            },
-            |v| ServiceEvent::Update(BrightnessEvent(v)),
-        )
+                        },
+                        |v| ServiceEvent::Update(BrightnessEvent::Backlight(v)),
+                    ),
+                    None => Task::none(),
+                };
+
+                let displays = if self.config.use_ddc && !self.data.displays.is_empty() {
+                    Task::perform(
+                        BrightnessService::refresh_ddc_displays(self.data.displays.clone()),
+                        |displays| ServiceEvent::Update(BrightnessEvent::Displays(displays)),
+                    )
+                } else {
+                    Task::none()
+                };
+
+                Task::batch(vec![backlight, displays])
+            }
+        }
     }
 }
 
@@ -305,6 +645,97 @@ impl Service for BrightnessService {
     default_path = "/org/freedesktop/login1/session/auto",
     interface = "org.freedesktop.login1.Session"
 )]
-trait BrightnessCtrl {
+pub trait BrightnessCtrl {
     fn set_brightness(&self, subsystem: &str, name: &str, value: u32) -> zbus::Result<()>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DETECT: &str = "Display 1
+   I2C bus:  /dev/i2c-4
+   DRM connector:           card1-DP-1
+   Monitor:                 DEL:DELL U2719D:7RBR1T2
+
+Display 2
+   I2C bus:  /dev/i2c-7
+   Monitor:                 GSM:LG HDR 4K:0x0004b2f1
+
+Invalid display
+   I2C bus:  /dev/i2c-5
+   DRM connector:           card1-eDP-1
+   Monitor:                 BOE::
+";
+
+    fn output(name: &str, model: &str, serial: Option<&str>) -> OutputInfo {
+        OutputInfo {
+            name: name.to_owned(),
+            model: model.to_owned(),
+            serial: serial.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn parse_ddc_detect_skips_invalid_displays() {
+        assert_eq!(
+            parse_ddc_detect(DETECT),
+            vec![
+                DdcDetected {
+                    display: 1,
+                    connector: Some("card1-DP-1".to_owned()),
+                    model: Some("DELL U2719D".to_owned()),
+                    serial: Some("7RBR1T2".to_owned()),
+                },
+                DdcDetected {
+                    display: 2,
+                    connector: None,
+                    model: Some("LG HDR 4K".to_owned()),
+                    serial: Some("0x0004b2f1".to_owned()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn output_name_prefers_the_niri_outputs() {
+        let displays = parse_ddc_detect(DETECT);
+        let outputs = [
+            output("DP-3", "DELL U2719D", Some("7RBR1T2")),
+            output("HDMI-A-1", "LG HDR 4K", Some("0x0004b2f1")),
+        ];
+
+        assert_eq!(displays[0].output_name(&outputs).as_deref(), Some("DP-3"));
+        assert_eq!(
+            displays[1].output_name(&outputs).as_deref(),
+            Some("HDMI-A-1")
+        );
+    }
+
+    #[test]
+    fn output_name_falls_back_to_the_connector() {
+        let displays = parse_ddc_detect(DETECT);
+
+        assert_eq!(displays[0].output_name(&[]).as_deref(), Some("DP-1"));
+        assert_eq!(displays[1].output_name(&[]), None);
+    }
+
+    #[test]
+    fn parse_getvcp_brightness_values() {
+        assert_eq!(
+            parse_getvcp_brightness("VCP 10 C 50 100\n"),
+            Some((50, 100))
+        );
+        assert_eq!(
+            parse_getvcp_brightness("VCP 10 C 120 255\n"),
+            Some((47, 255))
+        );
+        assert_eq!(
+            parse_getvcp_brightness("VCP 10 C 300 255\n"),
+            Some((100, 255))
+        );
+        assert_eq!(parse_getvcp_brightness("VCP 10 C 0 0\n"), Some((0, 1)));
+        assert_eq!(parse_getvcp_brightness("VCP 10 ERR\n"), None);
+        assert_eq!(parse_getvcp_brightness(""), None);
+    }
+}
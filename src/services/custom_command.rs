@@ -0,0 +1,101 @@
+use super::{ReadOnlyService, ServiceEvent};
+use crate::config::CustomModuleConfig;
+use iced::{
+    Subscription,
+    futures::{SinkExt, channel::mpsc::Sender},
+    stream::channel,
+};
+use log::warn;
+use std::{any::TypeId, process::Stdio, time::Duration};
+use tokio::{process::Command, time::sleep};
+
+#[derive(Debug, Clone)]
+pub struct CustomCommandService {
+    pub index: usize,
+    pub output: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomCommandEvent {
+    pub index: usize,
+    pub output: String,
+}
+
+impl CustomCommandService {
+    async fn run(command: &str) -> String {
+        match Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdout(Stdio::piped())
+            .output()
+            .await
+        {
+            Ok(output) => String::from_utf8_lossy(&output.stdout).trim().to_owned(),
+            Err(err) => {
+                warn!("Failed to run custom command {}: {}", command, err);
+
+                String::new()
+            }
+        }
+    }
+
+    async fn start_listening(
+        index: usize,
+        initialized: bool,
+        config: &CustomModuleConfig,
+        output: &mut Sender<ServiceEvent<Self>>,
+    ) {
+        let value = Self::run(&config.command).await;
+
+        let _ = if initialized {
+            output
+                .send(ServiceEvent::Update(CustomCommandEvent {
+                    index,
+                    output: value,
+                }))
+                .await
+        } else {
+            output
+                .send(ServiceEvent::Init(CustomCommandService {
+                    index,
+                    output: value,
+                }))
+                .await
+        };
+
+        sleep(Duration::from_millis(config.interval_ms.max(100))).await;
+    }
+
+    pub fn subscribe_with_config(
+        index: usize,
+        config: &CustomModuleConfig,
+    ) -> Subscription<ServiceEvent<Self>> {
+        let id = format!("{:?}-{}-{:?}", TypeId::of::<Self>(), index, config);
+        let config = config.clone();
+
+        Subscription::run_with_id(
+            id,
+            channel(10, async move |mut output| {
+                let mut initialized = false;
+
+                loop {
+                    Self::start_listening(index, initialized, &config, &mut output).await;
+                    initialized = true;
+                }
+            }),
+        )
+    }
+}
+
+impl ReadOnlyService for CustomCommandService {
+    type UpdateEvent = CustomCommandEvent;
+    type Error = ();
+
+    fn update(&mut self, event: Self::UpdateEvent) {
+        self.output = event.output;
+    }
+
+    fn subscribe() -> Subscription<ServiceEvent<Self>> {
+        Self::subscribe_with_config(0, &CustomModuleConfig::default())
+    }
+}
@@ -0,0 +1,149 @@
+use super::{ListenerState, ReadOnlyService, Retry, Service, ServiceEvent};
+use crate::{
+    config::{NotificationDaemon, NotificationsModuleConfig},
+    utils::dnd::{disable_dnd, enable_dnd, is_dnd_active},
+};
+use iced::{
+    Subscription, Task,
+    futures::{SinkExt, StreamExt, channel::mpsc::Sender, stream::pending},
+    stream::channel,
+};
+use log::{debug, error};
+use std::{any::TypeId, time::Duration};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone)]
+pub struct DndService {
+    pub active: bool,
+    daemon: NotificationDaemon,
+}
+
+impl DndService {
+    async fn start_listening(
+        state: State,
+        config: &NotificationsModuleConfig,
+        output: &mut Sender<ServiceEvent<Self>>,
+    ) -> State {
+        match state {
+            State::Init => match is_dnd_active(config.daemon).await {
+                Ok(active) => {
+                    let _ = output
+                        .send(ServiceEvent::Init(DndService {
+                            active,
+                            daemon: config.daemon,
+                        }))
+                        .await;
+
+                    State::Active(active)
+                }
+                Err(err) => {
+                    error!("Failed to get the do not disturb state: {}", err);
+
+                    State::Error
+                }
+            },
+            State::Active(active) => {
+                tokio::time::sleep(POLL_INTERVAL).await;
+
+                match is_dnd_active(config.daemon).await {
+                    Ok(new_active) => {
+                        if new_active != active {
+                            debug!("Do not disturb changed: {}", new_active);
+                            let _ = output
+                                .send(ServiceEvent::Update(DndEvent(new_active)))
+                                .await;
+                        }
+
+                        State::Active(new_active)
+                    }
+                    Err(err) => {
+                        error!("Failed to get the do not disturb state: {}", err);
+
+                        State::Active(active)
+                    }
+                }
+            }
+            State::Error => {
+                error!("Do not disturb service error");
+
+                let _ = pending::<u8>().next().await;
+                State::Error
+            }
+        }
+    }
+}
+
+type State = ListenerState<bool>;
+
+#[derive(Debug, Clone)]
+pub struct DndEvent(pub bool);
+
+impl ReadOnlyService for DndService {
+    type UpdateEvent = DndEvent;
+    type Error = ();
+
+    fn update(&mut self, event: Self::UpdateEvent) {
+        self.active = event.0;
+    }
+
+    fn subscribe() -> Subscription<ServiceEvent<Self>> {
+        Self::subscribe_with_config(&NotificationsModuleConfig::default())
+    }
+}
+
+impl DndService {
+    pub fn subscribe_with_config(
+        config: &NotificationsModuleConfig,
+    ) -> Subscription<ServiceEvent<Self>> {
+        let id = format!("{:?}-{:?}", TypeId::of::<Self>(), config);
+        let config = config.clone();
+
+        Subscription::run_with_id(
+            id,
+            channel(10, async move |mut output| {
+                Retry::run(std::any::type_name::<DndService>(), async |state| {
+                    DndService::start_listening(state, &config, &mut output).await
+                })
+                .await;
+            }),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum DndCommand {
+    Toggle,
+}
+
+impl Service for DndService {
+    type Command = DndCommand;
+
+    fn command(&mut self, command: Self::Command) -> Task<ServiceEvent<Self>> {
+        match command {
+            DndCommand::Toggle => {
+                let daemon = self.daemon;
+                let active = self.active;
+
+                Task::perform(
+                    async move {
+                        let res = if active {
+                            disable_dnd(daemon).await
+                        } else {
+                            enable_dnd(daemon).await
+                        };
+
+                        match res {
+                            Ok(_) => !active,
+                            Err(err) => {
+                                error!("Failed to toggle do not disturb: {}", err);
+                                active
+                            }
+                        }
+                    },
+                    |active| ServiceEvent::Update(DndEvent(active)),
+                )
+            }
+        }
+    }
+}
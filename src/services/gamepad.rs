@@ -0,0 +1,149 @@
+use super::{ReadOnlyService, ServiceEvent};
+use iced::{
+    Subscription,
+    futures::{SinkExt, StreamExt, stream::pending},
+    stream::channel,
+};
+use inotify::{Inotify, WatchMask};
+use log::{debug, error};
+use std::{any::TypeId, fs, ops::Deref};
+
+const INPUT_DIR: &str = "/dev/input";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GamepadDevice {
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GamepadData {
+    pub connected: bool,
+    pub devices: Vec<GamepadDevice>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GamepadService {
+    data: GamepadData,
+}
+
+impl Deref for GamepadService {
+    type Target = GamepadData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+// every block of /proc/bus/input/devices lists the device name and its
+// handlers, the joystick handler is the jsN node under /dev/input
+fn device_names() -> Vec<(String, String)> {
+    let content = fs::read_to_string("/proc/bus/input/devices").unwrap_or_default();
+
+    content
+        .split("\n\n")
+        .filter_map(|block| {
+            let name = block
+                .lines()
+                .find_map(|line| line.strip_prefix("N: Name="))?
+                .trim_matches('"')
+                .to_owned();
+            let js = block
+                .lines()
+                .find_map(|line| line.strip_prefix("H: Handlers="))?
+                .split_whitespace()
+                .find(|handler| handler.starts_with("js"))?
+                .to_owned();
+
+            Some((js, name))
+        })
+        .collect()
+}
+
+fn scan() -> GamepadData {
+    let names = device_names();
+
+    let mut devices = fs::read_dir(INPUT_DIR)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let file_name = entry.file_name().to_string_lossy().into_owned();
+
+                    file_name.starts_with("js").then(|| GamepadDevice {
+                        name: names
+                            .iter()
+                            .find(|(js, _)| *js == file_name)
+                            .map(|(_, name)| name.clone())
+                            .unwrap_or_else(|| file_name.clone()),
+                        path: entry.path().to_string_lossy().into_owned(),
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    devices.sort_by(|a, b| a.path.cmp(&b.path));
+
+    GamepadData {
+        connected: !devices.is_empty(),
+        devices,
+    }
+}
+
+impl ReadOnlyService for GamepadService {
+    type UpdateEvent = GamepadData;
+    type Error = ();
+
+    fn update(&mut self, event: Self::UpdateEvent) {
+        self.data = event;
+    }
+
+    fn subscribe() -> Subscription<ServiceEvent<Self>> {
+        let id = TypeId::of::<Self>();
+
+        Subscription::run_with_id(
+            id,
+            channel(10, async |mut output| {
+                let mut data = scan();
+                let _ = output
+                    .send(ServiceEvent::Init(GamepadService { data: data.clone() }))
+                    .await;
+
+                let events = Inotify::init().and_then(|inotify| {
+                    inotify
+                        .watches()
+                        .add(INPUT_DIR, WatchMask::CREATE | WatchMask::DELETE)?;
+
+                    inotify.into_event_stream([0; 512])
+                });
+
+                match events {
+                    Ok(mut events) => {
+                        while let Some(event) = events.next().await {
+                            let is_joystick = event
+                                .ok()
+                                .and_then(|e| e.name)
+                                .is_some_and(|name| name.to_string_lossy().starts_with("js"));
+                            if !is_joystick {
+                                continue;
+                            }
+
+                            let current = scan();
+                            if current != data {
+                                debug!("Gamepads changed: {:?}", current);
+
+                                data = current;
+                                let _ = output.send(ServiceEvent::Update(data.clone())).await;
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        error!("Failed to watch {}: {}", INPUT_DIR, err);
+                    }
+                }
+
+                let _ = pending::<u8>().next().await;
+            }),
+        )
+    }
+}
@@ -1,4 +1,5 @@
 use log::{debug, info, warn};
+use std::time::{Duration, Instant};
 use wayland_client::{
     Connection, Dispatch, DispatchError, EventQueue, Proxy, QueueHandle,
     protocol::{
@@ -19,6 +20,7 @@ pub struct IdleInhibitorManager {
     event_queue: EventQueue<IdleInhibitorManagerData>,
     handle: QueueHandle<IdleInhibitorManagerData>,
     data: IdleInhibitorManagerData,
+    activated_at: Option<Instant>,
 }
 
 impl IdleInhibitorManager {
@@ -37,6 +39,7 @@ impl IdleInhibitorManager {
                 event_queue,
                 handle,
                 data: IdleInhibitorManagerData::default(),
+                activated_at: None,
             };
 
             obj.roundtrip()?;
@@ -61,6 +64,16 @@ impl IdleInhibitorManager {
         self.data.idle_inhibitor_state.is_some()
     }
 
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.activated_at.map(|activated_at| activated_at.elapsed())
+    }
+
+    pub fn deactivate(&mut self) {
+        if let Err(err) = self.set_inhibit_idle(false) {
+            warn!("Failed to deactivate idle inhibitor: {}", err);
+        }
+    }
+
     pub fn toggle(&mut self) {
         let res = if self.is_inhibited() {
             self.set_inhibit_idle(false)
@@ -89,12 +102,15 @@ impl IdleInhibitorManager {
                 self.data.idle_inhibitor_state =
                     Some(idle_manager.create_inhibitor(surface, &self.handle, ()));
 
+                self.activated_at = Some(Instant::now());
+
                 self.roundtrip()?;
                 info!(target: "IdleInhibitor::set_inhibit_idle", "Idle Inhibitor was ENABLED");
             }
         } else if let Some(state) = &self.data.idle_inhibitor_state {
             state.destroy();
             self.data.idle_inhibitor_state = None;
+            self.activated_at = None;
 
             self.roundtrip()?;
             info!(target: "IdleInhibitor::set_inhibit_idle", "Idle Inhibitor was DISABLED");
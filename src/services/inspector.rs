@@ -0,0 +1,75 @@
+use super::{ReadOnlyService, ServiceEvent};
+use std::{
+    any::type_name,
+    collections::BTreeMap,
+    fmt::Debug,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Instant,
+};
+
+const MAX_DATA_LENGTH: usize = 200;
+
+#[derive(Debug, Clone)]
+pub struct ServiceDebugInfo {
+    pub last_event: Instant,
+    pub event_count: u64,
+    pub data: String,
+}
+
+// filled from the subscriptions, which can't reach the app state
+static SERVICES: Mutex<BTreeMap<&'static str, ServiceDebugInfo>> = Mutex::new(BTreeMap::new());
+
+// the events are only formatted while the debug panel is open
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Starts or stops recording the service events, the recorded ones are
+/// dropped when it stops.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        if let Ok(mut services) = SERVICES.lock() {
+            services.clear();
+        }
+    }
+}
+
+/// Records the event of a service subscription for the debug panel, meant to be
+/// chained with `.map(inspector::record)` before mapping it to a module message.
+pub fn record<S: ReadOnlyService>(event: ServiceEvent<S>) -> ServiceEvent<S>
+where
+    ServiceEvent<S>: Debug,
+{
+    if !ENABLED.load(Ordering::Relaxed) {
+        return event;
+    }
+
+    if let Ok(mut services) = SERVICES.lock() {
+        let info = services
+            .entry(type_name::<S>())
+            .or_insert_with(|| ServiceDebugInfo {
+                last_event: Instant::now(),
+                event_count: 0,
+                data: String::new(),
+            });
+        info.last_event = Instant::now();
+        if matches!(event, ServiceEvent::Update(_)) {
+            info.event_count += 1;
+        }
+        info.data = format!("{:?}", event)
+            .chars()
+            .take(MAX_DATA_LENGTH)
+            .collect();
+    }
+
+    event
+}
+
+pub fn snapshot() -> BTreeMap<&'static str, ServiceDebugInfo> {
+    SERVICES
+        .lock()
+        .map(|services| services.clone())
+        .unwrap_or_default()
+}
@@ -0,0 +1,99 @@
+use crate::config::ModuleName;
+use iced::{
+    Subscription,
+    futures::{SinkExt, StreamExt, channel::mpsc::Sender, stream::pending},
+    stream::channel,
+};
+use log::{debug, error, info, warn};
+use serde::Deserialize;
+use std::{any::TypeId, env, fs, path::PathBuf};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{UnixListener, UnixStream},
+};
+
+/// Commands accepted on the IPC socket, one JSON object per line, e.g.
+/// `{"toggle_module": "MediaPlayer"}` or `{"reload_config": null}`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum IpcCommand {
+    ToggleModule(ModuleName),
+    ReloadConfig,
+    SetVolume(i32),
+    EditLayout,
+    ToggleDebugPanel,
+}
+
+/// The socket lives in the user runtime dir, a shared fallback like `/tmp`
+/// would let other users send commands.
+pub fn socket_path() -> Option<PathBuf> {
+    env::var_os("XDG_RUNTIME_DIR").map(|dir| PathBuf::from(dir).join("ashell.sock"))
+}
+
+async fn handle_connection(stream: UnixStream, mut output: Sender<IpcCommand>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        debug!("IPC request: {}", line);
+
+        let response = match serde_json::from_str::<IpcCommand>(&line) {
+            Ok(command) => {
+                let _ = output.send(command).await;
+
+                "ok\n".to_owned()
+            }
+            Err(err) => format!("error: {}\n", err),
+        };
+
+        if let Err(err) = writer.write_all(response.as_bytes()).await {
+            warn!("Failed to answer the IPC client: {}", err);
+            break;
+        }
+    }
+}
+
+pub fn subscription() -> Subscription<IpcCommand> {
+    Subscription::run_with_id(
+        TypeId::of::<IpcCommand>(),
+        channel(10, async |output| {
+            let Some(path) = socket_path() else {
+                error!("XDG_RUNTIME_DIR is not set, the IPC socket is disabled");
+
+                let _ = pending::<u8>().next().await;
+                return;
+            };
+
+            // a socket nobody answers on was left behind by a previous instance
+            if UnixStream::connect(&path).await.is_ok() {
+                error!("Another ashell instance is listening on {:?}", path);
+
+                let _ = pending::<u8>().next().await;
+                return;
+            }
+            let _ = fs::remove_file(&path);
+
+            match UnixListener::bind(&path) {
+                Ok(listener) => {
+                    info!("Listening for IPC commands on {:?}", path);
+
+                    loop {
+                        match listener.accept().await {
+                            Ok((stream, _)) => {
+                                tokio::spawn(handle_connection(stream, output.clone()));
+                            }
+                            Err(err) => {
+                                warn!("Failed to accept an IPC connection: {}", err);
+                            }
+                        }
+                    }
+                }
+                Err(err) => {
+                    error!("Failed to bind the IPC socket {:?}: {}", path, err);
+
+                    let _ = pending::<u8>().next().await;
+                }
+            }
+        }),
+    )
+}
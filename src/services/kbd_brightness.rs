@@ -0,0 +1,239 @@
+use super::{
+    ListenerState, ReadOnlyService, Retry, Service, ServiceEvent, brightness::BrightnessCtrlProxy,
+};
+use iced::{
+    Subscription, Task,
+    futures::{SinkExt, StreamExt, channel::mpsc::Sender, stream::pending},
+    stream::channel,
+};
+use log::{debug, error, info, warn};
+use std::{
+    any::TypeId,
+    fs,
+    ops::Deref,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct KbdBrightnessData {
+    pub current: u32,
+    pub max: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct KbdBrightnessService {
+    data: KbdBrightnessData,
+    device_path: PathBuf,
+    conn: zbus::Connection,
+}
+
+impl Deref for KbdBrightnessService {
+    type Target = KbdBrightnessData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl KbdBrightnessService {
+    fn get_max_brightness(device_path: &Path) -> anyhow::Result<u32> {
+        let max_brightness = fs::read_to_string(device_path.join("max_brightness"))?;
+        let max_brightness = max_brightness.trim().parse::<u32>()?;
+
+        Ok(max_brightness)
+    }
+
+    fn get_brightness(device_path: &Path) -> anyhow::Result<u32> {
+        let brightness = fs::read_to_string(device_path.join("brightness"))?;
+        let brightness = brightness.trim().parse::<u32>()?;
+
+        Ok(brightness)
+    }
+
+    fn initialize_data(device_path: &Path) -> anyhow::Result<KbdBrightnessData> {
+        let max_brightness = Self::get_max_brightness(device_path)?;
+        let brightness = Self::get_brightness(device_path)?;
+
+        debug!(
+            "Max keyboard brightness: {}, current keyboard brightness: {}",
+            max_brightness, brightness
+        );
+
+        Ok(KbdBrightnessData {
+            current: brightness,
+            max: max_brightness,
+        })
+    }
+
+    fn find_device() -> anyhow::Result<PathBuf> {
+        let mut enumerator = udev::Enumerator::new()?;
+        enumerator.match_subsystem("leds")?;
+
+        enumerator
+            .scan_devices()?
+            .find(|d| {
+                d.sysname()
+                    .to_str()
+                    .is_some_and(|name| name.contains("kbd_backlight"))
+            })
+            .map(|d| d.syspath().to_path_buf())
+            .ok_or_else(|| {
+                warn!("No keyboard backlight devices found");
+                anyhow::anyhow!("No keyboard backlight devices found")
+            })
+    }
+
+    async fn init_service() -> anyhow::Result<(zbus::Connection, PathBuf)> {
+        let device_path = Self::find_device()?;
+        let conn = zbus::Connection::system().await?;
+
+        Ok((conn, device_path))
+    }
+
+    async fn start_listening(state: State, output: &mut Sender<ServiceEvent<Self>>) -> State {
+        match state {
+            State::Init => match Self::init_service().await {
+                Ok((conn, device_path)) => match Self::initialize_data(&device_path) {
+                    Ok(data) => {
+                        info!("Keyboard brightness service initialized");
+
+                        let _ = output
+                            .send(ServiceEvent::Init(KbdBrightnessService {
+                                data,
+                                device_path: device_path.clone(),
+                                conn,
+                            }))
+                            .await;
+
+                        State::Active(device_path)
+                    }
+                    Err(err) => {
+                        error!("Failed to initialize keyboard brightness data: {}", err);
+
+                        State::Error
+                    }
+                },
+                Err(err) => {
+                    error!("Failed to access to keyboard brightness files: {}", err);
+
+                    State::Error
+                }
+            },
+            State::Active(device_path) => {
+                info!("Listening for keyboard brightness changes");
+
+                // the leds brightness file doesn't notify changes made by the
+                // firmware hotkeys, so the value is polled
+                let mut interval = tokio::time::interval(Duration::from_secs(1));
+                let mut current_value = Self::get_brightness(&device_path).unwrap_or_default();
+
+                loop {
+                    interval.tick().await;
+
+                    match Self::get_brightness(&device_path) {
+                        Ok(new_value) => {
+                            if new_value != current_value {
+                                current_value = new_value;
+
+                                let _ = output
+                                    .send(ServiceEvent::Update(KbdBrightnessEvent(new_value)))
+                                    .await;
+                            }
+                        }
+                        Err(err) => {
+                            error!("Failed to read keyboard brightness: {}", err);
+
+                            break State::Error;
+                        }
+                    }
+                }
+            }
+            State::Error => {
+                error!("Keyboard brightness service error");
+
+                let _ = pending::<u8>().next().await;
+                State::Error
+            }
+        }
+    }
+
+    async fn set_brightness(
+        conn: &zbus::Connection,
+        device_path: &Path,
+        value: u32,
+    ) -> anyhow::Result<()> {
+        let brightness_ctrl = BrightnessCtrlProxy::new(conn).await?;
+        let device_name = device_path
+            .iter()
+            .next_back()
+            .and_then(|d| d.to_str())
+            .unwrap_or_default();
+
+        brightness_ctrl
+            .set_brightness("leds", device_name, value)
+            .await?;
+
+        Ok(())
+    }
+}
+
+type State = ListenerState<PathBuf>;
+
+#[derive(Debug, Clone)]
+pub struct KbdBrightnessEvent(u32);
+
+impl ReadOnlyService for KbdBrightnessService {
+    type UpdateEvent = KbdBrightnessEvent;
+    type Error = ();
+
+    fn update(&mut self, event: Self::UpdateEvent) {
+        self.data.current = event.0;
+    }
+
+    fn subscribe() -> Subscription<ServiceEvent<Self>> {
+        let id = TypeId::of::<Self>();
+
+        Subscription::run_with_id(
+            id,
+            channel(100, async |mut output| {
+                Retry::run(
+                    std::any::type_name::<KbdBrightnessService>(),
+                    async |state| KbdBrightnessService::start_listening(state, &mut output).await,
+                )
+                .await;
+            }),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum KbdBrightnessCommand {
+    Set(u32),
+}
+
+impl Service for KbdBrightnessService {
+    type Command = KbdBrightnessCommand;
+
+    fn command(&mut self, command: Self::Command) -> Task<ServiceEvent<Self>> {
+        Task::perform(
+            {
+                let conn = self.conn.clone();
+                let device_path = self.device_path.clone();
+
+                async move {
+                    match command {
+                        KbdBrightnessCommand::Set(v) => {
+                            debug!("Setting keyboard brightness to {}", v);
+                            let _ =
+                                KbdBrightnessService::set_brightness(&conn, &device_path, v).await;
+
+                            v
+                        }
+                    }
+                }
+            },
+            |v| ServiceEvent::Update(KbdBrightnessEvent(v)),
+        )
+    }
+}
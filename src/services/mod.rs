@@ -1,14 +1,32 @@
 use iced::{Subscription, Task};
+use log::{debug, error, warn};
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+use tokio::time::{Instant, sleep_until};
 
 pub mod audio;
 pub mod bluetooth;
 pub mod brightness;
+pub mod custom_command;
+pub mod dnd;
+pub mod gamepad;
 pub mod idle_inhibitor;
+pub mod inspector;
+pub mod ipc;
+pub mod kbd_brightness;
 pub mod mpris;
 pub mod network;
+pub mod night_mode;
+pub mod niri;
+pub mod notifications;
+pub mod printer;
 pub mod privacy;
+pub mod systemd;
 pub mod tray;
 pub mod upower;
+pub mod usb;
 
 #[derive(Debug, Clone)]
 pub enum ServiceEvent<S: ReadOnlyService> {
@@ -23,6 +41,99 @@ pub trait Service: ReadOnlyService {
     fn command(&mut self, command: Self::Command) -> Task<ServiceEvent<Self>>;
 }
 
+pub const DEFAULT_MAX_RETRY_ATTEMPTS: u32 = 10;
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+// set from the config, the subscriptions of the services can't read it
+static MAX_RETRY_ATTEMPTS: AtomicU32 = AtomicU32::new(DEFAULT_MAX_RETRY_ATTEMPTS);
+
+pub fn set_max_retry_attempts(attempts: u32) {
+    MAX_RETRY_ATTEMPTS.store(attempts, Ordering::Relaxed);
+}
+
+/// The state of a service listener restarted by [`Retry::run`].
+pub enum ListenerState<A> {
+    Init,
+    Active(A),
+    Error,
+}
+
+/// Tracks the restart attempts of a service after it enters the error state.
+pub struct Retry {
+    name: &'static str,
+    attempt: u32,
+}
+
+impl Retry {
+    pub fn new(name: &'static str) -> Self {
+        Self { name, attempt: 0 }
+    }
+
+    /// Drives a service listener forever: an error restarts it from `Init`
+    /// after an exponential backoff, an active state resets the attempts and
+    /// once they are exhausted the listener is called with `Error`.
+    pub async fn run<A>(
+        name: &'static str,
+        mut step: impl AsyncFnMut(ListenerState<A>) -> ListenerState<A>,
+    ) {
+        let mut retry = Retry::new(name);
+        let mut state = ListenerState::Init;
+
+        loop {
+            state = match step(state).await {
+                ListenerState::Error => match retry.backoff() {
+                    Some((attempt, wake_at)) => {
+                        Retry::wait(wake_at).await;
+                        debug!("Restarting {} after backoff, attempt {}", name, attempt);
+
+                        ListenerState::Init
+                    }
+                    // the listener parks itself when it's given the error state
+                    None => ListenerState::Error,
+                },
+                ListenerState::Active(active) => {
+                    retry.reset();
+
+                    ListenerState::Active(active)
+                }
+                ListenerState::Init => ListenerState::Init,
+            };
+        }
+    }
+
+    // returns None once the service should stay in the error state
+    pub fn backoff(&mut self) -> Option<(u32, Instant)> {
+        let max_attempts = MAX_RETRY_ATTEMPTS.load(Ordering::Relaxed);
+        if self.attempt >= max_attempts {
+            error!("{} failed {} times, giving up", self.name, max_attempts);
+
+            return None;
+        }
+
+        // the shift is clamped since the attempts are configurable
+        let delay = Duration::from_secs(1 << self.attempt.min(6)).min(MAX_RETRY_DELAY);
+        self.attempt += 1;
+
+        warn!(
+            "{} failed, retrying in {}s (attempt {}/{})",
+            self.name,
+            delay.as_secs(),
+            self.attempt,
+            max_attempts
+        );
+
+        Some((self.attempt, Instant::now() + delay))
+    }
+
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    pub async fn wait(wake_at: Instant) {
+        sleep_until(wake_at).await;
+    }
+}
+
 pub trait ReadOnlyService: Sized {
     type UpdateEvent;
     type Error: Clone;
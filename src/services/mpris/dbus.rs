@@ -1,6 +1,14 @@
 use std::collections::HashMap;
 use std::ops::Deref;
-use zbus::{Result, proxy, zvariant::OwnedValue};
+use zbus::{
+    Result,
+    fdo::PropertiesProxy,
+    names::InterfaceName,
+    proxy,
+    zvariant::{ObjectPath, OwnedValue},
+};
+
+const PLAYER_INTERFACE: &str = "org.mpris.MediaPlayer2.Player";
 
 pub struct MprisPlayerDbus<'a>(MprisPlayerProxy<'a>);
 
@@ -20,6 +28,7 @@ pub trait MprisPlayer {
     fn next(&self) -> Result<()>;
     fn play_pause(&self) -> Result<()>;
     fn previous(&self) -> Result<()>;
+    fn set_position(&self, track_id: &ObjectPath<'_>, position: i64) -> Result<()>;
 
     #[zbus(property)]
     fn metadata(&self) -> Result<HashMap<String, OwnedValue>>;
@@ -29,4 +38,32 @@ pub trait MprisPlayer {
     fn volume(&self) -> Result<f64>;
     #[zbus(property)]
     fn can_control(&self) -> Result<bool>;
+    #[zbus(property)]
+    fn shuffle(&self) -> Result<bool>;
+    #[zbus(property)]
+    fn set_shuffle(&self, v: bool) -> Result<()>;
+    #[zbus(property)]
+    fn loop_status(&self) -> Result<String>;
+    #[zbus(property)]
+    fn set_loop_status(&self, v: &str) -> Result<()>;
+    // not part of the MPRIS spec, only players that expose a writable rating support it
+    #[zbus(property)]
+    fn set_rating(&self, v: f64) -> Result<()>;
+    #[zbus(property)]
+    fn playback_status(&self) -> Result<String>;
+}
+
+impl MprisPlayerProxy<'_> {
+    // a single GetAll instead of one call per property
+    pub async fn get_all_player_properties(&self) -> anyhow::Result<HashMap<String, OwnedValue>> {
+        let properties = PropertiesProxy::builder(self.inner().connection())
+            .destination(self.inner().destination().to_owned())?
+            .path(self.inner().path().to_owned())?
+            .build()
+            .await?;
+
+        Ok(properties
+            .get_all(InterfaceName::from_static_str_unchecked(PLAYER_INTERFACE).into())
+            .await?)
+    }
 }
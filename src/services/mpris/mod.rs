@@ -1,4 +1,8 @@
-use super::{ReadOnlyService, Service, ServiceEvent};
+use super::{ListenerState, ReadOnlyService, Retry, Service, ServiceEvent};
+use crate::{
+    config::MprisConfig,
+    utils::dbus_session::{is_service_unknown, session_connection},
+};
 use dbus::MprisPlayerProxy;
 use iced::{
     Subscription,
@@ -9,10 +13,21 @@ use iced::{
         stream::{SelectAll, pending},
     },
     stream::channel,
+    widget::image,
+};
+use log::{debug, error, info, warn};
+use std::{
+    any::TypeId,
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    ops::Deref,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use zbus::{
+    fdo::DBusProxy,
+    zvariant::{ObjectPath, OwnedObjectPath, OwnedValue},
 };
-use log::{debug, error, info};
-use std::{any::TypeId, collections::HashMap, fmt::Display, ops::Deref, sync::Arc};
-use zbus::{fdo::DBusProxy, zvariant::OwnedValue};
 
 mod dbus;
 
@@ -21,13 +36,79 @@ pub struct MprisPlayerData {
     pub service: String,
     pub metadata: Option<MprisPlayerMetadata>,
     pub volume: Option<f64>,
+    pub shuffle: Option<bool>,
+    pub loop_status: Option<LoopStatus>,
+    pub playback_status: Option<PlaybackStatus>,
+    pub position: Option<Duration>,
+    pub art: Option<image::Handle>,
     proxy: MprisPlayerProxy<'static>,
 }
 
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LoopStatus {
+    None,
+    Track,
+    Playlist,
+}
+
+impl LoopStatus {
+    pub fn next(self) -> Self {
+        match self {
+            LoopStatus::None => LoopStatus::Playlist,
+            LoopStatus::Playlist => LoopStatus::Track,
+            LoopStatus::Track => LoopStatus::None,
+        }
+    }
+}
+
+impl From<String> for LoopStatus {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "Track" => LoopStatus::Track,
+            "Playlist" => LoopStatus::Playlist,
+            _ => LoopStatus::None,
+        }
+    }
+}
+
+impl From<LoopStatus> for &'static str {
+    fn from(value: LoopStatus) -> Self {
+        match value {
+            LoopStatus::None => "None",
+            LoopStatus::Track => "Track",
+            LoopStatus::Playlist => "Playlist",
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum PlaybackStatus {
+    Playing,
+    Paused,
+    Stopped,
+}
+
+impl From<String> for PlaybackStatus {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "Playing" => PlaybackStatus::Playing,
+            "Paused" => PlaybackStatus::Paused,
+            _ => PlaybackStatus::Stopped,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub struct MprisPlayerMetadata {
     pub artists: Option<Vec<String>>,
     pub title: Option<String>,
+    pub album: Option<String>,
+    pub track_number: Option<u32>,
+    pub disc_number: Option<u32>,
+    pub art_url: Option<String>,
+    pub length: Option<Duration>,
+    pub track_id: Option<String>,
+    pub user_rating: Option<f32>,
 }
 
 impl Display for MprisPlayerMetadata {
@@ -42,6 +123,31 @@ impl Display for MprisPlayerMetadata {
     }
 }
 
+impl MprisPlayerMetadata {
+    /// "Artist – Title (Album, Track N)", skipping whatever the player doesn't report
+    pub fn full_display(&self) -> String {
+        let mut t = match (&self.artists, &self.title) {
+            (None, None) => String::new(),
+            (None, Some(t)) => t.clone(),
+            (Some(a), None) => a.join(", "),
+            (Some(a), Some(t)) => format!("{} – {}", a.join(", "), t),
+        };
+
+        let details = [
+            self.album.clone(),
+            self.track_number.map(|n| format!("Track {}", n)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        if !details.is_empty() {
+            t.push_str(&format!(" ({})", details.join(", ")));
+        }
+
+        t
+    }
+}
+
 impl From<HashMap<String, OwnedValue>> for MprisPlayerMetadata {
     fn from(value: HashMap<String, OwnedValue>) -> Self {
         let artists = match value.get("xesam:artist") {
@@ -52,15 +158,143 @@ impl From<HashMap<String, OwnedValue>> for MprisPlayerMetadata {
             Some(v) => v.clone().try_into().ok(),
             None => None,
         };
+        let album = property::<String>(&value, "xesam:album").filter(|a| !a.is_empty());
+        let track_number = property::<i32>(&value, "xesam:trackNumber")
+            .and_then(|n| u32::try_from(n).ok())
+            .filter(|n| *n > 0);
+        let disc_number = property::<i32>(&value, "xesam:discNumber")
+            .and_then(|n| u32::try_from(n).ok())
+            .filter(|n| *n > 0);
+        let art_url = match value.get("mpris:artUrl") {
+            Some(v) => v.clone().try_into().ok(),
+            None => None,
+        };
+
+        // the spec says x, but plenty of players send the length as t
+        let length = property::<i64>(&value, "mpris:length")
+            .or_else(|| property::<u64>(&value, "mpris:length").map(|l| l as i64))
+            .filter(|l| *l > 0)
+            .map(|l| Duration::from_micros(l as u64));
+        let track_id = property::<OwnedObjectPath>(&value, "mpris:trackid")
+            .map(|p| p.to_string())
+            .or_else(|| property::<String>(&value, "mpris:trackid"));
+        let user_rating = property::<f64>(&value, "xesam:userRating").map(|r| r as f32);
+
+        Self {
+            artists,
+            title,
+            album,
+            track_number,
+            disc_number,
+            art_url,
+            length,
+            track_id,
+            user_rating,
+        }
+    }
+}
+
+fn property<T: TryFrom<OwnedValue>>(
+    properties: &HashMap<String, OwnedValue>,
+    name: &str,
+) -> Option<T> {
+    properties
+        .get(name)
+        .cloned()
+        .and_then(|value| T::try_from(value).ok())
+}
+
+const ART_CACHE_SIZE: usize = 10;
+
+#[derive(Debug, Default)]
+struct ArtCache {
+    images: HashMap<String, image::Handle>,
+    order: VecDeque<String>,
+}
+
+impl ArtCache {
+    fn get(&mut self, url: &str) -> Option<image::Handle> {
+        let handle = self.images.get(url).cloned();
+        if handle.is_some() {
+            self.order.retain(|u| u != url);
+            self.order.push_back(url.to_string());
+        }
+
+        handle
+    }
+
+    fn insert(&mut self, url: String, handle: image::Handle) {
+        if self.images.insert(url.clone(), handle).is_none() {
+            while self.order.len() >= ART_CACHE_SIZE {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.images.remove(&oldest);
+                }
+            }
+        } else {
+            self.order.retain(|u| u != &url);
+        }
+        self.order.push_back(url);
+    }
+}
 
-        Self { artists, title }
+type SharedArtCache = Arc<Mutex<ArtCache>>;
+
+pub async fn fetch_art(url: &str) -> Option<image::Handle> {
+    if let Some(path) = url.strip_prefix("file://") {
+        tokio::fs::read(path)
+            .await
+            .map(image::Handle::from_bytes)
+            .inspect_err(|e| warn!("Failed to read album art {}: {}", url, e))
+            .ok()
+    } else if url.starts_with("https://") || url.starts_with("http://") {
+        let response = reqwest::get(url)
+            .await
+            .and_then(|r| r.error_for_status())
+            .inspect_err(|e| warn!("Failed to download album art {}: {}", url, e))
+            .ok()?;
+
+        response
+            .bytes()
+            .await
+            .map(image::Handle::from_bytes)
+            .inspect_err(|e| warn!("Failed to download album art {}: {}", url, e))
+            .ok()
+    } else {
+        None
     }
 }
 
+async fn get_art(
+    cache: &SharedArtCache,
+    metadata: Option<&MprisPlayerMetadata>,
+) -> Option<image::Handle> {
+    let url = metadata.and_then(|m| m.art_url.as_ref())?;
+
+    if let Some(handle) = cache.lock().ok().and_then(|mut cache| cache.get(url)) {
+        return Some(handle);
+    }
+
+    let handle = fetch_art(url).await?;
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(url.to_string(), handle.clone());
+    }
+
+    Some(handle)
+}
+
 #[derive(Debug, Clone)]
 pub struct MprisPlayerService {
     data: Vec<MprisPlayerData>,
     conn: zbus::Connection,
+    config: MprisConfig,
+    art_cache: SharedArtCache,
+    active_index: usize,
+}
+
+impl MprisPlayerService {
+    pub fn active_player(&self) -> Option<&MprisPlayerData> {
+        self.data.get(self.active_index)
+    }
 }
 
 impl Deref for MprisPlayerService {
@@ -71,17 +305,21 @@ impl Deref for MprisPlayerService {
     }
 }
 
-enum State {
-    Init,
-    Active(zbus::Connection),
-    Error,
-}
+type PlayerProxies = HashMap<String, MprisPlayerProxy<'static>>;
+
+type State = ListenerState<(zbus::Connection, PlayerProxies)>;
 
 #[derive(Debug, Clone)]
 pub enum MprisPlayerEvent {
     Refresh(Vec<MprisPlayerData>),
+    Active(usize),
     Metadata(String, Option<MprisPlayerMetadata>),
+    Art(String, Option<image::Handle>),
     Volume(String, Option<f64>),
+    Shuffle(String, Option<bool>),
+    LoopStatus(String, Option<LoopStatus>),
+    PlaybackStatus(String, Option<PlaybackStatus>),
+    Elapsed(Duration),
 }
 
 impl ReadOnlyService for MprisPlayerService {
@@ -90,35 +328,88 @@ impl ReadOnlyService for MprisPlayerService {
 
     fn update(&mut self, event: Self::UpdateEvent) {
         match event {
-            MprisPlayerEvent::Refresh(data) => self.data = data,
+            MprisPlayerEvent::Refresh(data) => {
+                let active = self.active_player().map(|d| d.service.clone());
+                self.data = data;
+                let is_playing =
+                    |d: &MprisPlayerData| d.playback_status == Some(PlaybackStatus::Playing);
+                // keep the selected player unless it stopped playing while another one started
+                self.active_index = active
+                    .and_then(|active| self.data.iter().position(|d| d.service == active))
+                    .filter(|index| {
+                        is_playing(&self.data[*index]) || !self.data.first().is_some_and(is_playing)
+                    })
+                    .unwrap_or_default();
+            }
+            MprisPlayerEvent::Active(index) => {
+                if index < self.data.len() {
+                    self.active_index = index;
+                }
+            }
             MprisPlayerEvent::Metadata(service, metadata) => {
                 let s = self.data.iter_mut().find(|d| d.service == service);
                 if let Some(s) = s {
+                    if s.metadata.as_ref().and_then(|m| m.track_id.as_ref())
+                        != metadata.as_ref().and_then(|m| m.track_id.as_ref())
+                    {
+                        s.position = Some(Duration::ZERO);
+                    }
                     s.metadata = metadata;
                 }
             }
+            MprisPlayerEvent::Art(service, art) => {
+                let s = self.data.iter_mut().find(|d| d.service == service);
+                if let Some(s) = s {
+                    s.art = art;
+                }
+            }
             MprisPlayerEvent::Volume(service, volume) => {
                 let s = self.data.iter_mut().find(|d| d.service == service);
                 if let Some(s) = s {
                     s.volume = volume;
                 }
             }
+            MprisPlayerEvent::Shuffle(service, shuffle) => {
+                let s = self.data.iter_mut().find(|d| d.service == service);
+                if let Some(s) = s {
+                    s.shuffle = shuffle;
+                }
+            }
+            MprisPlayerEvent::LoopStatus(service, loop_status) => {
+                let s = self.data.iter_mut().find(|d| d.service == service);
+                if let Some(s) = s {
+                    s.loop_status = loop_status;
+                }
+            }
+            MprisPlayerEvent::PlaybackStatus(service, playback_status) => {
+                let s = self.data.iter_mut().find(|d| d.service == service);
+                if let Some(s) = s {
+                    s.playback_status = playback_status;
+                }
+
+                // the player order depends on the playback status
+                let data = sort_by_playback_status(std::mem::take(&mut self.data));
+                self.update(MprisPlayerEvent::Refresh(data));
+            }
+            // Position is not signalled, estimate it while playing
+            MprisPlayerEvent::Elapsed(elapsed) => {
+                for s in self
+                    .data
+                    .iter_mut()
+                    .filter(|d| d.playback_status == Some(PlaybackStatus::Playing))
+                {
+                    let length = s.metadata.as_ref().and_then(|m| m.length);
+                    s.position = s.position.map(|p| match length {
+                        Some(length) => (p + elapsed).min(length),
+                        None => p + elapsed,
+                    });
+                }
+            }
         }
     }
 
     fn subscribe() -> Subscription<ServiceEvent<Self>> {
-        let id = TypeId::of::<Self>();
-
-        Subscription::run_with_id(
-            id,
-            channel(10, async |mut output| {
-                let mut state = State::Init;
-
-                loop {
-                    state = Self::start_listening(state, &mut output).await;
-                }
-            }),
-        )
+        Self::subscribe_with_config(&MprisConfig::default())
     }
 }
 
@@ -129,44 +420,134 @@ enum Event {
     NameOwner,
     Metadata(String, Option<MprisPlayerMetadata>),
     Volume(String, Option<f64>),
+    Shuffle(String, Option<bool>),
+    LoopStatus(String, Option<LoopStatus>),
+    PlaybackStatus(String, Option<PlaybackStatus>),
+}
+
+pub fn sort_players(
+    mut data: Vec<MprisPlayerData>,
+    preferred: &Option<String>,
+) -> Vec<MprisPlayerData> {
+    if let Some(index) = preferred
+        .as_ref()
+        .and_then(|preferred| data.iter().position(|d| &d.service == preferred))
+    {
+        let player = data.remove(index);
+        data.insert(0, player);
+    }
+
+    data
+}
+
+/// Playing players first, then paused and stopped ones, keeping the order otherwise
+pub fn sort_by_playback_status(mut data: Vec<MprisPlayerData>) -> Vec<MprisPlayerData> {
+    data.sort_by_key(|d| match d.playback_status {
+        Some(PlaybackStatus::Playing) => 0u8,
+        Some(PlaybackStatus::Paused) => 1,
+        Some(PlaybackStatus::Stopped) | None => 2,
+    });
+
+    data
 }
 
 impl MprisPlayerService {
-    async fn initialize_data(conn: &zbus::Connection) -> anyhow::Result<Vec<MprisPlayerData>> {
+    pub fn subscribe_with_config(config: &MprisConfig) -> Subscription<ServiceEvent<Self>> {
+        let id = TypeId::of::<Self>();
+        let config = config.clone();
+
+        Subscription::run_with_id(
+            format!("{:?}-{:?}", id, config),
+            channel(10, async move |mut output| {
+                let art_cache = SharedArtCache::default();
+                Retry::run(std::any::type_name::<Self>(), async |state| {
+                    Self::start_listening(state, &mut output, &config, &art_cache).await
+                })
+                .await;
+            }),
+        )
+    }
+
+    async fn initialize_data(
+        conn: &zbus::Connection,
+        config: &MprisConfig,
+        art_cache: &SharedArtCache,
+        proxies: &mut PlayerProxies,
+    ) -> anyhow::Result<Vec<MprisPlayerData>> {
         let dbus = DBusProxy::new(conn).await?;
         let names: Vec<String> = dbus
             .list_names()
             .await?
             .iter()
             .filter_map(|a| {
-                if a.starts_with(MPRIS_PLAYER_SERVICE_PREFIX) {
+                if a.starts_with(MPRIS_PLAYER_SERVICE_PREFIX)
+                    && !config.excluded_players.iter().any(|e| e == a.as_str())
+                {
                     Some(a.to_string())
                 } else {
                     None
                 }
             })
             .collect();
-        Ok(Self::get_mpris_player_data(conn, &names).await)
+        // players that stayed on the bus keep their proxy, gone ones are dropped
+        proxies.retain(|name, _| names.contains(name));
+        let data = Self::get_mpris_player_data(conn, &names, proxies, art_cache).await;
+        for d in data.iter() {
+            proxies
+                .entry(d.service.clone())
+                .or_insert_with(|| d.proxy.clone());
+        }
+
+        Ok(sort_players(data, &config.preferred_player))
     }
 
     async fn get_mpris_player_data(
         conn: &zbus::Connection,
         names: &[String],
+        proxies: &PlayerProxies,
+        art_cache: &SharedArtCache,
     ) -> Vec<MprisPlayerData> {
         join_all(names.iter().map(|s| async {
-            match MprisPlayerProxy::new(conn, s.to_string()).await {
+            let proxy = match proxies.get(s) {
+                Some(proxy) => Ok(proxy.clone()),
+                None => MprisPlayerProxy::new(conn, s.to_string()).await,
+            };
+
+            match proxy {
                 Ok(proxy) => {
-                    let metadata = proxy
-                        .metadata()
+                    let properties = proxy
+                        .get_all_player_properties()
                         .await
-                        .map_or(None, |m| Some(MprisPlayerMetadata::from(m)));
+                        .inspect_err(|err| warn!("Failed to read properties of {}: {}", s, err))
+                        .unwrap_or_default();
 
-                    let volume = proxy.volume().await.map(|v| v * 100.0).ok();
+                    let metadata = property::<HashMap<String, OwnedValue>>(&properties, "Metadata")
+                        .map(MprisPlayerMetadata::from);
+
+                    let volume = property::<f64>(&properties, "Volume").map(|v| v * 100.0);
+
+                    let shuffle = property::<bool>(&properties, "Shuffle");
+
+                    let loop_status =
+                        property::<String>(&properties, "LoopStatus").map(LoopStatus::from);
+
+                    let playback_status =
+                        property::<String>(&properties, "PlaybackStatus").map(PlaybackStatus::from);
+
+                    let position = property::<i64>(&properties, "Position")
+                        .map(|p| Duration::from_micros(p.max(0) as u64));
+
+                    let art = get_art(art_cache, metadata.as_ref()).await;
 
                     Some(MprisPlayerData {
                         service: s.to_string(),
                         metadata,
                         volume,
+                        shuffle,
+                        loop_status,
+                        playback_status,
+                        position,
+                        art,
                         proxy,
                     })
                 }
@@ -179,9 +560,14 @@ impl MprisPlayerService {
         .collect()
     }
 
-    async fn events(conn: &zbus::Connection) -> anyhow::Result<impl Stream<Item = Event> + use<>> {
+    async fn events(
+        conn: &zbus::Connection,
+        config: &MprisConfig,
+        art_cache: &SharedArtCache,
+        proxies: &mut PlayerProxies,
+    ) -> anyhow::Result<impl Stream<Item = Event> + use<>> {
         let dbus = DBusProxy::new(conn).await?;
-        let data = Self::initialize_data(conn).await?;
+        let data = Self::initialize_data(conn, config, art_cache, proxies).await?;
 
         let mut combined = SelectAll::new();
 
@@ -259,26 +645,117 @@ impl MprisPlayerService {
             );
         }
 
+        for s in data.iter() {
+            let shuffle = s.shuffle;
+
+            combined.push(
+                s.proxy
+                    .receive_shuffle_changed()
+                    .await
+                    .filter_map({
+                        let service = s.service.clone();
+                        move |v| {
+                            let service = service.clone();
+                            async move {
+                                let new_shuffle = v.get().await.ok();
+                                if shuffle == new_shuffle {
+                                    None
+                                } else {
+                                    debug!("Shuffle changed: {:?}", new_shuffle);
+
+                                    Some(Event::Shuffle(service, new_shuffle))
+                                }
+                            }
+                        }
+                    })
+                    .boxed(),
+            );
+        }
+
+        for s in data.iter() {
+            let loop_status = s.loop_status;
+
+            combined.push(
+                s.proxy
+                    .receive_loop_status_changed()
+                    .await
+                    .filter_map({
+                        let service = s.service.clone();
+                        move |v| {
+                            let service = service.clone();
+                            async move {
+                                let new_loop_status = v.get().await.map(LoopStatus::from).ok();
+                                if loop_status == new_loop_status {
+                                    None
+                                } else {
+                                    debug!("Loop status changed: {:?}", new_loop_status);
+
+                                    Some(Event::LoopStatus(service, new_loop_status))
+                                }
+                            }
+                        }
+                    })
+                    .boxed(),
+            );
+        }
+
+        for s in data.iter() {
+            let playback_status = s.playback_status;
+
+            combined.push(
+                s.proxy
+                    .receive_playback_status_changed()
+                    .await
+                    .filter_map({
+                        let service = s.service.clone();
+                        move |v| {
+                            let service = service.clone();
+                            async move {
+                                let new_playback_status =
+                                    v.get().await.map(PlaybackStatus::from).ok();
+                                if playback_status == new_playback_status {
+                                    None
+                                } else {
+                                    debug!("Playback status changed: {:?}", new_playback_status);
+
+                                    Some(Event::PlaybackStatus(service, new_playback_status))
+                                }
+                            }
+                        }
+                    })
+                    .boxed(),
+            );
+        }
+
         Ok(combined)
     }
 
-    async fn start_listening(state: State, output: &mut Sender<ServiceEvent<Self>>) -> State {
+    async fn start_listening(
+        state: State,
+        output: &mut Sender<ServiceEvent<Self>>,
+        config: &MprisConfig,
+        art_cache: &SharedArtCache,
+    ) -> State {
         match state {
-            State::Init => match zbus::Connection::session().await {
-                Ok(conn) => {
-                    let data = Self::initialize_data(&conn).await;
+            State::Init => match session_connection().await {
+                Some(conn) => {
+                    let mut proxies = PlayerProxies::new();
+                    let data = Self::initialize_data(&conn, config, art_cache, &mut proxies).await;
                     match data {
                         Ok(data) => {
                             info!("MPRIS player service initialized");
 
                             let _ = output
                                 .send(ServiceEvent::Init(MprisPlayerService {
-                                    data,
+                                    data: sort_by_playback_status(data),
                                     conn: conn.clone(),
+                                    config: config.clone(),
+                                    art_cache: art_cache.clone(),
+                                    active_index: 0,
                                 }))
                                 .await;
 
-                            State::Active(conn)
+                            State::Active((conn, proxies))
                         }
                         Err(err) => {
                             error!("Failed to initialize MPRIS player service: {}", err);
@@ -287,66 +764,106 @@ impl MprisPlayerService {
                         }
                     }
                 }
-                Err(err) => {
-                    error!("Failed to connect to system bus for MPRIS player: {}", err);
-                    State::Error
-                }
+                None => State::Error,
             },
-            State::Active(conn) => match Self::events(&conn).await {
-                Ok(events) => {
-                    let mut chunks = events.ready_chunks(10);
-
-                    while let Some(chunk) = chunks.next().await {
-                        debug!("MPRIS player service receive events: {:?}", chunk);
-
-                        let mut need_refresh = false;
-
-                        for event in chunk {
-                            match event {
-                                Event::NameOwner => {
-                                    need_refresh = true;
-                                }
-                                Event::Metadata(service, metadata) => {
-                                    let _ = output
-                                        .send(ServiceEvent::Update(MprisPlayerEvent::Metadata(
-                                            service, metadata,
-                                        )))
-                                        .await;
-                                }
-                                Event::Volume(service, volume) => {
-                                    let _ = output
-                                        .send(ServiceEvent::Update(MprisPlayerEvent::Volume(
-                                            service, volume,
-                                        )))
-                                        .await;
+            State::Active((conn, mut proxies)) => {
+                match Self::events(&conn, config, art_cache, &mut proxies).await {
+                    Ok(events) => {
+                        let mut chunks = events.ready_chunks(10);
+
+                        while let Some(chunk) = chunks.next().await {
+                            debug!("MPRIS player service receive events: {:?}", chunk);
+
+                            let mut need_refresh = false;
+
+                            for event in chunk {
+                                match event {
+                                    Event::NameOwner => {
+                                        need_refresh = true;
+                                    }
+                                    Event::Metadata(service, metadata) => {
+                                        let art = get_art(art_cache, metadata.as_ref()).await;
+
+                                        let _ = output
+                                            .send(ServiceEvent::Update(MprisPlayerEvent::Metadata(
+                                                service.clone(),
+                                                metadata,
+                                            )))
+                                            .await;
+                                        let _ = output
+                                            .send(ServiceEvent::Update(MprisPlayerEvent::Art(
+                                                service, art,
+                                            )))
+                                            .await;
+                                    }
+                                    Event::Volume(service, volume) => {
+                                        let _ = output
+                                            .send(ServiceEvent::Update(MprisPlayerEvent::Volume(
+                                                service, volume,
+                                            )))
+                                            .await;
+                                    }
+                                    Event::Shuffle(service, shuffle) => {
+                                        let _ = output
+                                            .send(ServiceEvent::Update(MprisPlayerEvent::Shuffle(
+                                                service, shuffle,
+                                            )))
+                                            .await;
+                                    }
+                                    Event::LoopStatus(service, loop_status) => {
+                                        let _ = output
+                                            .send(ServiceEvent::Update(
+                                                MprisPlayerEvent::LoopStatus(service, loop_status),
+                                            ))
+                                            .await;
+                                    }
+                                    Event::PlaybackStatus(service, playback_status) => {
+                                        let _ = output
+                                            .send(ServiceEvent::Update(
+                                                MprisPlayerEvent::PlaybackStatus(
+                                                    service,
+                                                    playback_status,
+                                                ),
+                                            ))
+                                            .await;
+                                    }
                                 }
                             }
-                        }
 
-                        if need_refresh {
-                            match Self::initialize_data(&conn).await {
-                                Ok(data) => {
-                                    let _ = output
-                                        .send(ServiceEvent::Update(MprisPlayerEvent::Refresh(data)))
-                                        .await;
-                                }
-                                Err(err) => {
-                                    error!("Failed to fetch MPRIS player data: {}", err);
+                            if need_refresh {
+                                match Self::initialize_data(&conn, config, art_cache, &mut proxies)
+                                    .await
+                                {
+                                    Ok(data) => {
+                                        let _ = output
+                                            .send(ServiceEvent::Update(MprisPlayerEvent::Refresh(
+                                                sort_by_playback_status(data),
+                                            )))
+                                            .await;
+                                    }
+                                    Err(err) => {
+                                        error!("Failed to fetch MPRIS player data: {}", err);
+                                    }
                                 }
-                            }
 
-                            break;
+                                break;
+                            }
                         }
+
+                        State::Active((conn, proxies))
                     }
+                    Err(err) if is_service_unknown(&err) => {
+                        warn!("MPRIS bus went away, reconnecting: {}", err);
 
-                    State::Active(conn)
-                }
-                Err(err) => {
-                    error!("Failed to listen for MPRIS player events: {}", err);
+                        State::Init
+                    }
+                    Err(err) => {
+                        error!("Failed to listen for MPRIS player events: {}", err);
 
-                    State::Error
+                        State::Error
+                    }
                 }
-            },
+            }
             State::Error => {
                 let _ = pending::<u8>().next().await;
 
@@ -368,18 +885,53 @@ pub enum PlayerCommand {
     PlayPause,
     Next,
     Volume(f64),
+    SetShuffle(bool),
+    SetLoop(LoopStatus),
+    SelectPlayer(String),
+    CyclePlayer,
+    Seek(Duration),
+    SetRating(f32),
 }
 
 impl Service for MprisPlayerService {
     type Command = MprisPlayerCommand;
 
     fn command(&mut self, command: Self::Command) -> iced::Task<ServiceEvent<Self>> {
+        match &command.command {
+            PlayerCommand::SelectPlayer(service) => {
+                return match self.data.iter().position(|d| &d.service == service) {
+                    Some(index) => {
+                        iced::Task::done(ServiceEvent::Update(MprisPlayerEvent::Active(index)))
+                    }
+                    None => iced::Task::none(),
+                };
+            }
+            PlayerCommand::CyclePlayer => {
+                return if self.data.is_empty() {
+                    iced::Task::none()
+                } else {
+                    iced::Task::done(ServiceEvent::Update(MprisPlayerEvent::Active(
+                        (self.active_index + 1) % self.data.len(),
+                    )))
+                };
+            }
+            _ => {}
+        }
+
         {
             let names: Vec<String> = self.data.iter().map(|d| d.service.clone()).collect();
+            let proxies: PlayerProxies = self
+                .data
+                .iter()
+                .map(|d| (d.service.clone(), d.proxy.clone()))
+                .collect();
             let s = self.data.iter().find(|d| d.service == command.service_name);
             if let Some(s) = s {
                 let mpris_player_proxy = s.proxy.clone();
+                let track_id = s.metadata.as_ref().and_then(|m| m.track_id.clone());
                 let conn = self.conn.clone();
+                let preferred_player = self.config.preferred_player.clone();
+                let art_cache = self.art_cache.clone();
                 iced::Task::perform(
                     async move {
                         match command.command {
@@ -407,8 +959,41 @@ impl Service for MprisPlayerService {
                                     .await
                                     .inspect_err(|e| error!("Set volume command error: {}", e));
                             }
+                            PlayerCommand::SetShuffle(v) => {
+                                let _ = mpris_player_proxy
+                                    .set_shuffle(v)
+                                    .await
+                                    .inspect_err(|e| error!("Set shuffle command error: {}", e));
+                            }
+                            PlayerCommand::SetLoop(v) => {
+                                let _ = mpris_player_proxy
+                                    .set_loop_status(v.into())
+                                    .await
+                                    .inspect_err(|e| error!("Set loop command error: {}", e));
+                            }
+                            PlayerCommand::Seek(position) => {
+                                match track_id.as_deref().map(ObjectPath::try_from) {
+                                    Some(Ok(track_id)) => {
+                                        let _ = mpris_player_proxy
+                                            .set_position(&track_id, position.as_micros() as i64)
+                                            .await
+                                            .inspect_err(|e| error!("Seek command error: {}", e));
+                                    }
+                                    _ => warn!("Cannot seek, the current track has no track id"),
+                                }
+                            }
+                            PlayerCommand::SetRating(v) => {
+                                let _ = mpris_player_proxy
+                                    .set_rating(v as f64)
+                                    .await
+                                    .inspect_err(|e| error!("Set rating command error: {}", e));
+                            }
+                            PlayerCommand::SelectPlayer(_) | PlayerCommand::CyclePlayer => {}
                         }
-                        Self::get_mpris_player_data(&conn, &names).await
+                        let data =
+                            Self::get_mpris_player_data(&conn, &names, &proxies, &art_cache).await;
+
+                        sort_by_playback_status(sort_players(data, &preferred_player))
                     },
                     |data| ServiceEvent::Update(MprisPlayerEvent::Refresh(data)),
                 )
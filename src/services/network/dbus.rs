@@ -1,4 +1,6 @@
-use super::{AccessPoint, ActiveConnectionInfo, KnownConnection, Vpn};
+use super::{
+    AccessPoint, ActiveConnectionInfo, InterfaceKind, KnownConnection, NetworkInterface, Vpn,
+};
 use iced::futures::StreamExt;
 use itertools::Itertools;
 use log::debug;
@@ -138,6 +140,125 @@ impl NetworkDbus<'_> {
         Ok(info)
     }
 
+    pub async fn interfaces(&self, filter: &[String]) -> anyhow::Result<Vec<NetworkInterface>> {
+        let devices = self.devices().await?;
+
+        let mut interfaces = Vec::with_capacity(devices.len());
+        for d in devices {
+            let device = DeviceProxy::builder(self.0.inner().connection())
+                .path(d)?
+                .build()
+                .await?;
+
+            let name = device.interface().await?;
+            if name == "lo" || (!filter.is_empty() && !filter.contains(&name)) {
+                continue;
+            }
+
+            let connected = device
+                .state()
+                .await
+                .map(DeviceState::from)
+                .unwrap_or_default()
+                == DeviceState::Activated;
+            if filter.is_empty() && !connected {
+                continue;
+            }
+
+            let kind = match device.device_type().await.map(DeviceType::from) {
+                Ok(DeviceType::Ethernet) => InterfaceKind::Ethernet {
+                    speed_mbps: link_speed(&name),
+                },
+                Ok(DeviceType::Wifi) => {
+                    let (ssid, signal) = self
+                        .active_access_point(device.0.path())
+                        .await
+                        .unwrap_or_default();
+
+                    InterfaceKind::WiFi { ssid, signal }
+                }
+                Ok(DeviceType::Modem) => InterfaceKind::Cellular {
+                    operator: self.modem_operator(&device).await,
+                },
+                Ok(DeviceType::WireGuard | DeviceType::TunTap) => InterfaceKind::Vpn,
+                _ => InterfaceKind::Other,
+            };
+
+            let ip4 = if connected {
+                self.ip4_address(device.ip4_config().await?).await
+            } else {
+                None
+            };
+
+            interfaces.push(NetworkInterface {
+                name,
+                kind,
+                connected,
+                ip4,
+            });
+        }
+
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(interfaces)
+    }
+
+    async fn active_access_point(&self, device: &ObjectPath<'_>) -> Option<(String, u8)> {
+        let wireless_device = WirelessDeviceProxy::builder(self.0.inner().connection())
+            .path(device)
+            .ok()?
+            .build()
+            .await
+            .ok()?;
+        let access_point = AccessPointProxy::builder(self.0.inner().connection())
+            .path(wireless_device.active_access_point().await.ok()?)
+            .ok()?
+            .build()
+            .await
+            .ok()?;
+
+        Some((
+            String::from_utf8_lossy(&access_point.ssid().await.ok()?).into_owned(),
+            access_point.strength().await.unwrap_or_default(),
+        ))
+    }
+
+    // NetworkManager doesn't know the operator, but the device udi points
+    // to the ModemManager object that does
+    async fn modem_operator(&self, device: &DeviceProxy<'_>) -> Option<String> {
+        let modem = Modem3gppProxy::builder(self.0.inner().connection())
+            .path(device.udi().await.ok()?)
+            .ok()?
+            .build()
+            .await
+            .ok()?;
+
+        modem
+            .operator_name()
+            .await
+            .ok()
+            .filter(|name| !name.is_empty())
+    }
+
+    async fn ip4_address(&self, path: OwnedObjectPath) -> Option<String> {
+        let ip4_config = Ip4ConfigProxy::builder(self.0.inner().connection())
+            .path(path)
+            .ok()?
+            .build()
+            .await
+            .ok()?;
+
+        ip4_config
+            .address_data()
+            .await
+            .ok()?
+            .iter()
+            .find_map(|address| match address.get("address").map(|v| v.deref()) {
+                Some(Value::Str(v)) => Some(v.to_string()),
+                _ => None,
+            })
+    }
+
     pub async fn known_connections(
         &self,
         wireless_access_points: &[AccessPoint],
@@ -416,11 +537,23 @@ impl NetworkSettingsDbus<'_> {
     }
 }
 
+// the kernel reports -1 while the link speed is unknown
+fn link_speed(interface: &str) -> Option<u32> {
+    std::fs::read_to_string(format!("/sys/class/net/{interface}/speed"))
+        .ok()?
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|speed| *speed > 0)
+        .map(|speed| speed as u32)
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DeviceType {
     Ethernet,
     Wifi,
     Bluetooth,
+    Modem,
     TunTap,
     WireGuard,
     Generic,
@@ -435,6 +568,7 @@ impl From<u32> for DeviceType {
             1 => DeviceType::Ethernet,
             2 => DeviceType::Wifi,
             5 => DeviceType::Bluetooth,
+            8 => DeviceType::Modem,
             14 => DeviceType::Generic,
             16 => DeviceType::TunTap,
             29 => DeviceType::WireGuard,
@@ -567,7 +701,7 @@ pub trait NetworkManager {
     default_path = "/org/freedesktop/NetworkManager/Connection/Active",
     interface = "org.freedesktop.NetworkManager.Connection.Active"
 )]
-trait ActiveConnection {
+pub trait ActiveConnection {
     #[zbus(property)]
     fn id(&self) -> Result<String>;
 
@@ -604,6 +738,33 @@ pub trait Device {
 
     #[zbus(property)]
     fn state(&self) -> Result<u32>;
+
+    #[zbus(property)]
+    fn interface(&self) -> Result<String>;
+
+    #[zbus(property)]
+    fn ip4_config(&self) -> Result<OwnedObjectPath>;
+
+    #[zbus(property)]
+    fn udi(&self) -> Result<String>;
+}
+
+#[proxy(
+    default_service = "org.freedesktop.ModemManager1",
+    interface = "org.freedesktop.ModemManager1.Modem.Modem3gpp"
+)]
+trait Modem3gpp {
+    #[zbus(property)]
+    fn operator_name(&self) -> Result<String>;
+}
+
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.IP4Config",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+trait Ip4Config {
+    #[zbus(property)]
+    fn address_data(&self) -> Result<Vec<HashMap<String, OwnedValue>>>;
 }
 
 #[proxy(
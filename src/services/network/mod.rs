@@ -1,20 +1,30 @@
-use super::{Service, ServiceEvent};
-use crate::services::{ReadOnlyService, bluetooth::BluetoothService};
+use super::{ListenerState, Retry, Service, ServiceEvent};
+use crate::{
+    config::NetworkConfig,
+    services::{ReadOnlyService, bluetooth::BluetoothService},
+    utils::RateLimitedSender,
+};
 use dbus::{
-    AccessPointProxy, ConnectivityState, DeviceProxy, DeviceState, NetworkDbus,
-    NetworkSettingsDbus, WirelessDeviceProxy,
+    AccessPointProxy, ActiveConnectionProxy, ConnectivityState, DeviceProxy, DeviceState,
+    NetworkDbus, NetworkSettingsDbus, WirelessDeviceProxy,
 };
 use iced::{
     Subscription, Task,
     futures::{
         SinkExt, Stream, StreamExt,
         channel::mpsc::Sender,
-        stream::{pending, select_all},
+        future::{self, Either},
+        stream::{self, BoxStream, pending, select, select_all},
     },
     stream::channel,
 };
-use log::{debug, error, info};
-use std::{any::TypeId, collections::HashMap, ops::Deref};
+use log::{debug, error, info, warn};
+use std::{
+    any::TypeId,
+    collections::{HashMap, VecDeque},
+    ops::Deref,
+    time::{Duration, Instant},
+};
 use tokio::process::Command;
 use zbus::zvariant::{ObjectPath, OwnedObjectPath};
 
@@ -35,6 +45,8 @@ pub enum NetworkEvent {
     Strength((String, u8)),
     RequestPasswordForSSID(String),
     ScanningNearbyWifi,
+    Throughput(NetworkThroughput),
+    Interfaces(Vec<NetworkInterface>),
 }
 
 #[derive(Debug, Clone)]
@@ -96,6 +108,44 @@ impl ActiveConnectionInfo {
     }
 }
 
+impl NetworkData {
+    pub fn vpn_active(&self) -> bool {
+        self.vpn_name().is_some()
+    }
+
+    pub fn vpn_name(&self) -> Option<&str> {
+        self.active_connections.iter().find_map(|c| match c {
+            ActiveConnectionInfo::Vpn { name, .. } => Some(name.as_str()),
+            _ => None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterfaceKind {
+    Ethernet { speed_mbps: Option<u32> },
+    WiFi { ssid: String, signal: u8 },
+    Cellular { operator: Option<String> },
+    Vpn,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkInterface {
+    pub name: String,
+    pub kind: InterfaceKind,
+    pub connected: bool,
+    pub ip4: Option<String>,
+}
+
+const THROUGHPUT_HISTORY_SIZE: usize = 60;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkThroughput {
+    pub rx_bps: u64,
+    pub tx_bps: u64,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct NetworkData {
     pub wifi_present: bool,
@@ -106,12 +156,16 @@ pub struct NetworkData {
     pub airplane_mode: bool,
     pub connectivity: ConnectivityState,
     pub scanning_nearby_wifi: bool,
+    pub throughput: Option<NetworkThroughput>,
+    pub throughput_history: VecDeque<NetworkThroughput>,
+    pub interfaces: Vec<NetworkInterface>,
 }
 
 #[derive(Debug, Clone)]
 pub struct NetworkService {
     data: NetworkData,
     conn: zbus::Connection,
+    pub config: NetworkConfig,
 }
 
 impl Deref for NetworkService {
@@ -122,11 +176,7 @@ impl Deref for NetworkService {
     }
 }
 
-enum State {
-    Init,
-    Active(zbus::Connection),
-    Error,
-}
+type State = ListenerState<zbus::Connection>;
 
 impl ReadOnlyService for NetworkService {
     type UpdateEvent = NetworkEvent;
@@ -183,28 +233,46 @@ impl ReadOnlyService for NetworkService {
             NetworkEvent::WirelessAccessPoint(wireless_access_points) => {
                 self.data.wireless_access_points = wireless_access_points;
             }
+            NetworkEvent::Throughput(throughput) => {
+                self.data.throughput = Some(throughput);
+
+                self.data.throughput_history.push_back(throughput);
+                if self.data.throughput_history.len() > THROUGHPUT_HISTORY_SIZE {
+                    self.data.throughput_history.pop_front();
+                }
+            }
+            NetworkEvent::Interfaces(interfaces) => {
+                self.data.interfaces = interfaces;
+            }
             NetworkEvent::RequestPasswordForSSID(_) => {}
         }
     }
 
     fn subscribe() -> Subscription<ServiceEvent<Self>> {
-        let id = TypeId::of::<Self>();
+        Self::subscribe_with_config(&NetworkConfig::default())
+    }
+}
+
+impl NetworkService {
+    pub fn subscribe_with_config(config: &NetworkConfig) -> Subscription<ServiceEvent<Self>> {
+        let id = format!("{:?}-{:?}", TypeId::of::<Self>(), config);
+        let config = config.clone();
 
         Subscription::run_with_id(
             id,
-            channel(50, async |mut output| {
-                let mut state = State::Init;
-
-                loop {
-                    state = NetworkService::start_listening(state, &mut output).await;
-                }
+            channel(50, async move |mut output| {
+                Retry::run(std::any::type_name::<NetworkService>(), async |state| {
+                    NetworkService::start_listening(state, &config, &mut output).await
+                })
+                .await;
             }),
         )
     }
-}
 
-impl NetworkService {
-    async fn initialize_data(conn: &zbus::Connection) -> anyhow::Result<NetworkData> {
+    async fn initialize_data(
+        conn: &zbus::Connection,
+        config: &NetworkConfig,
+    ) -> anyhow::Result<NetworkData> {
         let nm = NetworkDbus::new(conn).await?;
 
         // airplane mode
@@ -229,6 +297,9 @@ impl NetworkService {
         let known_connections = nm.known_connections(&wireless_access_points).await?;
         debug!("Known connections: {:?}", known_connections);
 
+        let interfaces = nm.interfaces(&config.interfaces).await?;
+        debug!("Interfaces: {:?}", interfaces);
+
         Ok(NetworkData {
             wifi_present,
             active_connections,
@@ -238,14 +309,21 @@ impl NetworkService {
             wireless_access_points,
             known_connections,
             scanning_nearby_wifi: false,
+            throughput: None,
+            throughput_history: VecDeque::new(),
+            interfaces,
         })
     }
 
-    async fn start_listening(state: State, output: &mut Sender<ServiceEvent<Self>>) -> State {
+    async fn start_listening(
+        state: State,
+        config: &NetworkConfig,
+        output: &mut Sender<ServiceEvent<Self>>,
+    ) -> State {
         match state {
             State::Init => match zbus::Connection::system().await {
                 Ok(conn) => {
-                    let data = NetworkService::initialize_data(&conn).await;
+                    let data = NetworkService::initialize_data(&conn, config).await;
 
                     match data {
                         Ok(data) => {
@@ -255,6 +333,7 @@ impl NetworkService {
                                 .send(ServiceEvent::Init(NetworkService {
                                     data,
                                     conn: conn.clone(),
+                                    config: config.clone(),
                                 }))
                                 .await;
 
@@ -276,14 +355,24 @@ impl NetworkService {
             State::Active(conn) => {
                 info!("Listening for network events");
 
-                match NetworkService::events(&conn).await {
+                match NetworkService::events(&conn, config).await {
                     Ok(mut events) => {
+                        let mut throughput_output = RateLimitedSender::new(
+                            output.clone(),
+                            Duration::from_millis(config.min_update_interval_ms),
+                        );
+
                         while let Some(event) = events.next().await {
                             let mut exit_loop = false;
                             if let NetworkEvent::WirelessDevice { .. } = event {
                                 exit_loop = true;
                             }
-                            let _ = output.send(ServiceEvent::Update(event)).await;
+
+                            if let NetworkEvent::Throughput(_) = event {
+                                throughput_output.send(ServiceEvent::Update(event)).await;
+                            } else {
+                                let _ = output.send(ServiceEvent::Update(event)).await;
+                            }
 
                             if exit_loop {
                                 break;
@@ -313,6 +402,7 @@ impl NetworkService {
 
     async fn events(
         conn: &zbus::Connection,
+        config: &NetworkConfig,
     ) -> anyhow::Result<impl Stream<Item = NetworkEvent> + use<>> {
         let nm = NetworkDbus::new(conn).await?;
         let settings = NetworkSettingsDbus::new(conn).await?;
@@ -357,6 +447,26 @@ impl NetworkService {
             })
             .boxed();
 
+        let interfaces_changes = nm
+            .receive_active_connections_changed()
+            .await
+            .then({
+                let conn = conn.clone();
+                let filter = config.interfaces.clone();
+                move |_| {
+                    let conn = conn.clone();
+                    let filter = filter.clone();
+                    async move {
+                        let nm = NetworkDbus::new(&conn).await.unwrap();
+                        let value = nm.interfaces(&filter).await.unwrap_or_default();
+
+                        debug!("Interfaces changed: {:?}", value);
+                        NetworkEvent::Interfaces(value)
+                    }
+                }
+            })
+            .boxed();
+
         let devices = nm.wireless_devices().await.unwrap_or_default();
 
         let wireless_devices_changed = nm
@@ -393,6 +503,44 @@ impl NetworkService {
             })
             .boxed();
 
+        // Refresh the active connections when a wireless device roams to another access point
+        // or changes its state, so the SSID and signal strength stay up to date
+        let mut active_ap_changes = Vec::with_capacity(devices.len());
+        for device_path in devices.iter() {
+            let wd = WirelessDeviceProxy::builder(conn)
+                .path(device_path.clone())?
+                .build()
+                .await?;
+            let dp = DeviceProxy::builder(conn)
+                .path(device_path.clone())?
+                .build()
+                .await?;
+
+            let active_ap_changed = wd.receive_active_access_point_changed().await.map(|_| ());
+            let state_changed = dp.receive_state_changed().await.map(|_| ());
+
+            active_ap_changes.push(
+                select(active_ap_changed, state_changed)
+                    .then({
+                        let conn = conn.clone();
+                        move |_| {
+                            let conn = conn.clone();
+                            async move {
+                                let nm = NetworkDbus::new(&conn).await.unwrap();
+                                let value = nm.active_connections_info().await.unwrap_or_default();
+
+                                debug!("Active access point changed: {:?}", value);
+                                NetworkEvent::ActiveConnections(value)
+                            }
+                        }
+                    })
+                    .boxed(),
+            );
+        }
+        let active_ap_changes = select_all(active_ap_changes).boxed();
+
+        let vpn_state_changes = Self::vpn_state_changes(conn).await?.boxed();
+
         // When devices list change I need to update the wireless device state changes
         let wireless_ac = nm.wireless_access_points().await?;
 
@@ -505,19 +653,145 @@ impl NetworkService {
             })
             .boxed();
 
+        let throughput = if config.show_throughput {
+            NetworkService::throughput_events(config.throughput_interface.clone()).boxed()
+        } else {
+            pending::<NetworkEvent>().boxed()
+        };
+
         let events = select_all(vec![
             wireless_enabled,
             wireless_devices_changed,
             connectivity_changed,
             active_connections_changes,
+            interfaces_changes,
+            active_ap_changes,
+            vpn_state_changes,
             access_points,
             strength_changes,
             known_connections,
+            throughput,
         ]);
 
         Ok(events)
     }
 
+    async fn vpn_state_streams(
+        conn: &zbus::Connection,
+    ) -> anyhow::Result<BoxStream<'static, NetworkEvent>> {
+        let nm = NetworkDbus::new(conn).await?;
+        let active_connections = nm.active_connections_info().await?;
+
+        let mut vpn_state_changes = Vec::new();
+        for ac in active_connections {
+            if let ActiveConnectionInfo::Vpn { object_path, .. } = ac {
+                let acp = ActiveConnectionProxy::builder(conn)
+                    .path(object_path)?
+                    .build()
+                    .await?;
+
+                vpn_state_changes.push(
+                    acp.receive_state_changed()
+                        .await
+                        .then({
+                            let conn = conn.clone();
+                            move |_| {
+                                let conn = conn.clone();
+                                async move {
+                                    let nm = NetworkDbus::new(&conn).await.unwrap();
+                                    let value =
+                                        nm.active_connections_info().await.unwrap_or_default();
+
+                                    debug!("Vpn state changed: {:?}", value);
+                                    NetworkEvent::ActiveConnections(value)
+                                }
+                            }
+                        })
+                        .boxed(),
+                );
+            }
+        }
+
+        Ok(select_all(vpn_state_changes).boxed())
+    }
+
+    // the vpn connections come and go with the active connections, their
+    // state streams are rebuilt each time the list changes
+    async fn vpn_state_changes(
+        conn: &zbus::Connection,
+    ) -> anyhow::Result<impl Stream<Item = NetworkEvent> + use<>> {
+        let nm = NetworkDbus::new(conn).await?;
+        let active_connections_changes = nm.receive_active_connections_changed().await.boxed();
+        let vpn_states = Self::vpn_state_streams(conn).await?;
+
+        Ok(stream::unfold(
+            (conn.clone(), active_connections_changes, vpn_states),
+            |(conn, mut changes, mut vpn_states)| async move {
+                loop {
+                    let next = match future::select(changes.next(), vpn_states.next()).await {
+                        Either::Left((change, _)) => Either::Left(change),
+                        Either::Right((event, _)) => Either::Right(event),
+                    };
+
+                    match next {
+                        Either::Left(change) => {
+                            change?;
+
+                            vpn_states =
+                                Self::vpn_state_streams(&conn).await.unwrap_or_else(|err| {
+                                    warn!("Failed to listen for the vpn state changes: {}", err);
+                                    pending().boxed()
+                                });
+                        }
+                        Either::Right(Some(event)) => {
+                            return Some((event, (conn, changes, vpn_states)));
+                        }
+                        // no vpn is active, wait for the next change
+                        Either::Right(None) => {
+                            vpn_states = pending().boxed();
+                        }
+                    }
+                }
+            },
+        ))
+    }
+
+    fn throughput_events(interface: String) -> impl Stream<Item = NetworkEvent> {
+        let interval = tokio::time::interval(Duration::from_secs(1));
+
+        stream::unfold(
+            (interval, None::<(String, u64, u64, Instant)>),
+            move |(mut interval, last)| {
+                let interface = interface.clone();
+                async move {
+                    interval.tick().await;
+
+                    let current = read_interface_bytes(&interface).await;
+                    let now = Instant::now();
+
+                    let throughput = match (&last, &current) {
+                        (Some((last_name, last_rx, last_tx, last_check)), Some((name, rx, tx)))
+                            if last_name == name =>
+                        {
+                            let elapsed = now.duration_since(*last_check).as_secs_f64();
+
+                            Some(NetworkThroughput {
+                                rx_bps: (rx.saturating_sub(*last_rx) as f64 / elapsed) as u64,
+                                tx_bps: (tx.saturating_sub(*last_tx) as f64 / elapsed) as u64,
+                            })
+                        }
+                        _ => None,
+                    };
+
+                    let last = current.map(|(name, rx, tx)| (name, rx, tx, now));
+
+                    Some((throughput, (interval, last)))
+                }
+            },
+        )
+        .filter_map(|throughput| async move { throughput.map(NetworkEvent::Throughput) })
+    }
+
     async fn set_airplane_mode(conn: &zbus::Connection, airplane_mode: bool) -> anyhow::Result<()> {
         Command::new("/usr/sbin/rfkill")
             .arg(if airplane_mode { "block" } else { "unblock" })
@@ -695,3 +969,39 @@ impl Service for NetworkService {
         }
     }
 }
+
+async fn default_route_interface() -> Option<String> {
+    let routes = tokio::fs::read_to_string("/proc/net/route").await.ok()?;
+
+    routes.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let interface = fields.next()?;
+        let destination = fields.next()?;
+
+        (destination == "00000000").then(|| interface.to_owned())
+    })
+}
+
+async fn read_interface_bytes(interface: &str) -> Option<(String, u64, u64)> {
+    let interface = if interface == "auto" {
+        default_route_interface().await?
+    } else {
+        interface.to_owned()
+    };
+
+    let dev = tokio::fs::read_to_string("/proc/net/dev").await.ok()?;
+
+    dev.lines().skip(2).find_map(|line| {
+        let (name, counters) = line.split_once(':')?;
+        if name.trim() != interface {
+            return None;
+        }
+
+        let counters = counters
+            .split_whitespace()
+            .map(|v| v.parse::<u64>().unwrap_or_default())
+            .collect::<Vec<_>>();
+
+        Some((interface.clone(), *counters.first()?, *counters.get(8)?))
+    })
+}
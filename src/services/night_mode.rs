@@ -0,0 +1,182 @@
+use crate::config::{NightModeConfig, NightModeTool};
+use iced::Task;
+use log::{debug, warn};
+use std::{process::Stdio, time::Duration};
+use tokio::process::{Child, Command};
+
+// the tools exit right away when the compositor lacks the gamma protocol
+const STARTUP_CHECK_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+pub enum NightModeCommand {
+    Toggle,
+    SetTemperature(u32),
+}
+
+/// Sent once the task returned by [`NightModeManager::command`] completes.
+#[derive(Debug, Clone, Copy)]
+pub enum NightModeEvent {
+    Started,
+    Stopped,
+}
+
+#[derive(Debug, Default)]
+pub struct NightModeManager {
+    child: Option<Child>,
+    temperature: Option<u32>,
+    // the tool is started again with the new temperature once the old one exited
+    restart_pending: bool,
+}
+
+impl NightModeManager {
+    pub fn is_active(&self) -> bool {
+        self.child.is_some() || self.restart_pending
+    }
+
+    pub fn temperature(&self, config: &NightModeConfig) -> u32 {
+        self.temperature.unwrap_or(config.night_temp)
+    }
+
+    pub fn preview_temperature(&mut self, temperature: u32) {
+        self.temperature = Some(temperature);
+    }
+
+    /// The returned task completes once the command settled, its event should
+    /// be given back to [`Self::settled`].
+    pub fn command(
+        &mut self,
+        command: NightModeCommand,
+        config: &NightModeConfig,
+    ) -> Task<NightModeEvent> {
+        match command {
+            NightModeCommand::Toggle => {
+                if self.is_active() {
+                    self.restart_pending = false;
+                    self.stop()
+                } else {
+                    self.start(config)
+                }
+            }
+            NightModeCommand::SetTemperature(temperature) => {
+                self.temperature = Some(temperature);
+
+                // two instances would fight over the gamma control
+                if self.child.is_some() {
+                    self.restart_pending = true;
+                    self.stop()
+                } else {
+                    Task::none()
+                }
+            }
+        }
+    }
+
+    pub fn settled(
+        &mut self,
+        event: NightModeEvent,
+        config: &NightModeConfig,
+    ) -> Task<NightModeEvent> {
+        match event {
+            NightModeEvent::Started => {
+                self.check();
+                Task::none()
+            }
+            NightModeEvent::Stopped if self.restart_pending => {
+                self.restart_pending = false;
+                self.start(config)
+            }
+            NightModeEvent::Stopped => Task::none(),
+        }
+    }
+
+    fn check(&mut self) {
+        if let Some(child) = self.child.as_mut() {
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    warn!("Night mode exited: {}", status);
+                    self.child = None;
+                }
+                Ok(None) => {}
+                Err(err) => warn!("Failed to check the night mode process: {}", err),
+            }
+        }
+    }
+
+    fn start(&mut self, config: &NightModeConfig) -> Task<NightModeEvent> {
+        let night_temp = self.temperature(config);
+        let day_temp = config.day_temp.max(night_temp + 1);
+
+        let mut command = match config.tool {
+            NightModeTool::Wlsunset => {
+                let mut command = Command::new("wlsunset");
+                command
+                    .arg("-t")
+                    .arg(night_temp.to_string())
+                    .arg("-T")
+                    .arg(day_temp.to_string());
+
+                if let (Some(latitude), Some(longitude)) = (config.latitude, config.longitude) {
+                    command
+                        .arg("-l")
+                        .arg(latitude.to_string())
+                        .arg("-L")
+                        .arg(longitude.to_string());
+                }
+
+                command
+            }
+            NightModeTool::Gammastep => {
+                let mut command = Command::new("gammastep");
+                command
+                    .arg("-t")
+                    .arg(format!("{}:{}", day_temp, night_temp));
+
+                if let (Some(latitude), Some(longitude)) = (config.latitude, config.longitude) {
+                    command.arg("-l").arg(format!("{}:{}", latitude, longitude));
+                }
+
+                command
+            }
+        };
+
+        debug!("Starting night mode: {:?}", command);
+
+        match command
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+        {
+            Ok(child) => {
+                self.child = Some(child);
+                self.check();
+
+                Task::perform(tokio::time::sleep(STARTUP_CHECK_DELAY), |_| {
+                    NightModeEvent::Started
+                })
+            }
+            Err(err) => {
+                warn!("Failed to start night mode: {}", err);
+                Task::none()
+            }
+        }
+    }
+
+    // completes once the process exited
+    fn stop(&mut self) -> Task<NightModeEvent> {
+        match self.child.take() {
+            Some(mut child) => {
+                debug!("Stopping night mode");
+
+                Task::perform(async move { child.kill().await }, |result| {
+                    if let Err(err) = result {
+                        warn!("Failed to stop night mode: {}", err);
+                    }
+
+                    NightModeEvent::Stopped
+                })
+            }
+            None => Task::done(NightModeEvent::Stopped),
+        }
+    }
+}
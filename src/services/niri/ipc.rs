@@ -0,0 +1,173 @@
+use anyhow::{Context, anyhow};
+use log::debug;
+use serde_json::{Value, json};
+use std::path::Path;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+    net::UnixStream,
+};
+
+pub fn socket_path() -> Option<String> {
+    std::env::var("NIRI_SOCKET").ok()
+}
+
+async fn write_request(stream: &mut UnixStream, request: &Value) -> anyhow::Result<()> {
+    let mut payload = serde_json::to_vec(request)?;
+    payload.push(b'\n');
+    stream.write_all(&payload).await?;
+
+    Ok(())
+}
+
+async fn send(request: &Value) -> anyhow::Result<BufReader<UnixStream>> {
+    let path = socket_path().context("NIRI_SOCKET is not set")?;
+    let mut stream = UnixStream::connect(path).await?;
+
+    write_request(&mut stream, request).await?;
+
+    Ok(BufReader::new(stream))
+}
+
+async fn read_reply(reader: &mut BufReader<UnixStream>) -> anyhow::Result<Value> {
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+
+    parse_reply(&line)
+}
+
+fn parse_reply(line: &str) -> anyhow::Result<Value> {
+    match serde_json::from_str::<Value>(line)? {
+        Value::Object(mut reply) => {
+            if let Some(response) = reply.remove("Ok") {
+                Ok(response)
+            } else {
+                Err(anyhow!(
+                    "niri error: {}",
+                    reply.remove("Err").unwrap_or_default()
+                ))
+            }
+        }
+        reply => Err(anyhow!("unexpected niri reply: {}", reply)),
+    }
+}
+
+pub async fn request(request: Value) -> anyhow::Result<Value> {
+    let mut reader = send(&request).await?;
+
+    read_reply(&mut reader).await
+}
+
+pub async fn action(action: Value) -> anyhow::Result<()> {
+    request(json!({ "Action": action })).await.map(|_| ())
+}
+
+/// Requests sent one after the other on the same connection, e.g. an action
+/// followed by the query of the state it changed.
+#[derive(Debug, Default)]
+pub struct NiriIpcBatch {
+    requests: Vec<Value>,
+}
+
+impl NiriIpcBatch {
+    pub fn action(mut self, action: Value) -> Self {
+        self.requests.push(json!({ "Action": action }));
+        self
+    }
+
+    pub fn request(mut self, request: Value) -> Self {
+        self.requests.push(request);
+        self
+    }
+
+    // None when niri closed the connection instead of replying
+    async fn exchange(
+        reader: &mut BufReader<UnixStream>,
+        request: &Value,
+    ) -> anyhow::Result<Option<String>> {
+        write_request(reader.get_mut(), request).await?;
+
+        let mut line = String::new();
+        let read = reader.read_line(&mut line).await?;
+
+        Ok((read > 0).then_some(line))
+    }
+
+    /// Returns the reply of every request in order, stops at the first error.
+    pub async fn execute(self, socket_path: &Path) -> anyhow::Result<Vec<Value>> {
+        let mut reader = BufReader::new(UnixStream::connect(socket_path).await?);
+        let mut replies = Vec::with_capacity(self.requests.len());
+
+        for (index, request) in self.requests.iter().enumerate() {
+            let line = match Self::exchange(&mut reader, request).await {
+                Ok(Some(line)) => line,
+                // niri versions that serve a single request per connection close
+                // it after the first reply, the rest of the batch reconnects
+                Ok(None) | Err(_) if index > 0 => {
+                    debug!("Niri closed the batch connection, reconnecting");
+
+                    reader = BufReader::new(UnixStream::connect(socket_path).await?);
+                    Self::exchange(&mut reader, request)
+                        .await?
+                        .context("niri closed the connection")?
+                }
+                result => result?.context("niri closed the connection")?,
+            };
+
+            replies.push(parse_reply(&line)?);
+        }
+
+        Ok(replies)
+    }
+}
+
+pub async fn batch(batch: NiriIpcBatch) -> anyhow::Result<Vec<Value>> {
+    let path = socket_path().context("NIRI_SOCKET is not set")?;
+
+    batch.execute(Path::new(&path)).await
+}
+
+pub async fn event_stream() -> anyhow::Result<Lines<BufReader<UnixStream>>> {
+    let mut reader = send(&json!({ "EventStream": null })).await?;
+    read_reply(&mut reader).await?;
+
+    Ok(reader.lines())
+}
+
+/// Splits a niri event like `{"WorkspaceActivated": {...}}` into its name and body
+pub fn parse_event(line: &str) -> Option<(String, Value)> {
+    match serde_json::from_str::<Value>(line).ok()? {
+        Value::Object(event) => event.into_iter().next(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_event_name_and_body() {
+        let (name, body) =
+            parse_event(r#"{"WorkspaceActivated":{"id":3,"focused":true}}"#).unwrap();
+        assert_eq!(name, "WorkspaceActivated");
+        assert_eq!(body, json!({ "id": 3, "focused": true }));
+
+        let (name, body) = parse_event(
+            r#"{"KeyboardLayoutsChanged":{"keyboard_layouts":{"names":["English (US)","Russian"],"current_idx":0}}}"#,
+        )
+        .unwrap();
+        assert_eq!(name, "KeyboardLayoutsChanged");
+        assert_eq!(body["keyboard_layouts"]["current_idx"], 0);
+
+        let (name, body) = parse_event(r#"{"WindowFocusChanged":{"id":null}}"#).unwrap();
+        assert_eq!(name, "WindowFocusChanged");
+        assert!(body["id"].is_null());
+    }
+
+    #[test]
+    fn parse_event_rejects_non_events() {
+        assert!(parse_event(r#""Handled""#).is_none());
+        assert!(parse_event("{}").is_none());
+        assert!(parse_event(r#"{"WorkspaceActivated":"#).is_none());
+    }
+}
@@ -0,0 +1,188 @@
+use super::ipc;
+use crate::services::{ListenerState, ReadOnlyService, Retry, Service, ServiceEvent};
+use iced::{
+    Subscription, Task,
+    futures::{SinkExt, StreamExt, channel::mpsc::Sender, stream::pending},
+    stream::channel,
+};
+use log::{debug, error, warn};
+use serde::Deserialize;
+use serde_json::json;
+use std::{any::TypeId, ops::Deref, time::Duration};
+use tokio::{
+    io::{BufReader, Lines},
+    net::UnixStream,
+};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NiriKeyboardData {
+    #[serde(rename = "names")]
+    pub layouts: Vec<String>,
+    #[serde(rename = "current_idx")]
+    pub current_index: usize,
+}
+
+impl NiriKeyboardData {
+    pub fn current(&self) -> Option<&str> {
+        self.layouts.get(self.current_index).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NiriKeyboardService {
+    data: NiriKeyboardData,
+}
+
+impl Deref for NiriKeyboardService {
+    type Target = NiriKeyboardData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl NiriKeyboardService {
+    async fn initialize_data() -> anyhow::Result<NiriKeyboardData> {
+        let response = ipc::request(json!({ "KeyboardLayouts": null })).await?;
+
+        Ok(serde_json::from_value(response["KeyboardLayouts"].clone())?)
+    }
+
+    fn parse_event(line: &str) -> Option<NiriKeyboardEvent> {
+        let (name, body) = ipc::parse_event(line)?;
+
+        match name.as_str() {
+            "KeyboardLayoutsChanged" => serde_json::from_value(body["keyboard_layouts"].clone())
+                .ok()
+                .map(NiriKeyboardEvent::LayoutsChanged),
+            "KeyboardLayoutSwitched" => body["idx"]
+                .as_u64()
+                .map(|idx| NiriKeyboardEvent::LayoutSwitched(idx as usize)),
+            _ => None,
+        }
+    }
+
+    async fn start_listening(state: State, output: &mut Sender<ServiceEvent<Self>>) -> State {
+        match state {
+            State::Init => match Self::initialize_data().await {
+                Ok(data) => match ipc::event_stream().await {
+                    Ok(events) => {
+                        let _ = output
+                            .send(ServiceEvent::Init(NiriKeyboardService { data }))
+                            .await;
+
+                        State::Active(events)
+                    }
+                    Err(err) => {
+                        error!("Failed to open niri event stream: {}", err);
+
+                        State::Error
+                    }
+                },
+                Err(err) => {
+                    error!("Failed to query niri keyboard layouts: {}", err);
+
+                    State::Error
+                }
+            },
+            State::Active(mut events) => match events.next_line().await {
+                Ok(Some(line)) => {
+                    if let Some(event) = Self::parse_event(&line) {
+                        debug!("Niri keyboard event: {:?}", event);
+                        let _ = output.send(ServiceEvent::Update(event)).await;
+                    }
+
+                    State::Active(events)
+                }
+                Ok(None) => {
+                    warn!("Niri event stream closed, reconnecting");
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+
+                    State::Init
+                }
+                Err(err) => {
+                    error!("Failed to read niri event: {}", err);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+
+                    State::Init
+                }
+            },
+            State::Error => {
+                error!("Niri keyboard service error");
+
+                let _ = pending::<u8>().next().await;
+                State::Error
+            }
+        }
+    }
+}
+
+type State = ListenerState<Lines<BufReader<UnixStream>>>;
+
+#[derive(Debug, Clone)]
+pub enum NiriKeyboardEvent {
+    LayoutsChanged(NiriKeyboardData),
+    LayoutSwitched(usize),
+}
+
+impl ReadOnlyService for NiriKeyboardService {
+    type UpdateEvent = NiriKeyboardEvent;
+    type Error = ();
+
+    fn update(&mut self, event: Self::UpdateEvent) {
+        match event {
+            NiriKeyboardEvent::LayoutsChanged(data) => {
+                self.data = data;
+            }
+            NiriKeyboardEvent::LayoutSwitched(index) => {
+                self.data.current_index = index;
+            }
+        }
+    }
+
+    fn subscribe() -> Subscription<ServiceEvent<Self>> {
+        let id = TypeId::of::<Self>();
+
+        Subscription::run_with_id(
+            id,
+            channel(10, async |mut output| {
+                Retry::run(
+                    std::any::type_name::<NiriKeyboardService>(),
+                    async |state| NiriKeyboardService::start_listening(state, &mut output).await,
+                )
+                .await;
+            }),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum NiriKeyboardCommand {
+    SwitchLayout,
+}
+
+impl Service for NiriKeyboardService {
+    type Command = NiriKeyboardCommand;
+
+    fn command(&mut self, command: Self::Command) -> Task<ServiceEvent<Self>> {
+        match command {
+            NiriKeyboardCommand::SwitchLayout => {
+                let next = (self.data.current_index + 1) % self.data.layouts.len().max(1);
+
+                Task::perform(
+                    async move {
+                        debug!("Switching niri keyboard layout");
+                        if let Err(err) =
+                            ipc::action(json!({ "SwitchLayout": { "layout": "Next" } })).await
+                        {
+                            error!("Failed to switch niri keyboard layout: {}", err);
+                        }
+
+                        NiriKeyboardEvent::LayoutSwitched(next)
+                    },
+                    ServiceEvent::Update,
+                )
+            }
+        }
+    }
+}
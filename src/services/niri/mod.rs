@@ -0,0 +1,457 @@
+use super::{ListenerState, ReadOnlyService, Retry, Service, ServiceEvent};
+use iced::{
+    Subscription, Task,
+    futures::{SinkExt, StreamExt, channel::mpsc::Sender, stream::pending},
+    stream::channel,
+};
+use ipc::NiriIpcBatch;
+use log::{debug, error, warn};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::{any::TypeId, ops::Deref, time::Duration};
+use tokio::{
+    io::{BufReader, Lines},
+    net::UnixStream,
+};
+
+pub mod ipc;
+pub mod keyboard;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NiriWorkspace {
+    pub id: u64,
+    pub idx: u8,
+    pub name: Option<String>,
+    pub output: Option<String>,
+    pub is_active: bool,
+    pub is_focused: bool,
+    pub active_window_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NiriWindow {
+    pub id: u64,
+    pub title: Option<String>,
+    pub app_id: Option<String>,
+    pub is_focused: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct NiriOutput {
+    pub name: String,
+    pub scale: Option<f64>,
+    pub on: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NiriData {
+    pub workspaces: Vec<NiriWorkspace>,
+    pub windows: Vec<NiriWindow>,
+    pub outputs: Vec<NiriOutput>,
+    pub overview_open: bool,
+}
+
+impl NiriData {
+    pub fn focused_window(&self) -> Option<&NiriWindow> {
+        self.windows.iter().find(|w| w.is_focused)
+    }
+
+    pub fn focused_output(&self) -> Option<String> {
+        self.workspaces
+            .iter()
+            .find(|w| w.is_focused)
+            .and_then(|w| w.output.clone())
+    }
+
+    // the scratchpad is shown while its workspace is the active one of its output
+    pub fn scratchpad_visible(&self, name: &str) -> bool {
+        self.workspaces
+            .iter()
+            .any(|w| w.is_active && w.name.as_deref() == Some(name))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NiriService {
+    data: NiriData,
+}
+
+impl Deref for NiriService {
+    type Target = NiriData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl NiriService {
+    pub fn is_available() -> bool {
+        ipc::socket_path().is_some()
+    }
+
+    async fn initialize_data() -> anyhow::Result<NiriData> {
+        let response = ipc::request(json!({ "Workspaces": null })).await?;
+        let workspaces = serde_json::from_value(response["Workspaces"].clone())?;
+
+        let response = ipc::request(json!({ "Windows": null })).await?;
+        let windows = serde_json::from_value(response["Windows"].clone())?;
+
+        let outputs = Self::outputs().await?;
+
+        // older niri versions have no overview
+        let overview_open = ipc::request(json!({ "OverviewState": null }))
+            .await
+            .ok()
+            .and_then(|response| response["OverviewState"]["is_open"].as_bool())
+            .unwrap_or_default();
+
+        Ok(NiriData {
+            workspaces,
+            windows,
+            outputs,
+            overview_open,
+        })
+    }
+
+    // outputs come as a map keyed by name, disabled ones have no mode and no logical size
+    async fn outputs() -> anyhow::Result<Vec<NiriOutput>> {
+        let response = ipc::request(json!({ "Outputs": null })).await?;
+
+        Ok(Self::parse_outputs(&response))
+    }
+
+    fn parse_outputs(response: &Value) -> Vec<NiriOutput> {
+        let mut outputs = response["Outputs"]
+            .as_object()
+            .map(|outputs| {
+                outputs
+                    .iter()
+                    .map(|(name, output)| NiriOutput {
+                        name: name.clone(),
+                        scale: output["logical"]["scale"].as_f64(),
+                        on: !output["current_mode"].is_null(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        outputs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        outputs
+    }
+
+    fn output_task(request: Option<Value>, outputs: Vec<NiriOutput>) -> Task<ServiceEvent<Self>> {
+        Task::perform(
+            async move {
+                let updated = match request {
+                    Some(request) => {
+                        debug!("Niri output request: {}", request);
+
+                        // the change and the query of the new state share the connection
+                        ipc::batch(
+                            NiriIpcBatch::default()
+                                .request(request)
+                                .request(json!({ "Outputs": null })),
+                        )
+                        .await
+                        .map(|replies| Self::parse_outputs(&replies[1]))
+                    }
+                    None => Self::outputs().await,
+                };
+
+                match updated {
+                    Ok(updated) => NiriEvent::OutputsChanged(updated),
+                    Err(err) => {
+                        error!("Failed to update niri outputs: {}", err);
+
+                        NiriEvent::OutputsChanged(outputs)
+                    }
+                }
+            },
+            ServiceEvent::Update,
+        )
+    }
+
+    fn parse_event(line: &str) -> Option<NiriEvent> {
+        let (name, body) = ipc::parse_event(line)?;
+
+        match name.as_str() {
+            "WorkspacesChanged" => serde_json::from_value(body["workspaces"].clone())
+                .ok()
+                .map(NiriEvent::WorkspacesChanged),
+            "WorkspaceActivated" => Some(NiriEvent::WorkspaceActivated {
+                id: body["id"].as_u64()?,
+                focused: body["focused"].as_bool().unwrap_or_default(),
+            }),
+            "WindowsChanged" => serde_json::from_value(body["windows"].clone())
+                .ok()
+                .map(NiriEvent::WindowsChanged),
+            "WindowOpenedOrChanged" => serde_json::from_value(body["window"].clone())
+                .ok()
+                .map(NiriEvent::WindowOpenedOrChanged),
+            "WindowClosed" => body["id"].as_u64().map(NiriEvent::WindowClosed),
+            "WindowFocusChanged" => Some(NiriEvent::WindowFocusChanged(body["id"].as_u64())),
+            "OverviewOpenedOrClosed" => body["is_open"]
+                .as_bool()
+                .map(NiriEvent::OverviewOpenedOrClosed),
+            _ => None,
+        }
+    }
+
+    async fn start_listening(state: State, output: &mut Sender<ServiceEvent<Self>>) -> State {
+        match state {
+            State::Init => match Self::initialize_data().await {
+                Ok(data) => match ipc::event_stream().await {
+                    Ok(events) => {
+                        let _ = output.send(ServiceEvent::Init(NiriService { data })).await;
+
+                        State::Active(events)
+                    }
+                    Err(err) => {
+                        error!("Failed to open niri event stream: {}", err);
+
+                        State::Error
+                    }
+                },
+                Err(err) => {
+                    error!("Failed to query niri: {}", err);
+
+                    State::Error
+                }
+            },
+            State::Active(mut events) => match events.next_line().await {
+                Ok(Some(line)) => {
+                    if let Some(event) = Self::parse_event(&line) {
+                        debug!("Niri event: {:?}", event);
+                        let _ = output.send(ServiceEvent::Update(event)).await;
+                    }
+
+                    State::Active(events)
+                }
+                Ok(None) => {
+                    warn!("Niri event stream closed, reconnecting");
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+
+                    State::Init
+                }
+                Err(err) => {
+                    error!("Failed to read niri event: {}", err);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+
+                    State::Init
+                }
+            },
+            State::Error => {
+                error!("Niri service error");
+
+                let _ = pending::<u8>().next().await;
+                State::Error
+            }
+        }
+    }
+}
+
+type State = ListenerState<Lines<BufReader<UnixStream>>>;
+
+#[derive(Debug, Clone)]
+pub enum NiriEvent {
+    WorkspacesChanged(Vec<NiriWorkspace>),
+    WorkspaceActivated { id: u64, focused: bool },
+    WindowsChanged(Vec<NiriWindow>),
+    WindowOpenedOrChanged(NiriWindow),
+    WindowClosed(u64),
+    WindowFocusChanged(Option<u64>),
+    OutputsChanged(Vec<NiriOutput>),
+    OverviewOpenedOrClosed(bool),
+}
+
+impl ReadOnlyService for NiriService {
+    type UpdateEvent = NiriEvent;
+    type Error = ();
+
+    fn update(&mut self, event: Self::UpdateEvent) {
+        match event {
+            NiriEvent::WorkspacesChanged(workspaces) => {
+                self.data.workspaces = workspaces;
+            }
+            NiriEvent::WorkspaceActivated { id, focused } => {
+                let output = self
+                    .data
+                    .workspaces
+                    .iter()
+                    .find(|w| w.id == id)
+                    .map(|w| w.output.clone());
+
+                if let Some(output) = output {
+                    for workspace in self.data.workspaces.iter_mut() {
+                        if workspace.output == output {
+                            workspace.is_active = workspace.id == id;
+                        }
+
+                        if focused {
+                            workspace.is_focused = workspace.id == id;
+                        }
+                    }
+                }
+            }
+            NiriEvent::WindowsChanged(windows) => {
+                self.data.windows = windows;
+            }
+            NiriEvent::WindowOpenedOrChanged(window) => {
+                if window.is_focused {
+                    for w in self.data.windows.iter_mut() {
+                        w.is_focused = false;
+                    }
+                }
+
+                match self.data.windows.iter_mut().find(|w| w.id == window.id) {
+                    Some(w) => *w = window,
+                    None => self.data.windows.push(window),
+                }
+            }
+            NiriEvent::WindowClosed(id) => {
+                self.data.windows.retain(|w| w.id != id);
+            }
+            NiriEvent::WindowFocusChanged(id) => {
+                for window in self.data.windows.iter_mut() {
+                    window.is_focused = Some(window.id) == id;
+                }
+            }
+            NiriEvent::OutputsChanged(outputs) => {
+                self.data.outputs = outputs;
+            }
+            NiriEvent::OverviewOpenedOrClosed(is_open) => {
+                self.data.overview_open = is_open;
+            }
+        }
+    }
+
+    fn subscribe() -> Subscription<ServiceEvent<Self>> {
+        let id = TypeId::of::<Self>();
+
+        Subscription::run_with_id(
+            id,
+            channel(100, async |mut output| {
+                Retry::run(std::any::type_name::<NiriService>(), async |state| {
+                    NiriService::start_listening(state, &mut output).await
+                })
+                .await;
+            }),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum NiriCommand {
+    FocusWorkspace(u64),
+    ToggleScratchpad(String),
+    ToggleOverview,
+    RefreshOutputs,
+    SetOutputScale { output: String, scale: f64 },
+    SetOutputPower { output: String, on: bool },
+}
+
+impl Service for NiriService {
+    type Command = NiriCommand;
+
+    fn command(&mut self, command: Self::Command) -> Task<ServiceEvent<Self>> {
+        match command {
+            NiriCommand::FocusWorkspace(id) => {
+                let focus = json!({ "FocusWorkspace": { "reference": { "Id": id } } });
+                // picking a workspace from the bar also leaves the overview
+                let close_overview = self.data.overview_open;
+
+                Task::perform(
+                    async move {
+                        debug!("Focusing niri workspace {}", id);
+                        let result = if close_overview {
+                            ipc::batch(
+                                NiriIpcBatch::default()
+                                    .action(focus)
+                                    .action(json!({ "CloseOverview": {} })),
+                            )
+                            .await
+                            .map(|_| ())
+                        } else {
+                            ipc::action(focus).await
+                        };
+                        if let Err(err) = result {
+                            error!("Failed to focus niri workspace {}: {}", id, err);
+                        }
+
+                        NiriEvent::WorkspaceActivated { id, focused: true }
+                    },
+                    ServiceEvent::Update,
+                )
+            }
+            NiriCommand::ToggleScratchpad(name) => {
+                // going back to the previous workspace hides it again
+                let action = if self.data.scratchpad_visible(&name) {
+                    json!({ "FocusWorkspacePrevious": {} })
+                } else {
+                    json!({ "FocusWorkspace": { "reference": { "Name": name } } })
+                };
+
+                let workspaces = self.data.workspaces.clone();
+
+                Task::perform(
+                    async move {
+                        debug!("Toggling niri scratchpad {}", name);
+                        let replies = ipc::batch(
+                            NiriIpcBatch::default()
+                                .action(action)
+                                .request(json!({ "Workspaces": null })),
+                        )
+                        .await
+                        .inspect_err(|err| {
+                            error!("Failed to toggle niri scratchpad {}: {}", name, err);
+                        });
+                        let updated = replies.ok().and_then(|mut replies| {
+                            serde_json::from_value(replies.pop()?["Workspaces"].take()).ok()
+                        });
+
+                        NiriEvent::WorkspacesChanged(updated.unwrap_or(workspaces))
+                    },
+                    ServiceEvent::Update,
+                )
+            }
+            NiriCommand::ToggleOverview => {
+                let is_open = self.data.overview_open;
+
+                Task::perform(
+                    async move {
+                        debug!("Toggling niri overview");
+                        match ipc::action(json!({ "ToggleOverview": {} })).await {
+                            Ok(()) => !is_open,
+                            Err(err) => {
+                                error!("Failed to toggle niri overview: {}", err);
+
+                                is_open
+                            }
+                        }
+                    },
+                    |is_open| ServiceEvent::Update(NiriEvent::OverviewOpenedOrClosed(is_open)),
+                )
+            }
+            NiriCommand::RefreshOutputs => Self::output_task(None, self.data.outputs.clone()),
+            // output changes go through the Output request, they aren't actions
+            NiriCommand::SetOutputScale { output, scale } => Self::output_task(
+                Some(json!({
+                    "Output": {
+                        "output": output,
+                        "action": { "Scale": { "scale": { "Specific": scale } } },
+                    }
+                })),
+                self.data.outputs.clone(),
+            ),
+            NiriCommand::SetOutputPower { output, on } => Self::output_task(
+                Some(json!({
+                    "Output": {
+                        "output": output,
+                        "action": if on { "On" } else { "Off" },
+                    }
+                })),
+                self.data.outputs.clone(),
+            ),
+        }
+    }
+}
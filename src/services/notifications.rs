@@ -0,0 +1,186 @@
+use super::{ListenerState, ReadOnlyService, Retry, ServiceEvent};
+use crate::{
+    config::{NotificationDaemon, NotificationsModuleConfig},
+    utils::dbus_session::{is_service_unknown, session_connection},
+};
+use iced::{
+    Subscription,
+    futures::{SinkExt, StreamExt, channel::mpsc::Sender, stream::pending},
+    stream::channel,
+};
+use log::{debug, error, warn};
+use std::{any::TypeId, collections::HashMap, ops::Deref, time::Duration};
+use zbus::{proxy, zvariant::OwnedValue};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Default)]
+pub struct NotificationsData {
+    pub count: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct NotificationsService {
+    data: NotificationsData,
+}
+
+impl Deref for NotificationsService {
+    type Target = NotificationsData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl NotificationsService {
+    async fn get_count(conn: &zbus::Connection, daemon: NotificationDaemon) -> anyhow::Result<u32> {
+        match daemon {
+            NotificationDaemon::Mako => {
+                let mako = MakoProxy::new(conn).await?;
+
+                Ok(mako.list_notifications().await?.len() as u32)
+            }
+            NotificationDaemon::Dunst => {
+                let dunst = DunstProxy::new(conn).await?;
+
+                Ok(dunst.displayed_length().await? + dunst.waiting_length().await?)
+            }
+        }
+    }
+
+    async fn start_listening(
+        state: State,
+        config: &NotificationsModuleConfig,
+        output: &mut Sender<ServiceEvent<Self>>,
+    ) -> State {
+        match state {
+            State::Init => {
+                let init = async {
+                    let conn = session_connection()
+                        .await
+                        .ok_or_else(|| anyhow::anyhow!("session bus unavailable"))?;
+                    let count = Self::get_count(&conn, config.daemon).await?;
+
+                    anyhow::Ok((conn, count))
+                };
+
+                match init.await {
+                    Ok((conn, count)) => {
+                        let _ = output
+                            .send(ServiceEvent::Init(NotificationsService {
+                                data: NotificationsData { count },
+                            }))
+                            .await;
+
+                        State::Active((conn, count))
+                    }
+                    Err(err) => {
+                        error!("Failed to connect to the notification daemon: {}", err);
+
+                        State::Error
+                    }
+                }
+            }
+            State::Active((conn, count)) => {
+                tokio::time::sleep(POLL_INTERVAL).await;
+
+                match Self::get_count(&conn, config.daemon).await {
+                    Ok(new_count) => {
+                        if new_count != count {
+                            debug!("Notification count changed: {}", new_count);
+                            let _ = output
+                                .send(ServiceEvent::Update(NotificationsEvent(new_count)))
+                                .await;
+                        }
+
+                        State::Active((conn, new_count))
+                    }
+                    Err(err) if is_service_unknown(&err) => {
+                        warn!("Notification daemon disappeared from the bus: {}", err);
+
+                        State::Init
+                    }
+                    Err(err) => {
+                        error!("Failed to get the notification count: {}", err);
+
+                        State::Active((conn, count))
+                    }
+                }
+            }
+            State::Error => {
+                error!("Notifications service error");
+
+                let _ = pending::<u8>().next().await;
+                State::Error
+            }
+        }
+    }
+}
+
+type State = ListenerState<(zbus::Connection, u32)>;
+
+#[derive(Debug, Clone)]
+pub struct NotificationsEvent(pub u32);
+
+impl ReadOnlyService for NotificationsService {
+    type UpdateEvent = NotificationsEvent;
+    type Error = ();
+
+    fn update(&mut self, event: Self::UpdateEvent) {
+        self.data.count = event.0;
+    }
+
+    fn subscribe() -> Subscription<ServiceEvent<Self>> {
+        Self::subscribe_with_config(&NotificationsModuleConfig::default())
+    }
+}
+
+impl NotificationsService {
+    pub fn subscribe_with_config(
+        config: &NotificationsModuleConfig,
+    ) -> Subscription<ServiceEvent<Self>> {
+        let id = format!("{:?}-{:?}", TypeId::of::<Self>(), config);
+        let config = config.clone();
+
+        Subscription::run_with_id(
+            id,
+            channel(10, async move |mut output| {
+                Retry::run(
+                    std::any::type_name::<NotificationsService>(),
+                    async |state| {
+                        NotificationsService::start_listening(state, &config, &mut output).await
+                    },
+                )
+                .await;
+            }),
+        )
+    }
+}
+
+#[proxy(
+    default_service = "org.freedesktop.Notifications",
+    default_path = "/fr/emersion/Mako",
+    interface = "fr.emersion.Mako"
+)]
+trait Mako {
+    fn list_notifications(&self) -> zbus::Result<Vec<HashMap<String, OwnedValue>>>;
+}
+
+#[proxy(
+    default_service = "org.freedesktop.Notifications",
+    default_path = "/org/freedesktop/Notifications",
+    interface = "org.dunstproject.cmd0"
+)]
+pub trait Dunst {
+    #[zbus(property, name = "paused")]
+    fn paused(&self) -> zbus::Result<bool>;
+
+    #[zbus(property, name = "paused")]
+    fn set_paused(&self, value: bool) -> zbus::Result<()>;
+
+    #[zbus(property, name = "displayedLength")]
+    fn displayed_length(&self) -> zbus::Result<u32>;
+
+    #[zbus(property, name = "waitingLength")]
+    fn waiting_length(&self) -> zbus::Result<u32>;
+}
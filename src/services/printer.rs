@@ -0,0 +1,175 @@
+use super::{ReadOnlyService, ServiceEvent};
+use iced::{Subscription, futures::SinkExt, stream::channel};
+use log::{debug, error};
+use std::{any::TypeId, ops::Deref, process::Stdio, time::Duration};
+use tokio::{process::Command, time::sleep};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+// used while no printer is configured or cups keeps failing
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+// same values as the ipp printer-state attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrinterState {
+    Idle = 3,
+    Processing = 4,
+    Stopped = 5,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrinterStatus {
+    pub name: String,
+    pub state: PrinterState,
+    pub queue_count: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrinterData {
+    pub printers: Vec<PrinterStatus>,
+}
+
+impl PrinterData {
+    pub fn processing(&self) -> bool {
+        self.printers
+            .iter()
+            .any(|p| p.state == PrinterState::Processing)
+    }
+
+    pub fn stopped(&self) -> bool {
+        self.printers
+            .iter()
+            .any(|p| p.state == PrinterState::Stopped)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PrinterService {
+    data: PrinterData,
+}
+
+impl Deref for PrinterService {
+    type Target = PrinterData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+async fn lpstat(arg: &str) -> anyhow::Result<String> {
+    let output = Command::new("lpstat")
+        .arg(arg)
+        .env("LC_ALL", "C")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .await?;
+
+    // lpstat fails when there are no printers or jobs at all
+    if !output.status.success() && output.stdout.is_empty() {
+        return Ok(String::new());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+impl PrinterService {
+    // `printer NAME is idle.`, `printer NAME now printing JOB.`,
+    // `printer NAME disabled since ...`
+    fn parse_printers(printers: &str, jobs: &str) -> PrinterData {
+        let printers = printers
+            .lines()
+            .filter_map(|line| {
+                let mut words = line.strip_prefix("printer ")?.split_whitespace();
+                let name = words.next()?.to_owned();
+                let state = match words.next()? {
+                    "disabled" => PrinterState::Stopped,
+                    "now" => PrinterState::Processing,
+                    _ => PrinterState::Idle,
+                };
+                // jobs are listed as `NAME-ID user size date`
+                let queue_count = jobs
+                    .lines()
+                    .filter_map(|job| job.split_whitespace().next()?.rsplit_once('-'))
+                    .filter(|(printer, _)| *printer == name)
+                    .count() as u32;
+
+                Some(PrinterStatus {
+                    name,
+                    state,
+                    queue_count,
+                })
+            })
+            .collect();
+
+        PrinterData { printers }
+    }
+
+    async fn poll() -> anyhow::Result<PrinterData> {
+        let printers = lpstat("-p").await?;
+        let jobs = lpstat("-o").await?;
+
+        Ok(Self::parse_printers(&printers, &jobs))
+    }
+}
+
+impl ReadOnlyService for PrinterService {
+    type UpdateEvent = PrinterData;
+    type Error = ();
+
+    fn update(&mut self, event: Self::UpdateEvent) {
+        self.data = event;
+    }
+
+    // cupsd only sends its dbus notifications to clients that created a
+    // subscription on the scheduler, lpstat is polled instead
+    fn subscribe() -> Subscription<ServiceEvent<Self>> {
+        let id = TypeId::of::<Self>();
+
+        Subscription::run_with_id(
+            id,
+            channel(10, async |mut output| {
+                let mut last: Option<PrinterData> = None;
+                let mut failures = 0;
+
+                loop {
+                    let interval = match Self::poll().await {
+                        Ok(data) => {
+                            failures = 0;
+                            let interval = if data.printers.is_empty() {
+                                IDLE_POLL_INTERVAL
+                            } else {
+                                POLL_INTERVAL
+                            };
+
+                            if last.as_ref() != Some(&data) {
+                                debug!("Printers state: {:?}", data);
+
+                                let _ = if last.is_none() {
+                                    output
+                                        .send(ServiceEvent::Init(PrinterService {
+                                            data: data.clone(),
+                                        }))
+                                        .await
+                                } else {
+                                    output.send(ServiceEvent::Update(data.clone())).await
+                                };
+                                last = Some(data);
+                            }
+
+                            interval
+                        }
+                        Err(err) => {
+                            error!("Failed to read the printers state: {}", err);
+
+                            // cupsd may only be starting, it's polled again less and less often
+                            failures += 1;
+                            (POLL_INTERVAL * 2u32.pow(failures.min(4))).min(IDLE_POLL_INTERVAL)
+                        }
+                    };
+
+                    sleep(interval).await;
+                }
+            }),
+        )
+    }
+}
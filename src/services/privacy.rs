@@ -1,4 +1,5 @@
-use super::{ReadOnlyService, ServiceEvent};
+use super::{ListenerState, ReadOnlyService, Retry, ServiceEvent};
+use crate::{config::PrivacyModuleConfig, utils::state_dir};
 use iced::{
     Subscription,
     futures::{
@@ -9,34 +10,49 @@ use iced::{
 use inotify::{EventMask, Inotify, WatchMask};
 use log::{debug, error, info, warn};
 use pipewire::{context::Context, main_loop::MainLoop};
-use std::{any::TypeId, fs, ops::Deref, path::Path, thread};
-use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
-
-const WEBCAM_DEVICE_PATH: &str = "/dev/video0";
+use serde::{Deserialize, Serialize};
+use std::{
+    any::TypeId,
+    fs,
+    io::{Read, Seek, SeekFrom},
+    ops::Deref,
+    path::{Path, PathBuf},
+    thread,
+};
+use tokio::{
+    io::AsyncWriteExt,
+    sync::mpsc::{UnboundedReceiver, unbounded_channel},
+};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Media {
     Video,
     Audio,
+    Screen,
 }
 
 #[derive(Debug, Clone)]
 pub struct ApplicationNode {
     pub id: u32,
     pub media: Media,
+    pub app_name: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct PrivacyData {
     nodes: Vec<ApplicationNode>,
     webcam_access: i32,
+    camera_apps: Vec<String>,
 }
 
 impl PrivacyData {
-    fn new() -> Self {
+    fn new(camera_devices: &[PathBuf]) -> Self {
+        let (webcam_access, camera_apps) = devices_in_use(camera_devices);
+
         Self {
             nodes: Vec::new(),
-            webcam_access: is_device_in_use(WEBCAM_DEVICE_PATH),
+            webcam_access,
+            camera_apps,
         }
     }
 
@@ -48,13 +64,77 @@ impl PrivacyData {
         self.nodes.iter().any(|n| n.media == Media::Audio)
     }
 
+    pub fn microphone_apps(&self) -> Vec<String> {
+        self.apps(Media::Audio)
+    }
+
     pub fn webcam_access(&self) -> bool {
         self.webcam_access > 0
     }
 
+    pub fn camera_apps(&self) -> &[String] {
+        &self.camera_apps
+    }
+
     pub fn screenshare_access(&self) -> bool {
         self.nodes.iter().any(|n| n.media == Media::Video)
     }
+
+    pub fn screenshare_apps(&self) -> Vec<String> {
+        self.apps(Media::Video)
+    }
+
+    // a portal screen cast publishes a source node and the app reading it a
+    // stream node, the cast is only reported once as a screen share
+    pub fn screen_record_access(&self) -> bool {
+        !self.screenshare_access() && self.nodes.iter().any(|n| n.media == Media::Screen)
+    }
+
+    pub fn screen_record_apps(&self) -> Vec<String> {
+        self.apps(Media::Screen)
+    }
+
+    // every app using a resource, the ones pipewire doesn't name are grouped as unknown
+    pub fn accesses(&self) -> Vec<(PrivacyResource, String)> {
+        let mut accesses = Vec::new();
+        for (resource, active, apps) in [
+            (
+                PrivacyResource::Microphone,
+                self.microphone_access(),
+                self.microphone_apps(),
+            ),
+            (
+                PrivacyResource::Camera,
+                self.webcam_access(),
+                self.camera_apps.clone(),
+            ),
+            (
+                PrivacyResource::ScreenShare,
+                self.screenshare_access(),
+                self.screenshare_apps(),
+            ),
+        ] {
+            if active && apps.is_empty() {
+                accesses.push((resource, "unknown".to_owned()));
+            }
+            accesses.extend(apps.into_iter().map(|app| (resource, app)));
+        }
+
+        accesses
+    }
+
+    fn apps(&self, media: Media) -> Vec<String> {
+        let mut apps = self
+            .nodes
+            .iter()
+            .filter(|n| n.media == media)
+            .filter_map(|n| n.app_name.clone())
+            .collect::<Vec<_>>();
+        apps.sort();
+        apps.dedup();
+
+        apps
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -86,17 +166,25 @@ impl PrivacyService {
                     let tx = tx.clone();
                     move |global| {
                         if let Some(props) = global.props {
-                            if let Some(media) = props.get("media.class").filter(|v| {
-                                v == &"Stream/Input/Video" || v == &"Stream/Input/Audio"
-                            }) {
+                            // video sources backed by a device are cameras, the
+                            // others are screen casts
+                            let media = match props.get("media.class") {
+                                Some("Stream/Input/Video") => Some(Media::Video),
+                                Some("Stream/Input/Audio") => Some(Media::Audio),
+                                Some("Video/Source") if props.get("device.api").is_none() => {
+                                    Some(Media::Screen)
+                                }
+                                _ => None,
+                            };
+                            if let Some(media) = media {
                                 debug!("New global: {:?}", global);
                                 let _ = tx.send(PrivacyEvent::AddNode(ApplicationNode {
                                     id: global.id,
-                                    media: if media == "Stream/Input/Video" {
-                                        Media::Video
-                                    } else {
-                                        Media::Audio
-                                    },
+                                    media,
+                                    app_name: props
+                                        .get("application.name")
+                                        .or_else(|| props.get("node.name"))
+                                        .map(str::to_owned),
                                 }));
                             }
                         }
@@ -119,48 +207,69 @@ impl PrivacyService {
         Ok(rx)
     }
 
-    async fn webcam_listener() -> anyhow::Result<Box<dyn Stream<Item = PrivacyEvent> + Unpin + Send>>
-    {
+    async fn webcam_listener(
+        camera_devices: Vec<PathBuf>,
+    ) -> anyhow::Result<Box<dyn Stream<Item = PrivacyEvent> + Unpin + Send>> {
+        if camera_devices.is_empty() {
+            return Err(anyhow::anyhow!("No camera device found"));
+        }
+
         let inotify = Inotify::init()?;
 
-        inotify.watches().add(
-            WEBCAM_DEVICE_PATH,
-            WatchMask::CLOSE_WRITE
-                | WatchMask::CLOSE_NOWRITE
-                | WatchMask::DELETE_SELF
-                | WatchMask::OPEN
-                | WatchMask::ATTRIB,
-        )?;
+        for device in &camera_devices {
+            inotify.watches().add(
+                device,
+                WatchMask::CLOSE_WRITE
+                    | WatchMask::CLOSE_NOWRITE
+                    | WatchMask::DELETE_SELF
+                    | WatchMask::OPEN
+                    | WatchMask::ATTRIB,
+            )?;
+        }
 
         let buffer = [0; 512];
         Ok(Box::new(
             inotify
                 .into_event_stream(buffer)?
-                .filter_map(async move |event| match event {
-                    Ok(event) => {
-                        debug!("Webcam event: {:?}", event);
-                        match event.mask {
-                            EventMask::OPEN => Some(PrivacyEvent::WebcamOpen),
-                            EventMask::CLOSE_WRITE | EventMask::CLOSE_NOWRITE => {
-                                Some(PrivacyEvent::WebcamClose)
+                .filter_map(move |event| {
+                    let camera_devices = camera_devices.clone();
+                    async move {
+                        match event {
+                            Ok(event) => {
+                                debug!("Webcam event: {:?}", event);
+                                match event.mask {
+                                    EventMask::OPEN => Some(PrivacyEvent::WebcamOpen(
+                                        devices_in_use(&camera_devices).1,
+                                    )),
+                                    EventMask::CLOSE_WRITE | EventMask::CLOSE_NOWRITE => {
+                                        Some(PrivacyEvent::WebcamClose(
+                                            devices_in_use(&camera_devices).1,
+                                        ))
+                                    }
+                                    _ => None,
+                                }
                             }
                             _ => None,
                         }
                     }
-                    _ => None,
                 })
                 .boxed(),
         ))
     }
 
-    async fn start_listening(state: State, output: &mut Sender<ServiceEvent<Self>>) -> State {
+    async fn start_listening(
+        state: State,
+        config: &PrivacyModuleConfig,
+        output: &mut Sender<ServiceEvent<Self>>,
+    ) -> State {
         match state {
             State::Init => {
+                let camera_devices = camera_devices(config);
                 let pipewire = Self::create_pipewire_listener().await;
-                let webcam = Self::webcam_listener().await;
+                let webcam = Self::webcam_listener(camera_devices.clone()).await;
                 match (pipewire, webcam) {
                     (Ok(pipewire), Ok(webcam)) => {
-                        let data = PrivacyData::new();
+                        let data = PrivacyData::new(&camera_devices);
 
                         let _ = output
                             .send(ServiceEvent::Init(PrivacyService { data }))
@@ -176,6 +285,12 @@ impl PrivacyService {
                     (Ok(pipewire), Err(webcam_error)) => {
                         warn!("Failed to connect to webcam: {}", webcam_error);
 
+                        let data = PrivacyData::new(&[]);
+
+                        let _ = output
+                            .send(ServiceEvent::Init(PrivacyService { data }))
+                            .await;
+
                         State::Active((pipewire, Box::new(pending::<PrivacyEvent>().boxed())))
                     }
                     (Err(pipewire_error), Err(webcam_error)) => {
@@ -224,23 +339,17 @@ impl PrivacyService {
     }
 }
 
-enum State {
-    Init,
-    Active(
-        (
-            UnboundedReceiver<PrivacyEvent>,
-            Box<dyn Stream<Item = PrivacyEvent> + Unpin + Send>,
-        ),
-    ),
-    Error,
-}
+type State = ListenerState<(
+    UnboundedReceiver<PrivacyEvent>,
+    Box<dyn Stream<Item = PrivacyEvent> + Unpin + Send>,
+)>;
 
 #[derive(Debug, Clone)]
 pub enum PrivacyEvent {
     AddNode(ApplicationNode),
     RemoveNode(u32),
-    WebcamOpen,
-    WebcamClose,
+    WebcamOpen(Vec<String>),
+    WebcamClose(Vec<String>),
 }
 
 impl ReadOnlyService for PrivacyService {
@@ -255,35 +364,67 @@ impl ReadOnlyService for PrivacyService {
             PrivacyEvent::RemoveNode(id) => {
                 self.data.nodes.retain(|n| n.id != id);
             }
-            PrivacyEvent::WebcamOpen => {
+            PrivacyEvent::WebcamOpen(apps) => {
                 self.data.webcam_access += 1;
+                self.data.camera_apps = apps;
                 debug!("Webcam opened {}", self.data.webcam_access);
             }
-            PrivacyEvent::WebcamClose => {
+            PrivacyEvent::WebcamClose(apps) => {
                 self.data.webcam_access = i32::max(self.data.webcam_access - 1, 0);
+                self.data.camera_apps = apps;
                 debug!("Webcam closed {}", self.data.webcam_access);
             }
         }
     }
 
     fn subscribe() -> Subscription<ServiceEvent<Self>> {
-        let id = TypeId::of::<Self>();
+        Self::subscribe_with_config(&PrivacyModuleConfig::default())
+    }
+}
+
+impl PrivacyService {
+    pub fn subscribe_with_config(config: &PrivacyModuleConfig) -> Subscription<ServiceEvent<Self>> {
+        let id = format!("{:?}-{:?}", TypeId::of::<Self>(), config);
+        let config = config.clone();
 
         Subscription::run_with_id(
             id,
-            channel(100, async |mut output| {
-                let mut state = State::Init;
-
-                loop {
-                    state = PrivacyService::start_listening(state, &mut output).await;
-                }
+            channel(100, async move |mut output| {
+                Retry::run(std::any::type_name::<PrivacyService>(), async |state| {
+                    PrivacyService::start_listening(state, &config, &mut output).await
+                })
+                .await;
             }),
         )
     }
 }
 
-fn is_device_in_use(target: &str) -> i32 {
+fn camera_devices(config: &PrivacyModuleConfig) -> Vec<PathBuf> {
+    if !config.camera_devices.is_empty() {
+        return config.camera_devices.iter().map(PathBuf::from).collect();
+    }
+
+    let mut devices = fs::read_dir("/dev")
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with("video"))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    devices.sort();
+
+    devices
+}
+
+fn devices_in_use(targets: &[PathBuf]) -> (i32, Vec<String>) {
     let mut used_by = 0;
+    let mut apps = Vec::new();
     if let Ok(entries) = fs::read_dir("/proc") {
         for entry in entries.flatten() {
             let pid_path = entry.path();
@@ -297,14 +438,135 @@ fn is_device_in_use(target: &str) -> i32 {
             if let Ok(fd_entries) = fs::read_dir(pid_path.join("fd")) {
                 for fd_entry in fd_entries.flatten() {
                     if let Ok(link_path) = fs::read_link(fd_entry.path()) {
-                        if link_path == Path::new(target) {
+                        if targets.iter().any(|target| link_path == Path::new(target)) {
                             used_by += 1;
+
+                            if let Ok(name) = fs::read_to_string(pid_path.join("comm")) {
+                                apps.push(name.trim().to_owned());
+                            }
                         }
                     }
                 }
             }
         }
     }
+    apps.sort();
+    apps.dedup();
+
+    (used_by, apps)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrivacyResource {
+    Microphone,
+    Camera,
+    ScreenShare,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccessAction {
+    Started,
+    Stopped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacyLog {
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    pub resource: PrivacyResource,
+    pub app: String,
+    pub action: AccessAction,
+}
+
+impl PrivacyLog {
+    // one entry for each app that started or stopped using a resource
+    pub fn diff(
+        before: &[(PrivacyResource, String)],
+        after: &[(PrivacyResource, String)],
+    ) -> Vec<PrivacyLog> {
+        let timestamp = chrono::Local::now();
+        let entry = |(resource, app): &(PrivacyResource, String), action| PrivacyLog {
+            timestamp,
+            resource: *resource,
+            app: app.clone(),
+            action,
+        };
+
+        before
+            .iter()
+            .filter(|access| !after.contains(access))
+            .map(|access| entry(access, AccessAction::Stopped))
+            .chain(
+                after
+                    .iter()
+                    .filter(|access| !before.contains(access))
+                    .map(|access| entry(access, AccessAction::Started)),
+            )
+            .collect()
+    }
+}
+
+// past this size the log is moved to `privacy.log.1`, replacing the older one
+const MAX_LOG_SIZE: u64 = 512 * 1024;
+// enough for the entries shown in the menu
+const LOG_TAIL_SIZE: u64 = 16 * 1024;
+
+fn log_path() -> Option<PathBuf> {
+    state_dir().map(|dir| dir.join("privacy.log"))
+}
+
+// appended as json lines
+pub async fn flush_log(entries: &[PrivacyLog]) -> anyhow::Result<()> {
+    let path = log_path().ok_or_else(|| anyhow::anyhow!("no state directory"))?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let mut content = Vec::new();
+    for entry in entries {
+        serde_json::to_writer(&mut content, entry)?;
+        content.push(b'\n');
+    }
+
+    if tokio::fs::metadata(&path)
+        .await
+        .is_ok_and(|metadata| metadata.len() >= MAX_LOG_SIZE)
+    {
+        tokio::fs::rename(&path, path.with_extension("log.1")).await?;
+    }
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(&content).await?;
+
+    Ok(())
+}
+
+// only the end of the file is read, the first line may be cut
+fn read_log_tail(path: &Path) -> std::io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(LOG_TAIL_SIZE)))?;
+
+    let mut content = Vec::new();
+    file.read_to_end(&mut content)?;
+
+    Ok(String::from_utf8_lossy(&content).into_owned())
+}
 
-    used_by
+pub fn read_log(max_entries: usize) -> Vec<PrivacyLog> {
+    let content = log_path()
+        .and_then(|path| read_log_tail(&path).ok())
+        .unwrap_or_default();
+    let mut entries = content
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<PrivacyLog>(line).ok())
+        .take(max_entries)
+        .collect::<Vec<_>>();
+    entries.reverse();
+
+    entries
 }
@@ -0,0 +1,221 @@
+use super::{ListenerState, ReadOnlyService, Retry, ServiceEvent};
+use crate::{config::SystemdUnitConfig, utils::IndicatorState};
+use iced::{
+    Subscription,
+    futures::{
+        SinkExt, StreamExt,
+        channel::mpsc::Sender,
+        stream::{pending, select_all},
+    },
+    stream::channel,
+};
+use log::{debug, error, warn};
+use std::any::TypeId;
+use zbus::{Result, proxy, zvariant::OwnedObjectPath};
+
+#[proxy(
+    interface = "org.freedesktop.systemd1.Manager",
+    default_service = "org.freedesktop.systemd1",
+    default_path = "/org/freedesktop/systemd1"
+)]
+trait Manager {
+    fn get_unit(&self, name: &str) -> Result<OwnedObjectPath>;
+
+    fn load_unit(&self, name: &str) -> Result<OwnedObjectPath>;
+
+    fn subscribe(&self) -> Result<()>;
+}
+
+#[proxy(
+    interface = "org.freedesktop.systemd1.Unit",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait Unit {
+    #[zbus(property)]
+    fn active_state(&self) -> Result<String>;
+}
+
+#[derive(Debug, Clone)]
+pub struct SystemdUnitStatus {
+    pub name: String,
+    pub label: String,
+    pub active_state: String,
+}
+
+impl SystemdUnitStatus {
+    pub fn indicator_state(&self) -> IndicatorState {
+        match self.active_state.as_str() {
+            "active" => IndicatorState::Success,
+            // a unit starting or stopping isn't failing yet
+            "activating" | "deactivating" | "reloading" => IndicatorState::Warning,
+            _ => IndicatorState::Danger,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SystemdService {
+    pub units: Vec<SystemdUnitStatus>,
+}
+
+type State = ListenerState<Vec<(SystemdUnitStatus, UnitProxy<'static>)>>;
+
+impl ReadOnlyService for SystemdService {
+    type UpdateEvent = Vec<SystemdUnitStatus>;
+    type Error = ();
+
+    fn update(&mut self, event: Self::UpdateEvent) {
+        self.units = event;
+    }
+
+    fn subscribe() -> Subscription<ServiceEvent<Self>> {
+        Self::subscribe_with_config(&[])
+    }
+}
+
+impl SystemdService {
+    pub fn subscribe_with_config(config: &[SystemdUnitConfig]) -> Subscription<ServiceEvent<Self>> {
+        let id = TypeId::of::<Self>();
+        let config = config.to_vec();
+
+        Subscription::run_with_id(
+            format!("{:?}-{:?}", id, config),
+            channel(10, async move |mut output| {
+                Retry::run(std::any::type_name::<SystemdService>(), async |state| {
+                    SystemdService::start_listening(state, &mut output, &config).await
+                })
+                .await;
+            }),
+        )
+    }
+
+    async fn unit_proxy(
+        conn: &zbus::Connection,
+        manager: &ManagerProxy<'_>,
+        name: &str,
+    ) -> anyhow::Result<UnitProxy<'static>> {
+        // GetUnit fails for units that are not loaded, e.g. a stopped
+        // service nobody depends on, LoadUnit brings them in
+        let path = match manager.get_unit(name).await {
+            Ok(path) => path,
+            Err(_) => manager.load_unit(name).await?,
+        };
+
+        Ok(UnitProxy::builder(conn).path(path)?.build().await?)
+    }
+
+    // the user units are managed by the systemd instance on the session bus
+    async fn manager(user: bool) -> anyhow::Result<(zbus::Connection, ManagerProxy<'static>)> {
+        let conn = if user {
+            zbus::Connection::session().await?
+        } else {
+            zbus::Connection::system().await?
+        };
+        let manager = ManagerProxy::new(&conn).await?;
+        // systemd only emits the unit signals once a client subscribed
+        manager.subscribe().await?;
+
+        Ok((conn, manager))
+    }
+
+    async fn initialize_data(
+        config: &[SystemdUnitConfig],
+    ) -> anyhow::Result<Vec<(SystemdUnitStatus, UnitProxy<'static>)>> {
+        let mut system = None;
+        let mut session = None;
+
+        let mut units = Vec::new();
+        for unit in config {
+            let manager = if unit.user { &mut session } else { &mut system };
+            if manager.is_none() {
+                *manager = Some(Self::manager(unit.user).await?);
+            }
+            let Some((conn, manager)) = manager.as_ref() else {
+                continue;
+            };
+
+            match Self::unit_proxy(conn, manager, &unit.name).await {
+                Ok(proxy) => {
+                    let active_state = proxy.active_state().await.unwrap_or_default();
+
+                    units.push((
+                        SystemdUnitStatus {
+                            name: unit.name.clone(),
+                            label: unit.label.clone().unwrap_or_else(|| unit.name.clone()),
+                            active_state,
+                        },
+                        proxy,
+                    ));
+                }
+                Err(err) => {
+                    warn!("Failed to get the systemd unit {}: {}", unit.name, err);
+                }
+            }
+        }
+
+        Ok(units)
+    }
+
+    async fn start_listening(
+        state: State,
+        output: &mut Sender<ServiceEvent<Self>>,
+        config: &[SystemdUnitConfig],
+    ) -> State {
+        match state {
+            State::Init => match SystemdService::initialize_data(config).await {
+                Ok(units) => {
+                    let _ = output
+                        .send(ServiceEvent::Init(SystemdService {
+                            units: units.iter().map(|(status, _)| status.clone()).collect(),
+                        }))
+                        .await;
+
+                    State::Active(units)
+                }
+                Err(err) => {
+                    error!("Failed to initialize systemd service: {}", err);
+
+                    State::Error
+                }
+            },
+            State::Active(mut units) => {
+                let mut events = Vec::new();
+                for (index, (_, proxy)) in units.iter().enumerate() {
+                    events.push(
+                        proxy
+                            .receive_active_state_changed()
+                            .await
+                            .map(move |change| (index, change))
+                            .boxed(),
+                    );
+                }
+                let mut events = select_all(events);
+
+                while let Some((index, change)) = events.next().await {
+                    let Ok(active_state) = change.get().await else {
+                        continue;
+                    };
+                    debug!("systemd unit {} is {}", units[index].0.name, active_state);
+
+                    units[index].0.active_state = active_state;
+                    let _ = output
+                        .send(ServiceEvent::Update(
+                            units.iter().map(|(status, _)| status.clone()).collect(),
+                        ))
+                        .await;
+                }
+
+                if units.is_empty() {
+                    let _ = pending::<u8>().next().await;
+                }
+
+                State::Error
+            }
+            State::Error => {
+                let _ = pending::<u8>().next().await;
+
+                State::Error
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::utils::dbus_session::session_connection;
 use iced::futures::StreamExt;
 use log::{info, warn};
 use zbus::{
@@ -22,7 +23,9 @@ pub struct StatusNotifierWatcher {
 
 impl StatusNotifierWatcher {
     pub async fn start_server() -> anyhow::Result<Connection> {
-        let connection = zbus::connection::Connection::session().await?;
+        let connection = session_connection()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("session bus unavailable"))?;
         connection
             .object_server()
             .at(OBJECT_PATH, StatusNotifierWatcher::default())
@@ -164,6 +167,11 @@ pub trait StatusNotifierItem {
 
     #[zbus(property)]
     fn menu(&self) -> zbus::Result<OwnedObjectPath>;
+
+    #[zbus(property)]
+    fn title(&self) -> zbus::Result<String>;
+
+    fn activate(&self, x: i32, y: i32) -> zbus::Result<()>;
 }
 
 #[derive(Clone, Debug, Type)]
@@ -1,4 +1,4 @@
-use super::{ReadOnlyService, Service, ServiceEvent};
+use super::{ListenerState, ReadOnlyService, Retry, Service, ServiceEvent};
 use dbus::{
     DBusMenuProxy, Layout, StatusNotifierItemProxy, StatusNotifierWatcher,
     StatusNotifierWatcherProxy,
@@ -16,12 +16,12 @@ use iced::{
     widget::{image, svg},
 };
 use linicon_theme::get_icon_theme;
-use log::{debug, error, info, trace};
+use log::{debug, error, info, trace, warn};
 use std::{any::TypeId, ops::Deref};
 
 pub mod dbus;
 
-fn get_icon_from_name(icon_name: &str) -> Option<TrayIcon> {
+pub fn get_icon_from_name(icon_name: &str) -> Option<TrayIcon> {
     debug!("get icon from name {}", icon_name);
 
     let lookup = lookup(icon_name).with_cache();
@@ -62,6 +62,7 @@ pub enum TrayEvent {
 pub struct StatusNotifierItem {
     pub name: String,
     pub icon: Option<TrayIcon>,
+    pub title: Option<String>,
     pub menu: Layout,
     item_proxy: StatusNotifierItemProxy<'static>,
     menu_proxy: DBusMenuProxy<'static>,
@@ -114,6 +115,12 @@ impl StatusNotifierItem {
                 .and_then(get_icon_from_name),
         };
 
+        let title = item_proxy
+            .title()
+            .await
+            .ok()
+            .filter(|title| !title.is_empty());
+
         let menu_path = item_proxy.menu().await?;
         let menu_proxy = dbus::DBusMenuProxy::builder(conn)
             .destination(dest.to_owned())?
@@ -126,6 +133,7 @@ impl StatusNotifierItem {
         Ok(Self {
             name,
             icon,
+            title,
             menu,
             item_proxy,
             menu_proxy,
@@ -157,11 +165,7 @@ impl Deref for TrayService {
     }
 }
 
-enum State {
-    Init,
-    Active(zbus::Connection),
-    Error,
-}
+type State = ListenerState<zbus::Connection>;
 
 impl TrayService {
     async fn initialize_data(conn: &zbus::Connection) -> anyhow::Result<TrayData> {
@@ -454,11 +458,10 @@ impl ReadOnlyService for TrayService {
         Subscription::run_with_id(
             id,
             channel(100, async |mut output| {
-                let mut state = State::Init;
-
-                loop {
-                    state = TrayService::start_listening(state, &mut output).await;
-                }
+                Retry::run(std::any::type_name::<TrayService>(), async |state| {
+                    TrayService::start_listening(state, &mut output).await
+                })
+                .await;
             }),
         )
     }
@@ -467,6 +470,7 @@ impl ReadOnlyService for TrayService {
 #[derive(Debug, Clone)]
 pub enum TrayCommand {
     MenuSelected(String, i32),
+    Activate(String, i32, i32),
 }
 
 impl Service for TrayService {
@@ -499,6 +503,24 @@ impl Service for TrayService {
                     Task::none()
                 }
             }
+            TrayCommand::Activate(name, x, y) => {
+                match self.data.iter().find(|item| item.name == name) {
+                    Some(item) => {
+                        let proxy = item.item_proxy.clone();
+
+                        Task::perform(
+                            async move {
+                                debug!("Activate tray item {}", name);
+                                if let Err(err) = proxy.activate(x, y).await {
+                                    warn!("Failed to activate tray item {}: {}", name, err);
+                                }
+                            },
+                            |_| ServiceEvent::Update(TrayEvent::None),
+                        )
+                    }
+                    None => Task::none(),
+                }
+            }
         }
     }
 }
@@ -1,7 +1,7 @@
-use std::ops::Deref;
+use std::{collections::HashMap, ops::Deref};
 use zbus::{
     Result, proxy,
-    zvariant::{ObjectPath, OwnedObjectPath},
+    zvariant::{ObjectPath, OwnedObjectPath, OwnedValue},
 };
 
 pub struct UPowerDbus<'a>(UPowerProxy<'a>);
@@ -82,6 +82,27 @@ impl Battery {
         time
     }
 
+    pub async fn health_percent(&self) -> Option<u8> {
+        let mut energy_full = 0.0;
+        let mut energy_full_design = 0.0;
+
+        for device in &self.0 {
+            if let (Ok(full), Ok(design)) = (
+                device.energy_full().await,
+                device.energy_full_design().await,
+            ) {
+                energy_full += full;
+                energy_full_design += design;
+            }
+        }
+
+        health_percent(energy_full, energy_full_design)
+    }
+
+    pub fn devices(&self) -> &[DeviceProxy<'static>] {
+        &self.0
+    }
+
     pub fn get_devices_path(self) -> Vec<ObjectPath<'static>> {
         self.0
             .into_iter()
@@ -90,6 +111,14 @@ impl Battery {
     }
 }
 
+pub fn health_percent(energy_full: f64, energy_full_design: f64) -> Option<u8> {
+    if energy_full > 0.0 && energy_full_design > 0.0 {
+        Some((energy_full / energy_full_design * 100.0).min(100.0) as u8)
+    } else {
+        None
+    }
+}
+
 impl UPowerDbus<'_> {
     pub async fn new(conn: &zbus::Connection) -> anyhow::Result<Self> {
         let nm = UPowerProxy::new(conn).await?;
@@ -123,6 +152,64 @@ impl UPowerDbus<'_> {
         }
     }
 
+    pub async fn get_ups_devices(&self) -> anyhow::Result<Vec<DeviceProxy<'static>>> {
+        let devices = self.enumerate_devices().await?;
+
+        let mut res = Vec::new();
+
+        for device in devices {
+            let device = DeviceProxy::builder(self.inner().connection())
+                .path(device)?
+                .build()
+                .await?;
+
+            if device.device_type().await? == 3 {
+                res.push(device);
+            }
+        }
+
+        Ok(res)
+    }
+
+    pub async fn get_display_battery(&self) -> anyhow::Result<Option<DeviceProxy<'static>>> {
+        let path = self.get_display_device().await?;
+
+        let device = DeviceProxy::builder(self.inner().connection())
+            .path(path)?
+            .build()
+            .await?;
+
+        if device.is_present().await? {
+            Ok(Some(device))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn get_bluetooth_device(
+        &self,
+        bluez_path: &str,
+        address: &str,
+    ) -> anyhow::Result<Option<DeviceProxy<'static>>> {
+        let devices = self.enumerate_devices().await?;
+
+        for device in devices {
+            let device = DeviceProxy::builder(self.inner().connection())
+                .path(device)?
+                .build()
+                .await?;
+
+            let native_path = device.native_path().await.unwrap_or_default();
+            let serial = device.serial().await.unwrap_or_default();
+
+            if native_path == bluez_path || serial.eq_ignore_ascii_case(address) {
+                return Ok(Some(device));
+            }
+        }
+
+        Ok(None)
+    }
+
     pub async fn get_device(
         &self,
         path: &ObjectPath<'static>,
@@ -144,8 +231,13 @@ impl UPowerDbus<'_> {
 pub trait UPower {
     fn enumerate_devices(&self) -> Result<Vec<OwnedObjectPath>>;
 
+    fn get_display_device(&self) -> Result<OwnedObjectPath>;
+
     #[zbus(signal)]
     fn device_added(&self) -> Result<OwnedObjectPath>;
+
+    #[zbus(signal)]
+    fn device_removed(&self) -> Result<OwnedObjectPath>;
 }
 
 #[proxy(
@@ -160,6 +252,18 @@ pub trait Device {
     #[zbus(property)]
     fn power_supply(&self) -> Result<bool>;
 
+    #[zbus(property)]
+    fn native_path(&self) -> Result<String>;
+
+    #[zbus(property)]
+    fn serial(&self) -> Result<String>;
+
+    #[zbus(property)]
+    fn model(&self) -> Result<String>;
+
+    #[zbus(property)]
+    fn is_present(&self) -> Result<bool>;
+
     #[zbus(property)]
     fn time_to_empty(&self) -> Result<i64>;
 
@@ -169,6 +273,12 @@ pub trait Device {
     #[zbus(property)]
     fn percentage(&self) -> Result<f64>;
 
+    #[zbus(property)]
+    fn energy_full(&self) -> Result<f64>;
+
+    #[zbus(property)]
+    fn energy_full_design(&self) -> Result<f64>;
+
     #[zbus(property)]
     fn state(&self) -> Result<u32>;
 }
@@ -184,4 +294,7 @@ pub trait PowerProfiles {
 
     #[zbus(property)]
     fn set_active_profile(&self, profile: &str) -> Result<()>;
+
+    #[zbus(property)]
+    fn profiles(&self) -> Result<Vec<HashMap<String, OwnedValue>>>;
 }
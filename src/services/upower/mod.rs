@@ -1,11 +1,16 @@
-use super::{ReadOnlyService, Service, ServiceEvent};
-use crate::{components::icons::Icons, utils::IndicatorState};
-use dbus::{Battery, PowerProfilesProxy, UPowerDbus};
+use super::{ListenerState, ReadOnlyService, Retry, Service, ServiceEvent};
+use crate::{
+    components::icons::Icons,
+    config::BatteryConfig,
+    utils::{IndicatorState, classify, launcher::notify},
+};
+use dbus::{DeviceProxy, PowerProfilesProxy, UPowerDbus, health_percent};
 use iced::{
     Subscription,
     futures::{
         SinkExt, Stream, StreamExt,
         channel::mpsc::Sender,
+        select,
         stream::{once, pending, select_all},
         stream_select,
     },
@@ -15,16 +20,17 @@ use log::{error, warn};
 use std::{any::TypeId, time::Duration};
 use zbus::zvariant::ObjectPath;
 
-mod dbus;
+pub mod dbus;
 
 #[derive(Clone, Copy, Debug)]
 pub struct BatteryData {
     pub capacity: i64,
     pub status: BatteryStatus,
+    pub health_percent: Option<u8>,
 }
 
 impl BatteryData {
-    pub fn get_indicator_state(&self) -> IndicatorState {
+    pub fn get_indicator_state(&self, config: &BatteryConfig) -> IndicatorState {
         match self {
             BatteryData {
                 status: BatteryStatus::Charging(_),
@@ -33,7 +39,15 @@ impl BatteryData {
             BatteryData {
                 status: BatteryStatus::Discharging(_),
                 capacity,
-            } if *capacity < 20 => IndicatorState::Danger,
+                ..
+            } => classify(*capacity as f32, &config.thresholds()),
+            _ => IndicatorState::Normal,
+        }
+    }
+
+    pub fn get_health_indicator_state(&self, config: &BatteryConfig) -> IndicatorState {
+        match self.health_percent {
+            Some(health) if health < config.health_warn_threshold => IndicatorState::Warning,
             _ => IndicatorState::Normal,
         }
     }
@@ -47,28 +61,77 @@ impl BatteryData {
             BatteryData {
                 status: BatteryStatus::Discharging(_),
                 capacity,
+                ..
             } if *capacity < 20 => Icons::Battery0,
             BatteryData {
                 status: BatteryStatus::Discharging(_),
                 capacity,
+                ..
             } if *capacity < 40 => Icons::Battery1,
             BatteryData {
                 status: BatteryStatus::Discharging(_),
                 capacity,
+                ..
             } if *capacity < 60 => Icons::Battery2,
             BatteryData {
                 status: BatteryStatus::Discharging(_),
                 capacity,
+                ..
             } if *capacity < 80 => Icons::Battery3,
             _ => Icons::Battery4,
         }
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UpsStatus {
+    Charging,
+    Discharging,
+    Full,
+    Unknown,
+}
+
+impl UpsStatus {
+    fn new(state: u32) -> Self {
+        match state {
+            1 => UpsStatus::Charging,
+            2 => UpsStatus::Discharging,
+            4 => UpsStatus::Full,
+            _ => UpsStatus::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UpsData {
+    pub label: String,
+    pub charge: u8,
+    pub status: UpsStatus,
+    pub time_remaining: Option<Duration>,
+}
+
+impl UpsData {
+    // a discharging UPS means the mains power is gone
+    pub fn get_indicator_state(&self, config: &BatteryConfig) -> IndicatorState {
+        if self.status == UpsStatus::Discharging || self.charge < config.ups_threshold {
+            IndicatorState::Danger
+        } else {
+            IndicatorState::Normal
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BatteryDevice {
+    pub id: String,
+    pub data: BatteryData,
+}
+
 #[derive(Debug, Clone)]
 pub enum UPowerEvent {
-    UpdateBattery(BatteryData),
+    UpdateBattery(BatteryData, Vec<BatteryDevice>),
     NoBattery,
+    UpdateUps(Vec<UpsData>),
     UpdatePowerProfile(PowerProfile),
 }
 
@@ -79,6 +142,17 @@ pub enum BatteryStatus {
     Full,
 }
 
+impl BatteryStatus {
+    fn new(state: u32, time_to_full: i64, time_to_empty: i64) -> Self {
+        match state {
+            1 => BatteryStatus::Charging(Duration::from_secs(time_to_full as u64)),
+            2 => BatteryStatus::Discharging(Duration::from_secs(time_to_empty as u64)),
+            4 => BatteryStatus::Full,
+            _ => BatteryStatus::Discharging(Duration::from_secs(0)),
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PowerProfile {
     Balanced,
@@ -99,6 +173,52 @@ impl From<String> for PowerProfile {
     }
 }
 
+impl PowerProfile {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PowerProfile::Balanced => "balanced",
+            PowerProfile::Performance => "performance",
+            PowerProfile::PowerSaver => "power-saver",
+            PowerProfile::Unknown => "",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PowerProfile::Balanced => "Balanced",
+            PowerProfile::Performance => "Performance",
+            PowerProfile::PowerSaver => "Power Saver",
+            PowerProfile::Unknown => "",
+        }
+    }
+
+    pub fn get_indicator_state(&self) -> IndicatorState {
+        match self {
+            PowerProfile::Balanced => IndicatorState::Warning,
+            PowerProfile::Performance => IndicatorState::Danger,
+            PowerProfile::PowerSaver | PowerProfile::Unknown => IndicatorState::Normal,
+        }
+    }
+
+    // cycles in the same order as before, skipping the profiles the daemon doesn't expose
+    fn next(&self, available: &[PowerProfile]) -> PowerProfile {
+        const ORDER: [PowerProfile; 3] = [
+            PowerProfile::Balanced,
+            PowerProfile::Performance,
+            PowerProfile::PowerSaver,
+        ];
+
+        let Some(current) = ORDER.iter().position(|p| p == self) else {
+            return *self;
+        };
+
+        (1..=ORDER.len())
+            .map(|offset| ORDER[(current + offset) % ORDER.len()])
+            .find(|p| available.is_empty() || available.contains(p))
+            .unwrap_or(*self)
+    }
+}
+
 impl From<PowerProfile> for Icons {
     fn from(profile: PowerProfile) -> Self {
         match profile {
@@ -113,15 +233,21 @@ impl From<PowerProfile> for Icons {
 #[derive(Debug, Clone)]
 pub struct UPowerService {
     pub battery: Option<BatteryData>,
+    pub batteries: Vec<BatteryDevice>,
+    pub ups: Vec<UpsData>,
     pub power_profile: PowerProfile,
+    pub power_profiles: Vec<PowerProfile>,
+    pub config: BatteryConfig,
+    low_battery_notified: bool,
+    critical_battery_notified: bool,
     conn: zbus::Connection,
 }
 
-enum State {
-    Init,
-    Active(zbus::Connection, Option<Vec<ObjectPath<'static>>>),
-    Error,
-}
+type State = ListenerState<(
+    zbus::Connection,
+    Option<Vec<ObjectPath<'static>>>,
+    Vec<ObjectPath<'static>>,
+)>;
 
 impl ReadOnlyService for UPowerService {
     type UpdateEvent = UPowerEvent;
@@ -129,11 +255,18 @@ impl ReadOnlyService for UPowerService {
 
     fn update(&mut self, event: Self::UpdateEvent) {
         match event {
-            UPowerEvent::UpdateBattery(data) => {
+            UPowerEvent::UpdateBattery(data, batteries) => {
+                self.check_battery_level(&data);
                 self.battery.replace(data);
+                self.batteries = batteries;
             }
             UPowerEvent::NoBattery => {
                 self.battery = None;
+                self.batteries.clear();
+            }
+            UPowerEvent::UpdateUps(ups) => {
+                self.check_ups_status(&ups);
+                self.ups = ups;
             }
             UPowerEvent::UpdatePowerProfile(profile) => {
                 self.power_profile = profile;
@@ -142,43 +275,96 @@ impl ReadOnlyService for UPowerService {
     }
 
     fn subscribe() -> Subscription<ServiceEvent<Self>> {
+        Self::subscribe_with_config(&BatteryConfig::default())
+    }
+}
+
+impl UPowerService {
+    pub fn subscribe_with_config(config: &BatteryConfig) -> Subscription<ServiceEvent<Self>> {
         let id = TypeId::of::<Self>();
+        let config = config.clone();
 
         Subscription::run_with_id(
-            id,
-            channel(100, async |mut output| {
-                let mut state = State::Init;
-
-                loop {
-                    state = UPowerService::start_listening(state, &mut output).await;
-                }
+            format!("{:?}-{:?}", id, config),
+            channel(100, async move |mut output| {
+                Retry::run(std::any::type_name::<UPowerService>(), async |state| {
+                    UPowerService::start_listening(state, &mut output, &config).await
+                })
+                .await;
             }),
         )
     }
-}
 
-impl UPowerService {
+    fn check_battery_level(&mut self, data: &BatteryData) {
+        match data.status {
+            BatteryStatus::Discharging(_) => {
+                match classify(data.capacity as f32, &self.config.thresholds()) {
+                    IndicatorState::Danger if !self.critical_battery_notified => {
+                        notify(
+                            "Battery critically low",
+                            &format!("{}% remaining", data.capacity),
+                            "critical",
+                        );
+                        self.critical_battery_notified = true;
+                        self.low_battery_notified = true;
+                    }
+                    IndicatorState::Danger | IndicatorState::Warning
+                        if !self.low_battery_notified =>
+                    {
+                        notify(
+                            "Battery low",
+                            &format!("{}% remaining", data.capacity),
+                            "normal",
+                        );
+                        self.low_battery_notified = true;
+                    }
+                    _ => {}
+                }
+            }
+            _ => {
+                self.low_battery_notified = false;
+                self.critical_battery_notified = false;
+            }
+        }
+    }
+
+    fn check_ups_status(&self, ups: &[UpsData]) {
+        for device in ups.iter().filter(|u| u.status == UpsStatus::Discharging) {
+            let was_discharging = self
+                .ups
+                .iter()
+                .find(|u| u.label == device.label)
+                .is_some_and(|u| u.status == UpsStatus::Discharging);
+
+            if !was_discharging {
+                notify(
+                    "Power outage detected",
+                    &format!(
+                        "{} is running on battery, {}% remaining",
+                        device.label, device.charge
+                    ),
+                    "critical",
+                );
+            }
+        }
+    }
+
     async fn initialize_data(
         conn: &zbus::Connection,
+        config: &BatteryConfig,
     ) -> anyhow::Result<(
-        Option<(BatteryData, Vec<ObjectPath<'static>>)>,
+        Option<(BatteryData, Vec<BatteryDevice>, Vec<ObjectPath<'static>>)>,
         PowerProfile,
     )> {
-        let battery = UPowerService::initialize_battery_data(conn).await?;
+        let battery = UPowerService::initialize_battery_data(conn, config).await?;
         let power_profile = UPowerService::initialize_power_profile_data(conn).await;
 
         match (battery, power_profile) {
-            (Some(battery), Ok(power_profile)) => Ok((
-                Some((battery.0, battery.1.get_devices_path())),
-                power_profile,
-            )),
+            (Some(battery), Ok(power_profile)) => Ok((Some(battery), power_profile)),
             (Some(battery), Err(err)) => {
                 warn!("Failed to get power profile: {}", err);
 
-                Ok((
-                    Some((battery.0, battery.1.get_devices_path())),
-                    PowerProfile::Unknown,
-                ))
+                Ok((Some(battery), PowerProfile::Unknown))
             }
             (None, Ok(power_profile)) => Ok((None, power_profile)),
             (None, Err(err)) => {
@@ -202,42 +388,157 @@ impl UPowerService {
         Ok(profile)
     }
 
+    async fn available_power_profiles(conn: &zbus::Connection) -> Vec<PowerProfile> {
+        let profiles = match PowerProfilesProxy::new(conn).await {
+            Ok(powerprofiles) => powerprofiles.profiles().await,
+            Err(err) => Err(err),
+        };
+
+        match profiles {
+            Ok(profiles) => profiles
+                .into_iter()
+                .filter_map(|profile| {
+                    profile
+                        .get("Profile")
+                        .and_then(|value| String::try_from(value.clone()).ok())
+                })
+                .map(PowerProfile::from)
+                .filter(|profile| *profile != PowerProfile::Unknown)
+                .collect(),
+            Err(err) => {
+                warn!("Failed to get available power profiles: {}", err);
+
+                Vec::new()
+            }
+        }
+    }
+
     async fn initialize_battery_data(
         conn: &zbus::Connection,
-    ) -> anyhow::Result<Option<(BatteryData, Battery)>> {
+        config: &BatteryConfig,
+    ) -> anyhow::Result<Option<(BatteryData, Vec<BatteryDevice>, Vec<ObjectPath<'static>>)>> {
         let upower = UPowerDbus::new(conn).await?;
         let battery = upower.get_battery_devices().await?;
 
         match battery {
             Some(battery) => {
-                let state = battery.state().await;
-                let state = match state {
-                    1 => BatteryStatus::Charging(Duration::from_secs(
-                        battery.time_to_full().await as u64,
-                    )),
-                    2 => BatteryStatus::Discharging(Duration::from_secs(
-                        battery.time_to_empty().await as u64,
-                    )),
-                    4 => BatteryStatus::Full,
-                    _ => BatteryStatus::Discharging(Duration::from_secs(0)),
+                let health_percent = battery.health_percent().await;
+
+                let mut batteries = Vec::new();
+                for device in battery.devices() {
+                    batteries.push(Self::get_battery_device(device).await);
+                }
+
+                let display_device = if config.use_display_device {
+                    upower.get_display_battery().await.unwrap_or_else(|err| {
+                        warn!("Failed to get UPower display device: {}", err);
+
+                        None
+                    })
+                } else {
+                    None
                 };
-                let percentage = battery.percentage().await as i64;
-
-                Ok(Some((
-                    BatteryData {
-                        capacity: percentage,
-                        status: state,
-                    },
-                    battery,
-                )))
+
+                let (data, display_device_path) = match display_device {
+                    Some(display_device) => (
+                        BatteryData {
+                            health_percent,
+                            ..Self::get_battery_device(&display_device).await.data
+                        },
+                        Some(display_device.inner().path().to_owned()),
+                    ),
+                    None => (
+                        BatteryData {
+                            capacity: battery.percentage().await as i64,
+                            status: BatteryStatus::new(
+                                battery.state().await as u32,
+                                battery.time_to_full().await,
+                                battery.time_to_empty().await,
+                            ),
+                            health_percent,
+                        },
+                        None,
+                    ),
+                };
+
+                let mut devices_path = battery.get_devices_path();
+                devices_path.extend(display_device_path);
+
+                Ok(Some((data, batteries, devices_path)))
             }
             _ => Ok(None),
         }
     }
 
+    async fn initialize_ups_data(
+        conn: &zbus::Connection,
+    ) -> anyhow::Result<(Vec<UpsData>, Vec<ObjectPath<'static>>)> {
+        let upower = UPowerDbus::new(conn).await?;
+        let devices = upower.get_ups_devices().await?;
+
+        let mut ups = Vec::with_capacity(devices.len());
+        for device in &devices {
+            ups.push(Self::get_ups_data(device).await);
+        }
+
+        Ok((
+            ups,
+            devices
+                .iter()
+                .map(|device| device.inner().path().to_owned())
+                .collect(),
+        ))
+    }
+
+    async fn get_ups_data(device: &DeviceProxy<'static>) -> UpsData {
+        let status = UpsStatus::new(device.state().await.unwrap_or_default());
+        let time_to_empty = device.time_to_empty().await.unwrap_or_default();
+
+        let label = match device.model().await {
+            Ok(model) if !model.is_empty() => model,
+            _ => device
+                .native_path()
+                .await
+                .unwrap_or_else(|_| device.inner().path().to_string()),
+        };
+
+        UpsData {
+            label,
+            charge: device.percentage().await.unwrap_or_default() as u8,
+            status,
+            time_remaining: (status == UpsStatus::Discharging && time_to_empty > 0)
+                .then(|| Duration::from_secs(time_to_empty as u64)),
+        }
+    }
+
+    async fn get_battery_device(device: &DeviceProxy<'static>) -> BatteryDevice {
+        let status = BatteryStatus::new(
+            device.state().await.unwrap_or_default(),
+            device.time_to_full().await.unwrap_or_default(),
+            device.time_to_empty().await.unwrap_or_default(),
+        );
+
+        BatteryDevice {
+            id: device
+                .native_path()
+                .await
+                .unwrap_or_else(|_| device.inner().path().to_string()),
+            data: BatteryData {
+                capacity: device.percentage().await.unwrap_or_default() as i64,
+                status,
+                health_percent: health_percent(
+                    device.energy_full().await.unwrap_or_default(),
+                    device.energy_full_design().await.unwrap_or_default(),
+                ),
+            },
+        }
+    }
+
     async fn events(
         conn: &zbus::Connection,
         battery_devices: &Option<Vec<ObjectPath<'static>>>,
+        ups_devices: &[ObjectPath<'static>],
+        config: &BatteryConfig,
     ) -> anyhow::Result<impl Stream<Item = UPowerEvent> + use<>> {
         let battery_event = if let Some(battery_devices) = battery_devices {
             let upower = UPowerDbus::new(conn).await?;
@@ -256,13 +557,18 @@ impl UPowerService {
                     )
                     .filter_map({
                         let conn = conn.clone();
+                        let config = config.clone();
                         move |_| {
                             let conn = conn.clone();
+                            let config = config.clone();
                             async move {
-                                if let Some((data, _)) =
-                                    Self::initialize_battery_data(&conn).await.ok().flatten()
+                                if let Some((data, batteries, _)) =
+                                    Self::initialize_battery_data(&conn, &config)
+                                        .await
+                                        .ok()
+                                        .flatten()
                                 {
-                                    Some(UPowerEvent::UpdateBattery(data))
+                                    Some(UPowerEvent::UpdateBattery(data, batteries))
                                 } else {
                                     None
                                 }
@@ -278,6 +584,42 @@ impl UPowerService {
             once(async {}).map(|_| UPowerEvent::NoBattery).boxed()
         };
 
+        let ups_event = if ups_devices.is_empty() {
+            pending().boxed()
+        } else {
+            let upower = UPowerDbus::new(conn).await?;
+
+            let mut events = Vec::new();
+
+            for device_path in ups_devices {
+                let device = upower.get_device(device_path).await?;
+
+                events.push(
+                    stream_select!(
+                        device.receive_state_changed().await.map(|_| ()),
+                        device.receive_percentage_changed().await.map(|_| ()),
+                        device.receive_time_to_empty_changed().await.map(|_| ()),
+                    )
+                    .boxed(),
+                );
+            }
+
+            select_all(events)
+                .filter_map({
+                    let conn = conn.clone();
+                    move |_| {
+                        let conn = conn.clone();
+                        async move {
+                            Self::initialize_ups_data(&conn)
+                                .await
+                                .ok()
+                                .map(|(ups, _)| UPowerEvent::UpdateUps(ups))
+                        }
+                    }
+                })
+                .boxed()
+        };
+
         let powerprofiles = PowerProfilesProxy::new(conn).await?;
         let power_profile_event =
             powerprofiles
@@ -292,19 +634,42 @@ impl UPowerService {
                     )
                 });
 
-        Ok(stream_select!(battery_event, power_profile_event))
+        Ok(stream_select!(
+            battery_event,
+            ups_event,
+            power_profile_event
+        ))
+    }
+
+    // an UPS plugged in after the start is only known by these signals
+    async fn devices_changed(
+        conn: &zbus::Connection,
+    ) -> anyhow::Result<impl Stream<Item = ()> + use<>> {
+        let upower = UPowerDbus::new(conn).await?;
+
+        Ok(stream_select!(
+            upower.receive_device_added().await?.map(|_| ()),
+            upower.receive_device_removed().await?.map(|_| ()),
+        ))
     }
 
-    async fn start_listening(state: State, output: &mut Sender<ServiceEvent<Self>>) -> State {
+    async fn start_listening(
+        state: State,
+        output: &mut Sender<ServiceEvent<Self>>,
+        config: &BatteryConfig,
+    ) -> State {
         match state {
             State::Init => match zbus::Connection::system().await {
                 Ok(conn) => {
-                    let (battery, battery_path, power_profile) =
-                        match UPowerService::initialize_data(&conn).await {
-                            Ok((Some((battery_data, battery_path)), power_profile)) => {
-                                (Some(battery_data), Some(battery_path), power_profile)
-                            }
-                            Ok((None, power_profile)) => (None, None, power_profile),
+                    let (battery, batteries, battery_path, power_profile) =
+                        match UPowerService::initialize_data(&conn, config).await {
+                            Ok((Some((battery_data, batteries, battery_path)), power_profile)) => (
+                                Some(battery_data),
+                                batteries,
+                                Some(battery_path),
+                                power_profile,
+                            ),
+                            Ok((None, power_profile)) => (None, Vec::new(), None, power_profile),
                             Err(err) => {
                                 error!("Failed to initialize upower service: {}", err);
 
@@ -312,28 +677,92 @@ impl UPowerService {
                             }
                         };
 
-                    let service = UPowerService {
-                        battery,
+                    let (ups, ups_path) = UPowerService::initialize_ups_data(&conn)
+                        .await
+                        .unwrap_or_else(|err| {
+                            warn!("Failed to get UPS devices: {}", err);
+
+                            (Vec::new(), Vec::new())
+                        });
+
+                    let power_profiles = if power_profile != PowerProfile::Unknown {
+                        UPowerService::available_power_profiles(&conn).await
+                    } else {
+                        Vec::new()
+                    };
+
+                    let mut service = UPowerService {
+                        battery: None,
+                        batteries,
+                        ups: Vec::new(),
                         power_profile,
+                        power_profiles,
+                        config: config.clone(),
+                        low_battery_notified: false,
+                        critical_battery_notified: false,
                         conn: conn.clone(),
                     };
+                    if let Some(battery) = battery {
+                        if let Some(health) = battery
+                            .health_percent
+                            .filter(|health| *health < config.health_warn_threshold)
+                        {
+                            warn!("Battery health is {}%", health);
+                        }
+
+                        service.check_battery_level(&battery);
+                        service.battery = Some(battery);
+                    }
+                    service.check_ups_status(&ups);
+                    service.ups = ups;
                     let _ = output.send(ServiceEvent::Init(service)).await;
 
-                    State::Active(conn, battery_path)
+                    State::Active((conn, battery_path, ups_path))
                 }
                 Err(err) => {
                     error!("Failed to connect to system bus for upower: {}", err);
                     State::Error
                 }
             },
-            State::Active(conn, battery_devices) => {
-                match UPowerService::events(&conn, &battery_devices).await {
-                    Ok(mut events) => {
-                        while let Some(event) = events.next().await {
-                            let _ = output.send(ServiceEvent::Update(event)).await;
+            State::Active((conn, battery_devices, ups_devices)) => {
+                match UPowerService::events(&conn, &battery_devices, &ups_devices, config).await {
+                    Ok(events) => {
+                        let mut events = events.boxed().fuse();
+                        let mut devices_changed = match UPowerService::devices_changed(&conn).await
+                        {
+                            Ok(devices_changed) => devices_changed.boxed().fuse(),
+                            Err(err) => {
+                                warn!("Failed to listen for upower devices: {}", err);
+
+                                pending().boxed().fuse()
+                            }
+                        };
+
+                        loop {
+                            select! {
+                                event = events.next() => match event {
+                                    Some(event) => {
+                                        let _ = output.send(ServiceEvent::Update(event)).await;
+                                    }
+                                    None => break,
+                                },
+                                _ = devices_changed.next() => {
+                                    // the events are listened again with the new UPS list
+                                    match UPowerService::initialize_ups_data(&conn).await {
+                                        Ok((ups, ups_devices)) => {
+                                            let _ = output
+                                                .send(ServiceEvent::Update(UPowerEvent::UpdateUps(ups)))
+                                                .await;
+
+                                            return State::Active((conn, battery_devices, ups_devices));
+                                        }
+                                        Err(err) => warn!("Failed to get UPS devices: {}", err),
+                                    }
+                                }
+                            }
                         }
 
-                        State::Active(conn, battery_devices)
+                        State::Active((conn, battery_devices, ups_devices))
                     }
                     Err(err) => {
                         error!("Failed to listen for upower events: {}", err);
@@ -353,6 +782,7 @@ impl UPowerService {
 
 pub enum PowerProfileCommand {
     Toggle,
+    SetProfile(PowerProfile),
 }
 
 impl Service for UPowerService {
@@ -363,32 +793,27 @@ impl Service for UPowerService {
             {
                 let conn = self.conn.clone();
                 let power_profile = self.power_profile;
+                let power_profiles = self.power_profiles.clone();
                 async move {
                     let powerprofiles = PowerProfilesProxy::new(&conn)
                         .await
                         .expect("Failed to create PowerProfilesProxy");
 
-                    match command {
-                        PowerProfileCommand::Toggle => {
-                            let current_profile = power_profile;
-                            match current_profile {
-                                PowerProfile::Balanced => {
-                                    let _ = powerprofiles.set_active_profile("performance").await;
+                    let profile = match command {
+                        PowerProfileCommand::Toggle => power_profile.next(&power_profiles),
+                        PowerProfileCommand::SetProfile(profile) => profile,
+                    };
 
-                                    PowerProfile::Performance
-                                }
-                                PowerProfile::Performance => {
-                                    let _ = powerprofiles.set_active_profile("power-saver").await;
+                    if profile == PowerProfile::Unknown || profile == power_profile {
+                        return power_profile;
+                    }
 
-                                    PowerProfile::PowerSaver
-                                }
-                                PowerProfile::PowerSaver => {
-                                    let _ = powerprofiles.set_active_profile("balanced").await;
+                    match powerprofiles.set_active_profile(profile.as_str()).await {
+                        Ok(_) => profile,
+                        Err(err) => {
+                            warn!("Failed to set power profile {}: {}", profile.as_str(), err);
 
-                                    PowerProfile::Balanced
-                                }
-                                PowerProfile::Unknown => PowerProfile::Unknown,
-                            }
+                            power_profile
                         }
                     }
                 }
@@ -0,0 +1,271 @@
+use super::{ReadOnlyService, Service, ServiceEvent};
+use crate::utils::launcher::notify;
+use iced::{
+    Subscription, Task,
+    futures::{SinkExt, StreamExt, stream::pending},
+    stream::channel,
+};
+use log::{debug, error, warn};
+use std::{any::TypeId, fs};
+use tokio::{
+    io::{Interest, unix::AsyncFd},
+    process::Command,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsbDevice {
+    pub name: String,
+    pub id_vendor: String,
+    pub id_product: String,
+    pub mount_point: Option<String>,
+    pub dev_node: String,
+}
+
+impl UsbDevice {
+    // only usb disks are tracked, they are the ones that can be mounted and ejected
+    fn from_udev(device: &udev::Device) -> Option<Self> {
+        let property = |name: &str| {
+            device
+                .property_value(name)
+                .map(|value| value.to_string_lossy().into_owned())
+        };
+
+        if property("ID_BUS").as_deref() != Some("usb")
+            || device.devtype().and_then(|t| t.to_str()) != Some("disk")
+        {
+            return None;
+        }
+
+        let dev_node = device.devnode()?.to_string_lossy().into_owned();
+
+        Some(UsbDevice {
+            name: property("ID_MODEL")
+                .map(|model| model.replace('_', " "))
+                .unwrap_or_else(|| device.sysname().to_string_lossy().into_owned()),
+            id_vendor: property("ID_VENDOR_ID").unwrap_or_default(),
+            id_product: property("ID_MODEL_ID").unwrap_or_default(),
+            mount_point: mount_points(&dev_node).into_iter().next(),
+            dev_node,
+        })
+    }
+}
+
+// mount points of the disk itself or of any of its partitions
+fn mount_points(dev_node: &str) -> Vec<String> {
+    mounted_nodes(dev_node)
+        .into_iter()
+        .map(|(_, mount_point)| mount_point)
+        .collect()
+}
+
+fn mounted_nodes(dev_node: &str) -> Vec<(String, String)> {
+    parse_mounts(
+        &fs::read_to_string("/proc/mounts").unwrap_or_default(),
+        dev_node,
+    )
+}
+
+// (source, mount point) of the /proc/mounts entries belonging to `dev_node`
+fn parse_mounts(mounts: &str, dev_node: &str) -> Vec<(String, String)> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let source = fields.next()?;
+            let mount_point = fields.next()?;
+
+            // `/dev/sdb` must not match `/dev/sdbc1`, only the partitions
+            // like `/dev/sdb1` or `/dev/nvme0n1p1`
+            let suffix = source.strip_prefix(dev_node)?;
+            let partition = suffix
+                .strip_prefix('p')
+                .filter(|number| !number.is_empty())
+                .unwrap_or(suffix);
+            partition
+                .chars()
+                .all(|c| c.is_ascii_digit())
+                .then(|| (source.to_owned(), mount_point.replace("\\040", " ")))
+        })
+        .collect()
+}
+
+fn enumerate() -> anyhow::Result<Vec<UsbDevice>> {
+    let mut enumerator = udev::Enumerator::new()?;
+    enumerator.match_subsystem("block")?;
+    enumerator.match_property("ID_BUS", "usb")?;
+
+    Ok(enumerator
+        .scan_devices()?
+        .filter_map(|device| UsbDevice::from_udev(&device))
+        .collect())
+}
+
+#[derive(Debug, Clone)]
+pub enum UsbEvent {
+    Connected(UsbDevice),
+    Disconnected(UsbDevice),
+    Refreshed(Vec<UsbDevice>),
+}
+
+#[derive(Debug, Clone)]
+pub struct UsbService {
+    pub devices: Vec<UsbDevice>,
+}
+
+impl ReadOnlyService for UsbService {
+    type UpdateEvent = UsbEvent;
+    type Error = ();
+
+    fn update(&mut self, event: Self::UpdateEvent) {
+        match event {
+            UsbEvent::Connected(device) => {
+                self.devices.retain(|d| d.dev_node != device.dev_node);
+                self.devices.push(device);
+            }
+            UsbEvent::Disconnected(device) => {
+                self.devices.retain(|d| d.dev_node != device.dev_node);
+            }
+            UsbEvent::Refreshed(devices) => {
+                self.devices = devices;
+            }
+        }
+    }
+
+    fn subscribe() -> Subscription<ServiceEvent<Self>> {
+        let id = TypeId::of::<Self>();
+
+        Subscription::run_with_id(
+            id,
+            channel(10, async |mut output| {
+                let devices = enumerate().unwrap_or_else(|err| {
+                    warn!("Failed to enumerate usb devices: {}", err);
+
+                    Vec::new()
+                });
+                let _ = output
+                    .send(ServiceEvent::Init(UsbService { devices }))
+                    .await;
+
+                let socket = udev::MonitorBuilder::new()
+                    .and_then(|builder| builder.match_subsystem_devtype("block", "disk"))
+                    .and_then(|builder| builder.listen())
+                    .and_then(|socket| AsyncFd::with_interest(socket, Interest::READABLE));
+
+                match socket {
+                    Ok(mut socket) => loop {
+                        let Ok(mut guard) = socket.readable_mut().await else {
+                            error!("Failed to read the udev monitor socket");
+                            break;
+                        };
+
+                        for evt in guard.get_inner().iter() {
+                            let Some(device) = UsbDevice::from_udev(&evt.device()) else {
+                                continue;
+                            };
+                            debug!("Usb event {:?}: {:?}", evt.event_type(), device);
+
+                            let event = match evt.event_type() {
+                                udev::EventType::Add => UsbEvent::Connected(device),
+                                udev::EventType::Remove => UsbEvent::Disconnected(device),
+                                _ => continue,
+                            };
+                            let _ = output.send(ServiceEvent::Update(event)).await;
+                        }
+                        guard.clear_ready();
+                    },
+                    Err(err) => {
+                        error!("Failed to listen for usb events: {}", err);
+                    }
+                }
+
+                let _ = pending::<u8>().next().await;
+            }),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum UsbCommand {
+    // mount points aren't reported by udev, they are read again on demand
+    Refresh,
+    Eject(String),
+}
+
+impl Service for UsbService {
+    type Command = UsbCommand;
+
+    fn command(&mut self, command: Self::Command) -> Task<ServiceEvent<Self>> {
+        Task::perform(
+            async move {
+                if let UsbCommand::Eject(dev_node) = command {
+                    for (source, _) in mounted_nodes(&dev_node) {
+                        let _ = Command::new("udisksctl")
+                            .args(["unmount", "-b", &source])
+                            .output()
+                            .await;
+                    }
+
+                    match Command::new("udisksctl")
+                        .args(["power-off", "-b", &dev_node])
+                        .output()
+                        .await
+                    {
+                        Ok(output) if output.status.success() => {}
+                        Ok(output) => {
+                            let err = String::from_utf8_lossy(&output.stderr);
+                            warn!("Failed to eject {}: {}", dev_node, err.trim());
+                            notify("Failed to eject the device", err.trim(), "normal");
+                        }
+                        Err(err) => warn!("Failed to run udisksctl: {}", err),
+                    }
+                }
+
+                enumerate().unwrap_or_default()
+            },
+            |devices| ServiceEvent::Update(UsbEvent::Refreshed(devices)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOUNTS: &str = "\
+/dev/nvme0n1p2 / ext4 rw,relatime 0 0
+/dev/nvme0n1p1 /boot vfat rw,relatime,fmask=0022,dmask=0022,codepage=437 0 0
+tmpfs /tmp tmpfs rw,nosuid,nodev,size=16318340k,nr_inodes=1048576 0 0
+/dev/sdb /run/media/user/DISK vfat rw,nosuid,nodev,relatime,uid=1000,gid=1000 0 0
+/dev/sdb1 /run/media/user/USB\\040STICK exfat rw,nosuid,nodev,relatime,uid=1000 0 0
+/dev/sdbc1 /run/media/user/OTHER ext4 rw,nosuid,nodev,relatime 0 0
+";
+
+    fn sources(dev_node: &str) -> Vec<String> {
+        parse_mounts(MOUNTS, dev_node)
+            .into_iter()
+            .map(|(source, _)| source)
+            .collect()
+    }
+
+    #[test]
+    fn parse_mounts_disk_and_partitions() {
+        assert_eq!(sources("/dev/sdb"), ["/dev/sdb", "/dev/sdb1"]);
+        assert_eq!(sources("/dev/sdbc"), ["/dev/sdbc1"]);
+        assert_eq!(
+            sources("/dev/nvme0n1"),
+            ["/dev/nvme0n1p2", "/dev/nvme0n1p1"]
+        );
+        assert!(sources("/dev/sdc").is_empty());
+    }
+
+    #[test]
+    fn parse_mounts_unescapes_spaces() {
+        assert_eq!(
+            parse_mounts(MOUNTS, "/dev/sdb1"),
+            [(
+                "/dev/sdb1".to_owned(),
+                "/run/media/user/USB STICK".to_owned()
+            )]
+        );
+    }
+}
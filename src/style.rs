@@ -1,4 +1,4 @@
-use crate::config::{Appearance, AppearanceColor, AppearanceStyle};
+use crate::config::{AccessibilityConfig, Appearance, AppearanceColor, AppearanceStyle};
 use iced::{
     Background, Border, Color, Theme,
     theme::{Palette, palette},
@@ -8,110 +8,136 @@ use iced::{
     },
 };
 
-pub fn ashell_theme(appearance: &Appearance) -> Theme {
+pub fn ashell_theme(appearance: &Appearance, accessibility: &AccessibilityConfig) -> Theme {
+    if accessibility.high_contrast {
+        return high_contrast_theme(appearance.theme.colors().is_dark);
+    }
+
+    let background_color = appearance.background_color();
+    let primary_color = appearance.primary_color();
+    let secondary_color = appearance.secondary_color();
+    let success_color = appearance.success_color();
+    let danger_color = appearance.danger_color();
+    let text_color = appearance.text_color();
+
     Theme::custom_with_fn(
         "local".to_string(),
         Palette {
-            background: appearance.background_color.get_base(),
-            text: appearance.text_color.get_base(),
-            primary: appearance.primary_color.get_base(),
-            success: appearance.success_color.get_base(),
-            danger: appearance.danger_color.get_base(),
+            background: background_color.get_base(),
+            text: text_color.get_base(),
+            primary: primary_color.get_base(),
+            success: success_color.get_base(),
+            danger: danger_color.get_base(),
         },
         |palette| {
             let default_bg = palette::Background::new(
                 palette.background,
-                appearance
-                    .background_color
-                    .get_text()
-                    .unwrap_or(palette.text),
+                background_color.get_text().unwrap_or(palette.text),
             );
             let default_primary = palette::Primary::generate(
                 palette.primary,
                 palette.background,
-                appearance.primary_color.get_text().unwrap_or(palette.text),
+                primary_color.get_text().unwrap_or(palette.text),
             );
             let default_secondary = palette::Primary::generate(
-                appearance.secondary_color.get_base(),
+                secondary_color.get_base(),
                 palette.background,
-                appearance
-                    .secondary_color
-                    .get_text()
-                    .unwrap_or(palette.text),
+                secondary_color.get_text().unwrap_or(palette.text),
             );
             let default_success = palette::Success::generate(
                 palette.success,
                 palette.background,
-                appearance.success_color.get_text().unwrap_or(palette.text),
+                success_color.get_text().unwrap_or(palette.text),
             );
             let default_danger = palette::Danger::generate(
                 palette.danger,
                 palette.background,
-                appearance.danger_color.get_text().unwrap_or(palette.text),
+                danger_color.get_text().unwrap_or(palette.text),
             );
 
             palette::Extended {
                 background: palette::Background {
                     base: default_bg.base,
-                    weak: appearance
-                        .background_color
+                    weak: background_color
                         .get_weak_pair(palette.text)
                         .unwrap_or(default_bg.weak),
-                    strong: appearance
-                        .background_color
+                    strong: background_color
                         .get_strong_pair(palette.text)
                         .unwrap_or(default_bg.strong),
                 },
                 primary: palette::Primary {
                     base: default_primary.base,
-                    weak: appearance
-                        .primary_color
+                    weak: primary_color
                         .get_weak_pair(palette.text)
                         .unwrap_or(default_primary.weak),
-                    strong: appearance
-                        .primary_color
+                    strong: primary_color
                         .get_strong_pair(palette.text)
                         .unwrap_or(default_primary.strong),
                 },
                 secondary: palette::Secondary {
                     base: default_secondary.base,
-                    weak: appearance
-                        .secondary_color
+                    weak: secondary_color
                         .get_weak_pair(palette.text)
                         .unwrap_or(default_secondary.weak),
-                    strong: appearance
-                        .secondary_color
+                    strong: secondary_color
                         .get_strong_pair(palette.text)
                         .unwrap_or(default_secondary.strong),
                 },
                 success: palette::Success {
                     base: default_success.base,
-                    weak: appearance
-                        .success_color
+                    weak: success_color
                         .get_weak_pair(palette.text)
                         .unwrap_or(default_success.weak),
-                    strong: appearance
-                        .success_color
+                    strong: success_color
                         .get_strong_pair(palette.text)
                         .unwrap_or(default_success.strong),
                 },
                 danger: palette::Danger {
                     base: default_danger.base,
-                    weak: appearance
-                        .danger_color
+                    weak: danger_color
                         .get_weak_pair(palette.text)
                         .unwrap_or(default_danger.weak),
-                    strong: appearance
-                        .danger_color
+                    strong: danger_color
                         .get_strong_pair(palette.text)
                         .unwrap_or(default_danger.strong),
                 },
-                is_dark: true,
+                is_dark: appearance.theme.colors().is_dark,
             }
         },
     )
 }
 
+// pure black and white with saturated indicator colors, the warning
+// indicators are drawn with the weak danger color
+fn high_contrast_theme(is_dark: bool) -> Theme {
+    let (background, text) = if is_dark {
+        (Color::BLACK, Color::WHITE)
+    } else {
+        (Color::WHITE, Color::BLACK)
+    };
+    let success = Color::from_rgb8(0x00, 0xFF, 0x00);
+    let warning = Color::from_rgb8(0xFF, 0xFF, 0x00);
+    let danger = Color::from_rgb8(0xFF, 0x00, 0x00);
+
+    Theme::custom_with_fn(
+        "high-contrast".to_string(),
+        Palette {
+            background,
+            text,
+            primary: text,
+            success,
+            danger,
+        },
+        move |palette| {
+            let mut extended = palette::Extended::generate(palette);
+            extended.danger.weak = palette::Pair::new(warning, Color::BLACK);
+            extended.is_dark = is_dark;
+
+            extended
+        },
+    )
+}
+
 /// Note: the transparent argument, when true, makes the base color bg
 /// transparent but still has a hover bg color. Not to be confused with opacity,
 /// which affects opacity at all times.
@@ -190,6 +216,29 @@ pub fn ghost_button_style(opacity: f32) -> impl Fn(&Theme, Status) -> button::St
     }
 }
 
+// keyboard focus is shown as a ring, hover keeps the ghost button background
+pub fn focus_ring_button_style(
+    focused: bool,
+    opacity: f32,
+) -> impl Fn(&Theme, Status) -> button::Style {
+    with_focus_ring(focused, ghost_button_style(opacity))
+}
+
+// outlines the button reached with the keyboard
+pub fn with_focus_ring(
+    focused: bool,
+    style: impl Fn(&Theme, Status) -> button::Style,
+) -> impl Fn(&Theme, Status) -> button::Style {
+    move |theme, status| {
+        let mut base = style(theme, status);
+        if focused {
+            base.border.width = 2.0;
+            base.border.color = theme.palette().primary;
+        }
+        base
+    }
+}
+
 pub fn outline_button_style(opacity: f32) -> impl Fn(&Theme, Status) -> button::Style {
     move |theme, status| {
         let mut base = button::Style {
@@ -299,9 +348,11 @@ pub fn settings_button_style(opacity: f32) -> impl Fn(&Theme, Status) -> button:
     }
 }
 
+// dimmed is used for the workspaces of the outputs without focus
 pub fn workspace_button_style(
     is_empty: bool,
     colors: Option<Option<AppearanceColor>>,
+    dimmed: bool,
 ) -> impl Fn(&Theme, Status) -> button::Style {
     move |theme: &Theme, status: Status| {
         let (bg_color, fg_color) = colors
@@ -343,7 +394,7 @@ pub fn workspace_button_style(
             },
             ..button::Style::default()
         };
-        match status {
+        let mut style = match status {
             Status::Active => base,
             Status::Hovered => {
                 let (bg_color, fg_color) = colors
@@ -381,7 +432,16 @@ pub fn workspace_button_style(
                 base
             }
             _ => base,
+        };
+
+        if dimmed {
+            style.background = style
+                .background
+                .map(|background| background.scale_alpha(0.5));
+            style.text_color = style.text_color.scale_alpha(0.7);
         }
+
+        style
     }
 }
 
@@ -0,0 +1,52 @@
+use crate::services::Retry;
+use iced::futures::{Stream, StreamExt, stream};
+use log::error;
+use std::pin::pin;
+use tokio::sync::OnceCell;
+
+const SERVICE_UNKNOWN: &str = "org.freedesktop.DBus.Error.ServiceUnknown";
+
+/// Yields a new session bus connection each time it's polled, retrying with
+/// backoff while the bus is unavailable.
+pub fn get_session_connection() -> impl Stream<Item = zbus::Connection> {
+    stream::unfold(Retry::new("D-Bus session connection"), |mut retry| async {
+        loop {
+            match zbus::Connection::session().await {
+                Ok(conn) => {
+                    retry.reset();
+
+                    return Some((conn, retry));
+                }
+                Err(err) => {
+                    error!("Failed to connect to the session bus: {}", err);
+
+                    let (_, wake_at) = retry.backoff()?;
+                    Retry::wait(wake_at).await;
+                }
+            }
+        }
+    })
+}
+
+pub async fn session_connection() -> Option<zbus::Connection> {
+    pin!(get_session_connection()).next().await
+}
+
+static SHARED_CONNECTION: OnceCell<zbus::Connection> = OnceCell::const_new();
+
+/// A connection kept for the one-off calls, opened on the first use. The
+/// services listening for signals open their own with [`session_connection`].
+pub async fn shared_session_connection() -> Option<zbus::Connection> {
+    SHARED_CONNECTION
+        .get_or_try_init(|| async { pin!(get_session_connection()).next().await.ok_or(()) })
+        .await
+        .ok()
+        .cloned()
+}
+
+pub fn is_service_unknown(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<zbus::Error>(),
+        Some(zbus::Error::MethodError(name, _, _)) if name.as_str() == SERVICE_UNKNOWN
+    )
+}
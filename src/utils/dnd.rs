@@ -0,0 +1,52 @@
+use super::dbus_session::shared_session_connection;
+use crate::{config::NotificationDaemon, services::notifications::DunstProxy};
+use anyhow::bail;
+use tokio::process::Command;
+
+const MAKO_DND_MODE: &str = "do-not-disturb";
+
+async fn makoctl(args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("makoctl").args(args).output().await?;
+
+    if !output.status.success() {
+        bail!(
+            "makoctl {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+async fn dunst() -> anyhow::Result<DunstProxy<'static>> {
+    let conn = shared_session_connection()
+        .await
+        .ok_or_else(|| anyhow::anyhow!("no session bus connection"))?;
+
+    Ok(DunstProxy::new(&conn).await?)
+}
+
+pub async fn is_dnd_active(daemon: NotificationDaemon) -> anyhow::Result<bool> {
+    match daemon {
+        NotificationDaemon::Mako => Ok(makoctl(&["mode"])
+            .await?
+            .lines()
+            .any(|mode| mode.trim() == MAKO_DND_MODE)),
+        NotificationDaemon::Dunst => Ok(dunst().await?.paused().await?),
+    }
+}
+
+pub async fn enable_dnd(daemon: NotificationDaemon) -> anyhow::Result<()> {
+    match daemon {
+        NotificationDaemon::Mako => makoctl(&["mode", "-a", MAKO_DND_MODE]).await.map(|_| ()),
+        NotificationDaemon::Dunst => Ok(dunst().await?.set_paused(true).await?),
+    }
+}
+
+pub async fn disable_dnd(daemon: NotificationDaemon) -> anyhow::Result<()> {
+    match daemon {
+        NotificationDaemon::Mako => makoctl(&["mode", "-r", MAKO_DND_MODE]).await.map(|_| ()),
+        NotificationDaemon::Dunst => Ok(dunst().await?.set_paused(false).await?),
+    }
+}
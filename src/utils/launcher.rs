@@ -1,4 +1,7 @@
-use std::process::Command;
+use super::portal;
+use crate::config::{ScreenshotConfig, ScreenshotTool, SettingsModuleConfig};
+use log::{debug, error};
+use std::{env, path::Path, process::Command};
 
 pub fn execute_command(command: String) {
     tokio::spawn(async move {
@@ -11,6 +14,27 @@ pub fn execute_command(command: String) {
     });
 }
 
+pub fn notify(summary: &str, body: &str, urgency: &str) {
+    let summary = summary.to_owned();
+    let body = body.to_owned();
+    let urgency = urgency.to_owned();
+
+    tokio::spawn(async move {
+        match Command::new("notify-send")
+            .arg("-u")
+            .arg(&urgency)
+            .arg(&summary)
+            .arg(&body)
+            .spawn()
+        {
+            Ok(mut child) => {
+                let _ = child.wait();
+            }
+            Err(err) => error!("Failed to send the \"{}\" notification: {}", summary, err),
+        }
+    });
+}
+
 pub fn suspend() {
     tokio::spawn(async move {
         let _ = Command::new("bash")
@@ -22,6 +46,30 @@ pub fn suspend() {
     });
 }
 
+pub fn hibernate() {
+    tokio::spawn(async move {
+        let status = Command::new("bash")
+            .arg("-c")
+            .arg("systemctl hibernate")
+            .spawn()
+            .expect("Failed to execute command.")
+            .wait();
+
+        if !status.is_ok_and(|status| status.success()) {
+            notify(
+                "Hibernate failed",
+                "systemctl hibernate exited with an error",
+                "normal",
+            );
+        }
+    });
+}
+
+pub fn can_hibernate() -> bool {
+    std::fs::read_to_string("/sys/power/state")
+        .is_ok_and(|states| states.split_whitespace().any(|state| state == "disk"))
+}
+
 pub fn shutdown() {
     tokio::spawn(async move {
         let _ = Command::new("bash")
@@ -44,6 +92,128 @@ pub fn reboot() {
     });
 }
 
+fn screenshot_command(config: &ScreenshotConfig, region: bool, file: Option<&Path>) -> String {
+    let command = match (config.tool, region) {
+        (ScreenshotTool::Grim, false) => "grim",
+        (ScreenshotTool::Grim, true) => "grim -g \"$(slurp)\"",
+        (ScreenshotTool::Wayshot, false) => "wayshot",
+        (ScreenshotTool::Wayshot, true) => "wayshot -s \"$(slurp)\"",
+    };
+
+    // give the menu the time to close before taking the screenshot
+    if config.copy_to_clipboard {
+        let stdout = match config.tool {
+            ScreenshotTool::Grim => "-",
+            ScreenshotTool::Wayshot => "--stdout",
+        };
+
+        format!("sleep 0.2 && {} {} | wl-copy", command, stdout)
+    } else if let Some(file) = file {
+        let output = match config.tool {
+            ScreenshotTool::Grim => "",
+            ScreenshotTool::Wayshot => "-f ",
+        };
+
+        format!("sleep 0.2 && {} {}'{}'", command, output, file.display())
+    } else {
+        format!("sleep 0.2 && {}", command)
+    }
+}
+
+fn take_screenshot(config: &ScreenshotConfig, region: bool) {
+    if !config.prompt_save || config.copy_to_clipboard {
+        execute_command(screenshot_command(config, region, None));
+        return;
+    }
+
+    let config = config.clone();
+    tokio::spawn(async move {
+        if let Err(err) = save_screenshot(&config, region).await {
+            error!("Failed to save the screenshot: {}", err);
+        }
+    });
+}
+
+// the screenshot is taken first so the file chooser doesn't end up in it
+async fn save_screenshot(config: &ScreenshotConfig, region: bool) -> anyhow::Result<()> {
+    let capture = env::temp_dir().join(format!("ashell-screenshot-{}.png", std::process::id()));
+
+    let status = tokio::process::Command::new("bash")
+        .arg("-c")
+        .arg(screenshot_command(config, region, Some(&capture)))
+        .status()
+        .await?;
+    if !status.success() {
+        anyhow::bail!("the screenshot command exited with {}", status);
+    }
+
+    let name = chrono::Local::now()
+        .format("Screenshot_%Y-%m-%d_%H-%M-%S.png")
+        .to_string();
+    let result = match portal::save_file("Save screenshot", &name).await {
+        Ok(Some(path)) => tokio::fs::copy(&capture, &path)
+            .await
+            .map(|_| ())
+            .map_err(anyhow::Error::from),
+        Ok(None) => {
+            debug!("Screenshot save cancelled, discarding it");
+            Ok(())
+        }
+        Err(err) => Err(err),
+    };
+    let _ = tokio::fs::remove_file(&capture).await;
+
+    result
+}
+
+pub fn screenshot(config: &ScreenshotConfig) {
+    take_screenshot(config, false);
+}
+
+pub fn screenshot_region(config: &ScreenshotConfig) {
+    take_screenshot(config, true);
+}
+
+pub fn is_in_path(binary: &str) -> bool {
+    if binary.contains('/') {
+        return Path::new(binary).exists();
+    }
+
+    env::var_os("PATH")
+        .map(|paths| env::split_paths(&paths).any(|dir| dir.join(binary).is_file()))
+        .unwrap_or_default()
+}
+
+const DEFAULT_LOCKER: &str = "swaylock";
+
+pub fn can_lock(config: &SettingsModuleConfig) -> bool {
+    match (&config.power.locker, &config.lock_cmd) {
+        (Some(locker), _) => is_in_path(locker),
+        (None, Some(_)) => true,
+        (None, None) => is_in_path(DEFAULT_LOCKER),
+    }
+}
+
+// the power locker wins over the lock_cmd shared with the lock button
+pub fn lock_screen(config: &SettingsModuleConfig) {
+    let locker = match (&config.power.locker, &config.lock_cmd) {
+        (Some(locker), _) => locker.as_str(),
+        (None, Some(lock_cmd)) => {
+            execute_command(lock_cmd.to_owned());
+            return;
+        }
+        (None, None) => DEFAULT_LOCKER,
+    };
+
+    // the locker keeps running until the session is unlocked, don't wait for it
+    if let Err(err) = tokio::process::Command::new(locker)
+        .args(&config.power.locker_args)
+        .spawn()
+    {
+        error!("Failed to run the screen locker {}: {}", locker, err);
+    }
+}
+
 pub fn logout() {
     tokio::spawn(async move {
         let _ = Command::new("bash")
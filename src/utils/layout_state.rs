@@ -0,0 +1,40 @@
+use crate::config::ModuleName;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+// state changed from the bar itself, kept apart from the config file
+#[derive(Serialize, Deserialize, Default)]
+struct LayoutState {
+    hidden_modules: Vec<ModuleName>,
+}
+
+fn state_path() -> Option<PathBuf> {
+    super::state_dir().map(|dir| dir.join("layout.json"))
+}
+
+pub fn load_hidden_modules() -> Vec<ModuleName> {
+    state_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<LayoutState>(&content).ok())
+        .map(|state| state.hidden_modules)
+        .unwrap_or_default()
+}
+
+pub fn save_hidden_modules(hidden_modules: &[ModuleName]) {
+    let Some(path) = state_path() else {
+        return;
+    };
+
+    let state = LayoutState {
+        hidden_modules: hidden_modules.to_vec(),
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, serde_json::to_string(&state).unwrap_or_default()));
+
+    if let Err(err) = result {
+        warn!("Failed to save the layout state to {:?}: {}", path, err);
+    }
+}
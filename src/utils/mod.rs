@@ -1,6 +1,17 @@
-use std::time::Duration;
+use crate::config::ThresholdConfig;
+use iced::futures::{SinkExt, channel::mpsc::Sender};
+use log::debug;
+use std::{
+    env,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
+pub mod dbus_session;
+pub mod dnd;
 pub mod launcher;
+pub mod layout_state;
+pub mod portal;
 
 pub enum IndicatorState {
     Normal,
@@ -9,6 +20,38 @@ pub enum IndicatorState {
     Danger,
 }
 
+// Warning above `warn`, Danger from `danger` on, the other way around when inverted
+pub fn classify(value: f32, thresholds: &ThresholdConfig) -> IndicatorState {
+    let (value, warn, danger) = if thresholds.inverted {
+        (-value, -thresholds.warn, -thresholds.danger)
+    } else {
+        (value, thresholds.warn, thresholds.danger)
+    };
+
+    if value >= danger {
+        IndicatorState::Danger
+    } else if value > warn {
+        IndicatorState::Warning
+    } else {
+        IndicatorState::Normal
+    }
+}
+
+// $XDG_STATE_HOME/ashell, for what the bar saves on its own
+pub fn state_dir() -> Option<PathBuf> {
+    let state_home = env::var("XDG_STATE_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var("HOME")
+                .ok()
+                .map(|home| PathBuf::from(home).join(".local/state"))
+        })?;
+
+    Some(state_home.join("ashell"))
+}
+
 pub fn format_duration(duration: &Duration) -> String {
     let h = duration.as_secs() / 60 / 60;
     let m = duration.as_secs() / 60 % 60;
@@ -19,6 +62,76 @@ pub fn format_duration(duration: &Duration) -> String {
     }
 }
 
+pub fn format_duration_full(duration: &Duration) -> String {
+    let h = duration.as_secs() / 60 / 60;
+    let m = duration.as_secs() / 60 % 60;
+    let s = duration.as_secs() % 60;
+    if h > 0 {
+        format!("{}h {}m {}s", h, m, s)
+    } else if m > 0 {
+        format!("{}m {}s", m, s)
+    } else {
+        format!("{}s", s)
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    // compared once rounded, 1023.6 KB would be shown as 1024 KB
+    while value.round() >= 1024. && unit < UNITS.len() - 1 {
+        value /= 1024.;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else if value >= 100. {
+        format!("{:.0} {}", value, UNITS[unit])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+pub fn format_bytes_rate(bytes_per_sec: u64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec))
+}
+
+/// Wraps a service output dropping the events sent within `min_interval`
+/// of the last delivered one.
+pub struct RateLimitedSender<T> {
+    sender: Sender<T>,
+    min_interval: Duration,
+    last_sent: Option<Instant>,
+}
+
+impl<T> RateLimitedSender<T> {
+    pub fn new(sender: Sender<T>, min_interval: Duration) -> Self {
+        Self {
+            sender,
+            min_interval,
+            last_sent: None,
+        }
+    }
+
+    pub async fn send(&mut self, event: T) {
+        let now = Instant::now();
+        if self
+            .last_sent
+            .is_some_and(|last_sent| now.duration_since(last_sent) < self.min_interval)
+        {
+            debug!("Rate limit reached, dropping event");
+
+            return;
+        }
+
+        self.last_sent = Some(now);
+        let _ = self.sender.send(event).await;
+    }
+}
+
 pub fn truncate_text(value: &str, max_length: u32) -> String {
     let length = value.len();
 
@@ -31,3 +144,63 @@ pub fn truncate_text(value: &str, max_length: u32) -> String {
         value.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_bytes_boundaries() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.0 KB");
+        assert_eq!(format_bytes(1_048_576 - 1), "1.0 MB");
+        assert_eq!(format_bytes(1_048_576), "1.0 MB");
+    }
+
+    #[test]
+    fn format_bytes_rate_boundaries() {
+        assert_eq!(format_bytes_rate(0), "0 B/s");
+        assert_eq!(format_bytes_rate(1023), "1023 B/s");
+        assert_eq!(format_bytes_rate(1024), "1.0 KB/s");
+        assert_eq!(format_bytes_rate(1_048_576 - 1), "1.0 MB/s");
+        assert_eq!(format_bytes_rate(1_048_576), "1.0 MB/s");
+    }
+
+    #[test]
+    fn format_duration_full_boundaries() {
+        let format = |secs| format_duration_full(&Duration::from_secs(secs));
+
+        assert_eq!(format(0), "0s");
+        assert_eq!(format(59), "59s");
+        assert_eq!(format(60), "1m 0s");
+        assert_eq!(format(3599), "59m 59s");
+        assert_eq!(format(3600), "1h 0m 0s");
+        assert_eq!(format(86400 + 3661), "25h 1m 1s");
+    }
+
+    #[test]
+    fn classify_boundaries() {
+        let thresholds = ThresholdConfig::new(60., 80.);
+
+        assert!(matches!(classify(60., &thresholds), IndicatorState::Normal));
+        assert!(matches!(
+            classify(61., &thresholds),
+            IndicatorState::Warning
+        ));
+        assert!(matches!(classify(80., &thresholds), IndicatorState::Danger));
+    }
+
+    #[test]
+    fn classify_inverted_boundaries() {
+        let thresholds = ThresholdConfig::new(20., 10.).inverted();
+
+        assert!(matches!(classify(20., &thresholds), IndicatorState::Normal));
+        assert!(matches!(
+            classify(19., &thresholds),
+            IndicatorState::Warning
+        ));
+        assert!(matches!(classify(10., &thresholds), IndicatorState::Danger));
+        assert!(matches!(classify(0., &thresholds), IndicatorState::Danger));
+    }
+}
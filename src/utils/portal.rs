@@ -0,0 +1,136 @@
+use super::dbus_session::session_connection;
+use iced::futures::StreamExt;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use zbus::{
+    Result, proxy,
+    zvariant::{OwnedObjectPath, OwnedValue, Value},
+};
+
+#[proxy(
+    interface = "org.freedesktop.portal.FileChooser",
+    default_service = "org.freedesktop.portal.Desktop",
+    default_path = "/org/freedesktop/portal/desktop"
+)]
+trait FileChooser {
+    fn save_file(
+        &self,
+        parent_window: &str,
+        title: &str,
+        options: HashMap<&str, Value<'_>>,
+    ) -> Result<OwnedObjectPath>;
+}
+
+#[proxy(
+    interface = "org.freedesktop.portal.Request",
+    default_service = "org.freedesktop.portal.Desktop"
+)]
+trait Request {
+    #[zbus(signal)]
+    fn response(&self, response: u32, results: HashMap<String, OwnedValue>) -> Result<()>;
+}
+
+static NEXT_TOKEN: AtomicUsize = AtomicUsize::new(0);
+
+// file uris from the portal are percent encoded
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?.as_bytes();
+
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut bytes = encoded.iter();
+    while let Some(byte) = bytes.next() {
+        if *byte == b'%' {
+            let hex = [*bytes.next()?, *bytes.next()?];
+            decoded.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            decoded.push(*byte);
+        }
+    }
+
+    String::from_utf8(decoded).ok().map(PathBuf::from)
+}
+
+/// Asks the user where to save a file, `None` when the dialog is cancelled.
+pub async fn save_file(title: &str, current_name: &str) -> anyhow::Result<Option<PathBuf>> {
+    let connection = session_connection()
+        .await
+        .ok_or_else(|| anyhow::anyhow!("no session bus connection"))?;
+
+    // the request object path is known in advance, subscribing to it before the
+    // call avoids missing a response sent right away
+    let token = format!("ashell{}", NEXT_TOKEN.fetch_add(1, Ordering::Relaxed));
+    let sender = connection
+        .unique_name()
+        .map(|name| name.as_str().trim_start_matches(':').replace('.', "_"))
+        .ok_or_else(|| anyhow::anyhow!("the session connection has no unique name"))?;
+    let request = RequestProxy::builder(&connection)
+        .path(format!(
+            "/org/freedesktop/portal/desktop/request/{sender}/{token}"
+        ))?
+        .build()
+        .await?;
+    let mut responses = request.receive_response().await?;
+
+    FileChooserProxy::new(&connection)
+        .await?
+        .save_file(
+            "",
+            title,
+            HashMap::from([
+                ("handle_token", Value::from(token.as_str())),
+                ("current_name", Value::from(current_name)),
+            ]),
+        )
+        .await?;
+
+    let Some(response) = responses.next().await else {
+        return Ok(None);
+    };
+    let mut args = response.args()?;
+
+    // 0 is success, 1 cancelled by the user and 2 any other error
+    if args.response != 0 {
+        return Ok(None);
+    }
+
+    Ok(args
+        .results
+        .remove("uris")
+        .and_then(|uris| Vec::<String>::try_from(uris).ok())
+        .and_then(|uris| uris.into_iter().next())
+        .and_then(|uri| uri_to_path(&uri)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uri_to_path_decodes_percent_encoding() {
+        assert_eq!(
+            uri_to_path("file:///home/user/Pictures/Screenshot%20from%202026-10-14%2010-32-07.png"),
+            Some(PathBuf::from(
+                "/home/user/Pictures/Screenshot from 2026-10-14 10-32-07.png"
+            ))
+        );
+        assert_eq!(
+            uri_to_path("file:///home/user/Images/capture%C3%A9cran.png"),
+            Some(PathBuf::from("/home/user/Images/captureécran.png"))
+        );
+        assert_eq!(
+            uri_to_path("file:///tmp/shot.png"),
+            Some(PathBuf::from("/tmp/shot.png"))
+        );
+    }
+
+    #[test]
+    fn uri_to_path_rejects_invalid_uris() {
+        assert_eq!(uri_to_path("https://example.com/shot.png"), None);
+        assert_eq!(uri_to_path("file:///tmp/shot%2"), None);
+        assert_eq!(uri_to_path("file:///tmp/shot%zz.png"), None);
+        assert_eq!(uri_to_path("file:///tmp/%FF.png"), None);
+    }
+}
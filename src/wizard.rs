@@ -0,0 +1,441 @@
+use crate::{
+    config::{
+        self, AppearanceTheme, FirstRunConfig, ModuleDef, ModuleName, ModuleSection, Modules,
+    },
+    style::{confirm_button_style, outline_button_style, text_input_style},
+};
+use iced::{
+    Alignment, Border, Color, Element, Length, Subscription, Task, Theme,
+    alignment::Vertical,
+    event::listen_with,
+    keyboard::{self, key::Named},
+    platform_specific::shell::commands::layer_surface::{
+        Anchor, KeyboardInteractivity, Layer, destroy_layer_surface, get_layer_surface,
+    },
+    runtime::platform_specific::wayland::layer_surface::{IcedOutput, SctkLayerSurfaceSettings},
+    widget::{
+        Column, button, checkbox, column, container, horizontal_space, radio, row, text, text_input,
+    },
+    window::Id,
+};
+use log::{error, info};
+
+const WIDTH: u32 = 460;
+const HEIGHT: u32 = 440;
+
+const CLOCK_24H: &str = "%a %d %b %R";
+const CLOCK_12H: &str = "%a %d %b %I:%M %p";
+
+// the modules offered on the second page with the section they are placed in
+const MODULES: [(ModuleName, ModuleSection, bool); 10] = [
+    (ModuleName::Workspaces, ModuleSection::Left, true),
+    (ModuleName::WindowTitle, ModuleSection::Center, true),
+    (ModuleName::SystemInfo, ModuleSection::Right, false),
+    (ModuleName::KeyboardLayout, ModuleSection::Right, false),
+    (ModuleName::Tray, ModuleSection::Right, false),
+    (ModuleName::MediaPlayer, ModuleSection::Right, false),
+    (ModuleName::Notifications, ModuleSection::Right, false),
+    (ModuleName::Clock, ModuleSection::Right, true),
+    (ModuleName::Privacy, ModuleSection::Right, true),
+    (ModuleName::Settings, ModuleSection::Right, true),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Page {
+    Locale,
+    Modules,
+    Theme,
+    Player,
+}
+
+impl Page {
+    const ALL: [Page; 4] = [Page::Locale, Page::Modules, Page::Theme, Page::Player];
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|page| *page == self).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockFormat {
+    H24,
+    H12,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Back,
+    Next,
+    Finish,
+    Skip,
+    SetClockFormat(ClockFormat),
+    ToggleModule(ModuleName, bool),
+    SetTheme(AppearanceTheme),
+    PreferredPlayerChanged(String),
+    FocusNext,
+    FocusPrev,
+    ActivateFocused,
+}
+
+pub enum Action {
+    None,
+    Close,
+}
+
+pub struct Wizard {
+    pub id: Id,
+    page: Page,
+    clock_format: ClockFormat,
+    modules: Vec<(ModuleName, bool)>,
+    theme: AppearanceTheme,
+    preferred_player: String,
+    // the option reached with Tab, none until Tab is pressed
+    focused_index: Option<usize>,
+}
+
+impl Wizard {
+    pub fn open<AppMessage: 'static>() -> (Self, Task<AppMessage>) {
+        let id = Id::unique();
+
+        // exclusive keyboard so the player name can be typed without clicking first
+        let task = get_layer_surface(SctkLayerSurfaceSettings {
+            id,
+            namespace: "ashell-wizard".to_string(),
+            size: Some((Some(WIDTH), Some(HEIGHT))),
+            layer: Layer::Overlay,
+            pointer_interactivity: true,
+            keyboard_interactivity: KeyboardInteractivity::Exclusive,
+            output: IcedOutput::Active,
+            anchor: Anchor::empty(),
+            ..Default::default()
+        });
+
+        (
+            Self {
+                id,
+                page: Page::Locale,
+                clock_format: ClockFormat::H24,
+                modules: MODULES
+                    .iter()
+                    .map(|(name, _, enabled)| (*name, *enabled))
+                    .collect(),
+                theme: AppearanceTheme::default(),
+                preferred_player: String::new(),
+                focused_index: None,
+            },
+            task,
+        )
+    }
+
+    pub fn close<AppMessage: 'static>(&self) -> Task<AppMessage> {
+        destroy_layer_surface(self.id)
+    }
+
+    pub fn update(&mut self, message: Message) -> Action {
+        match message {
+            Message::Back => {
+                self.page = Page::ALL[self.page.index().saturating_sub(1)];
+                self.focused_index = None;
+                Action::None
+            }
+            Message::Next => {
+                match Page::ALL.get(self.page.index() + 1) {
+                    Some(page) => self.page = *page,
+                    None => return self.update(Message::Finish),
+                }
+                self.focused_index = None;
+                Action::None
+            }
+            Message::Finish => {
+                match config::write_first_run_config(&self.first_run_config()) {
+                    Ok(()) => info!("First run config written"),
+                    Err(err) => error!("Failed to write the first run config: {}", err),
+                }
+                Action::Close
+            }
+            // the defaults are kept, an empty config stops the wizard from opening again
+            Message::Skip => {
+                if let Err(err) = config::write_empty_config() {
+                    error!("Failed to write the config file: {}", err);
+                }
+                Action::Close
+            }
+            Message::SetClockFormat(format) => {
+                self.clock_format = format;
+                Action::None
+            }
+            Message::ToggleModule(name, enabled) => {
+                if let Some((_, current)) = self.modules.iter_mut().find(|(n, _)| *n == name) {
+                    *current = enabled;
+                }
+                Action::None
+            }
+            Message::SetTheme(theme) => {
+                self.theme = theme;
+                Action::None
+            }
+            Message::PreferredPlayerChanged(player) => {
+                self.preferred_player = player;
+                Action::None
+            }
+            Message::FocusNext => {
+                let count = self.focusable_actions().len();
+                self.focused_index =
+                    (count > 0).then(|| self.focused_index.map_or(0, |index| (index + 1) % count));
+                Action::None
+            }
+            Message::FocusPrev => {
+                let count = self.focusable_actions().len();
+                self.focused_index = (count > 0).then(|| {
+                    self.focused_index
+                        .map_or(count - 1, |index| (index + count - 1) % count)
+                });
+                Action::None
+            }
+            Message::ActivateFocused => {
+                match self
+                    .focused_index
+                    .and_then(|index| self.focusable_actions().into_iter().nth(index))
+                {
+                    Some(action) => self.update(action),
+                    None => Action::None,
+                }
+            }
+        }
+    }
+
+    // the options of the current page in the order they are shown
+    fn focusable_actions(&self) -> Vec<Message> {
+        match self.page {
+            Page::Locale => vec![
+                Message::SetClockFormat(ClockFormat::H24),
+                Message::SetClockFormat(ClockFormat::H12),
+            ],
+            Page::Modules => self
+                .modules
+                .iter()
+                .map(|(name, enabled)| Message::ToggleModule(*name, !enabled))
+                .collect(),
+            Page::Theme => AppearanceTheme::ALL
+                .into_iter()
+                .map(Message::SetTheme)
+                .collect(),
+            // the text input takes the keyboard
+            Page::Player => Vec::new(),
+        }
+    }
+
+    fn is_focused(&self, index: usize) -> bool {
+        self.focused_index == Some(index)
+    }
+
+    fn first_run_config(&self) -> FirstRunConfig {
+        let section = |section: ModuleSection| {
+            MODULES
+                .iter()
+                .filter(|(name, s, _)| {
+                    *s == section
+                        && self
+                            .modules
+                            .iter()
+                            .any(|(n, enabled)| n == name && *enabled)
+                })
+                .map(|(name, _, _)| *name)
+                .collect::<Vec<_>>()
+        };
+        let right = section(ModuleSection::Right);
+
+        FirstRunConfig {
+            modules: Modules {
+                left: section(ModuleSection::Left)
+                    .into_iter()
+                    .map(ModuleDef::Single)
+                    .collect(),
+                center: section(ModuleSection::Center)
+                    .into_iter()
+                    .map(ModuleDef::Single)
+                    .collect(),
+                // grouped like the default layout
+                right: if right.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![ModuleDef::Group(right)]
+                },
+            },
+            clock_format: match self.clock_format {
+                ClockFormat::H24 => CLOCK_24H,
+                ClockFormat::H12 => CLOCK_12H,
+            }
+            .to_owned(),
+            theme: self.theme,
+            preferred_player: Some(self.preferred_player.trim())
+                .filter(|player| !player.is_empty())
+                .map(str::to_owned),
+        }
+    }
+
+    pub fn view(&self, opacity: f32) -> Element<Message> {
+        let (title, content): (&str, Element<Message>) = match self.page {
+            Page::Locale => (
+                "Date and time format",
+                Column::with_children(
+                    [
+                        (ClockFormat::H24, "24 hours (Mon 01 Jan 18:30)"),
+                        (ClockFormat::H12, "12 hours (Mon 01 Jan 06:30 PM)"),
+                    ]
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, (format, label))| {
+                        focus_ring(
+                            self.is_focused(index),
+                            radio(
+                                label,
+                                format,
+                                Some(self.clock_format),
+                                Message::SetClockFormat,
+                            ),
+                        )
+                    }),
+                )
+                .spacing(12)
+                .into(),
+            ),
+            Page::Modules => (
+                "Modules",
+                Column::with_children(
+                    self.modules
+                        .iter()
+                        .enumerate()
+                        .map(|(index, (name, enabled))| {
+                            let name = *name;
+                            focus_ring(
+                                self.is_focused(index),
+                                checkbox(format!("{:?}", name), *enabled)
+                                    .on_toggle(move |enabled| Message::ToggleModule(name, enabled)),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .spacing(8)
+                .into(),
+            ),
+            Page::Theme => (
+                "Theme",
+                Column::with_children(AppearanceTheme::ALL.into_iter().enumerate().map(
+                    |(index, theme)| {
+                        focus_ring(
+                            self.is_focused(index),
+                            radio(theme.name(), theme, Some(self.theme), Message::SetTheme),
+                        )
+                    },
+                ))
+                .spacing(12)
+                .into(),
+            ),
+            Page::Player => (
+                "Preferred media player",
+                column!(
+                    text("The MPRIS player shown first, leave it empty to follow the active one")
+                        .size(12),
+                    text_input("spotify", &self.preferred_player)
+                        .size(16)
+                        .padding([8, 16])
+                        .style(text_input_style)
+                        .on_input(Message::PreferredPlayerChanged)
+                        .on_submit(Message::Finish),
+                )
+                .spacing(12)
+                .into(),
+            ),
+        };
+
+        let last_page = self.page.index() + 1 == Page::ALL.len();
+
+        container(
+            column!(
+                text(format!(
+                    "Welcome to ashell ({}/{})",
+                    self.page.index() + 1,
+                    Page::ALL.len()
+                ))
+                .size(12),
+                text(title).size(22),
+                container(content).height(Length::Fill),
+                row!(
+                    button(text("Skip").align_y(Vertical::Center))
+                        .padding([4, 16])
+                        .style(outline_button_style(opacity))
+                        .on_press(Message::Skip),
+                    horizontal_space(),
+                    button(text("Back").align_y(Vertical::Center))
+                        .padding([4, 24])
+                        .style(outline_button_style(opacity))
+                        .on_press_maybe((self.page.index() > 0).then_some(Message::Back)),
+                    button(
+                        text(if last_page { "Finish" } else { "Next" }).align_y(Vertical::Center)
+                    )
+                    .padding([4, 24])
+                    .style(confirm_button_style(opacity))
+                    .on_press(Message::Next),
+                )
+                .spacing(8)
+                .align_y(Alignment::Center),
+            )
+            .spacing(16),
+        )
+        .padding(24)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(move |theme: &Theme| container::Style {
+            background: Some(theme.palette().background.scale_alpha(opacity).into()),
+            border: Border {
+                color: theme
+                    .extended_palette()
+                    .secondary
+                    .base
+                    .color
+                    .scale_alpha(opacity),
+                width: 1.,
+                radius: 16.0.into(),
+            },
+            ..Default::default()
+        })
+        .into()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        listen_with(|event, _, _| match event {
+            iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(key),
+                modifiers,
+                ..
+            }) => match key {
+                Named::Tab if modifiers.shift() => Some(Message::FocusPrev),
+                Named::Tab => Some(Message::FocusNext),
+                Named::Enter | Named::Space => Some(Message::ActivateFocused),
+                Named::Escape => Some(Message::Skip),
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+}
+
+// outlines the option reached with the keyboard
+fn focus_ring<'a>(focused: bool, option: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
+    container(option)
+        .padding([2, 4])
+        .style(move |theme: &Theme| container::Style {
+            border: Border {
+                color: if focused {
+                    theme.palette().primary
+                } else {
+                    Color::TRANSPARENT
+                },
+                width: 2.,
+                radius: 4.0.into(),
+            },
+            ..Default::default()
+        })
+        .into()
+}